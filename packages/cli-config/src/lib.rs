@@ -70,6 +70,7 @@ pub const APP_TITLE_ENV: &str = "DIOXUS_APP_TITLE";
 pub const OUT_DIR: &str = "DIOXUS_OUT_DIR";
 pub const SESSION_CACHE_DIR: &str = "DIOXUS_SESSION_CACHE_DIR";
 pub const BUILD_ID: &str = "DIOXUS_BUILD_ID";
+pub const SSG_OUT_DIR_ENV: &str = "DIOXUS_SSG_OUT_DIR";
 
 /// Reads an environment variable at runtime in debug mode or at compile time in
 /// release mode. When bundling in release mode, we will not be running under the
@@ -311,6 +312,14 @@ pub fn android_session_cache_dir() -> PathBuf {
     PathBuf::from("/data/local/tmp/dx/")
 }
 
+/// The directory the CLI asked a fullstack server to write a statically generated site into.
+///
+/// If this is set, the server renders every static route in process, writes the result into the directory and exits
+/// instead of listening for requests.
+pub fn ssg_out_dir() -> Option<PathBuf> {
+    std::env::var(SSG_OUT_DIR_ENV).ok().map(PathBuf::from)
+}
+
 /// The unique build id for this application, used to disambiguate between different builds of the same
 /// application.
 pub fn build_id() -> u64 {
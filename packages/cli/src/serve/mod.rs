@@ -227,6 +227,10 @@ pub(crate) async fn serve_all(args: ServeArgs, tracer: &mut TraceController) ->
                 )
             }
 
+            ServeUpdate::ToggleInspector { open } => {
+                devserver.set_inspector_open(open);
+            }
+
             ServeUpdate::OpenDebugger { id } => {
                 builder.open_debugger(&devserver, id).await;
             }
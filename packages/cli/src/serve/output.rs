@@ -12,6 +12,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
     ExecutableCommand,
 };
+use dioxus_devtools_types::{SignalKind, Subscriber};
 use ratatui::{
     prelude::*,
     widgets::{Block, BorderType, Borders, LineGauge, Paragraph},
@@ -19,7 +20,7 @@ use ratatui::{
 };
 use std::{
     cell::RefCell,
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     io::{self, stdout},
     rc::Rc,
     time::Duration,
@@ -31,7 +32,8 @@ use super::AppServer;
 const TICK_RATE_MS: u64 = 100;
 const VIEWPORT_MAX_WIDTH: u16 = 100;
 const VIEWPORT_HEIGHT_SMALL: u16 = 5;
-const VIEWPORT_HEIGHT_BIG: u16 = 13;
+const VIEWPORT_HEIGHT_BIG: u16 = 14;
+const VIEWPORT_HEIGHT_INSPECTOR: u16 = 24;
 
 /// The TUI that drives the console output.
 ///
@@ -47,6 +49,7 @@ pub struct Output {
 
     // A list of all messages from build, dev, app, and more.
     more_modal_open: bool,
+    inspector_open: bool,
     interactive: bool,
 
     // Whether to show verbose logs or not
@@ -85,6 +88,7 @@ impl Output {
             ),
             events: None,
             more_modal_open: false,
+            inspector_open: false,
             pending_logs: VecDeque::new(),
             throbber: RefCell::new(throbber_widgets_tui::ThrobberState::default()),
             trace: crate::logging::VERBOSITY.get().unwrap().trace,
@@ -272,27 +276,22 @@ impl Output {
                 });
             }
 
-            // Toggle the more modal by swapping the the terminal with a new one
-            // This is a bit of a hack since crossterm doesn't technically support changing the
-            // size of an inline viewport.
             KeyCode::Char('/') => {
-                if let Some(terminal) = self.term.borrow_mut().as_mut() {
-                    // Toggle the more modal, which will change our current viewport height
-                    self.more_modal_open = !self.more_modal_open;
-
-                    // Clear the terminal before resizing it, such that it doesn't tear
-                    terminal.clear()?;
-
-                    // And then set the new viewport, which essentially mimics a resize
-                    *terminal = Terminal::with_options(
-                        CrosstermBackend::new(stdout()),
-                        TerminalOptions {
-                            viewport: Viewport::Inline(self.viewport_current_height()),
-                        },
-                    )?;
+                let inspector_was_open = self.inspector_open;
+                self.set_modals(!self.more_modal_open, false)?;
+                if inspector_was_open {
+                    return Ok(Some(ServeUpdate::ToggleInspector { open: false }));
                 }
             }
 
+            // The devserver asks the app for snapshots of its components and signals while the inspector is open
+            KeyCode::Char('i') => {
+                self.set_modals(false, !self.inspector_open)?;
+                return Ok(Some(ServeUpdate::ToggleInspector {
+                    open: self.inspector_open,
+                }));
+            }
+
             _ => {}
         }
 
@@ -300,6 +299,30 @@ impl Output {
         Ok(Some(ServeUpdate::Redraw))
     }
 
+    /// Open or close the more modal and the inspector by swapping the the terminal with a new one
+    /// This is a bit of a hack since crossterm doesn't technically support changing the
+    /// size of an inline viewport.
+    fn set_modals(&mut self, more_modal_open: bool, inspector_open: bool) -> io::Result<()> {
+        if let Some(terminal) = self.term.borrow_mut().as_mut() {
+            // Toggle the modals, which will change our current viewport height
+            self.more_modal_open = more_modal_open;
+            self.inspector_open = inspector_open;
+
+            // Clear the terminal before resizing it, such that it doesn't tear
+            terminal.clear()?;
+
+            // And then set the new viewport, which essentially mimics a resize
+            *terminal = Terminal::with_options(
+                CrosstermBackend::new(stdout()),
+                TerminalOptions {
+                    viewport: Viewport::Inline(self.viewport_current_height()),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Push a TraceMsg to be printed on the next render
     pub fn push_log(&mut self, message: TraceMsg) {
         self.pending_logs.push_front(message);
@@ -423,6 +446,11 @@ impl Output {
     fn render_body_title(&self, frame: &mut Frame<'_>, area: Rect, _state: RenderState) {
         frame.render_widget(
             Line::from(vec![
+                " ".dark_gray(),
+                match self.inspector_open {
+                    true => "i:inspector".light_yellow(),
+                    false => "i:inspector".dark_gray(),
+                },
                 " ".dark_gray(),
                 match self.more_modal_open {
                     true => "/:more".light_yellow(),
@@ -455,6 +483,10 @@ impl Output {
         if self.more_modal_open {
             self.render_more_modal(frame, more, state);
         }
+
+        if self.inspector_open {
+            self.render_inspector(frame, more, state);
+        }
     }
 
     fn render_gauges(&self, frame: &mut Frame<'_>, area: Rect, state: RenderState) {
@@ -778,9 +810,10 @@ impl Output {
             "v: toggle verbose logs",
             "t: toggle tracing logs ",
             "c: clear the screen",
+            "i: toggle the inspector",
             "/: toggle more commands",
         ];
-        let layout: [_; 8] = Layout::vertical(cmds.iter().map(|_| Constraint::Length(1)))
+        let layout: [_; 9] = Layout::vertical(cmds.iter().map(|_| Constraint::Length(1)))
             .horizontal_margin(1)
             .areas(col2);
         for (idx, cmd) in cmds.iter().enumerate() {
@@ -800,6 +833,107 @@ impl Output {
         }
     }
 
+    /// Render the components of the app as a tree next to the signals and what they rerun when they change
+    fn render_inspector(&self, frame: &mut Frame<'_>, area: Rect, state: RenderState) {
+        let [components, signals] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .horizontal_margin(1)
+                .areas(area);
+
+        let Some(snapshot) = state.server.inspector_snapshot() else {
+            frame.render_widget(
+                Paragraph::new(Line::from(vec![
+                    "Waiting for the app to connect to the devserver...".dark_gray(),
+                ])),
+                components,
+            );
+            return;
+        };
+
+        let component_name = |id: usize| {
+            snapshot
+                .scopes
+                .iter()
+                .find(|scope| scope.id == id)
+                .map(|scope| short_name(&scope.name))
+                .unwrap_or("?")
+        };
+
+        // The scopes are sorted so parents come before their children
+        let mut depths: HashMap<usize, usize> = HashMap::new();
+        let mut lines = vec![Line::from(vec![
+            "Components ".gray(),
+            match state.server.server_address() {
+                Some(address) => {
+                    format!("(json at http://{address}/_dioxus/inspector)").dark_gray()
+                }
+                None => "".dark_gray(),
+            },
+        ])];
+        for scope in &snapshot.scopes {
+            let depth = scope
+                .parent
+                .and_then(|parent| depths.get(&parent))
+                .map_or(0, |depth| depth + 1);
+            depths.insert(scope.id, depth);
+
+            let mut line = vec![
+                "  ".repeat(depth).into(),
+                short_name(&scope.name).to_string().yellow(),
+                format!(" hooks: {} renders: {}", scope.hooks, scope.render_count).dark_gray(),
+            ];
+            let contexts: Vec<_> = scope
+                .contexts
+                .iter()
+                .map(|context| context.as_deref().map_or("?", short_name))
+                .collect();
+            if !contexts.is_empty() {
+                line.push(format!(" provides: {}", contexts.join(", ")).dark_gray());
+            }
+            if let Some(props) = &scope.props {
+                line.push(format!(" {props}").dark_gray());
+            }
+            lines.push(Line::from(line));
+        }
+        frame.render_widget(
+            Paragraph::new(truncate_lines(lines, components.height)),
+            components,
+        );
+
+        let mut lines = vec![Line::from("Signals".gray())];
+        for signal in &snapshot.signals {
+            lines.push(Line::from(vec![
+                match signal.kind {
+                    SignalKind::Signal => "signal ".yellow(),
+                    SignalKind::Memo => "memo ".yellow(),
+                },
+                short_name(&signal.type_name).to_string().white(),
+                format!(
+                    " in {} {}",
+                    component_name(signal.scope),
+                    signal.created_at.as_deref().unwrap_or_default()
+                )
+                .dark_gray(),
+            ]));
+            for subscriber in &signal.subscribers {
+                let subscriber = match subscriber {
+                    Subscriber::Component { scope } => component_name(*scope).to_string(),
+                    Subscriber::Memo { id } => format!("memo {id}"),
+                    Subscriber::Other { scope, created_at } => format!(
+                        "{} {}",
+                        component_name(*scope),
+                        created_at.as_deref().unwrap_or_default()
+                    ),
+                };
+                lines.push(Line::from(vec!["  -> ".dark_gray(), subscriber.gray()]));
+            }
+        }
+        frame.render_widget(
+            Paragraph::new(truncate_lines(lines, signals.height)),
+            signals,
+        );
+    }
+
     /// Render borders around the terminal, forcing an inner clear while we're at it
     fn render_borders(&self, frame: &mut Frame, area: Rect) {
         frame.render_widget(ratatui::widgets::Clear, area);
@@ -930,9 +1064,12 @@ impl Output {
     }
 
     fn viewport_current_height(&self) -> u16 {
-        match self.more_modal_open {
-            true => VIEWPORT_HEIGHT_BIG,
-            false => VIEWPORT_HEIGHT_SMALL,
+        if self.inspector_open {
+            VIEWPORT_HEIGHT_INSPECTOR
+        } else if self.more_modal_open {
+            VIEWPORT_HEIGHT_BIG
+        } else {
+            VIEWPORT_HEIGHT_SMALL
         }
     }
 
@@ -1021,3 +1158,24 @@ impl Output {
         lines
     }
 }
+
+/// Strip the module path from a component or type name
+fn short_name(name: &str) -> &str {
+    // Keep generic arguments intact by only looking at the path before them
+    let path_end = name.find('<').unwrap_or(name.len());
+    match name[..path_end].rfind("::") {
+        Some(idx) => &name[idx + 2..],
+        None => name,
+    }
+}
+
+/// Cut off the lines that don't fit in the height and note how many were hidden
+fn truncate_lines(mut lines: Vec<Line<'static>>, height: u16) -> Vec<Line<'static>> {
+    let height = height as usize;
+    if lines.len() > height && height > 0 {
+        let hidden = lines.len() - height + 1;
+        lines.truncate(height - 1);
+        lines.push(Line::from(format!("... {hidden} more").dark_gray()));
+    }
+    lines
+}
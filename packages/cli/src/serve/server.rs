@@ -18,7 +18,7 @@ use axum::{
     routing::{get, get_service},
     Extension, Router,
 };
-use dioxus_devtools_types::{DevserverMsg, HotReloadMsg};
+use dioxus_devtools_types::{ClientMsg, DevserverMsg, HotReloadMsg, InspectorSnapshot};
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures_util::{
     future,
@@ -33,10 +33,10 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
     path::Path,
     sync::{Arc, RwLock},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use subsecond_types::JumpTable;
-use tokio::process::Command;
+use tokio::{process::Command, sync::watch, time::Interval};
 use tower_http::{
     cors::Any,
     services::fs::{ServeDir, ServeFileSystemResponseBody},
@@ -63,8 +63,25 @@ pub(crate) struct WebServer {
     build_status: SharedStatus,
     application_name: String,
     platform: Platform,
+    inspector: Inspector,
 }
 
+/// The state of the component inspector. While the inspector is open in the TUI or its endpoint was requested
+/// recently, the devserver keeps asking the app for snapshots of its components and signals.
+struct Inspector {
+    snapshots: watch::Sender<Option<InspectorSnapshot>>,
+    requests: UnboundedReceiver<()>,
+    interval: Interval,
+    open: bool,
+    last_requested: Option<Instant>,
+}
+
+/// How often the devserver asks the app for a new snapshot while the inspector is in use
+const INSPECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the devserver keeps asking for snapshots after the inspector endpoint was requested
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub(crate) struct ConnectedWsClient {
     socket: WebSocket,
     build_id: Option<BuildId>,
@@ -83,6 +100,8 @@ impl WebServer {
     pub(crate) fn start(runner: &AppServer) -> Result<Self> {
         let (hot_reload_sockets_tx, hot_reload_sockets_rx) = futures_channel::mpsc::unbounded();
         let (build_status_sockets_tx, build_status_sockets_rx) = futures_channel::mpsc::unbounded();
+        let (inspect_requests_tx, inspect_requests_rx) = futures_channel::mpsc::unbounded();
+        let (snapshots_tx, snapshots_rx) = watch::channel(None);
 
         // Create the listener that we'll pass into the devserver, but save its IP here so
         // we can display it to the user in the tui
@@ -108,6 +127,10 @@ impl WebServer {
             build_status_sockets_tx,
             proxied_address,
             build_status.clone(),
+            InspectorEndpoint {
+                requests: inspect_requests_tx,
+                snapshots: snapshots_rx,
+            },
         )?;
 
         // And finally, start the server mainloop
@@ -129,11 +152,20 @@ impl WebServer {
             new_build_status_sockets: build_status_sockets_rx,
             application_name: runner.app_name().to_string(),
             platform: runner.client.build.platform,
+            inspector: Inspector {
+                snapshots: snapshots_tx,
+                requests: inspect_requests_rx,
+                interval: tokio::time::interval(INSPECT_INTERVAL),
+                open: false,
+                last_requested: None,
+            },
         })
     }
 
     /// Wait for new clients to be connected and then save them
     pub(crate) async fn wait(&mut self) -> ServeUpdate {
+        let inspecting = self.is_inspecting();
+        let mut new_inspect_request = self.inspector.requests.next();
         let mut new_hot_reload_socket = self.new_hot_reload_sockets.next();
         let mut new_build_status_socket = self.new_build_status_sockets.next();
         let mut new_message = self
@@ -173,9 +205,27 @@ impl WebServer {
                     panic!("Could not receive a socket - the devtools could not boot - the port is likely already in use");
                 }
             }
+            Some(()) = &mut new_inspect_request => {
+                drop(new_message);
+                self.inspector.last_requested = Some(Instant::now());
+                self.send_devserver_message_to_all(DevserverMsg::Inspect).await;
+                return ServeUpdate::Redraw;
+            }
+            _ = self.inspector.interval.tick(), if inspecting => {
+                drop(new_message);
+                self.send_devserver_message_to_all(DevserverMsg::Inspect).await;
+                return ServeUpdate::Redraw;
+            }
             Some((idx, message)) = new_message.next() => {
                 match message {
-                    Some(Ok(msg)) => return ServeUpdate::WsMessage { msg, platform: Platform::Web },
+                    Some(Ok(msg)) => {
+                        // Keep inspector snapshots for the TUI and the inspector endpoint instead of forwarding them
+                        if let Some(snapshot) = inspector_snapshot(&msg) {
+                            self.inspector.snapshots.send_replace(Some(snapshot));
+                            return ServeUpdate::Redraw;
+                        }
+                        return ServeUpdate::WsMessage { msg, platform: Platform::Web };
+                    }
                     _ => {
                         drop(new_message);
                         _ = self.hot_reload_sockets.remove(idx);
@@ -363,6 +413,24 @@ impl WebServer {
         }
     }
 
+    /// Open or close the inspector in the TUI. The app is asked for snapshots while the inspector is open.
+    pub(crate) fn set_inspector_open(&mut self, open: bool) {
+        self.inspector.open = open;
+    }
+
+    /// The latest snapshot of the components and signals in the app
+    pub(crate) fn inspector_snapshot(&self) -> Option<InspectorSnapshot> {
+        self.inspector.snapshots.borrow().clone()
+    }
+
+    fn is_inspecting(&self) -> bool {
+        let requested_recently = self
+            .inspector
+            .last_requested
+            .is_some_and(|requested| requested.elapsed() < INSPECT_TIMEOUT);
+        !self.hot_reload_sockets.is_empty() && (self.inspector.open || requested_recently)
+    }
+
     /// Get the address the devserver should run on
     pub fn devserver_address(&self) -> SocketAddr {
         SocketAddr::new(self.devserver_exposed_ip, self.devserver_port)
@@ -440,6 +508,7 @@ fn build_devserver_router(
     build_status_sockets: UnboundedSender<ConnectedWsClient>,
     fullstack_address: Option<SocketAddr>,
     build_status: SharedStatus,
+    inspector: InspectorEndpoint,
 ) -> Result<Router> {
     let mut router = Router::new();
     let build = runner.client();
@@ -521,7 +590,21 @@ fn build_devserver_router(
                     },
                 ),
             )
-            .layer(Extension(build_status_sockets)),
+            .layer(Extension(build_status_sockets))
+            .route(
+                "/inspector",
+                get(|ext: Extension<InspectorEndpoint>| async move {
+                    let mut snapshots = ext.0.snapshots.clone();
+                    snapshots.mark_unchanged();
+                    _ = ext.0.requests.unbounded_send(());
+
+                    // Wait for a fresh snapshot, but fall back to the last one if the app doesn't respond
+                    _ = tokio::time::timeout(Duration::from_secs(2), snapshots.changed()).await;
+                    let snapshot = snapshots.borrow().clone();
+                    axum::Json(snapshot)
+                }),
+            )
+            .layer(Extension(inspector)),
     );
 
     // Setup cors
@@ -723,6 +806,24 @@ async fn build_status_middleware(
     next.run(request).await
 }
 
+/// The handles the `/_dioxus/inspector` endpoint uses to ask the app for a snapshot and wait for it
+#[derive(Clone)]
+struct InspectorEndpoint {
+    requests: UnboundedSender<()>,
+    snapshots: watch::Receiver<Option<InspectorSnapshot>>,
+}
+
+/// Decode the message if it is an inspector snapshot from the app
+fn inspector_snapshot(msg: &Message) -> Option<InspectorSnapshot> {
+    let Message::Text(text) = msg else {
+        return None;
+    };
+    match serde_json::from_str(text.as_str()) {
+        Ok(ClientMsg::Inspector(snapshot)) => Some(snapshot),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 struct SharedStatus(Arc<RwLock<Status>>);
 
@@ -33,6 +33,10 @@ pub(crate) enum ServeUpdate {
 
     ToggleShouldRebuild,
 
+    ToggleInspector {
+        open: bool,
+    },
+
     OpenDebugger {
         id: BuildId,
     },
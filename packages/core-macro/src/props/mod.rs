@@ -754,6 +754,44 @@ mod struct_info {
             }
         }
 
+        /// Format the props like `#[derive(Debug)]` would for devtools. Fields that don't implement `Debug` are
+        /// formatted as `_`.
+        fn debug_props_impl(&self) -> TokenStream {
+            let name = strip_raw_ident_prefix(self.name.to_string());
+            if self.fields.is_empty() {
+                return quote!(Some(#name.to_string()));
+            }
+            let fields = self.fields.iter().map(|f| {
+                let field = f.name;
+                let field_name = strip_raw_ident_prefix(field.to_string());
+                quote!(format!("{}: {}", #field_name, (&&self.#field).debug_prop()))
+            });
+
+            quote! {
+                trait NonDebug {
+                    fn debug_prop(&self) -> String;
+                }
+
+                impl<T> NonDebug for &&T {
+                    fn debug_prop(&self) -> String {
+                        "_".to_string()
+                    }
+                }
+
+                trait CanDebug {
+                    fn debug_prop(&self) -> String;
+                }
+
+                impl<T: ::std::fmt::Debug> CanDebug for T {
+                    fn debug_prop(&self) -> String {
+                        format!("{:?}", self)
+                    }
+                }
+
+                Some(format!("{} {{ {} }}", #name, [#(#fields),*].join(", ")))
+            }
+        }
+
         pub fn builder_creation_impl(&self) -> Result<TokenStream, Error> {
             let StructInfo {
                 ref vis,
@@ -842,6 +880,7 @@ Finally, call `.build()` to create the instance of `{name}`.
             }
 
             let memoize = self.memoize_impl()?;
+            let debug_props = self.debug_props_impl();
 
             let global_fields = self
                 .extend_fields()
@@ -894,6 +933,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                     fn memoize(&mut self, new: &Self) -> bool {
                         #memoize
                     }
+                    fn debug_props(&self) -> Option<String> {
+                        #debug_props
+                    }
                 }
             })
         }
@@ -1500,6 +1542,9 @@ Finally, call `.build()` to create the instance of `{name}`.
                         fn memoize(&mut self, new: &Self) -> bool {
                             self.inner.memoize(&new.inner)
                         }
+                        fn debug_props(&self) -> Option<String> {
+                            self.inner.debug_props()
+                        }
                     }
 
                     #[allow(dead_code, non_camel_case_types, missing_docs)]
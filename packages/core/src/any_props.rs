@@ -13,6 +13,8 @@ pub(crate) trait AnyProps: 'static {
     fn props(&self) -> &dyn Any;
     /// Get the props as a type erased `dyn Any`.
     fn props_mut(&mut self) -> &mut dyn Any;
+    /// Format the props with `Debug` if they implement it.
+    fn debug_props(&self) -> Option<String>;
     /// Duplicate this component into a new boxed component.
     fn duplicate(&self) -> BoxedAnyProps;
 }
@@ -21,6 +23,7 @@ pub(crate) trait AnyProps: 'static {
 pub(crate) struct VProps<F: ComponentFunction<P, M>, P, M> {
     render_fn: F,
     memo: fn(&mut P, &P) -> bool,
+    debug: fn(&P) -> Option<String>,
     props: P,
     name: &'static str,
     phantom: std::marker::PhantomData<M>,
@@ -31,6 +34,7 @@ impl<F: ComponentFunction<P, M>, P: Clone, M> Clone for VProps<F, P, M> {
         Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
@@ -43,12 +47,14 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> VProps<
     pub fn new(
         render_fn: F,
         memo: fn(&mut P, &P) -> bool,
+        debug: fn(&P) -> Option<String>,
         props: P,
         name: &'static str,
     ) -> VProps<F, P, M> {
         VProps {
             render_fn,
             memo,
+            debug,
             props,
             name,
             phantom: std::marker::PhantomData,
@@ -74,6 +80,10 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         &mut self.props
     }
 
+    fn debug_props(&self) -> Option<String> {
+        (self.debug)(&self.props)
+    }
+
     fn render(&self) -> Element {
         fn render_inner(name: &str, res: Result<Element, Box<dyn Any + Send>>) -> Element {
             match res {
@@ -102,6 +112,7 @@ impl<F: ComponentFunction<P, M> + Clone, P: Clone + 'static, M: 'static> AnyProp
         Box::new(Self {
             render_fn: self.render_fn.clone(),
             memo: self.memo,
+            debug: self.debug,
             props: self.props.clone(),
             name: self.name,
             phantom: std::marker::PhantomData,
@@ -6,11 +6,12 @@ use std::{
 use crate::{
     any_props::AnyProps,
     innerlude::{
-        ElementRef, MountId, ScopeOrder, SuspenseBoundaryProps, SuspenseBoundaryPropsWithOwner,
-        VComponent, WriteMutations,
+        ElementRef, MountId, PortalProps, ScopeOrder, SuspenseBoundaryProps,
+        SuspenseBoundaryPropsWithOwner, VComponent, WriteMutations,
     },
     nodes::{AsVNode, VNode},
     prelude::SuspenseContext,
+    profiler::RenderCause,
     scopes::ScopeId,
     virtual_dom::VirtualDom,
     Element,
@@ -25,6 +26,8 @@ impl VirtualDom {
         let scope = &mut self.scopes[scope_id.0];
         if SuspenseBoundaryProps::downcast_from_props(&mut *scope.props).is_some() {
             SuspenseBoundaryProps::diff(scope_id, self, to)
+        } else if PortalProps::downcast_from_props(&mut *scope.props).is_some() {
+            PortalProps::diff(scope_id, self, to)
         } else {
             let new_nodes = self.run_scope(scope_id);
             self.diff_scope(to, scope_id, new_nodes);
@@ -43,6 +46,7 @@ impl VirtualDom {
             let Ok(new_real_nodes) = &new_nodes else {
                 return;
             };
+            self.runtime.profiler.enter(scope);
             let scope_state = &mut self.scopes[scope.0];
             // Load the old and new rendered nodes
             let old = scope_state.last_rendered_node.take().unwrap();
@@ -59,6 +63,7 @@ impl VirtualDom {
             if render_to.is_some() {
                 self.runtime.get_state(scope).unwrap().mount(&self.runtime);
             }
+            self.runtime.profiler.exit_diff();
         })
     }
 
@@ -74,6 +79,7 @@ impl VirtualDom {
         parent: Option<ElementRef>,
     ) -> usize {
         self.runtime.clone().with_scope_on_stack(scope, || {
+            self.runtime.profiler.enter(scope);
             // If there are suspended scopes, we need to check if the scope is suspended before we diff it
             // If it is suspended, we need to diff it but write the mutations nothing
             // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
@@ -90,6 +96,7 @@ impl VirtualDom {
             if render_to.is_some() {
                 self.runtime.get_state(scope).unwrap().mount(&self.runtime);
             }
+            self.runtime.profiler.exit_diff();
 
             nodes
         })
@@ -97,7 +104,7 @@ impl VirtualDom {
 
     pub(crate) fn remove_component_node<M: WriteMutations>(
         &mut self,
-        to: Option<&mut M>,
+        mut to: Option<&mut M>,
         destroy_component_state: bool,
         scope_id: ScopeId,
         replace_with: Option<usize>,
@@ -105,6 +112,14 @@ impl VirtualDom {
         // If this is a suspense boundary, remove the suspended nodes as well
         SuspenseContext::remove_suspended_nodes::<M>(self, scope_id, destroy_component_state);
 
+        // If this is a portal, remove the children from the portal target as well
+        PortalProps::remove_portal_nodes(
+            self,
+            to.as_deref_mut(),
+            scope_id,
+            destroy_component_state,
+        );
+
         // Remove the component from the dom
         if let Some(node) = self.scopes[scope_id.0].last_rendered_node.as_ref() {
             node.clone().as_vnode().remove_node_inner(
@@ -153,6 +168,9 @@ impl VNode {
         }
 
         // Now diff the scope
+        dom.runtime
+            .profiler
+            .record_cause(scope_id, RenderCause::Props);
         dom.run_and_diff_scope(to, scope_id);
 
         let height = dom.runtime.get_state(scope_id).unwrap().height;
@@ -195,6 +213,11 @@ impl VNode {
             return SuspenseBoundaryProps::create(mount, idx, component, parent, dom, to);
         }
 
+        // Portals write their children to the portal target instead of running a component
+        if component.props.props().type_id() == TypeId::of::<PortalProps>() {
+            return PortalProps::create(mount, idx, component, parent, dom, to);
+        }
+
         let mut scope_id = ScopeId(dom.get_mounted_dyn_node(mount, idx));
 
         // If the scopeid is a placeholder, we need to load up a new scope for this vcomponent. If it's already mounted, then we can just use that
@@ -269,7 +269,7 @@ impl VNode {
     pub(crate) fn remove_node_inner<M: WriteMutations>(
         &self,
         dom: &mut VirtualDom,
-        to: Option<&mut M>,
+        mut to: Option<&mut M>,
         destroy_component_state: bool,
         replace_with: Option<usize>,
     ) {
@@ -287,6 +287,11 @@ impl VNode {
         // But we still need to make sure to reclaim them from the arena and drop their hooks, etc
         self.remove_nested_dyn_nodes::<M>(mount, dom, destroy_component_state);
 
+        // Portals under the nested nodes are not removed with the parent, so remove them separately
+        if let Some(to) = to.as_deref_mut() {
+            dom.flush_removed_portals(to);
+        }
+
         // Clean up the roots, assuming we need to generate mutations for these
         // This is done last in order to preserve Node ID reclaim order (reclaim in reverse order of claim)
         self.reclaim_roots(mount, dom, to, destroy_component_state, replace_with);
@@ -714,30 +719,6 @@ impl VNode {
         dom: &mut VirtualDom,
         mut to: Option<&mut impl WriteMutations>,
     ) {
-        fn collect_dyn_node_range(
-            dynamic_nodes: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
-            root_idx: u8,
-        ) -> Option<(usize, usize)> {
-            let start = match dynamic_nodes.peek() {
-                Some((idx, [first, ..])) if *first == root_idx => *idx,
-                _ => return None,
-            };
-
-            let mut end = start;
-
-            while let Some((idx, p)) =
-                dynamic_nodes.next_if(|(_, p)| matches!(p, [idx, ..] if *idx == root_idx))
-            {
-                if p.len() == 1 {
-                    continue;
-                }
-
-                end = idx;
-            }
-
-            Some((start, end))
-        }
-
         let (start, end) = match collect_dyn_node_range(dynamic_nodes_iter, root_idx) {
             Some((a, b)) => (a, b),
             None => return,
@@ -905,3 +886,28 @@ impl MountId {
         id
     }
 }
+
+/// Take the range of dynamic nodes under a root node of a template out of the depth first iterator of dynamic nodes
+pub(super) fn collect_dyn_node_range(
+    dynamic_nodes: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
+    root_idx: u8,
+) -> Option<(usize, usize)> {
+    let start = match dynamic_nodes.peek() {
+        Some((idx, [first, ..])) if *first == root_idx => *idx,
+        _ => return None,
+    };
+
+    let mut end = start;
+
+    while let Some((idx, p)) =
+        dynamic_nodes.next_if(|(_, p)| matches!(p, [idx, ..] if *idx == root_idx))
+    {
+        if p.len() == 1 {
+            continue;
+        }
+
+        end = idx;
+    }
+
+    Some((start, end))
+}
@@ -0,0 +1,148 @@
+//! Replaying the mounted nodes as mutations that create them from scratch.
+//!
+//! Replaying walks the nodes that are currently mounted in the same order as creating them, but it never runs
+//! components or assigns new ids. Every node is written with the id it was mounted with.
+
+use core::iter::Peekable;
+
+use super::node::collect_dyn_node_range;
+use crate::{
+    arena::{ElementId, MountId},
+    innerlude::AsVNode,
+    nodes::DynamicNode,
+    scopes::ScopeId,
+    AttributeValue, TemplateNode, VNode, VirtualDom, WriteMutations,
+};
+
+impl VirtualDom {
+    /// Replay the last rendered nodes of a scope. Returns the number of nodes created on the stack
+    pub(crate) fn replay_scope(&self, scope: ScopeId, to: &mut impl WriteMutations) -> usize {
+        match self
+            .scopes
+            .get(scope.0)
+            .and_then(|scope| scope.last_rendered_node.as_ref())
+        {
+            Some(node) => node.as_vnode().replay(self, to),
+            None => 0,
+        }
+    }
+}
+
+impl VNode {
+    /// Replay this mounted rsx block. Returns the number of nodes created on the stack
+    pub(crate) fn replay(&self, dom: &VirtualDom, to: &mut impl WriteMutations) -> usize {
+        let mount = self.mount.get();
+        if !mount.mounted() {
+            return 0;
+        }
+        let template = self.template;
+
+        let mut nodes = template.node_paths.iter().copied().enumerate().peekable();
+        let mut attrs = template.attr_paths.iter().copied().enumerate().peekable();
+
+        template
+            .roots
+            .iter()
+            .enumerate()
+            .map(|(root_idx, root)| match root {
+                TemplateNode::Dynamic { id } => {
+                    nodes.next();
+                    self.replay_dynamic_node(mount, *id, dom, to)
+                }
+                TemplateNode::Text { .. } | TemplateNode::Element { .. } => {
+                    to.load_template(
+                        template,
+                        root_idx,
+                        dom.get_mounted_root_node(mount, root_idx),
+                    );
+
+                    if matches!(root, TemplateNode::Element { .. }) {
+                        // Like creating nodes, the attributes need to be written before the children change the paths
+                        self.replay_attrs(mount, &mut attrs, root_idx as u8, dom, to);
+                        self.replay_placeholders(mount, &mut nodes, root_idx as u8, dom, to);
+                    }
+
+                    1
+                }
+            })
+            .sum()
+    }
+
+    fn replay_dynamic_node(
+        &self,
+        mount: MountId,
+        dynamic_node_id: usize,
+        dom: &VirtualDom,
+        to: &mut impl WriteMutations,
+    ) -> usize {
+        let mounted = dom.get_mounted_dyn_node(mount, dynamic_node_id);
+        match &self.dynamic_nodes[dynamic_node_id] {
+            DynamicNode::Component(_) => dom.replay_scope(ScopeId(mounted), to),
+            DynamicNode::Fragment(children) => {
+                children.iter().map(|child| child.replay(dom, to)).sum()
+            }
+            DynamicNode::Text(text) => {
+                to.create_text_node(&text.value, ElementId(mounted));
+                1
+            }
+            DynamicNode::Placeholder(_) => {
+                to.create_placeholder(ElementId(mounted));
+                1
+            }
+        }
+    }
+
+    /// Replay the placeholders under the root node on the top of the stack in reverse order. See `load_placeholders`
+    fn replay_placeholders(
+        &self,
+        mount: MountId,
+        dynamic_nodes_iter: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
+        root_idx: u8,
+        dom: &VirtualDom,
+        to: &mut impl WriteMutations,
+    ) {
+        let Some((start, end)) = collect_dyn_node_range(dynamic_nodes_iter, root_idx) else {
+            return;
+        };
+
+        for dynamic_node_id in (start..=end).rev() {
+            let m = self.replay_dynamic_node(mount, dynamic_node_id, dom, to);
+            if m > 0 {
+                let path = &self.template.node_paths[dynamic_node_id][1..];
+                to.replace_placeholder_with_nodes(path, m);
+            }
+        }
+    }
+
+    /// Replay the attributes under the root node on the top of the stack. See `write_attrs`
+    fn replay_attrs(
+        &self,
+        mount: MountId,
+        dynamic_attributes_iter: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
+        root_idx: u8,
+        dom: &VirtualDom,
+        to: &mut impl WriteMutations,
+    ) {
+        let mut last_path = None;
+        let from_root_node = |(_, path): &(usize, &[u8])| path.first() == Some(&root_idx);
+        while let Some((attribute_idx, attribute_path)) =
+            dynamic_attributes_iter.next_if(from_root_node)
+        {
+            let id = dom.get_mounted_dyn_attr(mount, attribute_idx);
+            // Nodes below the root only need an id once, even if they have multiple dynamic attributes
+            if attribute_path.len() > 1 && last_path != Some(attribute_path) {
+                to.assign_node_id(&attribute_path[1..], id);
+            }
+            last_path = Some(attribute_path);
+
+            for attribute in &*self.dynamic_attrs[attribute_idx] {
+                match &attribute.value {
+                    AttributeValue::Listener(_) => {
+                        to.create_event_listener(&attribute.name[2..], id)
+                    }
+                    value => to.set_attribute(attribute.name, attribute.namespace, value, id),
+                }
+            }
+        }
+    }
+}
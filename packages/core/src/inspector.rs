@@ -0,0 +1,97 @@
+//! Inspect the scopes of a running VirtualDom for devtools
+
+use crate::{innerlude::ReactiveContext, ScopeId, VirtualDom};
+use rustc_hash::FxHashMap;
+
+/// A snapshot of one scope in the VirtualDom, made with [`VirtualDom::inspect_scopes`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ScopeInfo {
+    /// The id of the scope
+    pub id: ScopeId,
+    /// The parent of the scope, or `None` for [`ScopeId::ROOT`]
+    pub parent: Option<ScopeId>,
+    /// The name of the component that owns the scope
+    pub name: &'static str,
+    /// The number of ancestors the scope has
+    pub height: u32,
+    /// The props of the component formatted with `Debug`, or `None` if they don't implement `Debug`
+    pub props: Option<String>,
+    /// The number of hooks the component uses
+    pub hooks: usize,
+    /// The type names of the contexts the scope provides. Contexts inserted with
+    /// [`VirtualDom::insert_any_root_context`] have no name.
+    pub contexts: Vec<Option<&'static str>>,
+    /// The number of times the component rendered
+    pub render_count: usize,
+    /// The reactive context the component renders in. Signals the component reads while rendering are subscribed to it.
+    pub reactive_context: ReactiveContext,
+}
+
+impl VirtualDom {
+    /// Take a snapshot of every scope in the VirtualDom. Parents come before their children, and siblings are sorted by
+    /// their id.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// fn app() -> Element {
+    ///     use_context_provider(|| 0_u32);
+    ///     rsx! { Child { name: "world" } }
+    /// }
+    ///
+    /// #[component]
+    /// fn Child(name: String) -> Element {
+    ///     rsx! { "hello {name}" }
+    /// }
+    ///
+    /// let dom = VirtualDom::prebuilt(app);
+    /// let scopes = dom.inspect_scopes();
+    /// let child = scopes.iter().find(|scope| scope.name.ends_with("Child")).unwrap();
+    /// assert_eq!(child.props.as_deref(), Some(r#"ChildProps { name: "world" }"#));
+    /// ```
+    pub fn inspect_scopes(&self) -> Vec<ScopeInfo> {
+        // The scopes are iterated in order, so the children of every scope are sorted by id
+        let mut children: FxHashMap<ScopeId, Vec<ScopeId>> = FxHashMap::default();
+        for (id, _) in self.scopes.iter() {
+            let id = ScopeId(id);
+            if let Some(parent) = self.runtime.get_state(id).and_then(|state| state.parent_id) {
+                children.entry(parent).or_default().push(id);
+            }
+        }
+
+        let mut scopes = Vec::new();
+        let mut stack = vec![ScopeId::ROOT];
+        while let Some(id) = stack.pop() {
+            scopes.extend(self.inspect_scope(id));
+            // Push the children in reverse so the first child is inspected next
+            if let Some(children) = children.get(&id) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        scopes
+    }
+
+    fn inspect_scope(&self, id: ScopeId) -> Option<ScopeInfo> {
+        let scope = self.get_scope(id)?;
+        let state = self.runtime.get_state(id)?;
+        let names = state.context_names.borrow();
+        let contexts = state
+            .shared_contexts
+            .borrow()
+            .iter()
+            .map(|context| names.get(&(**context).type_id()).copied())
+            .collect();
+        let hooks = state.hooks.borrow().len();
+        Some(ScopeInfo {
+            id,
+            parent: state.parent_id,
+            name: state.name,
+            height: state.height,
+            props: scope.props.debug_props(),
+            hooks,
+            contexts,
+            render_count: state.render_count.get(),
+            reactive_context: scope.reactive_context,
+        })
+    }
+}
@@ -12,9 +12,12 @@ mod events;
 mod fragment;
 mod generational_box;
 mod global_context;
+mod inspector;
 mod launch;
 mod mutations;
 mod nodes;
+mod portal;
+mod profiler;
 mod properties;
 mod reactive_context;
 mod render_error;
@@ -26,6 +29,7 @@ mod scope_context;
 mod scopes;
 mod suspense;
 mod tasks;
+mod transition;
 mod virtual_dom;
 
 mod hotreload_utils;
@@ -54,9 +58,12 @@ pub(crate) mod innerlude {
     pub use crate::fragment::*;
     pub use crate::generational_box::*;
     pub use crate::global_context::*;
+    pub use crate::inspector::*;
     pub use crate::launch::*;
     pub use crate::mutations::*;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
+    pub use crate::profiler::*;
     pub use crate::properties::*;
     pub use crate::reactive_context::*;
     pub use crate::render_error::*;
@@ -65,6 +72,7 @@ pub(crate) mod innerlude {
     pub use crate::scopes::*;
     pub use crate::suspense::*;
     pub use crate::tasks::*;
+    pub use crate::transition::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`ScopeId`] or [`ScopeState`].
@@ -80,9 +88,10 @@ pub use crate::innerlude::{
     fc_to_builder, generation, schedule_update, schedule_update_any, use_hook, vdom_is_rendering,
     AnyValue, Attribute, AttributeValue, CapturedError, Component, ComponentFunction, DynamicNode,
     Element, ElementId, Event, Fragment, HasAttributes, IntoDynNode, LaunchConfig, MarkerWrapper,
-    Mutation, Mutations, NoOpMutations, Ok, Properties, Result, Runtime, ScopeId, ScopeState,
-    SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner,
-    VPlaceholder, VText, VirtualDom, WriteMutations,
+    Mutation, Mutations, NoOpMutations, Ok, PortalTarget, Profile, Properties, RenderCause,
+    RenderRecord, Result, Runtime, ScopeId, ScopeInfo, ScopeProfile, ScopeState, SpawnIfAsync,
+    SubscriptionSource, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode,
+    VNodeInner, VPlaceholder, VText, VirtualDom, WriteMutations,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -93,14 +102,15 @@ pub mod prelude {
         consume_context, consume_context_from_scope, current_owner, current_scope_id,
         fc_to_builder, force_all_dirty, generation, has_context, needs_update, needs_update_any,
         parent_scope, provide_context, provide_error_boundary, provide_root_context, queue_effect,
-        remove_future, spawn, spawn_forever, spawn_isomorphic, suspend, suspense_context,
-        throw_error, try_consume_context, use_after_render, use_before_render, use_drop, use_hook,
-        use_hook_with_cleanup, with_owner, AnyValue, Attribute, Callback, Component,
-        ComponentFunction, Context, Element, ErrorBoundary, ErrorContext, Event, EventHandler,
-        Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, OptionStringFromMarker,
-        Properties, ReactiveContext, RenderError, Runtime, RuntimeGuard, ScopeId, ScopeState,
-        SuperFrom, SuperInto, SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps,
-        SuspenseContext, SuspenseExtension, Task, Template, TemplateAttribute, TemplateNode, VNode,
+        remove_future, spawn, spawn_forever, spawn_isomorphic, start_transition, suspend,
+        suspense_context, throw_error, try_consume_context, use_after_render, use_before_render,
+        use_drop, use_hook, use_hook_with_cleanup, with_owner, AnyValue, Attribute, Callback,
+        Component, ComponentFunction, Context, Element, ErrorBoundary, ErrorContext, Event,
+        EventHandler, Fragment, HasAttributes, IntoAttributeValue, IntoDynNode,
+        OptionStringFromMarker, Portal, PortalProps, PortalTarget, Properties, ReactiveContext,
+        RenderError, Runtime, RuntimeGuard, ScopeId, ScopeState, SuperFrom, SuperInto,
+        SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps, SuspenseContext,
+        SuspenseExtension, Task, Template, TemplateAttribute, TemplateNode, Transition, VNode,
         VNodeInner, VirtualDom,
     };
 }
@@ -1,4 +1,4 @@
-use crate::{arena::ElementId, AttributeValue, Template};
+use crate::{arena::ElementId, AttributeValue, PortalTarget, Template};
 
 /// Something that can handle the mutations that are generated by the diffing process and apply them to the Real DOM
 ///
@@ -115,6 +115,18 @@ pub trait WriteMutations {
     ///
     /// Id: The ID of the root node to push.
     fn push_root(&mut self, id: ElementId);
+
+    /// Append the m nodes on the stack to a portal mount inside of the target. The portal mount is created the first
+    /// time the id is used, and moved if the target changes.
+    ///
+    /// Events that bubble from the children of the portal should stop at the portal mount and be sent to the
+    /// VirtualDom with the id of the portal mount. The VirtualDom continues bubbling them from the logical parent of
+    /// the portal.
+    ///
+    /// Target: Where the portal mount is placed
+    /// Id: The ID of the portal mount
+    /// M: The number of nodes on the stack to append to the portal mount
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize);
 }
 
 /// A `Mutation` represents a single instruction for the renderer to use to modify the UI tree to match the state
@@ -279,6 +291,19 @@ pub enum Mutation {
         /// The ID of the root node to push.
         id: ElementId,
     },
+
+    /// Append the m nodes on the stack to a portal mount inside of the target. The portal mount is created the first
+    /// time the id is used, and moved if the target changes.
+    MountPortal {
+        /// Where the portal mount is placed
+        target: PortalTarget,
+
+        /// The ID of the portal mount
+        id: ElementId,
+
+        /// The number of nodes on the stack to append to the portal mount
+        m: usize,
+    },
 }
 
 /// A static list of mutations that can be applied to the DOM. Note: this list does not contain any `Any` attribute values
@@ -378,6 +403,14 @@ impl WriteMutations for Mutations {
     fn push_root(&mut self, id: ElementId) {
         self.edits.push(Mutation::PushRoot { id })
     }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize) {
+        self.edits.push(Mutation::MountPortal {
+            target: target.clone(),
+            id,
+            m,
+        })
+    }
 }
 
 /// A struct that ignores all mutations
@@ -420,4 +453,6 @@ impl WriteMutations for NoOpMutations {
     fn remove_node(&mut self, _: ElementId) {}
 
     fn push_root(&mut self, _: ElementId) {}
+
+    fn mount_portal(&mut self, _: &PortalTarget, _: ElementId, _: usize) {}
 }
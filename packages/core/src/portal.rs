@@ -0,0 +1,540 @@
+//! Portals render their children into another location in the renderer while they stay attached to their logical
+//! parent in the component tree
+
+use crate::innerlude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// Where a [`Portal()`] mounts its children
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PortalTarget {
+    /// A mount point with this name. Renderers create the mount point at the end of the document the first time a
+    /// portal uses it, and every portal with the same name shares it.
+    Named(String),
+    /// The element with this id. Portals are mounted after the rest of the tree in each render, so the element may be
+    /// rendered by the same app, but not inside of the portal itself.
+    Element(String),
+}
+
+impl PortalTarget {
+    /// Target the mount point with this name
+    pub fn named(name: impl ToString) -> Self {
+        Self::Named(name.to_string())
+    }
+
+    /// Target the element with this id
+    pub fn element(id: impl ToString) -> Self {
+        Self::Element(id.to_string())
+    }
+}
+
+impl Default for PortalTarget {
+    fn default() -> Self {
+        Self::Named("default".to_string())
+    }
+}
+
+impl From<&str> for PortalTarget {
+    fn from(name: &str) -> Self {
+        Self::named(name)
+    }
+}
+
+impl From<String> for PortalTarget {
+    fn from(name: String) -> Self {
+        Self::Named(name)
+    }
+}
+
+/// The children of a portal that are currently mounted
+#[derive(Default)]
+struct PortalMount {
+    children: RefCell<Option<Element>>,
+    /// The target the portal mount was written to
+    target: RefCell<Option<PortalTarget>>,
+    /// The id of the portal mount if the children were written to the renderer
+    id: Cell<Option<ElementId>>,
+    /// The logical parent the children are created with
+    parent: Cell<Option<ElementRef>>,
+    /// If the children are waiting to be written to the renderer at the end of the render
+    queued: Cell<bool>,
+}
+
+/// Properties for the [`Portal()`] component.
+#[allow(non_camel_case_types)]
+pub struct PortalProps {
+    target: PortalTarget,
+    /// The children of the portal
+    children: Element,
+    mount: Rc<PortalMount>,
+}
+
+impl Clone for PortalProps {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            children: self.children.clone(),
+            mount: self.mount.clone(),
+        }
+    }
+}
+
+impl PortalProps {
+    /**
+    Create a builder for building `PortalProps`.
+    On the builder, call `.target(...)`(optional), `.children(...)`(optional) to set the values of the fields.
+    Finally, call `.build()` to create the instance of `PortalProps`.
+                        */
+    #[allow(dead_code)]
+    pub fn builder() -> PortalPropsBuilder<((), ())> {
+        PortalPropsBuilder { fields: ((), ()) }
+    }
+}
+#[must_use]
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub struct PortalPropsBuilder<TypedBuilderFields> {
+    fields: TypedBuilderFields,
+}
+impl Properties for PortalProps {
+    type Builder = PortalPropsBuilder<((), ())>;
+    fn builder() -> Self::Builder {
+        PortalProps::builder()
+    }
+    fn memoize(&mut self, new: &Self) -> bool {
+        let equal = self == new;
+        if !equal {
+            self.target.clone_from(&new.target);
+            self.children.clone_from(&new.children);
+        }
+        equal
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub trait PortalPropsBuilder_Optional<T> {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
+}
+impl<T> PortalPropsBuilder_Optional<T> for () {
+    fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
+        default()
+    }
+}
+impl<T> PortalPropsBuilder_Optional<T> for (T,) {
+    fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
+        self.0
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> PortalPropsBuilder<((), __children)> {
+    pub fn target(
+        self,
+        target: impl ::core::convert::Into<PortalTarget>,
+    ) -> PortalPropsBuilder<((PortalTarget,), __children)> {
+        let target = (target.into(),);
+        let (_, children) = self.fields;
+        PortalPropsBuilder {
+            fields: (target, children),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum PortalPropsBuilder_Error_Repeated_field_target {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__children> PortalPropsBuilder<((PortalTarget,), __children)> {
+    #[deprecated(note = "Repeated field target")]
+    pub fn target(
+        self,
+        _: PortalPropsBuilder_Error_Repeated_field_target,
+    ) -> PortalPropsBuilder<((PortalTarget,), __children)> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__target> PortalPropsBuilder<(__target, ())> {
+    pub fn children(self, children: Element) -> PortalPropsBuilder<(__target, (Element,))> {
+        let children = (children,);
+        let (target, _) = self.fields;
+        PortalPropsBuilder {
+            fields: (target, children),
+        }
+    }
+}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+pub enum PortalPropsBuilder_Error_Repeated_field_children {}
+#[doc(hidden)]
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<__target> PortalPropsBuilder<(__target, (Element,))> {
+    #[deprecated(note = "Repeated field children")]
+    pub fn children(
+        self,
+        _: PortalPropsBuilder_Error_Repeated_field_children,
+    ) -> PortalPropsBuilder<(__target, (Element,))> {
+        self
+    }
+}
+#[allow(dead_code, non_camel_case_types, missing_docs)]
+impl<
+        __target: PortalPropsBuilder_Optional<PortalTarget>,
+        __children: PortalPropsBuilder_Optional<Element>,
+    > PortalPropsBuilder<(__target, __children)>
+{
+    pub fn build(self) -> PortalProps {
+        let (target, children) = self.fields;
+        let target = PortalPropsBuilder_Optional::into_value(target, PortalTarget::default);
+        let children = PortalPropsBuilder_Optional::into_value(children, VNode::empty);
+        PortalProps {
+            target,
+            children,
+            mount: Default::default(),
+        }
+    }
+}
+#[automatically_derived]
+#[allow(non_camel_case_types)]
+impl ::core::cmp::PartialEq for PortalProps {
+    #[inline]
+    fn eq(&self, other: &PortalProps) -> bool {
+        self.target == other.target && self.children == other.children
+    }
+}
+
+/// Portals render their children into another location in the renderer, like a named mount point at the end of the
+/// document or an existing element. Use them for modals, tooltips and toasts that need to escape parents with
+/// `overflow: hidden` or a stacking context.
+///
+/// The children of a portal stay attached to the portal in the component tree. They can consume context from the
+/// portal's parents, events bubble from the children to the portal's parents, and errors and suspense are handled by
+/// the boundaries around the portal.
+///
+/// # Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn App() -> Element {
+///     let mut open = use_signal(|| false);
+///     rsx! {
+///         div { overflow: "hidden",
+///             button { onclick: move |_| open.set(true), "Open" }
+///             if open() {
+///                 Portal { target: "modals",
+///                     div { class: "modal", onclick: move |_| open.set(false), "Close" }
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// Pass a [`PortalTarget::element`] to render into an element that already exists in the document instead:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn Sidebar() -> Element {
+///     rsx! {
+///         Portal { target: PortalTarget::element("sidebar"), "Sidebar content" }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Portal(__props: PortalProps) -> Element {
+    unreachable!("Portal should not be called directly")
+}
+#[allow(non_snake_case)]
+#[doc(hidden)]
+mod Portal_completions {
+    #[doc(hidden)]
+    #[allow(non_camel_case_types)]
+    /// This enum is generated to help autocomplete the braces after the component. It does nothing
+    pub enum Component {
+        Portal {},
+    }
+}
+#[allow(unused)]
+pub use Portal_completions::Component::Portal;
+
+/// Portals have a custom diffing algorithm that writes their children to the portal target and a placeholder to their
+/// position in the parent
+impl PortalProps {
+    /// Try to downcast [`AnyProps`] to [`PortalProps`]
+    pub(crate) fn downcast_from_props(props: &mut dyn AnyProps) -> Option<&mut Self> {
+        props.props_mut().downcast_mut()
+    }
+
+    pub(crate) fn create<M: WriteMutations>(
+        mount: MountId,
+        idx: usize,
+        component: &VComponent,
+        parent: Option<ElementRef>,
+        dom: &mut VirtualDom,
+        to: Option<&mut M>,
+    ) -> usize {
+        let mut scope_id = ScopeId(dom.get_mounted_dyn_node(mount, idx));
+        // If the ScopeId is a placeholder, we need to load up a new scope for this vcomponent. If it's already mounted, then we can just use that
+        if scope_id.is_placeholder() {
+            scope_id = dom
+                .new_scope(component.props.duplicate(), component.name)
+                .state()
+                .id;
+
+            // Store the scope id for the next render
+            dom.set_mounted_dyn_node(mount, idx, scope_id.0);
+        }
+
+        dom.runtime.clone().with_scope_on_stack(scope_id, || {
+            let props = Self::downcast_from_props(&mut *dom.scopes[scope_id.0].props).unwrap();
+            let portal_mount = props.mount.clone();
+            portal_mount.parent.set(parent);
+
+            let mut to = to.filter(|_| dom.runtime.scope_should_render(scope_id));
+
+            match to.is_some() {
+                // The target of the portal may be created later in this render, so we write the children at the end of
+                // the render once the rest of the tree is mounted
+                true => {
+                    portal_mount.queued.set(true);
+                    dom.queued_portals.push(scope_id);
+                }
+                // Otherwise create the children in the background right away so they run with the rest of the tree
+                false => {
+                    let children = props.children.clone();
+                    children.as_vnode().create(dom, parent, None::<&mut M>);
+                    *portal_mount.children.borrow_mut() = Some(children);
+                }
+            }
+
+            // Then mark the position of the portal in the parent with a placeholder
+            let placeholder = dom.scopes[scope_id.0]
+                .last_rendered_node
+                .clone()
+                .unwrap_or_else(|| Element::Ok(VNode::placeholder()));
+            let nodes_created = placeholder
+                .as_vnode()
+                .create(dom, parent, to.as_deref_mut());
+            dom.scopes[scope_id.0].last_rendered_node = Some(placeholder);
+
+            if to.is_some() {
+                dom.runtime.get_state(scope_id).unwrap().mount(&dom.runtime);
+            }
+
+            nodes_created
+        })
+    }
+
+    /// Create the children of a portal that was queued while it was created and write them to the portal target
+    fn create_queued<M: WriteMutations>(scope_id: ScopeId, dom: &mut VirtualDom, to: &mut M) {
+        let Some(props) = dom
+            .scopes
+            .get_mut(scope_id.0)
+            .and_then(|scope| Self::downcast_from_props(&mut *scope.props))
+        else {
+            return;
+        };
+        // The portal may have been removed before the end of the render
+        let portal_mount = props.mount.clone();
+        if !portal_mount.queued.replace(false) {
+            return;
+        }
+        let target = props.target.clone();
+        // If the portal was moved to the background, the children are already mounted
+        let children = portal_mount
+            .children
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| props.children.clone());
+
+        dom.runtime.clone().with_scope_on_stack(scope_id, || {
+            // The children are created with the logical parent so events bubble through the component tree instead of
+            // the portal target
+            let m = children
+                .as_vnode()
+                .create(dom, portal_mount.parent.get(), Some(&mut *to));
+            // Events that bubble to the portal mount continue from the logical parent of the portal
+            let id = dom.next_element();
+            dom.runtime.elements.borrow_mut()[id.0] = portal_mount.parent.get();
+            to.mount_portal(&target, id, m);
+            portal_mount.id.set(Some(id));
+            *portal_mount.target.borrow_mut() = Some(target);
+            *portal_mount.children.borrow_mut() = Some(children);
+        })
+    }
+
+    pub(crate) fn diff<M: WriteMutations>(
+        scope_id: ScopeId,
+        dom: &mut VirtualDom,
+        to: Option<&mut M>,
+    ) {
+        dom.runtime.clone().with_scope_on_stack(scope_id, || {
+            let props = Self::downcast_from_props(&mut *dom.scopes[scope_id.0].props)
+                .unwrap()
+                .clone();
+            let portal_mount = props.mount;
+            let Some(old_children) = portal_mount.children.borrow().clone() else {
+                return;
+            };
+            let new_children = props.children;
+
+            // Only write mutations if the children were written to the renderer when they were created
+            let id = portal_mount.id.get();
+            let mut to = to
+                .filter(|_| id.is_some())
+                .filter(|_| dom.runtime.scope_should_render(scope_id));
+
+            old_children
+                .as_vnode()
+                .diff_node(new_children.as_vnode(), dom, to.as_deref_mut());
+            *portal_mount.children.borrow_mut() = Some(new_children);
+
+            // If the target changed, move the portal mount to the new target
+            if let (Some(to), Some(id)) = (to, id) {
+                let mut target = portal_mount.target.borrow_mut();
+                if target.as_ref() != Some(&props.target) {
+                    to.mount_portal(&props.target, id, 0);
+                    *target = Some(props.target);
+                }
+            }
+        })
+    }
+
+    /// Remove the children of the portal. The portal mount is removed from the renderer with the children if there is
+    /// a mutation writer. Otherwise, it is removed the next time the VirtualDom writes mutations.
+    pub(crate) fn remove_portal_nodes<M: WriteMutations>(
+        dom: &mut VirtualDom,
+        to: Option<&mut M>,
+        scope_id: ScopeId,
+        destroy_component_state: bool,
+    ) {
+        let Some(props) = Self::downcast_from_props(&mut *dom.scopes[scope_id.0].props) else {
+            return;
+        };
+        let portal_mount = props.mount.clone();
+        portal_mount.queued.set(false);
+        // If the portal is only moved to the background, keep the children so they can be created again later
+        let children = match destroy_component_state {
+            true => portal_mount.children.take(),
+            false => portal_mount.children.borrow().clone(),
+        };
+        let id = portal_mount.id.take();
+        portal_mount.target.take();
+
+        // Only write mutations if the children were written to the renderer
+        let mut to = to.filter(|_| id.is_some());
+        if let Some(children) = children {
+            children.as_vnode().remove_node_inner(
+                dom,
+                to.as_deref_mut(),
+                destroy_component_state,
+                None,
+            );
+        }
+        match (to, id) {
+            (Some(to), Some(id)) => {
+                to.remove_node(id);
+                dom.reclaim(id);
+            }
+            // The portal mount is not inside of the parent that is being removed, so it needs to be removed the next
+            // time the VirtualDom writes mutations
+            (None, Some(id)) => dom.removed_portals.push(id),
+            _ => {}
+        }
+    }
+
+    /// Replay the children of the portal into the portal mount they are mounted in, if they were written to the
+    /// renderer
+    pub(crate) fn replay(scope_id: ScopeId, dom: &VirtualDom, to: &mut impl WriteMutations) {
+        let Some(props) = dom
+            .scopes
+            .get(scope_id.0)
+            .and_then(|scope| scope.props.props().downcast_ref::<Self>())
+        else {
+            return;
+        };
+        let portal_mount = &props.mount;
+        let (Some(children), Some(target), Some(id)) = (
+            portal_mount.children.borrow().clone(),
+            portal_mount.target.borrow().clone(),
+            portal_mount.id.get(),
+        ) else {
+            return;
+        };
+        let m = children.as_vnode().replay(dom, to);
+        to.mount_portal(&target, id, m);
+    }
+
+    #[doc(hidden)]
+    /// Write the children of a portal and any portals nested inside of it that were created while the renderer
+    /// skipped mutations.
+    ///
+    /// This should only be called by dioxus-web after the placeholder of the portal has been hydrated.
+    pub fn mount_hydrated<M: WriteMutations>(scope_id: ScopeId, dom: &VirtualDom, to: &mut M) {
+        dom.replay_portals(Some(scope_id), to)
+    }
+}
+
+impl ScopeState {
+    /// If this scope is a [`Portal()`], get the target of the portal and the children it renders there.
+    ///
+    /// This is useful for renderers that walk the tree outside of the VirtualDom, like SSR.
+    pub fn portal(&self) -> Option<(PortalTarget, VNode)> {
+        let props = self.props.props().downcast_ref::<PortalProps>()?;
+        let children = props.mount.children.borrow();
+        let children = children.as_ref()?.as_vnode().clone();
+        Some((props.target.clone(), children))
+    }
+}
+
+impl VirtualDom {
+    /// Write the children of the portals that were created in this render. Creating a portal may queue more portals
+    /// nested inside of it.
+    pub(crate) fn flush_queued_portals(&mut self, to: &mut impl WriteMutations) {
+        while !self.queued_portals.is_empty() {
+            for scope_id in std::mem::take(&mut self.queued_portals) {
+                PortalProps::create_queued(scope_id, self, to);
+            }
+        }
+    }
+
+    /// Replay every portal that is written to the renderer after the rest of the tree. Portals are replayed from the
+    /// top of the tree down so portals that target elements in other portals find their target. If `under` is set,
+    /// only that portal and the portals nested inside of it are replayed.
+    pub(crate) fn replay_portals(&self, under: Option<ScopeId>, to: &mut impl WriteMutations) {
+        let is_under = |mut scope: ScopeId| loop {
+            if Some(scope) == under {
+                return true;
+            }
+            match self
+                .runtime
+                .get_state(scope)
+                .and_then(|state| state.parent_id())
+            {
+                Some(parent) => scope = parent,
+                None => return false,
+            }
+        };
+        let mut portals: Vec<_> = self
+            .scopes
+            .iter()
+            .filter(|(_, scope)| scope.props.props().is::<PortalProps>())
+            .map(|(id, _)| ScopeId(id))
+            .filter(|scope| under.is_none() || is_under(*scope))
+            .filter_map(|scope| Some((self.runtime.get_state(scope)?.height(), scope)))
+            .collect();
+        portals.sort();
+        for (_, scope) in portals {
+            PortalProps::replay(scope, self, to);
+        }
+    }
+
+    /// Remove the portal mounts that were removed with their parent while the VirtualDom was not writing mutations
+    pub(crate) fn flush_removed_portals(&mut self, to: &mut impl WriteMutations) {
+        for id in std::mem::take(&mut self.removed_portals) {
+            to.remove_node(id);
+            self.reclaim(id);
+        }
+    }
+}
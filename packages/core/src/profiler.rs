@@ -0,0 +1,510 @@
+//! An opt-in profiler that records every time a component renders, how long it took and what caused it.
+
+use crate::{AttributeValue, ElementId, PortalTarget, ScopeId, Template, WriteMutations};
+use generational_box::GenerationalBoxId;
+use rustc_hash::FxHashMap;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Write},
+    panic::Location,
+    time::Duration,
+};
+
+/// A reactive value that marked a [`ReactiveContext`](crate::prelude::ReactiveContext) dirty, like a signal or memo
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionSource {
+    /// The id of the value
+    pub id: GenerationalBoxId,
+    /// Where the value was created. This is only known in debug builds.
+    pub created_at: Option<&'static Location<'static>>,
+}
+
+/// Why a component rendered
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RenderCause {
+    /// The component rendered for the first time
+    Mount,
+    /// The parent of the component rendered and passed it props that changed
+    Props,
+    /// A value the component read while rendering changed
+    Subscription(SubscriptionSource),
+    /// The component was marked dirty without a known source, like with [`needs_update`](crate::prelude::needs_update)
+    Scheduled,
+}
+
+impl fmt::Display for RenderCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderCause::Mount => write!(f, "mount"),
+            RenderCause::Props => write!(f, "props changed"),
+            RenderCause::Subscription(source) => match source.created_at {
+                Some(location) => write!(f, "{:?} created at {location} changed", source.id),
+                None => write!(f, "{:?} changed", source.id),
+            },
+            RenderCause::Scheduled => write!(f, "scheduled"),
+        }
+    }
+}
+
+/// One render of a component recorded by the profiler
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RenderRecord {
+    /// The scope that rendered
+    pub scope: ScopeId,
+    /// The name of the component
+    pub name: &'static str,
+    /// Everything that marked the component dirty since it last rendered
+    pub causes: Vec<RenderCause>,
+    /// When the component started rendering, relative to when profiling started
+    pub start: Duration,
+    /// How long the component took to run
+    pub render_duration: Duration,
+    /// How long diffing the output of the component took. Child components that rendered while diffing are not included.
+    pub diff_duration: Duration,
+    /// How many mutations diffing the output emitted. Mutations from child components are not included.
+    pub mutations: usize,
+    /// The start and total duration of the diff, including child components
+    diff_span: Option<(Duration, Duration)>,
+}
+
+/// The renders of a component added up
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ScopeProfile {
+    /// The scope
+    pub scope: ScopeId,
+    /// The name of the component
+    pub name: &'static str,
+    /// How many times the component rendered
+    pub renders: usize,
+    /// How long the component ran in total
+    pub render_duration: Duration,
+    /// How long diffing the output of the component took in total
+    pub diff_duration: Duration,
+    /// How many mutations the component emitted in total
+    pub mutations: usize,
+    /// Every cause of a render, with how many renders it caused. Most frequent causes come first.
+    pub causes: Vec<(RenderCause, usize)>,
+}
+
+/// The renders a [`VirtualDom`](crate::VirtualDom) recorded while profiling
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     rsx! { "hello world" }
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.start_profiling();
+/// dom.rebuild_in_place();
+/// let profile = dom.stop_profiling();
+///
+/// let root = profile.scope(ScopeId::ROOT).unwrap();
+/// assert_eq!(root.renders, 1);
+///
+/// // Open the trace in chrome://tracing, Perfetto or speedscope
+/// std::fs::write("trace.json", profile.to_chrome_trace()).unwrap();
+/// # std::fs::remove_file("trace.json").unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    renders: Vec<RenderRecord>,
+}
+
+impl Profile {
+    /// Every render in the order the components started rendering
+    pub fn renders(&self) -> &[RenderRecord] {
+        &self.renders
+    }
+
+    /// The renders of one scope
+    pub fn renders_of(&self, scope: ScopeId) -> impl Iterator<Item = &RenderRecord> {
+        self.renders
+            .iter()
+            .filter(move |record| record.scope == scope)
+    }
+
+    /// The renders of one scope added up, or `None` if the scope didn't render
+    pub fn scope(&self, scope: ScopeId) -> Option<ScopeProfile> {
+        self.scopes()
+            .into_iter()
+            .find(|profile| profile.scope == scope)
+    }
+
+    /// The renders of every scope added up. The scopes that rendered most often come first.
+    pub fn scopes(&self) -> Vec<ScopeProfile> {
+        let mut scopes: Vec<ScopeProfile> = Vec::new();
+        let mut index = FxHashMap::default();
+        for record in &self.renders {
+            let profile = index.entry(record.scope).or_insert_with(|| {
+                scopes.push(ScopeProfile {
+                    scope: record.scope,
+                    name: record.name,
+                    renders: 0,
+                    render_duration: Duration::ZERO,
+                    diff_duration: Duration::ZERO,
+                    mutations: 0,
+                    causes: Vec::new(),
+                });
+                scopes.len() - 1
+            });
+            let profile = &mut scopes[*profile];
+            // Scope ids are reused after a component is dropped
+            profile.name = record.name;
+            profile.renders += 1;
+            profile.render_duration += record.render_duration;
+            profile.diff_duration += record.diff_duration;
+            profile.mutations += record.mutations;
+            for cause in &record.causes {
+                match profile.causes.iter_mut().find(|(c, _)| c == cause) {
+                    Some((_, count)) => *count += 1,
+                    None => profile.causes.push((*cause, 1)),
+                }
+            }
+        }
+        for profile in &mut scopes {
+            profile
+                .causes
+                .sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        }
+        scopes.sort_by_key(|profile| std::cmp::Reverse(profile.renders));
+        scopes
+    }
+
+    /// Export the profile in the [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU).
+    ///
+    /// The trace can be opened in `chrome://tracing`, [Perfetto](https://ui.perfetto.dev) or [speedscope](https://www.speedscope.app).
+    /// Every render is a `render` event followed by a `diff` event that contains the events of the child components that
+    /// rendered while diffing.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from(
+            r#"{"displayTimeUnit":"ms","traceEvents":[{"name":"thread_name","ph":"M","pid":1,"tid":1,"args":{"name":"VirtualDom"}}"#,
+        );
+        for record in &self.renders {
+            let mut args = format!(r#""scope":{},"causes":["#, record.scope.0);
+            for (i, cause) in record.causes.iter().enumerate() {
+                if i > 0 {
+                    args.push(',');
+                }
+                write_json_string(&mut args, &cause.to_string());
+            }
+            args.push(']');
+
+            write_trace_event(
+                &mut trace,
+                record.name,
+                "render",
+                record.start,
+                record.render_duration,
+                &args,
+            );
+            if let Some((start, duration)) = record.diff_span {
+                let args = format!(
+                    r#""scope":{},"mutations":{}"#,
+                    record.scope.0, record.mutations
+                );
+                write_trace_event(&mut trace, record.name, "diff", start, duration, &args);
+            }
+        }
+        trace.push_str("]}");
+        trace
+    }
+}
+
+fn write_trace_event(
+    trace: &mut String,
+    name: &str,
+    category: &str,
+    start: Duration,
+    duration: Duration,
+    args: &str,
+) {
+    trace.push_str(r#",{"name":"#);
+    write_json_string(trace, name);
+    _ = write!(
+        trace,
+        r#","cat":"{category}","ph":"X","pid":1,"tid":1,"ts":{},"dur":{},"args":{{{args}}}}}"#,
+        start.as_secs_f64() * 1_000_000.0,
+        duration.as_secs_f64() * 1_000_000.0,
+    );
+}
+
+fn write_json_string(buf: &mut String, value: &str) {
+    buf.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            c if (c as u32) < 0x20 => _ = write!(buf, "\\u{:04x}", c as u32),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+/// A render or diff that is in progress. Frames nest when child components render while their parent is diffed.
+struct Frame {
+    scope: ScopeId,
+    start: Duration,
+    mutations: usize,
+    /// The time and mutations of the frames nested in this frame
+    nested_time: Duration,
+    nested_mutations: usize,
+}
+
+/// The profiler of a [`Runtime`](crate::Runtime). Every method is a no-op unless profiling is enabled.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    clock: RefCell<Option<Clock>>,
+    /// The number of mutations emitted since profiling started
+    mutations: Cell<usize>,
+    frames: RefCell<Vec<Frame>>,
+    /// The causes of scopes that are dirty but didn't render yet
+    pending: RefCell<FxHashMap<ScopeId, Vec<RenderCause>>>,
+    /// The last render of every scope, so the diff of the render can be added to it
+    last_render: RefCell<FxHashMap<ScopeId, usize>>,
+    profile: RefCell<Profile>,
+}
+
+impl Profiler {
+    pub(crate) fn enabled(&self) -> bool {
+        self.clock.borrow().is_some()
+    }
+
+    pub(crate) fn start(&self) {
+        self.stop();
+        *self.clock.borrow_mut() = Some(Clock::new());
+    }
+
+    pub(crate) fn stop(&self) -> Profile {
+        *self.clock.borrow_mut() = None;
+        self.frames.borrow_mut().clear();
+        self.pending.borrow_mut().clear();
+        self.last_render.borrow_mut().clear();
+        self.mutations.set(0);
+        std::mem::take(&mut *self.profile.borrow_mut())
+    }
+
+    pub(crate) fn profile(&self) -> Profile {
+        self.profile.borrow().clone()
+    }
+
+    fn now(&self) -> Option<Duration> {
+        self.clock.borrow().as_ref().map(Clock::elapsed)
+    }
+
+    /// Record why a scope was marked dirty
+    pub(crate) fn record_cause(&self, scope: ScopeId, cause: RenderCause) {
+        if !self.enabled() {
+            return;
+        }
+        let mut pending = self.pending.borrow_mut();
+        let causes = pending.entry(scope).or_default();
+        if !causes.contains(&cause) {
+            causes.push(cause);
+        }
+    }
+
+    /// Start a render or diff of the scope
+    pub(crate) fn enter(&self, scope: ScopeId) {
+        let Some(start) = self.now() else {
+            return;
+        };
+        self.frames.borrow_mut().push(Frame {
+            scope,
+            start,
+            mutations: self.mutations.get(),
+            nested_time: Duration::ZERO,
+            nested_mutations: 0,
+        });
+    }
+
+    /// Finish the render on the top of the stack
+    pub(crate) fn exit_render(&self, name: &'static str, mounting: bool) {
+        let Some((frame, duration, mutations)) = self.pop_frame() else {
+            return;
+        };
+        let pending = self.pending.borrow_mut().remove(&frame.scope);
+        let causes = match mounting {
+            // A new scope may reuse the id of a scope that was dirty when it was dropped
+            true => vec![RenderCause::Mount],
+            false => pending.unwrap_or_default(),
+        };
+        let mut profile = self.profile.borrow_mut();
+        self.last_render
+            .borrow_mut()
+            .insert(frame.scope, profile.renders.len());
+        profile.renders.push(RenderRecord {
+            scope: frame.scope,
+            name,
+            causes,
+            start: frame.start,
+            render_duration: duration,
+            diff_duration: Duration::ZERO,
+            mutations,
+            diff_span: None,
+        });
+    }
+
+    /// Finish the diff on the top of the stack and add it to the last render of the scope
+    pub(crate) fn exit_diff(&self) {
+        let Some((frame, duration, mutations)) = self.pop_frame() else {
+            return;
+        };
+        let Some(index) = self.last_render.borrow().get(&frame.scope).copied() else {
+            return;
+        };
+        let total = frame.nested_time + duration;
+        let record = &mut self.profile.borrow_mut().renders[index];
+        record.diff_duration += duration;
+        record.mutations += mutations;
+        record.diff_span = Some((frame.start, total));
+    }
+
+    /// Pop the frame on the top of the stack and return the time it took and the mutations it emitted, not including
+    /// nested frames
+    fn pop_frame(&self) -> Option<(Frame, Duration, usize)> {
+        let now = self.now()?;
+        let mut frames = self.frames.borrow_mut();
+        // Profiling may have started while the frame was in progress
+        let frame = frames.pop()?;
+        let total = now.saturating_sub(frame.start);
+        let mutations = self.mutations.get() - frame.mutations;
+        if let Some(parent) = frames.last_mut() {
+            parent.nested_time += total;
+            parent.nested_mutations += mutations;
+        }
+        let duration = total.saturating_sub(frame.nested_time);
+        let mutations = mutations - frame.nested_mutations;
+        Some((frame, duration, mutations))
+    }
+
+    /// Count the mutations written to the mutation writer
+    pub(crate) fn count_mutations<'a, M: WriteMutations>(
+        &'a self,
+        to: &'a mut M,
+    ) -> CountMutations<'a, M> {
+        CountMutations {
+            to,
+            count: &self.mutations,
+        }
+    }
+}
+
+/// `std::time::Instant` panics on wasm32-unknown-unknown, so the clock uses `web_time` which falls back to
+/// `performance.now()` there
+struct Clock(web_time::Instant);
+
+impl Clock {
+    fn new() -> Self {
+        Self(web_time::Instant::now())
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// A mutation writer that counts the mutations written through it
+pub(crate) struct CountMutations<'a, M> {
+    to: &'a mut M,
+    count: &'a Cell<usize>,
+}
+
+impl<M> CountMutations<'_, M> {
+    fn count(&self) {
+        self.count.set(self.count.get() + 1);
+    }
+}
+
+impl<M: WriteMutations> WriteMutations for CountMutations<'_, M> {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.count();
+        self.to.append_children(id, m)
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.count();
+        self.to.assign_node_id(path, id)
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.count();
+        self.to.create_placeholder(id)
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.count();
+        self.to.create_text_node(value, id)
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        self.count();
+        self.to.load_template(template, index, id)
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.count();
+        self.to.replace_node_with(id, m)
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.count();
+        self.to.replace_placeholder_with_nodes(path, m)
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.count();
+        self.to.insert_nodes_after(id, m)
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.count();
+        self.to.insert_nodes_before(id, m)
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.count();
+        self.to.set_attribute(name, ns, value, id)
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.count();
+        self.to.set_node_text(value, id)
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count();
+        self.to.create_event_listener(name, id)
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count();
+        self.to.remove_event_listener(name, id)
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.count();
+        self.to.remove_node(id)
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.count();
+        self.to.push_root(id)
+    }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize) {
+        self.count();
+        self.to.mount_portal(target, id, m)
+    }
+}
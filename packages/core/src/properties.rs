@@ -56,6 +56,14 @@ pub trait Properties: Clone + Sized + 'static {
     /// Make the old props equal to the new props. Return if the props were equal and should be memoized.
     fn memoize(&mut self, other: &Self) -> bool;
 
+    /// Format the props so devtools can show them. Returns `None` if the props can't be formatted.
+    ///
+    /// The `Props` derive implements this by formatting every field that implements `Debug`. Other fields are shown
+    /// as `_`.
+    fn debug_props(&self) -> Option<String> {
+        None
+    }
+
     /// Create a component from the props.
     fn into_vcomponent<M: 'static>(self, render_fn: impl ComponentFunction<Self, M>) -> VComponent {
         let type_name = std::any::type_name_of_val(&render_fn);
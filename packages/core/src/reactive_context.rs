@@ -2,12 +2,12 @@ use crate::{
     prelude::{current_scope_id, ScopeId},
     scope_context::Scope,
     tasks::SchedulerMsg,
-    Runtime,
+    Runtime, SubscriptionSource,
 };
 use futures_channel::mpsc::UnboundedReceiver;
 use generational_box::{BorrowMutError, GenerationalBox, SyncStorage};
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::HashSet,
     hash::Hash,
     sync::{Arc, Mutex},
@@ -30,6 +30,9 @@ impl Eq for ReactiveContext {}
 
 thread_local! {
     static CURRENT: RefCell<Vec<ReactiveContext>> = const { RefCell::new(vec![]) };
+
+    // The value that is marking reactive contexts dirty with [`ReactiveContext::mark_dirty_from`]
+    static SOURCE: Cell<Option<SubscriptionSource>> = const { Cell::new(None) };
 }
 
 impl std::fmt::Display for ReactiveContext {
@@ -114,7 +117,11 @@ impl ReactiveContext {
         let sender = runtime.sender.clone();
         let update_scope = move || {
             tracing::trace!("Marking scope {:?} as dirty", id);
-            sender.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            let message = match SOURCE.get() {
+                Some(source) => SchedulerMsg::Subscription(id, source),
+                None => SchedulerMsg::Immediate(id),
+            };
+            sender.unbounded_send(message).unwrap();
         };
 
         // Otherwise, create a new context at the current scope
@@ -241,6 +248,17 @@ impl ReactiveContext {
         }
     }
 
+    /// Mark this reactive context as dirty because the value changed. The profiler records the value as the cause of
+    /// the render if this context belongs to a scope.
+    ///
+    /// Returns true if the context was marked as dirty, or false if the context has been dropped
+    pub fn mark_dirty_from(&self, source: SubscriptionSource) -> bool {
+        let previous = SOURCE.replace(Some(source));
+        let marked = self.mark_dirty();
+        SOURCE.set(previous);
+        marked
+    }
+
     /// Subscribe to this context. The reactive context will automatically remove itself from the subscriptions when it is reset.
     pub fn subscribe(&self, subscriptions: Arc<Mutex<HashSet<ReactiveContext>>>) {
         match self.inner.try_write() {
@@ -262,6 +280,17 @@ impl ReactiveContext {
     pub fn origin_scope(&self) -> ScopeId {
         self.scope
     }
+
+    /// Get where the reactive context was created. This is only known in debug builds.
+    pub fn origin(&self) -> Option<&'static std::panic::Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            if let Ok(read) = self.inner.try_read() {
+                return Some(read.origin);
+            }
+        }
+        None
+    }
 }
 
 impl Hash for ReactiveContext {
@@ -1,8 +1,10 @@
 use crate::arena::ElementRef;
 use crate::innerlude::{DirtyTasks, Effect};
 use crate::nodes::VNodeMount;
+use crate::profiler::{Profile, Profiler};
 use crate::scheduler::ScopeOrder;
 use crate::scope_context::SuspenseLocation;
+use crate::transition::Transition;
 use crate::{
     innerlude::{LocalTask, SchedulerMsg},
     scope_context::Scope,
@@ -64,6 +66,12 @@ pub struct Runtime {
     // We need to store this information on the virtual dom so that we know what nodes are mounted where when we bubble events
     // Each mount is associated with a whole rsx block. [`VirtualDom::elements`] link to a specific node in the block
     pub(crate) mounts: RefCell<Slab<VNodeMount>>,
+
+    // Records renders while profiling is enabled
+    pub(crate) profiler: Profiler,
+
+    // The transition that suspense boundaries keep their children on screen for
+    pub(crate) transition: RefCell<Option<Transition>>,
 }
 
 impl Runtime {
@@ -85,9 +93,33 @@ impl Runtime {
             dirty_tasks: Default::default(),
             elements: RefCell::new(elements),
             mounts: Default::default(),
+            profiler: Default::default(),
+            transition: Default::default(),
         })
     }
 
+    /// Start recording every render of every component. Anything recorded before is discarded.
+    ///
+    /// See [`VirtualDom::start_profiling`](crate::VirtualDom::start_profiling) for more information.
+    pub fn start_profiling(&self) {
+        self.profiler.start();
+    }
+
+    /// Stop recording renders and return everything that was recorded since profiling started
+    pub fn stop_profiling(&self) -> Profile {
+        self.profiler.stop()
+    }
+
+    /// Get everything that was recorded since profiling started without stopping the profiler
+    pub fn profile(&self) -> Profile {
+        self.profiler.profile()
+    }
+
+    /// Check if the runtime is recording renders
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.enabled()
+    }
+
     /// Get the current runtime
     pub fn current() -> Result<Rc<Self>, RuntimeError> {
         RUNTIMES
@@ -55,6 +55,9 @@ impl VirtualDom {
 
         self.runtime.clone().with_scope_on_stack(scope_id, || {
             let scope = &self.scopes[scope_id.0];
+            // A scope that never rendered has no last rendered node
+            let mounting = scope.last_rendered_node.is_none();
+            self.runtime.profiler.enter(scope_id);
             let output = {
                 let scope_state = scope.state();
 
@@ -98,6 +101,9 @@ impl VirtualDom {
             // remove this scope from dirty scopes
             self.dirty_scopes
                 .remove(&ScopeOrder::new(scope_state.height, scope_id));
+            self.runtime
+                .profiler
+                .exit_render(scope_state.name, mounting);
             output
         })
     }
@@ -5,9 +5,9 @@ use crate::{
     prelude::SuspenseContext,
 };
 use generational_box::{AnyStorage, Owner};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Cell, RefCell},
     future::Future,
     sync::Arc,
@@ -55,6 +55,8 @@ pub(crate) struct Scope {
     pub(crate) hooks: RefCell<Vec<Box<dyn Any>>>,
     pub(crate) hook_index: Cell<usize>,
     pub(crate) shared_contexts: RefCell<Vec<Box<dyn Any>>>,
+    // The type names of the contexts for devtools. Contexts inserted with `provide_any_context` don't have a name.
+    pub(crate) context_names: RefCell<FxHashMap<TypeId, &'static str>>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<Task>>,
     pub(crate) before_render: RefCell<Vec<Box<dyn FnMut()>>>,
     pub(crate) after_render: RefCell<Vec<Box<dyn FnMut()>>>,
@@ -80,6 +82,7 @@ impl Scope {
             height,
             render_count: Cell::new(0),
             shared_contexts: RefCell::new(vec![]),
+            context_names: RefCell::new(FxHashMap::default()),
             spawned_tasks: RefCell::new(FxHashSet::default()),
             hooks: RefCell::new(vec![]),
             hook_index: Cell::new(0),
@@ -302,6 +305,9 @@ impl Scope {
 
         // Else, just push it
         contexts.push(Box::new(value.clone()));
+        self.context_names
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
 
         value
     }
@@ -438,6 +438,9 @@ impl SuspenseBoundaryProps {
             props.children.clone_from(&children);
             scope_state.last_rendered_node = Some(children);
 
+            // Write any portals that were created while resolving the boundary
+            dom.flush_queued_portals(to);
+
             // Run any closures that were waiting for the suspense to resolve
             suspense_context.run_resolved_closures(&dom.runtime);
         })
@@ -462,7 +465,15 @@ impl SuspenseBoundaryProps {
 
             let suspense_context = scope.state().suspense_boundary().unwrap().clone();
             let suspended_nodes = suspense_context.suspended_nodes();
-            let suspended = !suspense_context.suspended_futures().is_empty();
+            let mut suspended = !suspense_context.suspended_futures().is_empty();
+            // While a transition is pending, a boundary that is showing its children keeps them on screen instead of
+            // moving them to the background
+            if suspended
+                && suspended_nodes.is_none()
+                && dom.runtime.hold_for_transition(&suspense_context)
+            {
+                suspended = false;
+            }
             match (suspended_nodes, suspended) {
                 // We already have suspended nodes that still need to be suspended
                 // Just diff the normal and suspended nodes
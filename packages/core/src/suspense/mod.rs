@@ -112,6 +112,11 @@ impl SuspenseContext {
         self.inner.id.set(scope);
     }
 
+    /// Get the scope of the suspense boundary
+    pub(crate) fn id(&self) -> ScopeId {
+        self.inner.id.get()
+    }
+
     /// Get the suspense boundary's suspended nodes
     pub fn suspended_nodes(&self) -> Option<VNode> {
         self.inner
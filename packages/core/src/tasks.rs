@@ -1,6 +1,6 @@
 use crate::innerlude::Effect;
 use crate::innerlude::ScopeOrder;
-use crate::innerlude::{remove_future, spawn, Runtime};
+use crate::innerlude::{remove_future, spawn, Runtime, SubscriptionSource};
 use crate::scope_context::ScopeStatus;
 use crate::scope_context::SuspenseLocation;
 use crate::ScopeId;
@@ -386,6 +386,9 @@ pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
     Immediate(ScopeId),
 
+    /// A reactive value the component subscribed to changed and marked it as dirty
+    Subscription(ScopeId, SubscriptionSource),
+
     /// A task has woken and needs to be progressed
     TaskNotified(slotmap::DefaultKey),
 
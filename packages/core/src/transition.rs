@@ -0,0 +1,177 @@
+//! Transitions mark updates as non-urgent so suspense boundaries keep showing their children while the update loads
+
+use crate::innerlude::*;
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+/// Run the closure as a non-urgent update.
+///
+/// While the transition is pending, suspense boundaries that were showing their children when they suspended keep
+/// showing them instead of their fallback. Components that were already mounted keep their last rendered nodes while
+/// they are suspended, so the old UI stays on screen until the new content resolves. Components that mount during the
+/// transition and suspend render nothing until they resolve.
+///
+/// The transition stops holding new boundaries once the VirtualDom runs out of work, and it finishes when every
+/// boundary it held is resolved. If another transition is still pending, the update joins it.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # async fn fetch_tab(tab: usize) -> String { String::new() }
+/// fn app() -> Element {
+///     let mut tab = use_signal(|| 0);
+///     rsx! {
+///         button { onclick: move |_| { start_transition(move || tab.set(1)); }, "Next tab" }
+///         SuspenseBoundary {
+///             fallback: |_| rsx! { "Loading..." },
+///             Tab { tab }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Tab(tab: ReadOnlySignal<usize>) -> Element {
+///     let content = use_resource(move || fetch_tab(tab())).suspend()?;
+///     rsx! { "{content}" }
+/// }
+/// ```
+pub fn start_transition(f: impl FnOnce()) -> Transition {
+    let transition = Runtime::with(|rt| rt.start_transition()).unwrap_or_else(|e| panic!("{}", e));
+    f();
+    transition
+}
+
+/// A pending update started with [`start_transition`]
+#[derive(Clone)]
+pub struct Transition {
+    inner: Rc<TransitionInner>,
+}
+
+impl PartialEq for Transition {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+struct TransitionInner {
+    /// The boundaries that kept their children on screen instead of showing their fallback
+    held: RefCell<Vec<SuspenseContext>>,
+    /// New boundaries are only held until the VirtualDom runs out of work after the transition starts
+    capturing: Cell<bool>,
+    finished: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+}
+
+impl Transition {
+    fn new() -> Self {
+        Self {
+            inner: Rc::new(TransitionInner {
+                held: Default::default(),
+                capturing: Cell::new(true),
+                finished: Cell::new(false),
+                wakers: Default::default(),
+            }),
+        }
+    }
+
+    /// Check if the transition is still waiting for suspended content
+    pub fn is_pending(&self) -> bool {
+        !self.inner.finished.get()
+    }
+
+    /// Wait until the new content is resolved or the transition is cancelled
+    pub async fn finished(&self) {
+        poll_fn(|cx| {
+            if self.inner.finished.get() {
+                return Poll::Ready(());
+            }
+            self.inner.wakers.borrow_mut().push(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Stop waiting for the new content. The boundaries the transition held show their fallback if they are still
+    /// suspended.
+    pub fn cancel(&self) {
+        if !self.is_pending() {
+            return;
+        }
+        _ = Runtime::with(|rt| {
+            if rt.transition.borrow().as_ref() == Some(self) {
+                rt.transition.take();
+            }
+            // Rerun the boundaries that are still suspended so they switch to their fallback
+            for boundary in self.inner.held.borrow().iter() {
+                if boundary.has_suspended_tasks() {
+                    _ = rt
+                        .sender
+                        .unbounded_send(SchedulerMsg::Immediate(boundary.id()));
+                }
+            }
+        });
+        self.inner.held.borrow_mut().clear();
+        self.finish();
+    }
+
+    fn finish(&self) {
+        self.inner.finished.set(true);
+        for waker in self.inner.wakers.borrow_mut().drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Check if every boundary the transition held is resolved
+    fn resolved(&self) -> bool {
+        self.inner
+            .held
+            .borrow()
+            .iter()
+            .all(|boundary| !boundary.has_suspended_tasks())
+    }
+}
+
+impl Runtime {
+    /// Start a new transition or join the one that is pending
+    pub(crate) fn start_transition(&self) -> Transition {
+        let mut current = self.transition.borrow_mut();
+        let transition = current.get_or_insert_with(Transition::new);
+        transition.inner.capturing.set(true);
+        transition.clone()
+    }
+
+    /// Check if the boundary should keep showing its children because a transition is pending
+    pub(crate) fn hold_for_transition(&self, boundary: &SuspenseContext) -> bool {
+        let current = self.transition.borrow();
+        let Some(transition) = current.as_ref() else {
+            return false;
+        };
+        let mut held = transition.inner.held.borrow_mut();
+        if held.contains(boundary) {
+            return true;
+        }
+        if transition.inner.capturing.get() {
+            held.push(boundary.clone());
+            return true;
+        }
+        false
+    }
+
+    /// Called when the VirtualDom runs out of work. The pending transition stops holding new boundaries and finishes if
+    /// all of the boundaries it held are resolved.
+    pub(crate) fn settle_transition(&self) {
+        let mut current = self.transition.borrow_mut();
+        let Some(transition) = current.as_ref() else {
+            return;
+        };
+        transition.inner.capturing.set(false);
+        if transition.resolved() {
+            let transition = current.take().unwrap();
+            drop(current);
+            transition.finish();
+        }
+    }
+}
@@ -7,7 +7,10 @@ use crate::properties::RootProps;
 use crate::root_wrapper::RootScopeWrapper;
 use crate::{
     arena::ElementId,
-    innerlude::{NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState, VProps, WriteMutations},
+    innerlude::{
+        ElementRef, NoOpMutations, Profile, RenderCause, SchedulerMsg, ScopeOrder, ScopeState,
+        SubscriptionSource, VProps, WriteMutations,
+    },
     runtime::{Runtime, RuntimeGuard},
     scopes::ScopeId,
     ComponentFunction, Element, Mutations,
@@ -212,6 +215,12 @@ pub struct VirtualDom {
     // The scopes that have been resolved since the last render
     pub(crate) resolved_scopes: Vec<ScopeId>,
 
+    // The portal mounts that were removed with their parent while the VirtualDom was not writing mutations
+    pub(crate) removed_portals: Vec<ElementId>,
+
+    // The portals that were created in this render and need their children written at the end of the render
+    pub(crate) queued_portals: Vec<ScopeId>,
+
     rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
 }
 
@@ -288,7 +297,7 @@ impl VirtualDom {
         root_props: P,
     ) -> Self {
         let render_fn = root.fn_ptr();
-        let props = VProps::new(root, |_, _| true, root_props, "Root");
+        let props = VProps::new(root, |_, _| true, |_| None, root_props, "Root");
         Self::new_with_component(VComponent {
             name: "root",
             render_fn,
@@ -314,11 +323,14 @@ impl VirtualDom {
             scopes: Default::default(),
             dirty_scopes: Default::default(),
             resolved_scopes: Default::default(),
+            removed_portals: Default::default(),
+            queued_portals: Default::default(),
         };
 
         let root = VProps::new(
             RootScopeWrapper,
             |_, _| true,
+            |_| None,
             RootProps(root),
             "RootWrapper",
         );
@@ -398,6 +410,20 @@ impl VirtualDom {
         self.queue_scope(order);
     }
 
+    fn mark_scheduled_dirty(&mut self, id: ScopeId) {
+        self.runtime
+            .profiler
+            .record_cause(id, RenderCause::Scheduled);
+        self.mark_dirty(id);
+    }
+
+    fn mark_subscription_dirty(&mut self, id: ScopeId, source: SubscriptionSource) {
+        self.runtime
+            .profiler
+            .record_cause(id, RenderCause::Subscription(source));
+        self.mark_dirty(id);
+    }
+
     /// Mark a task as dirty
     fn mark_task_dirty(&mut self, task: Task) {
         let Some(scope) = self.runtime.task_scope(task) else {
@@ -448,6 +474,9 @@ impl VirtualDom {
                 return;
             }
 
+            // We are out of work, so any pending transition has held every boundary it will suspend
+            self.runtime.settle_transition();
+
             // Make sure we set the runtime since we're running user code
             let _runtime = RuntimeGuard::new(self.runtime.clone());
 
@@ -460,7 +489,8 @@ impl VirtualDom {
     #[instrument(skip(self), level = "trace", name = "VirtualDom::wait_for_event")]
     async fn wait_for_event(&mut self) {
         match self.rx.next().await.expect("channel should never close") {
-            SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+            SchedulerMsg::Immediate(id) => self.mark_scheduled_dirty(id),
+            SchedulerMsg::Subscription(id, source) => self.mark_subscription_dirty(id, source),
             SchedulerMsg::TaskNotified(id) => {
                 // Instead of running the task immediately, we insert it into the runtime's task queue.
                 // The task may be marked dirty at the same time as the scope that owns the task is dropped.
@@ -476,7 +506,8 @@ impl VirtualDom {
         // Prevent a task from deadlocking the runtime by repeatedly queueing itself
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Immediate(id) => self.mark_scheduled_dirty(id),
+                SchedulerMsg::Subscription(id, source) => self.mark_subscription_dirty(id, source),
                 SchedulerMsg::TaskNotified(task) => self.mark_task_dirty(Task::from_id(task)),
                 SchedulerMsg::EffectQueued => {}
                 SchedulerMsg::AllDirty => self.mark_all_dirty(),
@@ -572,6 +603,8 @@ impl VirtualDom {
     /// ```
     #[instrument(skip(self, to), level = "trace", name = "VirtualDom::rebuild")]
     pub fn rebuild(&mut self, to: &mut impl WriteMutations) {
+        let runtime = self.runtime.clone();
+        let to = &mut runtime.profiler.count_mutations(to);
         let _runtime = RuntimeGuard::new(self.runtime.clone());
         let new_nodes = self
             .runtime
@@ -584,6 +617,35 @@ impl VirtualDom {
         let m = self.create_scope(Some(to), ScopeId::ROOT, new_nodes, None);
 
         to.append_children(ElementId(0), m);
+        self.flush_queued_portals(to);
+    }
+
+    /// Write every edit required to create the current dom from scratch, without running any components.
+    ///
+    /// Unlike [`VirtualDom::rebuild`], replaying keeps the state of every component and writes every node with the
+    /// [`ElementId`] it is already mounted with. This lets a renderer that lost its dom, like a liveview client that
+    /// reconnected, catch up with the VirtualDom.
+    ///
+    /// The mutations expect the RealDom's stack to be the root of the application and the root to be empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// fn app() -> Element {
+    ///     rsx! { "hello world" }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// let created = dom.rebuild_to_vec();
+    /// let mut replayed = Mutations::default();
+    /// dom.replay(&mut replayed);
+    /// assert_eq!(created.edits, replayed.edits);
+    /// ```
+    pub fn replay(&self, to: &mut impl WriteMutations) {
+        let m = self.replay_scope(ScopeId::ROOT, to);
+        to.append_children(ElementId(0), m);
+        self.replay_portals(None, to);
     }
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
@@ -597,6 +659,8 @@ impl VirtualDom {
 
         // Next, diff any dirty scopes
         // We choose not to poll the deadline since we complete pretty quickly anyways
+        let runtime = self.runtime.clone();
+        let to = &mut runtime.profiler.count_mutations(to);
         let _runtime = RuntimeGuard::new(self.runtime.clone());
         while let Some(work) = self.pop_work() {
             match work {
@@ -613,6 +677,8 @@ impl VirtualDom {
                 }
             }
         }
+        self.flush_queued_portals(to);
+        self.flush_removed_portals(to);
 
         self.runtime.finish_render();
     }
@@ -648,6 +714,38 @@ impl VirtualDom {
         self.runtime.suspended_tasks.get() > 0
     }
 
+    /// Estimate how many bytes the VirtualDom uses for its scopes, hooks, contexts and mounted templates.
+    ///
+    /// Only the memory the VirtualDom owns directly is counted. Memory that a hook points to, like the value of a signal
+    /// or the items of a `Vec` in a hook, is not included.
+    pub fn approximate_memory_usage(&self) -> usize {
+        use std::mem::{size_of, size_of_val};
+
+        let mut bytes = size_of::<Self>() + self.scopes.capacity() * size_of::<ScopeState>();
+
+        for scope in self.runtime.scope_states.borrow().iter().flatten() {
+            bytes += size_of_val(scope);
+            for hook in scope.hooks.borrow().iter() {
+                bytes += size_of_val(&**hook);
+            }
+            for context in scope.shared_contexts.borrow().iter() {
+                bytes += size_of_val(&**context);
+            }
+        }
+
+        let mounts = self.runtime.mounts.borrow();
+        bytes += mounts.capacity() * size_of::<crate::nodes::VNodeMount>();
+        for (_, mount) in mounts.iter() {
+            bytes += size_of_val(&*mount.root_ids)
+                + size_of_val(&*mount.mounted_attributes)
+                + size_of_val(&*mount.mounted_dynamic_nodes);
+        }
+
+        bytes += self.runtime.elements.borrow().capacity() * size_of::<Option<ElementRef>>();
+
+        bytes
+    }
+
     /// Wait for the scheduler to have any work that should be run during suspense.
     pub async fn wait_for_suspense_work(&mut self) {
         // Wait for a work to be ready (IE new suspense leaves to pop up)
@@ -744,6 +842,51 @@ impl VirtualDom {
         std::mem::take(&mut self.resolved_scopes)
     }
 
+    /// Start recording every render of every component. Anything recorded before is discarded.
+    ///
+    /// For every render, the profiler records what caused it, how long the component ran, how long diffing its output
+    /// took and how many mutations the diff emitted. Stop profiling with [`VirtualDom::stop_profiling`] to get the
+    /// [`Profile`], then look for the components that render most often or export it to a trace viewer with
+    /// [`Profile::to_chrome_trace`].
+    ///
+    /// Profiling keeps every render in memory until it stops. Durations are not recorded on `wasm32-unknown-unknown`.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// fn app() -> Element {
+    ///     let mut count = use_signal(|| 0);
+    ///     use_hook(move || spawn(async move { count += 1 }));
+    ///     rsx! { "{count}" }
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.start_profiling();
+    /// dom.rebuild_in_place();
+    /// dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    ///
+    /// let profile = dom.stop_profiling();
+    /// // The component the VirtualDom was created with is named `root`
+    /// let app = profile.scopes().into_iter().find(|scope| scope.name == "root").unwrap();
+    /// assert_eq!(app.renders, 2);
+    /// for (cause, renders) in app.causes {
+    ///     // The second render was caused by the count signal
+    ///     println!("{cause}: {renders} renders");
+    /// }
+    /// ```
+    pub fn start_profiling(&self) {
+        self.runtime.start_profiling();
+    }
+
+    /// Stop recording renders and return everything that was recorded since profiling started
+    pub fn stop_profiling(&self) -> Profile {
+        self.runtime.stop_profiling()
+    }
+
+    /// Get everything that was recorded since profiling started without stopping the profiler
+    pub fn profile(&self) -> Profile {
+        self.runtime.profile()
+    }
+
     /// Get the current runtime
     pub fn runtime(&self) -> Rc<Runtime> {
         self.runtime.clone()
@@ -0,0 +1,116 @@
+#![allow(non_snake_case)]
+//! The inspector takes snapshots of the scopes in a VirtualDom for devtools
+use dioxus::prelude::*;
+use dioxus_core::{NoOpMutations, ScopeInfo};
+
+fn scope_named<'a>(scopes: &'a [ScopeInfo], name: &str) -> &'a ScopeInfo {
+    scopes
+        .iter()
+        .find(|scope| scope.name.rsplit("::").next() == Some(name))
+        .unwrap()
+}
+
+#[derive(Clone, PartialEq)]
+struct NotDebug;
+
+#[derive(Props, Clone, PartialEq)]
+struct OpaqueProps {
+    id: u32,
+    hidden: NotDebug,
+}
+
+fn app() -> Element {
+    use_context_provider(|| 1_u8);
+    let _count = use_signal(|| 0);
+    rsx! {
+        Child { name: "first" }
+        Child { name: "second" }
+        Opaque { id: 1, hidden: NotDebug }
+        Generic { value: 1_u8 }
+    }
+}
+
+#[component]
+fn Child(name: String) -> Element {
+    rsx! {
+        Leaf {}
+    }
+}
+
+#[component]
+fn Leaf() -> Element {
+    rsx! { "leaf" }
+}
+
+fn Opaque(props: OpaqueProps) -> Element {
+    rsx! { "{props.id}" }
+}
+
+#[component]
+fn Generic<T: Clone + PartialEq + 'static>(value: T) -> Element {
+    rsx! { "generic" }
+}
+
+#[test]
+fn scopes_are_in_tree_order() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+
+    let scopes = dom.inspect_scopes();
+    let names: Vec<_> = scopes
+        .iter()
+        .map(|scope| scope.name.rsplit("::").next().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "app",
+            "SuspenseBoundary",
+            "ErrorBoundary",
+            "root",
+            "Child",
+            "Leaf",
+            "Child",
+            "Leaf",
+            "Opaque",
+            "Generic<u8>"
+        ]
+    );
+
+    for scope in &scopes[1..] {
+        let parent = scopes.iter().find(|other| Some(other.id) == scope.parent);
+        assert_eq!(parent.unwrap().height + 1, scope.height);
+    }
+}
+
+#[test]
+fn props_hooks_and_contexts() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    let scopes = dom.inspect_scopes();
+
+    let root = scope_named(&scopes, "root");
+    assert_eq!(root.hooks, 2);
+    // Signals store their owner in a context next to the contexts the component provides
+    assert!(root.contexts.contains(&Some("u8")));
+    assert_eq!(root.render_count, 1);
+
+    let child = scope_named(&scopes, "Child");
+    assert_eq!(
+        child.props.as_deref(),
+        Some(r#"ChildProps { name: "first" }"#)
+    );
+    assert_eq!(child.hooks, 0);
+
+    // Fields that don't implement Debug are hidden
+    assert_eq!(
+        scope_named(&scopes, "Opaque").props.as_deref(),
+        Some("OpaqueProps { id: 1, hidden: _ }")
+    );
+
+    // Generic fields can't be formatted because the props don't require them to implement Debug
+    assert_eq!(
+        scope_named(&scopes, "Generic<u8>").props.as_deref(),
+        Some("GenericProps { value: _ }")
+    );
+}
@@ -0,0 +1,35 @@
+#![allow(non_snake_case)]
+//! The memory estimate of the VirtualDom grows with the dom
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+
+#[test]
+fn memory_usage_grows_with_the_dom() {
+    fn app(children: usize) -> Element {
+        rsx! {
+            for i in 0..children {
+                Child { key: "{i}" }
+            }
+        }
+    }
+
+    #[component]
+    fn Child() -> Element {
+        let _state = use_hook(|| [0u8; 1024]);
+        rsx! { div { "child" } }
+    }
+
+    let memory_usage = |children: usize| {
+        let mut dom = VirtualDom::new_with_props(app, children);
+        dom.rebuild(&mut NoOpMutations);
+        dom.approximate_memory_usage()
+    };
+
+    let one = memory_usage(1);
+    let ten = memory_usage(10);
+    assert!(one > 1024);
+    assert!(
+        ten > one + 9 * 1024,
+        "{ten} should count the hooks of 10 children, {one} for one"
+    );
+}
@@ -0,0 +1,218 @@
+#![allow(non_snake_case)]
+//! Portals write their children to the portal target while they stay attached to their logical parent
+
+use dioxus::dioxus_core::{CapturedError, ElementId, Mutation, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use pretty_assertions::assert_eq;
+use std::{any::Any, cell::Cell, rc::Rc};
+
+fn replay(dom: &VirtualDom) -> Vec<Mutation> {
+    let mut mutations = Mutations::default();
+    dom.replay(&mut mutations);
+    mutations.edits
+}
+
+#[test]
+fn portal_children_are_mounted_in_the_target() {
+    fn app() -> Element {
+        rsx! {
+            div { Portal { target: "modals", p { "modal" } } }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild_to_vec();
+
+    assert_eq!(
+        edits.edits,
+        [
+            LoadTemplate { index: 0, id: ElementId(1) },
+            CreatePlaceholder { id: ElementId(2) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+            // The children are written after the rest of the tree is mounted
+            LoadTemplate { index: 0, id: ElementId(3) },
+            MountPortal { target: PortalTarget::named("modals"), id: ElementId(4), m: 1 },
+        ]
+    );
+
+    // Replaying writes the portal the same way as creating it
+    assert_eq!(replay(&dom), edits.edits);
+}
+
+#[test]
+fn portal_children_keep_context_and_bubble_to_the_logical_parent() {
+    thread_local! {
+        static CLICKS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn app() -> Element {
+        use_context_provider(|| "from the parent");
+        rsx! {
+            div { onclick: move |_| CLICKS.set(CLICKS.get() + 1),
+                Portal { Child {} }
+            }
+        }
+    }
+
+    fn Child() -> Element {
+        let context: &str = use_context();
+        rsx! { button { onclick: |_| {}, "{context}" } }
+    }
+
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild_to_vec();
+    assert!(edits
+        .edits
+        .contains(&CreateTextNode { value: "from the parent".to_string(), id: ElementId(4) }));
+
+    // The button is mounted in the portal target, but the click bubbles to the div that contains the portal
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+        true,
+    );
+    dom.runtime().handle_event("click", event, ElementId(3));
+    assert_eq!(CLICKS.get(), 1);
+}
+
+#[test]
+fn portals_are_diffed_in_place_and_moved_to_new_targets() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        let target = match count() {
+            0 | 1 => PortalTarget::named("modals"),
+            _ => PortalTarget::element("sidebar"),
+        };
+        rsx! {
+            button { onclick: move |_| count += 1, "increment" }
+            Portal { target, "{count}" }
+        }
+    }
+
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_to_vec();
+
+    let click = |dom: &mut VirtualDom| {
+        let event = Event::new(
+            Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+            true,
+        );
+        dom.runtime().handle_event("click", event, ElementId(1));
+        dom.render_immediate_to_vec().edits
+    };
+
+    // The children are diffed in the portal mount without touching the placeholder
+    assert_eq!(
+        click(&mut dom),
+        [SetText { value: "1".to_string(), id: ElementId(3) }]
+    );
+
+    // Changing the target moves the portal mount
+    assert_eq!(
+        click(&mut dom),
+        [
+            SetText { value: "2".to_string(), id: ElementId(3) },
+            MountPortal { target: PortalTarget::element("sidebar"), id: ElementId(4), m: 0 },
+        ]
+    );
+}
+
+#[test]
+fn removing_a_portal_removes_the_portal_mount() {
+    fn app() -> Element {
+        let mut show = use_signal(|| true);
+        rsx! {
+            button { onclick: move |_| show.toggle(), "toggle" }
+            if show() {
+                Portal { p { "directly in the parent" } }
+                div { Portal { p { "nested in an element" } } }
+            }
+        }
+    }
+
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild_to_vec().edits;
+    let mounts: Vec<_> = edits
+        .iter()
+        .filter_map(|edit| match edit {
+            MountPortal { id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(mounts.len(), 2);
+
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+        true,
+    );
+    dom.runtime().handle_event("click", event, ElementId(1));
+    let edits = dom.render_immediate_to_vec().edits;
+
+    // Both portal mounts are removed, even the one that was nested in an element that was removed
+    for id in mounts {
+        assert!(edits.contains(&Remove { id }), "{id:?} was not removed");
+    }
+}
+
+#[test]
+fn errors_in_portals_are_caught_by_the_logical_parent() {
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |_| rsx! { "caught" },
+                div { Portal { Thrower {} } }
+            }
+        }
+    }
+
+    fn Thrower() -> Element {
+        Err(CapturedError::from_display("error in a portal").into())
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    // The error boundary shows the error on the next render
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "caught");
+}
+
+#[tokio::test]
+async fn suspended_portals_are_mounted_when_they_resolve() {
+    fn app() -> Element {
+        rsx! {
+            SuspenseBoundary {
+                fallback: |_| rsx! { "loading" },
+                Portal { Suspender {} }
+            }
+        }
+    }
+
+    fn Suspender() -> Element {
+        let resource = use_resource(|| async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            "loaded"
+        })
+        .suspend()?;
+        rsx! { "{resource}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild_to_vec().edits;
+    // The suspense boundary is suspended, so the portal is rendered in the background
+    assert!(!edits.iter().any(|edit| matches!(edit, MountPortal { .. })));
+
+    let mut edits = Vec::new();
+    while dom.suspended_tasks_remaining() {
+        dom.wait_for_work().await;
+        edits.extend(dom.render_immediate_to_vec().edits);
+    }
+    assert!(edits
+        .iter()
+        .any(|edit| matches!(edit, MountPortal { m: 1, .. })));
+    assert!(edits
+        .iter()
+        .any(|edit| matches!(edit, CreateTextNode { value, .. } if value == "loaded")));
+}
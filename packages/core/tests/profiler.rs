@@ -0,0 +1,123 @@
+#![allow(non_snake_case)]
+//! The profiler records why and how long components render
+use dioxus::prelude::*;
+use dioxus_core::{Mutations, NoOpMutations, Profile, RenderCause, ScopeProfile};
+
+/// The scope of the component with the name. Components are named with their path, and the component the VirtualDom
+/// was created with is named `root`.
+fn scope_named(profile: &Profile, name: &str) -> ScopeProfile {
+    profile
+        .scopes()
+        .into_iter()
+        .find(|scope| scope.name.rsplit("::").next() == Some(name))
+        .unwrap()
+}
+
+fn app() -> Element {
+    let mut count = use_signal(|| 0);
+    use_hook(move || spawn(async move { count += 1 }));
+    rsx! {
+        Child { count: count() }
+        Memoized {}
+    }
+}
+
+#[component]
+fn Child(count: i32) -> Element {
+    rsx! { "{count}" }
+}
+
+#[component]
+fn Memoized() -> Element {
+    rsx! { "static" }
+}
+
+#[test]
+fn records_render_causes() {
+    let mut dom = VirtualDom::new(app);
+    dom.start_profiling();
+    dom.rebuild(&mut Mutations::default());
+    dom.render_immediate(&mut Mutations::default());
+    let profile = dom.stop_profiling();
+
+    let root: Vec<_> = profile
+        .renders_of(scope_named(&profile, "root").scope)
+        .collect();
+    assert_eq!(root.len(), 2);
+    assert_eq!(root[0].causes, [RenderCause::Mount]);
+    let [RenderCause::Subscription(source)] = root[1].causes[..] else {
+        panic!(
+            "the signal should cause the second render: {:?}",
+            root[1].causes
+        );
+    };
+    assert_eq!(source.created_at.unwrap().file(), file!());
+
+    let child = scope_named(&profile, "Child");
+    assert_eq!(child.renders, 2);
+    assert_eq!(
+        child.causes,
+        [(RenderCause::Mount, 1), (RenderCause::Props, 1)]
+    );
+    // Only the text of the child changes
+    let second = profile.renders_of(child.scope).nth(1).unwrap();
+    assert_eq!(second.mutations, 1);
+
+    assert_eq!(scope_named(&profile, "Memoized").renders, 1);
+}
+
+#[test]
+fn scheduled_updates_are_recorded() {
+    fn app() -> Element {
+        use_hook(|| spawn(async { needs_update() }));
+        rsx! { "hello" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.start_profiling();
+    dom.rebuild(&mut NoOpMutations);
+    dom.render_immediate(&mut NoOpMutations);
+
+    let profile = dom.profile();
+    let renders: Vec<_> = profile
+        .renders_of(scope_named(&profile, "root").scope)
+        .collect();
+    assert_eq!(renders[0].causes, [RenderCause::Mount]);
+    assert_eq!(renders[1].causes, [RenderCause::Scheduled]);
+    // The first render loads the template of the text node
+    assert_eq!(renders[0].mutations, 1);
+}
+
+#[test]
+fn renders_are_only_recorded_while_profiling() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    dom.start_profiling();
+    assert!(dom.runtime().is_profiling());
+    dom.render_immediate(&mut NoOpMutations);
+    let profile = dom.stop_profiling();
+    assert!(!dom.runtime().is_profiling());
+
+    // Only the rerender of the app and the child are recorded
+    assert_eq!(profile.renders().len(), 2);
+    assert!(dom.profile().renders().is_empty());
+}
+
+#[test]
+fn chrome_trace_export() {
+    let mut dom = VirtualDom::new(app);
+    dom.start_profiling();
+    dom.rebuild(&mut NoOpMutations);
+    dom.render_immediate(&mut NoOpMutations);
+    let profile = dom.stop_profiling();
+    let trace = profile.to_chrome_trace();
+
+    assert!(trace.starts_with(r#"{"displayTimeUnit":"ms","traceEvents":["#));
+    assert!(trace.ends_with("]}"));
+    // Every render is diffed
+    let renders = profile.renders().len();
+    assert_eq!(trace.matches(r#""cat":"render""#).count(), renders);
+    assert_eq!(trace.matches(r#""cat":"diff""#).count(), renders);
+    assert!(trace.contains(r#"::Child","cat":"render","ph":"X""#));
+    assert!(trace.contains(r#""causes":["props changed"]"#));
+}
@@ -0,0 +1,72 @@
+//! Replaying the mounted dom writes the same nodes with the same ids without running components
+
+use dioxus::dioxus_core::{ElementId, Mutation, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use pretty_assertions::assert_eq;
+use std::cell::Cell;
+
+fn replay(dom: &VirtualDom) -> Vec<Mutation> {
+    let mut mutations = Mutations::default();
+    dom.replay(&mut mutations);
+    mutations.edits
+}
+
+#[test]
+fn replay_matches_rebuild() {
+    fn app() -> Element {
+        rsx! {
+            div { class: "{1 + 1}", onclick: |_| {},
+                Child { name: "child" }
+                {(0..2).map(|i| rsx! { span { key: "{i}", "{i}" } })}
+                {None::<Element>}
+            }
+            "text"
+        }
+    }
+
+    #[component]
+    fn Child(name: String) -> Element {
+        rsx! { p { "{name}" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let created = dom.rebuild_to_vec();
+
+    assert_eq!(replay(&dom), created.edits);
+}
+
+#[test]
+fn replay_keeps_state_and_ids() {
+    thread_local! {
+        static RENDERS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn app() -> Element {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        let text = if generation() % 2 != 0 {
+            Some("hello")
+        } else {
+            None
+        };
+        rsx! {
+            div { {text} }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.mark_dirty(ScopeId::APP);
+    _ = dom.render_immediate_to_vec();
+    let renders = RENDERS.with(Cell::get);
+
+    assert_eq!(
+        replay(&dom),
+        [
+            LoadTemplate { index: 0, id: ElementId(1) },
+            CreateTextNode { value: "hello".to_string(), id: ElementId(3) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+    assert_eq!(RENDERS.with(Cell::get), renders);
+}
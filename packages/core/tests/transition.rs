@@ -0,0 +1,128 @@
+#![allow(non_snake_case)]
+//! Transitions keep suspense boundaries showing their children while a non-urgent update loads
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::time::Duration;
+
+static TAB: GlobalSignal<usize> = Signal::global(|| 0);
+
+fn app() -> Element {
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! { "fallback" },
+            Tab {}
+        }
+    }
+}
+
+#[component]
+fn Tab() -> Element {
+    let content = use_resource(|| async move {
+        let tab = TAB();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        format!("tab {tab}")
+    })
+    .suspend()?;
+    rsx! { "{content}" }
+}
+
+/// Render until the tab suspends on the new resource
+async fn suspend_tab(dom: &mut VirtualDom) {
+    while !dom.suspended_tasks_remaining() {
+        dom.wait_for_work().await;
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    // Suspending marks the boundary dirty, so it rerenders with the suspended tab next
+    dom.render_immediate(&mut NoOpMutations);
+}
+
+/// Create the app and wait for the first tab to load
+async fn loaded_app() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    while dioxus_ssr::render(&dom) != "tab 0" {
+        dom.wait_for_work().await;
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    dom
+}
+
+fn block_on(f: impl std::future::Future<Output = ()>) {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(f)
+}
+
+#[test]
+fn transition_keeps_children_on_screen() {
+    block_on(async {
+        let mut dom = loaded_app().await;
+
+        let transition = dom.in_runtime(|| start_transition(|| *TAB.write() = 1));
+        suspend_tab(&mut dom).await;
+        assert_eq!(dioxus_ssr::render(&dom), "tab 0");
+        assert!(transition.is_pending());
+
+        // The boundary shows the new tab once it loads without ever showing the fallback
+        loop {
+            tokio::select! {
+                _ = dom.wait_for_work() => {}
+                _ = transition.finished() => break,
+            }
+            dom.render_immediate(&mut NoOpMutations);
+            assert_ne!(dioxus_ssr::render(&dom), "fallback");
+        }
+        assert_eq!(dioxus_ssr::render(&dom), "tab 1");
+    });
+}
+
+#[test]
+fn urgent_updates_show_the_fallback() {
+    block_on(async {
+        let mut dom = loaded_app().await;
+
+        dom.in_runtime(|| *TAB.write() = 1);
+        suspend_tab(&mut dom).await;
+        assert_eq!(dioxus_ssr::render(&dom), "fallback");
+    });
+}
+
+#[test]
+fn cancelled_transitions_show_the_fallback() {
+    block_on(async {
+        let mut dom = loaded_app().await;
+
+        let transition = dom.in_runtime(|| start_transition(|| *TAB.write() = 1));
+        suspend_tab(&mut dom).await;
+        assert_eq!(dioxus_ssr::render(&dom), "tab 0");
+
+        dom.in_runtime(|| transition.cancel());
+        assert!(!transition.is_pending());
+        dom.render_immediate(&mut NoOpMutations);
+        assert_eq!(dioxus_ssr::render(&dom), "fallback");
+
+        dom.wait_for_suspense().await;
+        assert_eq!(dioxus_ssr::render(&dom), "tab 1");
+    });
+}
+
+#[test]
+fn transitions_without_suspense_finish_when_idle() {
+    block_on(async {
+        let mut dom = VirtualDom::new(|| rsx! { "{TAB}" });
+        dom.rebuild(&mut NoOpMutations);
+
+        let transition = dom.in_runtime(|| start_transition(|| *TAB.write() = 1));
+        dom.render_immediate(&mut NoOpMutations);
+        assert_eq!(dioxus_ssr::render(&dom), "1");
+
+        // The VirtualDom runs out of work without suspending any boundaries
+        tokio::select! {
+            _ = dom.wait_for_work() => panic!("there should be no more work"),
+            _ = transition.finished() => {}
+        }
+        assert!(!transition.is_pending());
+    });
+}
@@ -399,6 +399,12 @@ impl App {
             DevserverMsg::Shutdown => {
                 self.control_flow = ControlFlow::Exit;
             }
+            DevserverMsg::Inspect => {
+                for webview in self.webviews.values() {
+                    let snapshot = dioxus_devtools::inspect(&webview.dom);
+                    dioxus_devtools::send(dioxus_devtools::ClientMsg::Inspector(snapshot));
+                }
+            }
             _ => {}
         }
     }
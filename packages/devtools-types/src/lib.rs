@@ -25,6 +25,9 @@ pub enum DevserverMsg {
 
     /// The program is shutting down completely - maybe toss up a splash screen or something?
     Shutdown,
+
+    /// Ask the app for a snapshot of its components and signals. The app responds with [`ClientMsg::Inspector`].
+    Inspect,
 }
 
 /// A message the client sends from the frontend to the devserver
@@ -37,6 +40,9 @@ pub enum ClientMsg {
         level: String,
         messages: Vec<String>,
     },
+
+    /// A snapshot of the components and signals in the app, sent in response to [`DevserverMsg::Inspect`]
+    Inspector(InspectorSnapshot),
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -54,3 +60,59 @@ impl HotReloadMsg {
         self.templates.is_empty() && self.assets.is_empty() && self.jump_table.is_none()
     }
 }
+
+/// The components and signals of a running app, for the devtools inspector
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InspectorSnapshot {
+    /// Every scope in the app. Parents come before their children.
+    pub scopes: Vec<ScopeSnapshot>,
+    /// Every signal and memo in the app. Signals are only tracked in debug builds.
+    pub signals: Vec<SignalSnapshot>,
+}
+
+/// A component in the app
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScopeSnapshot {
+    pub id: usize,
+    pub parent: Option<usize>,
+    pub name: String,
+    /// The props formatted with `Debug`
+    pub props: Option<String>,
+    pub hooks: usize,
+    pub render_count: usize,
+    /// The type names of the contexts the scope provides. Contexts that were inserted without a type are `None`.
+    pub contexts: Vec<Option<String>>,
+}
+
+/// A signal or memo in the app
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SignalSnapshot {
+    pub id: String,
+    pub kind: SignalKind,
+    pub type_name: String,
+    /// The file, line and column the signal was created at
+    pub created_at: Option<String>,
+    /// The scope the signal was created in
+    pub scope: usize,
+    pub subscribers: Vec<Subscriber>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SignalKind {
+    Signal,
+    Memo,
+}
+
+/// Something that reruns when a signal changes
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Subscriber {
+    /// A component that read the signal while rendering
+    Component { scope: usize },
+    /// A memo that read the signal while recomputing
+    Memo { id: String },
+    /// Another reactive context, like an effect or a resource
+    Other {
+        scope: usize,
+        created_at: Option<String>,
+    },
+}
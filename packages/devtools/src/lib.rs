@@ -1,5 +1,5 @@
 use dioxus_core::internal::HotReloadedTemplate;
-use dioxus_core::{ScopeId, VirtualDom};
+use dioxus_core::{prelude::ReactiveContext, ScopeId, VirtualDom};
 use dioxus_signals::{GlobalKey, Signal, Writable};
 
 pub use dioxus_devtools_types::*;
@@ -54,6 +54,79 @@ pub fn try_apply_changes(dom: &VirtualDom, msg: &HotReloadMsg) -> Result<(), Pat
     })
 }
 
+/// Take a snapshot of the components and signals in the VirtualDom for the devtools inspector. Apps send it to the
+/// devserver as a [`ClientMsg::Inspector`] when the devserver asks for it with [`DevserverMsg::Inspect`].
+pub fn inspect(dom: &VirtualDom) -> InspectorSnapshot {
+    let scopes = dom.inspect_scopes();
+    let signals = dom.in_runtime(dioxus_signals::inspect_signals);
+
+    // Find out what each reactive context reruns so subscribers can point at components and memos
+    let rerun = |context: &ReactiveContext| {
+        if let Some(scope) = scopes.iter().find(|s| s.reactive_context == *context) {
+            return Subscriber::Component { scope: scope.id.0 };
+        }
+        if let Some(memo) = signals
+            .iter()
+            .find(|signal| signal.reactive_context == Some(*context))
+        {
+            return Subscriber::Memo {
+                id: format!("{:?}", memo.id),
+            };
+        }
+        Subscriber::Other {
+            scope: context.origin_scope().0,
+            created_at: context.origin().map(ToString::to_string),
+        }
+    };
+
+    InspectorSnapshot {
+        signals: signals
+            .iter()
+            .map(|signal| SignalSnapshot {
+                id: format!("{:?}", signal.id),
+                kind: match signal.reactive_context {
+                    Some(_) => SignalKind::Memo,
+                    None => SignalKind::Signal,
+                },
+                type_name: signal.type_name.to_string(),
+                created_at: signal.created_at.map(ToString::to_string),
+                scope: signal.origin_scope.0,
+                subscribers: signal.subscribers.iter().map(rerun).collect(),
+            })
+            .collect(),
+        scopes: scopes
+            .iter()
+            .map(|scope| ScopeSnapshot {
+                id: scope.id.0,
+                parent: scope.parent.map(|parent| parent.0),
+                name: scope.name.to_string(),
+                props: scope.props.clone(),
+                hooks: scope.hooks,
+                render_count: scope.render_count,
+                contexts: scope
+                    .contexts
+                    .iter()
+                    .map(|name| name.map(ToString::to_string))
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Send a message to the devserver. The message is dropped if the app isn't connected to the devserver with
+/// [`connect`].
+#[cfg(not(target_family = "wasm"))]
+pub fn send(msg: ClientMsg) {
+    if let Some(tx) = CLIENT_MSGS.lock().unwrap().as_ref() {
+        _ = tx.send(msg);
+    }
+}
+
+/// The messages waiting to be sent to the devserver by the thread [`connect_at`] spawns
+#[cfg(not(target_family = "wasm"))]
+static CLIENT_MSGS: std::sync::Mutex<Option<std::sync::mpsc::Sender<ClientMsg>>> =
+    std::sync::Mutex::new(None);
+
 /// Connect to the devserver and handle its messages with a callback.
 ///
 /// This doesn't use any form of security or protocol, so it's not safe to expose to the internet.
@@ -95,17 +168,90 @@ pub fn connect_at(endpoint: String, mut callback: impl FnMut(DevserverMsg) + Sen
             std::process::id()
         );
 
-        let (mut websocket, _req) = match tungstenite::connect(uri) {
-            Ok((websocket, req)) => (websocket, req),
-            Err(_) => return,
+        let Some((mut websocket, socket)) = connect_websocket(&uri) else {
+            return;
         };
 
-        while let Ok(msg) = websocket.read() {
-            if let tungstenite::Message::Text(text) = msg {
-                if let Ok(msg) = serde_json::from_str(&text) {
-                    callback(msg);
+        let (tx, rx) = std::sync::mpsc::channel();
+        *CLIENT_MSGS.lock().unwrap() = Some(tx);
+
+        // Stop waiting for the devserver every so often to send the messages the app queued up. The timeout is set
+        // on the tcp socket, so it applies to tls connections too.
+        _ = socket.set_read_timeout(Some(std::time::Duration::from_millis(50)));
+
+        'connection: loop {
+            match websocket.read() {
+                Ok(tungstenite::Message::Text(text)) => {
+                    if let Ok(msg) = serde_json::from_str(&text) {
+                        callback(msg);
+                    }
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(err))
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                Err(_) => break 'connection,
+            }
+
+            for msg in rx.try_iter() {
+                let Ok(text) = serde_json::to_string(&msg) else {
+                    continue;
+                };
+                if websocket
+                    .send(tungstenite::Message::Text(text.into()))
+                    .is_err()
+                {
+                    break 'connection;
                 }
             }
         }
+
+        CLIENT_MSGS.lock().unwrap().take();
     });
 }
+
+/// Open a websocket to the devserver. The tcp socket under the websocket is returned as well, so its options can be
+/// changed after the handshake even if the websocket wraps it in a tls stream.
+#[cfg(not(target_family = "wasm"))]
+fn connect_websocket(
+    uri: &str,
+) -> Option<(
+    tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
+    std::net::TcpStream,
+)> {
+    use tungstenite::client::IntoClientRequest;
+
+    let request = uri.into_client_request().ok()?;
+    let host = request.uri().host()?;
+    // Ipv6 hosts are wrapped in brackets in the uri
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = request
+        .uri()
+        .port_u16()
+        .unwrap_or(match request.uri().scheme_str() {
+            Some("wss") => 443,
+            _ => 80,
+        });
+
+    let stream = std::net::TcpStream::connect((host.as_str(), port)).ok()?;
+    let socket = stream.try_clone().ok()?;
+
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    let (websocket, _response) = tungstenite::client_tls(request, stream).ok()?;
+
+    // Without a tls feature only plain websockets are supported
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    let (websocket, _response) = match request.uri().scheme_str() {
+        Some("wss") => return None,
+        _ => {
+            tungstenite::client(request, tungstenite::stream::MaybeTlsStream::Plain(stream)).ok()?
+        }
+    };
+
+    Some((websocket, socket))
+}
@@ -1,3 +1,5 @@
+mod prefetch;
+pub use prefetch::PrefetchedServerFutures;
 mod server_cached;
 pub use server_cached::*;
 mod server_future;
@@ -0,0 +1,123 @@
+use dioxus_core::prelude::{provide_context, try_consume_context};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::RefCell, collections::HashMap, io::Cursor, panic::Location, rc::Rc};
+
+/// The results of the [`use_server_future`](crate::use_server_future)s of a subtree that rendered before it was
+/// shown, like a route that a router prefetches.
+///
+/// The results are recorded in the order the server futures are created for every call site. Once the subtree
+/// renders again, each server future starts with the result that was recorded for the same call site instead of
+/// running its future again.
+#[derive(Clone, Default)]
+pub struct PrefetchedServerFutures {
+    entries: Rc<RefCell<HashMap<&'static Location<'static>, PrefetchedEntries>>>,
+}
+
+impl PartialEq for PrefetchedServerFutures {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+#[derive(Default)]
+struct PrefetchedEntries {
+    /// The serialized results in the order the server futures were created in. Futures that are still running have
+    /// no result yet
+    results: Vec<Option<Vec<u8>>>,
+    /// The index of the next result that is read back
+    cursor: usize,
+}
+
+#[derive(Clone)]
+enum ServerFuturePrefetch {
+    Record(PrefetchedServerFutures),
+    Replay(PrefetchedServerFutures),
+}
+
+impl PrefetchedServerFutures {
+    /// Create an empty set of results.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the results of the server futures of every component that is rendered below the current scope.
+    pub fn record(&self) {
+        provide_context(ServerFuturePrefetch::Record(self.clone()));
+    }
+
+    /// Start the server futures of every component that is rendered below the current scope with the recorded
+    /// results. Each result is only used once.
+    pub fn replay(&self) {
+        provide_context(ServerFuturePrefetch::Replay(self.clone()));
+    }
+}
+
+/// The slot of a single server future in the [`PrefetchedServerFutures`] of the scope it was created in.
+#[derive(Clone)]
+pub(crate) enum PrefetchEntry {
+    Record {
+        prefetched: PrefetchedServerFutures,
+        location: &'static Location<'static>,
+        index: usize,
+    },
+    Replay(Rc<RefCell<Option<Vec<u8>>>>),
+    None,
+}
+
+impl PrefetchEntry {
+    /// Reserve a slot for a server future created at `location` in the current scope. This must be called in the
+    /// same order as the server futures are created.
+    pub(crate) fn new(location: &'static Location<'static>) -> Self {
+        match try_consume_context::<ServerFuturePrefetch>() {
+            Some(ServerFuturePrefetch::Record(prefetched)) => {
+                let index = {
+                    let mut entries = prefetched.entries.borrow_mut();
+                    let entry = entries.entry(location).or_default();
+                    entry.results.push(None);
+                    entry.results.len() - 1
+                };
+                Self::Record {
+                    prefetched,
+                    location,
+                    index,
+                }
+            }
+            Some(ServerFuturePrefetch::Replay(prefetched)) => {
+                let mut entries = prefetched.entries.borrow_mut();
+                let result = entries.get_mut(location).and_then(|entry| {
+                    let result = entry.results.get_mut(entry.cursor)?.take();
+                    entry.cursor += 1;
+                    result
+                });
+                Self::Replay(Rc::new(RefCell::new(result)))
+            }
+            None => Self::None,
+        }
+    }
+
+    /// Store the result of the server future if this entry is recorded
+    pub(crate) fn insert<T: Serialize>(&self, value: &T) {
+        if let Self::Record {
+            prefetched,
+            location,
+            index,
+        } = self
+        {
+            let mut serialized = Vec::new();
+            if ciborium::into_writer(value, &mut serialized).is_ok() {
+                if let Some(entry) = prefetched.entries.borrow_mut().get_mut(location) {
+                    entry.results[*index] = Some(serialized);
+                }
+            }
+        }
+    }
+
+    /// Take the recorded result of the server future if this entry is replayed
+    pub(crate) fn take<T: DeserializeOwned>(&self) -> Option<T> {
+        let Self::Replay(result) = self else {
+            return None;
+        };
+        let bytes = result.borrow_mut().take()?;
+        ciborium::from_reader(Cursor::new(bytes)).ok()
+    }
+}
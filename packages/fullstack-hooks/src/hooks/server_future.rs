@@ -4,6 +4,8 @@ use dioxus_signals::Readable;
 use serde::{de::DeserializeOwned, Serialize};
 use std::future::Future;
 
+use super::prefetch::PrefetchEntry;
+
 /// Runs a future with a manual list of dependencies and returns a resource with the result if the future is finished or a suspended error if it is still running.
 ///
 ///
@@ -73,9 +75,11 @@ where
     let storage_entry: dioxus_fullstack_protocol::SerializeContextEntry<T> =
         use_hook(|| serialize_context.create_entry());
 
-    #[cfg(feature = "server")]
     let caller = std::panic::Location::caller();
 
+    // If this component is rendered before it is shown, the data might have been prefetched
+    let prefetched = use_hook(|| PrefetchEntry::new(caller));
+
     // If this is the first run and we are on the web client, the data might be cached
     #[cfg(feature = "web")]
     let initial_web_result =
@@ -85,6 +89,7 @@ where
         #[cfg(feature = "server")]
         let storage_entry = storage_entry.clone();
 
+        let prefetched = prefetched.clone();
         let user_fut = future();
 
         #[cfg(feature = "web")]
@@ -110,8 +115,14 @@ where
                 None => {}
             }
 
+            // The data was recorded while the component was prefetched
+            if let Some(out) = prefetched.take() {
+                return out;
+            }
+
             // Otherwise just run the future itself
             let out = user_fut.await;
+            prefetched.insert(&out);
 
             // If this is the first run and we are on the server, cache the data in the slot we reserved for it
             #[cfg(feature = "server")]
@@ -136,3 +147,40 @@ where
 
     Ok(resource)
 }
+
+/// Run a future outside of a hook and serialize the result into the page like [`use_server_future`].
+///
+/// The hydration entry is created when this function is called, so it must be called in the same order on the server
+/// and the client. On the client, the future is only created if the server did not finish it before the page was sent.
+#[track_caller]
+pub fn serialized_future<T, F>(
+    future: impl FnOnce() -> F,
+) -> std::pin::Pin<Box<dyn Future<Output = T>>>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: Future<Output = T> + 'static,
+{
+    #[allow(unused)]
+    let storage_entry: dioxus_fullstack_protocol::SerializeContextEntry<T> =
+        dioxus_fullstack_protocol::serialize_context().create_entry();
+
+    #[cfg(feature = "server")]
+    let caller = std::panic::Location::caller();
+
+    // If the server resolved the future, use its data. Unlike `use_server_future`, nothing reruns this future when
+    // the data is streamed in later, so pending data is loaded again on the client
+    #[cfg(feature = "web")]
+    if let Ok(out) = storage_entry.get() {
+        return Box::pin(std::future::ready(out));
+    }
+
+    let user_fut = future();
+    Box::pin(async move {
+        let out = user_fut.await;
+
+        #[cfg(feature = "server")]
+        storage_entry.insert(&out, caller);
+
+        out
+    })
+}
@@ -20,6 +20,12 @@ pub fn provide_history_context(history: Rc<dyn History>) {
     provide_context(history);
 }
 
+/// A function that decides if navigation the router doesn't control, like the browser back button, should be blocked.
+///
+/// The function receives a callback that retries the navigation without asking again, or `None` if the navigation
+/// can't be retried like closing the page. It returns `true` if the navigation should be blocked.
+pub type HistoryBlocker = Rc<dyn Fn(Option<Rc<dyn Fn()>>) -> bool>;
+
 pub trait History {
     /// Get the path of the current URL.
     ///
@@ -178,6 +184,41 @@ pub trait History {
     /// ```
     fn go_forward(&self);
 
+    /// Get the route of the page [`History::go_back`] would go to without going there.
+    ///
+    /// Returns [`None`] if there is no previous page or the [`History`] doesn't know its route, which is the default.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// assert_eq!(history.back_route(), None);
+    ///
+    /// history.push("/posts".to_string());
+    /// assert_eq!(history.back_route().as_deref(), Some("/"));
+    /// ```
+    #[must_use]
+    fn back_route(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the route of the page [`History::go_forward`] would go to without going there.
+    ///
+    /// Returns [`None`] if there is no future page or the [`History`] doesn't know its route, which is the default.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push("/posts".to_string());
+    /// assert_eq!(history.forward_route(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.forward_route().as_deref(), Some("/posts"));
+    /// ```
+    #[must_use]
+    fn forward_route(&self) -> Option<String> {
+        None
+    }
+
     /// Go to another page.
     ///
     /// This should do three things:
@@ -235,6 +276,56 @@ pub trait History {
     /// ```
     fn replace(&self, path: String);
 
+    /// Go to another page and attach `state` to the new entry of the navigation history.
+    ///
+    /// The state is usually serialized data that should be restored when the user navigates back to the entry, like
+    /// the open tab of a page. It can be read with [`History::current_state`] while the entry is active. [`History`]s
+    /// that cannot store state should call [`History::push`] and drop the state, which is the default.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.push_with_state("/posts".to_string(), r#"{"filter":"unread"}"#.to_string());
+    /// history.push("/posts/1".to_string());
+    /// assert_eq!(history.current_state(), None);
+    ///
+    /// history.go_back();
+    /// assert_eq!(history.current_route(), "/posts");
+    /// assert_eq!(history.current_state().as_deref(), Some(r#"{"filter":"unread"}"#));
+    /// ```
+    #[allow(unused_variables)]
+    fn push_with_state(&self, route: String, state: String) {
+        self.push(route)
+    }
+
+    /// Replace the current page with another one and attach `state` to it.
+    ///
+    /// This works like [`History::replace`], but the state of the current entry is replaced with `state`.
+    /// [`History`]s that cannot store state should call [`History::replace`] and drop the state, which is the default.
+    ///
+    /// ```rust
+    /// # use dioxus_history::*;
+    /// let history = MemoryHistory::default();
+    /// history.replace_with_state("/".to_string(), "1".to_string());
+    /// assert_eq!(history.current_state().as_deref(), Some("1"));
+    ///
+    /// history.replace("/".to_string());
+    /// assert_eq!(history.current_state(), None);
+    /// ```
+    #[allow(unused_variables)]
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace(path)
+    }
+
+    /// Get the state attached to the current entry of the navigation history with [`History::push_with_state`] or
+    /// [`History::replace_with_state`].
+    ///
+    /// Returns [`None`] if the entry has no state or the [`History`] cannot store state.
+    #[must_use]
+    fn current_state(&self) -> Option<String> {
+        None
+    }
+
     /// Navigate to an external URL.
     ///
     /// This should navigate to an external URL, which isn't controlled by the router. If a
@@ -253,9 +344,24 @@ pub trait History {
     #[allow(unused_variables)]
     fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {}
 
+    /// Set or remove the function that blocks navigation the router doesn't control.
+    ///
+    /// [`History`]s that receive navigation from outside the router, like the back button of the browser, should
+    /// call the blocker before the navigation is committed and undo it if the blocker returns `true`. Navigation
+    /// through the methods of this trait is checked by the router itself and must not be blocked.
+    #[allow(unused_variables)]
+    fn set_navigation_blocker(&self, blocker: Option<HistoryBlocker>) {}
+
     /// Whether the router should include the legacy prevent default attribute instead of the new
     /// prevent default method. This should only be used by liveview.
     fn include_prevent_default(&self) -> bool {
         false
     }
+
+    /// Whether the router should scroll the page when the route changes and restore the scroll position of entries
+    /// when moving through the history. If this returns `false`, the page keeps the default scroll behavior of the
+    /// platform.
+    fn scroll_restoration(&self) -> bool {
+        true
+    }
 }
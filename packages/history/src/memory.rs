@@ -2,10 +2,36 @@ use std::cell::RefCell;
 
 use crate::History;
 
+struct MemoryHistoryEntry {
+    route: String,
+    state: Option<String>,
+}
+
+impl MemoryHistoryEntry {
+    fn new(route: String, state: Option<String>) -> Self {
+        Self { route, state }
+    }
+}
+
 struct MemoryHistoryState {
-    current: String,
-    history: Vec<String>,
-    future: Vec<String>,
+    current: MemoryHistoryEntry,
+    history: Vec<MemoryHistoryEntry>,
+    future: Vec<MemoryHistoryEntry>,
+}
+
+impl MemoryHistoryState {
+    fn push(&mut self, new: String, state: Option<String>) {
+        // don't push the same route twice
+        if self.current.route == new {
+            if state.is_some() {
+                self.current.state = state;
+            }
+            return;
+        }
+        let old = std::mem::replace(&mut self.current, MemoryHistoryEntry::new(new, state));
+        self.history.push(old);
+        self.future.clear();
+    }
 }
 
 /// A [`History`] provider that stores all navigation information in memory.
@@ -45,9 +71,9 @@ impl MemoryHistory {
     pub fn with_initial_path(path: impl ToString) -> Self {
         Self {
             state: MemoryHistoryState{
-                current: path.to_string().parse().unwrap_or_else(|err| {
+                current: MemoryHistoryEntry::new(path.to_string().parse().unwrap_or_else(|err| {
                     panic!("index route does not exist:\n{err}\n use MemoryHistory::with_initial_path to set a custom path")
-                }),
+                }), None),
                 history: Vec::new(),
                 future: Vec::new(),
             }.into(),
@@ -76,7 +102,11 @@ impl History for MemoryHistory {
     }
 
     fn current_route(&self) -> String {
-        self.state.borrow().current.clone()
+        self.state.borrow().current.route.clone()
+    }
+
+    fn current_state(&self) -> Option<String> {
+        self.state.borrow().current.state.clone()
     }
 
     fn can_go_back(&self) -> bool {
@@ -95,6 +125,16 @@ impl History for MemoryHistory {
         !self.state.borrow().future.is_empty()
     }
 
+    fn back_route(&self) -> Option<String> {
+        let state = self.state.borrow();
+        state.history.last().map(|entry| entry.route.clone())
+    }
+
+    fn forward_route(&self) -> Option<String> {
+        let state = self.state.borrow();
+        state.future.last().map(|entry| entry.route.clone())
+    }
+
     fn go_forward(&self) {
         let mut write = self.state.borrow_mut();
         if let Some(next) = write.future.pop() {
@@ -104,18 +144,20 @@ impl History for MemoryHistory {
     }
 
     fn push(&self, new: String) {
-        let mut write = self.state.borrow_mut();
-        // don't push the same route twice
-        if write.current == new {
-            return;
-        }
-        let old = std::mem::replace(&mut write.current, new);
-        write.history.push(old);
-        write.future.clear();
+        self.state.borrow_mut().push(new, None);
+    }
+
+    fn push_with_state(&self, new: String, state: String) {
+        self.state.borrow_mut().push(new, Some(state));
     }
 
     fn replace(&self, path: String) {
         let mut write = self.state.borrow_mut();
-        write.current = path;
+        write.current = MemoryHistoryEntry::new(path, None);
+    }
+
+    fn replace_with_state(&self, path: String, state: String) {
+        let mut write = self.state.borrow_mut();
+        write.current = MemoryHistoryEntry::new(path, Some(state));
     }
 }
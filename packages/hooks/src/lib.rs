@@ -98,3 +98,6 @@ pub use use_set_compare::*;
 
 mod use_after_suspense_resolved;
 pub use use_after_suspense_resolved::*;
+
+mod use_transition;
+pub use use_transition::*;
@@ -0,0 +1,117 @@
+use crate::use_signal;
+use dioxus_core::prelude::*;
+use dioxus_signals::Signal;
+use futures_util::{
+    future::{self, Either},
+    pin_mut,
+};
+use std::future::Future;
+
+/// Create a handle that starts non-urgent updates with [`start_transition`] and tracks if they are still loading.
+///
+/// While a transition is pending, suspense boundaries that are already showing content keep showing it instead of
+/// their fallback until the new content resolves. Use [`UseTransition::is_pending`] to show that the update is
+/// loading.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # async fn fetch_tab(tab: usize) -> String { String::new() }
+/// fn app() -> Element {
+///     let mut tab = use_signal(|| 0);
+///     let transition = use_transition();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 transition.start(move || tab += 1);
+///             },
+///             "Next tab"
+///         }
+///         if transition.is_pending() {
+///             "Loading the next tab..."
+///         }
+///         SuspenseBoundary {
+///             fallback: |_| rsx! { "Loading..." },
+///             Tab { tab }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Tab(tab: ReadOnlySignal<usize>) -> Element {
+///     let content = use_resource(move || fetch_tab(tab())).suspend()?;
+///     rsx! { "{content}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_transition() -> UseTransition {
+    let pending = use_signal(|| 0);
+    let scope = current_scope_id().unwrap_or_else(|e| panic!("{}", e));
+    UseTransition { pending, scope }
+}
+
+/// A handle to start transitions, created with [`use_transition`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseTransition {
+    // The number of transitions started with this handle that are still pending
+    pending: Signal<usize>,
+    scope: ScopeId,
+}
+
+impl UseTransition {
+    /// Check if any transition started with this handle is still pending. This subscribes to the state of the
+    /// transitions.
+    pub fn is_pending(&self) -> bool {
+        (self.pending)() > 0
+    }
+
+    /// Run the closure as a non-urgent update
+    pub fn start(&self, f: impl FnOnce()) -> Transition {
+        self.start_with_timeout(f, future::pending())
+    }
+
+    /// Run the closure as a non-urgent update. If the new content is not resolved when the timeout completes, the
+    /// transition is cancelled and the suspense boundaries it held show their fallback.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use std::time::Duration;
+    /// fn app() -> Element {
+    ///     let mut tab = use_signal(|| 0);
+    ///     let transition = use_transition();
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| {
+    ///                 transition.start_with_timeout(
+    ///                     move || tab += 1,
+    ///                     tokio::time::sleep(Duration::from_millis(500)),
+    ///                 );
+    ///             },
+    ///             "Next tab"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn start_with_timeout(
+        &self,
+        f: impl FnOnce(),
+        timeout: impl Future<Output = ()> + 'static,
+    ) -> Transition {
+        let transition = start_transition(f);
+        let mut pending = self.pending;
+        pending += 1;
+
+        let tracked = transition.clone();
+        self.scope.spawn(async move {
+            let finished = tracked.finished();
+            pin_mut!(finished);
+            pin_mut!(timeout);
+            if let Either::Right(_) = future::select(finished, timeout).await {
+                tracked.cancel();
+            }
+            pending -= 1;
+        });
+
+        transition
+    }
+}
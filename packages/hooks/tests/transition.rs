@@ -0,0 +1,92 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::{cell::RefCell, time::Duration};
+
+thread_local! {
+    static TRANSITION: RefCell<Option<UseTransition>> = const { RefCell::new(None) };
+    static RENDERS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+static TAB: GlobalSignal<u64> = Signal::global(|| 0);
+
+fn app() -> Element {
+    let transition = use_transition();
+    TRANSITION.with(|cell| *cell.borrow_mut() = Some(transition));
+    if transition.is_pending() {
+        RENDERS.with(|renders| renders.borrow_mut().push("pending"));
+    }
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| {
+                RENDERS.with(|renders| renders.borrow_mut().push("fallback"));
+                rsx! { "fallback" }
+            },
+            Tab {}
+        }
+    }
+}
+
+#[component]
+fn Tab() -> Element {
+    // The first tab loads instantly and the next tab loads in as many milliseconds as its number
+    let tab = use_resource(|| async move {
+        let tab = TAB();
+        tokio::time::sleep(Duration::from_millis(tab)).await;
+        tab
+    })
+    .suspend()?;
+    if tab() > 0 {
+        RENDERS.with(|renders| renders.borrow_mut().push("loaded"));
+    }
+    rsx! { "{tab}" }
+}
+
+/// Render the app until the transition is no longer pending and return what rendered while it was pending
+async fn run_transition(start: impl FnOnce(UseTransition) -> Transition) -> Vec<&'static str> {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+    while dom.suspended_tasks_remaining() {
+        dom.wait_for_work().await;
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    RENDERS.with(|renders| renders.borrow_mut().clear());
+
+    let transition = TRANSITION.with(|cell| cell.borrow().unwrap());
+    let started = dom.in_runtime(|| ScopeId::APP.in_runtime(|| start(transition)));
+    while started.is_pending() {
+        tokio::select! {
+            _ = dom.wait_for_work() => {}
+            _ = started.finished() => {}
+        }
+        dom.render_immediate(&mut NoOpMutations);
+    }
+    // Let the hook see that the transition finished
+    dom.render_immediate(&mut NoOpMutations);
+    assert!(!dom.in_runtime(|| transition.is_pending()));
+
+    RENDERS.with(|renders| renders.take())
+}
+
+#[tokio::test]
+async fn transitions_keep_content_while_pending() {
+    let renders = run_transition(|transition| transition.start(|| *TAB.write() = 10)).await;
+    assert!(renders.contains(&"pending"));
+    assert!(!renders.contains(&"fallback"));
+    assert_eq!(renders.last(), Some(&"loaded"));
+}
+
+#[tokio::test]
+async fn transitions_show_the_fallback_after_the_timeout() {
+    let renders = run_transition(|transition| {
+        transition.start_with_timeout(
+            || *TAB.write() = 1000,
+            tokio::time::sleep(Duration::from_millis(10)),
+        )
+    })
+    .await;
+    assert!(renders.contains(&"pending"));
+    assert_eq!(renders.last(), Some(&"fallback"));
+}
@@ -80,11 +80,27 @@ mod js {
             const node = this.nodes[id];
             if(node.listening){node.listening += 1;}else{node.listening = 1;}
             node.setAttribute('data-dioxus-id', `\${id}`);
-            this.createListener($event_name$, node, $bubbles$);
+            this.createListener(event_name, node, bubbles);
+            if (bubbles && this.portalMounts) {
+                for (const mount of this.portalMounts) {
+                    mount.addEventListener(event_name, this.global[event_name].callback);
+                }
+            }
         "#
     }
     fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{let node = this.nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); this.removeListener(node, $event_name$, $bubbles$);}"
+        r#"{
+            let node = this.nodes[id];
+            node.listening -= 1;
+            node.removeAttribute('data-dioxus-id');
+            const callback = bubbles && this.global[event_name] ? this.global[event_name].callback : undefined;
+            this.removeListener(node, event_name, bubbles);
+            if (callback && this.global[event_name] === undefined && this.portalMounts) {
+                for (const mount of this.portalMounts) {
+                    mount.removeEventListener(event_name, callback);
+                }
+            }
+        }"#
     }
     fn set_text(id: u32, text: &str) {
         "{this.nodes[$id$].textContent = $text$;}"
@@ -130,6 +146,65 @@ mod js {
     fn load_template(tmpl_id: u16, index: u16, id: u32) {
         "{let node = this.templates[$tmpl_id$][$index$].cloneNode(true); this.nodes[$id$] = node; this.stack.push(node);}"
     }
+    /// Move the portal mount with the given id into the target and append the top `many` nodes of the stack to it.
+    /// `kind` is 0 for a named target and 1 for the id of an element.
+    fn mount_portal(kind: u8, target: &str, id: u32, many: u16) {
+        r#"{
+            let mount = this.nodes[id];
+            if (!mount || !mount.isConnected || !mount.hasAttribute('data-dioxus-portal-mount')) {
+                mount = document.createElement('div');
+                mount.setAttribute('data-dioxus-portal-mount', '');
+                // Events that bubble out of the portal continue from the logical parent of the portal
+                mount.setAttribute('data-dioxus-id', `\${id}`);
+                mount.style.display = 'contents';
+                this.nodes[id] = mount;
+            }
+            const target_name = target;
+            let parent;
+            if (kind == 0) {
+                parent = document.querySelector(`[data-dioxus-portal="\${CSS.escape(target_name)}"]`);
+                if (!parent) {
+                    parent = document.createElement('div');
+                    parent.setAttribute('data-dioxus-portal', target_name);
+                    document.body.appendChild(parent);
+                }
+            } else {
+                parent = document.getElementById(target_name);
+                if (!parent) {
+                    console.warn(`No element with the id \${target_name} was found for a portal. Mounting it in the body instead.`);
+                    parent = document.body;
+                }
+            }
+            if (mount.parentNode !== parent) {
+                parent.appendChild(mount);
+            }
+            const els = this.stack.splice(this.stack.length - many);
+            for (const el of els) {
+                mount.appendChild(el);
+            }
+            // Bubbling listeners are delegated to the root. Portal mounts outside of the root need the same listeners
+            if (!this.portalMounts) {
+                this.portalMounts = new Set();
+            }
+            for (const other of this.portalMounts) {
+                if (!other.isConnected) {
+                    this.portalMounts.delete(other);
+                }
+            }
+            if (this.root.contains(mount)) {
+                if (this.portalMounts.delete(mount)) {
+                    for (const name in this.global) {
+                        mount.removeEventListener(name, this.global[name].callback);
+                    }
+                }
+            } else if (!this.portalMounts.has(mount)) {
+                this.portalMounts.add(mount);
+                for (const name in this.global) {
+                    mount.addEventListener(name, this.global[name].callback);
+                }
+            }
+        }"#
+    }
 
     #[cfg(feature = "binary-protocol")]
     fn append_children_to_top(many: u16) {
@@ -194,6 +269,11 @@ mod js {
         this.createListener(event_name, this_node, bubbles, (event) => {
             this.handler(event, event_name, bubbles);
         });
+        if (bubbles && this.portalMounts) {
+            for (const mount of this.portalMounts) {
+                mount.addEventListener(event_name, this.global[event_name].callback);
+            }
+        }
     }"#
     }
 
@@ -1,5 +1,5 @@
 use crate::unified_bindings::Interpreter as Channel;
-use dioxus_core::{Template, TemplateAttribute, TemplateNode, WriteMutations};
+use dioxus_core::{PortalTarget, Template, TemplateAttribute, TemplateNode, WriteMutations};
 use dioxus_core_types::event_bubbles;
 use rustc_hash::FxHashMap;
 
@@ -191,4 +191,13 @@ impl WriteMutations for MutationState {
     fn push_root(&mut self, id: dioxus_core::ElementId) {
         self.channel.push_root(id.0 as _);
     }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: dioxus_core::ElementId, m: usize) {
+        let (kind, target) = match target {
+            PortalTarget::Named(name) => (0, name),
+            PortalTarget::Element(element) => (1, element),
+        };
+        self.channel
+            .mount_portal(kind, target, id.0 as u32, m as u16);
+    }
 }
@@ -0,0 +1,353 @@
+//! The storage layer behind the incremental renderer.
+
+use chrono::{DateTime, Utc};
+
+use crate::{ContentEncoding, IncrementalRendererError};
+use std::{hash::Hasher, time::Duration};
+
+/// The character that separates a route from its variant in a cache key. Fragments are never sent to the server, so
+/// this can't appear in a route.
+pub const VARIANT_SEPARATOR: char = '#';
+
+/// Create the cache key of a variant of a route, like a route rendered for a specific language. An empty variant is the
+/// route itself.
+///
+/// ```rust
+/// # use dioxus_isrg::variant_key;
+/// assert_eq!(variant_key("/blog", "lang=en"), "/blog#lang=en");
+/// assert_eq!(variant_key("/blog", ""), "/blog");
+/// ```
+pub fn variant_key(route: &str, variant: &str) -> String {
+    if variant.is_empty() {
+        route.to_string()
+    } else {
+        format!("{route}{VARIANT_SEPARATOR}{variant}")
+    }
+}
+
+/// A single rendered page stored in a [`RenderCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheEntry {
+    /// The time the page was rendered
+    pub timestamp: DateTime<Utc>,
+    /// The rendered html
+    pub html: Vec<u8>,
+    /// Tags attached to the render that can be used to invalidate it with [`RenderCache::invalidate_tag`]
+    pub tags: Vec<String>,
+    /// Pre-compressed variants of the html
+    pub encodings: Vec<(ContentEncoding, Vec<u8>)>,
+    /// How long the render stays fresh. If this is `None`, the [`invalidate_after`](crate::IncrementalRendererConfig::invalidate_after)
+    /// duration of the renderer is used
+    pub max_age: Option<Duration>,
+    /// A weak `ETag` derived from the html when the entry was created
+    pub(crate) etag: String,
+}
+
+impl CacheEntry {
+    /// Create a new cache entry from a timestamp and the rendered html.
+    pub fn new(timestamp: DateTime<Utc>, html: impl Into<Vec<u8>>) -> Self {
+        let html = html.into();
+        let etag = etag_for(&html);
+        Self::restore(timestamp, html, etag)
+    }
+
+    /// Create a cache entry with the [`ETag`](Self::etag) that was derived from the html when it was first cached.
+    /// Caches that persist entries can use this to load them without hashing the html again.
+    pub fn restore(
+        timestamp: DateTime<Utc>,
+        html: impl Into<Vec<u8>>,
+        etag: impl Into<String>,
+    ) -> Self {
+        Self {
+            timestamp,
+            html: html.into(),
+            tags: Vec::new(),
+            encodings: Vec::new(),
+            max_age: None,
+            etag: etag.into(),
+        }
+    }
+
+    /// Attach tags to the entry.
+    pub fn with_tags<T: Into<String>>(mut self, tags: impl IntoIterator<Item = T>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set how long the entry stays fresh.
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Check if the entry has a tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Attach a pre-compressed variant of the html to the entry, replacing any existing variant with the same encoding.
+    pub fn with_encoding(mut self, encoding: ContentEncoding, bytes: impl Into<Vec<u8>>) -> Self {
+        self.encodings.retain(|(e, _)| *e != encoding);
+        self.encodings.push((encoding, bytes.into()));
+        self
+    }
+
+    /// Get the pre-compressed variant of the html with an encoding if it exists.
+    pub fn encoded(&self, encoding: ContentEncoding) -> Option<&[u8]> {
+        self.encodings
+            .iter()
+            .find(|(e, _)| *e == encoding)
+            .map(|(_, bytes)| bytes.as_slice())
+    }
+
+    /// A weak `ETag` derived from the html. Every encoding of the same html shares the same `ETag`.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+}
+
+/// Derive a weak `ETag` from rendered html
+fn etag_for(html: &[u8]) -> String {
+    let mut hasher = rustc_hash::FxHasher::default();
+    hasher.write(html);
+    format!("W/\"{:016x}-{:x}\"", hasher.finish(), html.len())
+}
+
+/// Counters that describe how well a [`RenderCache`] is performing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheMetrics {
+    /// The number of reads that found a cached route
+    pub hits: u64,
+    /// The number of reads that didn't find a cached route
+    pub misses: u64,
+    /// The number of routes removed to make room for new routes
+    pub evictions: u64,
+    /// The number of bytes currently stored
+    pub bytes: usize,
+    /// The number of routes currently stored
+    pub entries: usize,
+}
+
+impl CacheMetrics {
+    /// The fraction of reads that found a cached route, or zero if the cache was never read.
+    pub fn hit_ratio(&self) -> f64 {
+        let reads = self.hits + self.misses;
+        if reads == 0 {
+            0.0
+        } else {
+            self.hits as f64 / reads as f64
+        }
+    }
+}
+
+/// A storage backend for rendered routes.
+///
+/// The incremental renderer handles freshness itself, so implementations only need to store and return entries.
+/// Expired entries will be removed through [`RenderCache::invalidate`].
+///
+/// ```rust
+/// # use dioxus_isrg::{CacheEntry, IncrementalRendererError, RenderCache};
+/// use std::collections::HashMap;
+///
+/// #[derive(Default)]
+/// struct MapCache(HashMap<String, CacheEntry>);
+///
+/// impl RenderCache for MapCache {
+///     fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+///         Ok(self.0.get(route).cloned())
+///     }
+///
+///     fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+///         self.0.insert(route.to_string(), entry);
+///         Ok(())
+///     }
+///
+///     fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+///         self.0.remove(route);
+///         Ok(())
+///     }
+///
+///     fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+///         self.0.clear();
+///         Ok(())
+///     }
+///
+///     fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+///         Ok(self.0.keys().cloned().collect())
+///     }
+/// }
+///
+/// let mut renderer = dioxus_isrg::IncrementalRenderer::builder()
+///     .render_cache(MapCache::default)
+///     .build();
+/// renderer.cache("/index".to_string(), "<html></html>").unwrap();
+/// assert!(renderer.get("/index").unwrap().is_some());
+/// ```
+pub trait RenderCache: Send + Sync + 'static {
+    /// Get the entry for a route if it exists.
+    fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError>;
+
+    /// Store the entry for a route, replacing any previous entry.
+    fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError>;
+
+    /// Remove the entry for a route. Removing a route that is not cached is not an error.
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError>;
+
+    /// Remove every entry from the cache.
+    fn clear(&mut self) -> Result<(), IncrementalRendererError>;
+
+    /// List every route currently stored in the cache.
+    fn list(&self) -> Result<Vec<String>, IncrementalRendererError>;
+
+    /// Remove every entry with the tag. The default implementation reads every listed entry.
+    fn invalidate_tag(&mut self, tag: &str) -> Result<(), IncrementalRendererError> {
+        for route in self.list()? {
+            if self.get(&route)?.is_some_and(|entry| entry.has_tag(tag)) {
+                self.invalidate(&route)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove every entry whose route starts with the prefix.
+    fn invalidate_prefix(&mut self, prefix: &str) -> Result<(), IncrementalRendererError> {
+        for route in self.list()? {
+            if route.starts_with(prefix) {
+                self.invalidate(&route)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove every [variant](variant_key) of a route, but not the route itself. The default implementation removes
+    /// every entry that starts with the route followed by the [`VARIANT_SEPARATOR`].
+    fn invalidate_variants(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.invalidate_prefix(&format!("{route}{VARIANT_SEPARATOR}"))
+    }
+
+    /// Get the metrics of the cache if it tracks them. The default implementation returns `None`.
+    fn metrics(&self) -> Option<CacheMetrics> {
+        None
+    }
+}
+
+impl RenderCache for Box<dyn RenderCache> {
+    fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        (**self).get(route)
+    }
+
+    fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        (**self).put(route, entry)
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        (**self).invalidate(route)
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        (**self).clear()
+    }
+
+    fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        (**self).list()
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<(), IncrementalRendererError> {
+        (**self).invalidate_tag(tag)
+    }
+
+    fn invalidate_prefix(&mut self, prefix: &str) -> Result<(), IncrementalRendererError> {
+        (**self).invalidate_prefix(prefix)
+    }
+
+    fn invalidate_variants(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        (**self).invalidate_variants(route)
+    }
+
+    fn metrics(&self) -> Option<CacheMetrics> {
+        (**self).metrics()
+    }
+}
+
+/// A cache made of a fast front layer (typically an [`InMemoryCache`](crate::InMemoryCache)) in front of a slower backend.
+///
+/// Reads check the front layer first and fill it from the backend on a miss. Writes and invalidations go to both layers.
+/// The metrics of the front layer are reported if it tracks them, otherwise the metrics of the backend are reported.
+pub struct TieredCache<F, B> {
+    front: F,
+    back: B,
+}
+
+impl<F: RenderCache, B: RenderCache> TieredCache<F, B> {
+    /// Create a new tiered cache with `front` in front of `back`.
+    pub fn new(front: F, back: B) -> Self {
+        Self { front, back }
+    }
+
+    /// Get a reference to the front layer.
+    pub fn front(&self) -> &F {
+        &self.front
+    }
+
+    /// Get a reference to the backend.
+    pub fn back(&self) -> &B {
+        &self.back
+    }
+}
+
+impl<F: RenderCache, B: RenderCache> RenderCache for TieredCache<F, B> {
+    fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        if let Some(entry) = self.front.get(route)? {
+            return Ok(Some(entry));
+        }
+        let entry = self.back.get(route)?;
+        if let Some(entry) = &entry {
+            self.front.put(route, entry.clone())?;
+        }
+        Ok(entry)
+    }
+
+    fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        self.back.put(route, entry.clone())?;
+        self.front.put(route, entry)
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.front.invalidate(route)?;
+        self.back.invalidate(route)
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        self.front.clear()?;
+        self.back.clear()
+    }
+
+    fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut routes = self.back.list()?;
+        routes.extend(self.front.list()?);
+        routes.sort_unstable();
+        routes.dedup();
+        Ok(routes)
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<(), IncrementalRendererError> {
+        self.front.invalidate_tag(tag)?;
+        self.back.invalidate_tag(tag)
+    }
+
+    fn invalidate_prefix(&mut self, prefix: &str) -> Result<(), IncrementalRendererError> {
+        self.front.invalidate_prefix(prefix)?;
+        self.back.invalidate_prefix(prefix)
+    }
+
+    fn invalidate_variants(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.front.invalidate_variants(route)?;
+        self.back.invalidate_variants(route)
+    }
+
+    fn metrics(&self) -> Option<CacheMetrics> {
+        self.front.metrics().or_else(|| self.back.metrics())
+    }
+}
@@ -0,0 +1,147 @@
+//! Pre-compressed variants of cached renders and content negotiation between them.
+
+use std::io::Write;
+
+/// The brotli quality renders are compressed with. The highest qualities are several times slower for a few percent
+/// smaller output, which isn't worth it for renders that are regenerated at runtime.
+const BROTLI_QUALITY: u32 = 5;
+
+/// A compression format a cached render can be stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentEncoding {
+    /// Brotli compression (`br`)
+    Brotli,
+    /// Gzip compression (`gzip`)
+    Gzip,
+}
+
+impl ContentEncoding {
+    /// Every supported encoding in the order it is preferred when the client accepts several with the same weight.
+    pub const ALL: [ContentEncoding; 2] = [ContentEncoding::Brotli, ContentEncoding::Gzip];
+
+    /// The name of the encoding in the `Content-Encoding` and `Accept-Encoding` headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// The extension appended to the file name of a render stored with this encoding. This matches the naming used
+    /// by pre-compressed static assets, so `index.html` is stored as `index.html.br` and `index.html.gz`.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gz",
+        }
+    }
+
+    /// Compress the bytes with this encoding.
+    pub fn compress(&self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Brotli => {
+                let mut output = Vec::new();
+                {
+                    let mut writer =
+                        brotli::CompressorWriter::new(&mut output, 4096, BROTLI_QUALITY, 22);
+                    writer.write_all(bytes)?;
+                }
+                Ok(output)
+            }
+            ContentEncoding::Gzip => {
+                let mut writer =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                writer.write_all(bytes)?;
+                writer.finish()
+            }
+        }
+    }
+
+    /// Compress the bytes with every supported encoding.
+    pub fn compress_all(bytes: &[u8]) -> std::io::Result<Vec<(ContentEncoding, Vec<u8>)>> {
+        Self::ALL
+            .into_iter()
+            .map(|encoding| Ok((encoding, encoding.compress(bytes)?)))
+            .collect()
+    }
+
+    /// Pick the encoding to respond with from the value of an `Accept-Encoding` header and the encodings that are
+    /// available. Returns `None` if the uncompressed body should be sent.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::ContentEncoding;
+    /// let available = ContentEncoding::ALL;
+    /// assert_eq!(ContentEncoding::negotiate("gzip, deflate, br", available), Some(ContentEncoding::Brotli));
+    /// assert_eq!(ContentEncoding::negotiate("br;q=0.5, gzip", available), Some(ContentEncoding::Gzip));
+    /// assert_eq!(ContentEncoding::negotiate("*;q=0, identity", available), None);
+    /// assert_eq!(ContentEncoding::negotiate("gzip", [ContentEncoding::Brotli]), None);
+    /// ```
+    pub fn negotiate(
+        accept_encoding: &str,
+        available: impl IntoIterator<Item = ContentEncoding>,
+    ) -> Option<ContentEncoding> {
+        let weights: Vec<(&str, f32)> = accept_encoding
+            .split(',')
+            .filter_map(|directive| {
+                let mut parts = directive.split(';');
+                let name = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|param| {
+                        let (key, value) = param.split_once('=')?;
+                        (key.trim() == "q").then(|| value.trim().parse().ok())?
+                    })
+                    .unwrap_or(1.0);
+                (!name.is_empty()).then_some((name, quality))
+            })
+            .collect();
+        let weight_of = |name: &str| {
+            weights
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .or_else(|| weights.iter().find(|(n, _)| *n == "*"))
+                .map(|(_, quality)| *quality)
+        };
+
+        let preference = |encoding: ContentEncoding| {
+            Self::ALL
+                .iter()
+                .position(|e| *e == encoding)
+                .unwrap_or(usize::MAX)
+        };
+
+        let mut best: Option<(ContentEncoding, f32)> = None;
+        for encoding in available {
+            let Some(quality) = weight_of(encoding.as_str()) else {
+                continue;
+            };
+            let better = match best {
+                Some((best, best_quality)) => {
+                    quality > best_quality
+                        || (quality == best_quality && preference(encoding) < preference(best))
+                }
+                None => true,
+            };
+            if quality > 0.0 && better {
+                best = Some((encoding, quality));
+            }
+        }
+        best.map(|(encoding, _)| encoding)
+    }
+}
+
+/// Check if the value of an `If-None-Match` header matches an `ETag` using the weak comparison from RFC 9110.
+///
+/// ```rust
+/// # use dioxus_isrg::etag_matches;
+/// assert!(etag_matches("W/\"abc\", \"def\"", "W/\"def\""));
+/// assert!(etag_matches("*", "W/\"def\""));
+/// assert!(!etag_matches("\"abc\"", "W/\"def\""));
+/// ```
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
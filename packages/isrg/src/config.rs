@@ -3,22 +3,29 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::fs_cache::PathMapFn;
 
-use crate::memory_cache::InMemoryCache;
-use crate::IncrementalRenderer;
+use crate::{EvictionPolicy, InMemoryCache, IncrementalRenderer, RenderCache, TieredCache};
 
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
+type RenderCacheFactory = Arc<dyn Fn() -> Box<dyn RenderCache> + Send + Sync>;
+
 /// A configuration for the incremental renderer.
 #[derive(Clone)]
 pub struct IncrementalRendererConfig {
     static_dir: PathBuf,
     memory_cache_limit: usize,
+    memory_cache_byte_limit: Option<usize>,
+    eviction_policy: EvictionPolicy,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
     clear_cache: bool,
     pre_render: bool,
+    precompress: bool,
+    render_cache: Option<RenderCacheFactory>,
 
     #[cfg(not(target_arch = "wasm32"))]
     map_path: Option<PathMapFn>,
@@ -36,9 +43,14 @@ impl IncrementalRendererConfig {
         Self {
             static_dir: PathBuf::from("./static"),
             memory_cache_limit: 10000,
+            memory_cache_byte_limit: None,
+            eviction_policy: EvictionPolicy::Lru,
             invalidate_after: None,
+            stale_while_revalidate: None,
             clear_cache: false,
             pre_render: false,
+            precompress: true,
+            render_cache: None,
             #[cfg(not(target_arch = "wasm32"))]
             map_path: None,
         }
@@ -54,7 +66,7 @@ impl IncrementalRendererConfig {
     /// The function should return the path to the folder to store the index.html file in.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn map_path<F: Fn(&str) -> PathBuf + Send + Sync + 'static>(mut self, map_path: F) -> Self {
-        self.map_path = Some(std::sync::Arc::new(map_path));
+        self.map_path = Some(Arc::new(map_path));
         self
     }
 
@@ -70,29 +82,100 @@ impl IncrementalRendererConfig {
         self
     }
 
+    /// Limit the total size of the routes in the memory cache in bytes. Routes are evicted once either this limit or
+    /// [`Self::memory_cache_limit`] is reached.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{EvictionPolicy, IncrementalRendererConfig};
+    /// let config = IncrementalRendererConfig::new()
+    ///     .memory_cache_byte_limit(64 * 1024 * 1024)
+    ///     .eviction_policy(EvictionPolicy::Lfu);
+    /// ```
+    pub const fn memory_cache_byte_limit(mut self, memory_cache_byte_limit: usize) -> Self {
+        self.memory_cache_byte_limit = Some(memory_cache_byte_limit);
+        self
+    }
+
+    /// Set the policy the memory cache uses to pick which route to evict when it is full (default: [`EvictionPolicy::Lru`])
+    pub const fn eviction_policy(mut self, eviction_policy: EvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+
     /// Set the invalidation time.
     pub fn invalidate_after(mut self, invalidate_after: Duration) -> Self {
         self.invalidate_after = Some(invalidate_after);
         self
     }
 
+    /// Keep serving routes for `window` after they expire while a fresh render is generated in the background.
+    /// This only has an effect if [`Self::invalidate_after`] is set.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRendererConfig;
+    /// # use std::time::Duration;
+    /// let config = IncrementalRendererConfig::new()
+    ///     .invalidate_after(Duration::from_secs(60))
+    ///     .stale_while_revalidate(Duration::from_secs(600));
+    /// ```
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = Some(window);
+        self
+    }
+
     /// Set whether to include hydration ids in the pre-rendered html.
     pub fn pre_render(mut self, pre_render: bool) -> Self {
         self.pre_render = pre_render;
         self
     }
 
+    /// Store gzip and brotli compressed variants next to every cached route so they can be served without compressing
+    /// the html on every request (default: true)
+    pub fn precompress(mut self, precompress: bool) -> Self {
+        self.precompress = precompress;
+        self
+    }
+
+    /// Set the backend that stores rendered routes. The backend will be fronted by an in memory cache limited by
+    /// [`Self::memory_cache_limit`]. If no backend is set, routes are stored in the file system inside [`Self::static_dir`].
+    ///
+    /// The function is called once every time the renderer is built.
+    pub fn render_cache<C: RenderCache>(
+        mut self,
+        render_cache: impl Fn() -> C + Send + Sync + 'static,
+    ) -> Self {
+        self.render_cache = Some(Arc::new(move || Box::new(render_cache())));
+        self
+    }
+
     /// Build the incremental renderer.
     pub fn build(self) -> IncrementalRenderer {
-        let mut renderer = IncrementalRenderer {
+        let mut memory_cache =
+            InMemoryCache::new(self.memory_cache_limit).eviction_policy(self.eviction_policy);
+        if let Some(max_bytes) = self.memory_cache_byte_limit {
+            memory_cache = memory_cache.max_bytes(max_bytes);
+        }
+        let cache: Box<dyn RenderCache> = match &self.render_cache {
+            Some(render_cache) => Box::new(TieredCache::new(memory_cache, render_cache())),
             #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache: crate::fs_cache::FileSystemCache::new(
-                self.static_dir.clone(),
-                self.map_path,
-                self.invalidate_after,
-            ),
-            memory_cache: InMemoryCache::new(self.memory_cache_limit, self.invalidate_after),
+            None => Box::new(TieredCache::new(
+                memory_cache,
+                crate::FileSystemCache::from_parts(
+                    self.static_dir.clone(),
+                    self.map_path,
+                    self.invalidate_after.is_some(),
+                ),
+            )),
+            #[cfg(target_arch = "wasm32")]
+            None => Box::new(memory_cache),
+        };
+        let mut renderer = IncrementalRenderer {
+            cache,
             invalidate_after: self.invalidate_after,
+            stale_while_revalidate: self.stale_while_revalidate,
+            precompress: self.precompress,
+            pending_compression: Default::default(),
+            queued_compression: Vec::new(),
         };
 
         if self.clear_cache {
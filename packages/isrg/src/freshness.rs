@@ -11,27 +11,11 @@ pub struct RenderFreshness {
     max_age: Option<u64>,
     /// The time the response was rendered
     timestamp: DateTime<Utc>,
+    /// How long after the maximum age the response may still be served while it is regenerated
+    stale_while_revalidate: Option<u64>,
 }
 
 impl RenderFreshness {
-    /// Create new freshness information
-    pub(crate) fn new(age: u64, max_age: u64, timestamp: DateTime<Utc>) -> Self {
-        Self {
-            age,
-            max_age: Some(max_age),
-            timestamp,
-        }
-    }
-
-    /// Create new freshness information with only the age
-    pub(crate) fn new_age(age: u64, timestamp: DateTime<Utc>) -> Self {
-        Self {
-            age,
-            max_age: None,
-            timestamp,
-        }
-    }
-
     /// Create new freshness information from a timestamp
     pub(crate) fn created_at(timestamp: DateTime<Utc>, max_age: Option<Duration>) -> Self {
         Self {
@@ -41,15 +25,23 @@ impl RenderFreshness {
                 .unsigned_abs(),
             max_age: max_age.map(|d| d.as_secs()),
             timestamp,
+            stale_while_revalidate: None,
         }
     }
 
+    /// Set the window after the maximum age where the response may be served while it is regenerated
+    pub(crate) fn with_stale_while_revalidate(mut self, window: Option<Duration>) -> Self {
+        self.stale_while_revalidate = window.map(|d| d.as_secs());
+        self
+    }
+
     /// Create new freshness information at the current time
     pub fn now(max_age: Option<Duration>) -> Self {
         Self {
             age: 0,
             max_age: max_age.map(|d| d.as_secs()),
             timestamp: Utc::now(),
+            stale_while_revalidate: None,
         }
     }
 
@@ -68,14 +60,30 @@ impl RenderFreshness {
         self.timestamp
     }
 
+    /// Get the window after the maximum age in seconds where the response may be served while it is regenerated
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.stale_while_revalidate
+    }
+
+    /// Check if the response is older than its maximum age
+    pub fn is_stale(&self) -> bool {
+        self.max_age.is_some_and(|max_age| self.age > max_age)
+    }
+
     /// Write the freshness to the response headers.
+    ///
+    /// Stale responses keep their real age in the `Age` header so that it exceeds the `max-age` directive in `Cache-Control`.
     pub fn write(&self, headers: &mut http::HeaderMap<http::HeaderValue>) {
         let age = self.age();
         headers.insert(http::header::AGE, age.into());
         if let Some(max_age) = self.max_age() {
+            let mut cache_control = format!("max-age={}", max_age);
+            if let Some(window) = self.stale_while_revalidate() {
+                cache_control += &format!(", stale-while-revalidate={}", window);
+            }
             headers.insert(
                 http::header::CACHE_CONTROL,
-                http::HeaderValue::from_str(&format!("max-age={}", max_age)).unwrap(),
+                http::HeaderValue::from_str(&cache_control).unwrap(),
             );
         }
     }
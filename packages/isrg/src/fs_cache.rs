@@ -2,60 +2,146 @@
 
 use chrono::{DateTime, Utc};
 
-use super::{IncrementalRendererError, RenderFreshness};
-use std::{path::PathBuf, sync::Arc, time::SystemTime};
+use crate::{
+    CacheEntry, ContentEncoding, IncrementalRendererError, RenderCache, VARIANT_SEPARATOR,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
 
 pub(crate) type PathMapFn = Arc<dyn Fn(&str) -> PathBuf + Send + Sync>;
 
-pub(crate) struct FileSystemCache {
+/// A [`RenderCache`] that stores each route as an html file in a static directory.
+///
+/// By default the route `/blog/post` is stored in `{static_dir}/blog/post/index.html`. `{static_dir}/index.html` is the
+/// template of the app, so the route `/` is stored in `{static_dir}/.root/index.html` instead. If timestamps are tracked, the file
+/// is stored in `{static_dir}/blog/post/index/{timestamp}.html` instead so the render time survives restarts. Variants of a
+/// route like `/blog/post#lang=en` are stored in `{static_dir}/blog/post/#lang=en/index.html`.
+///
+/// Tags attached to a render are stored next to the html file in a `.tags` file with one tag per line and the maximum age
+/// of a render is stored in a `.max-age` file in seconds. Pre-compressed
+/// variants of the render are stored next to the html file with the extension of the encoding, like `index.html.br`.
+pub struct FileSystemCache {
     static_dir: PathBuf,
     map_path: PathMapFn,
-    invalidate_after: Option<std::time::Duration>,
+    custom_map_path: bool,
+    track_timestamps: bool,
 }
 
 impl FileSystemCache {
-    pub fn new(
+    /// Create a new file system cache that stores routes inside `static_dir`.
+    pub fn new(static_dir: impl Into<PathBuf>) -> Self {
+        Self::from_parts(static_dir.into(), None, false)
+    }
+
+    pub(crate) fn from_parts(
         static_dir: PathBuf,
         map_path: Option<PathMapFn>,
-        invalidate_after: Option<std::time::Duration>,
+        track_timestamps: bool,
     ) -> Self {
         Self {
             static_dir: static_dir.clone(),
+            custom_map_path: map_path.is_some(),
             map_path: map_path.unwrap_or_else(move || {
-                Arc::new(move |route: &str| {
+                Arc::new(move |key: &str| {
+                    let (route, variant) = key.split_once(VARIANT_SEPARATOR).unwrap_or((key, ""));
                     let (before_query, _) = route.split_once('?').unwrap_or((route, ""));
                     let mut path = static_dir.clone();
                     for segment in before_query.split('/') {
                         path.push(segment);
                     }
+                    // Variants are stored in a folder inside the folder of the route
+                    if !variant.is_empty() {
+                        path.push(format!("{VARIANT_SEPARATOR}{variant}"));
+                    }
                     path
                 })
             }),
-            invalidate_after,
+            track_timestamps,
         }
     }
 
-    pub fn put(
-        &mut self,
-        route: String,
-        timestamp: DateTime<Utc>,
-        data: Vec<u8>,
-    ) -> Result<(), IncrementalRendererError> {
-        use std::io::Write;
-        let file_path = self.route_as_path(&route, timestamp);
-        if let Some(parent) = file_path.parent() {
-            if !parent.exists() {
-                std::fs::create_dir_all(parent)?;
+    /// Set a mapping from the route to the folder the route is stored in.
+    ///
+    /// Routes stored with a custom mapping cannot be recovered from the directory layout, so [`RenderCache::list`] will only return routes
+    /// that were stored with the default mapping.
+    pub fn map_path<F: Fn(&str) -> PathBuf + Send + Sync + 'static>(mut self, map_path: F) -> Self {
+        self.map_path = Arc::new(map_path);
+        self.custom_map_path = true;
+        self
+    }
+
+    /// Store the render timestamp in the file name so the age of the render survives restarts (default: false)
+    pub fn track_timestamps(mut self, track_timestamps: bool) -> Self {
+        self.track_timestamps = track_timestamps;
+        self
+    }
+
+    /// The folder the renders of the route are stored in
+    fn route_folder(&self, route: &str) -> PathBuf {
+        let folder = (self.map_path)(route);
+        // `{static_dir}/index.html` is the app template, so `/` is stored in a folder of its own
+        if folder == self.static_dir {
+            return folder.join(ROOT_ROUTE_FOLDER);
+        }
+        folder
+    }
+
+    fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
+        let mut file_path = self.route_folder(route);
+        if self.track_timestamps {
+            // find the newest html file for the route
+            file_path.push("index");
+            std::fs::read_dir(file_path)
+                .ok()?
+                .flatten()
+                .filter_map(|entry| ValidCachedPath::try_from_path(entry.path()))
+                .max_by_key(|cached_path| cached_path.timestamp)
+        } else {
+            file_path.push("index.html");
+            let timestamp = std::fs::metadata(&file_path).ok()?.modified().ok()?;
+            Some(ValidCachedPath {
+                full_path: file_path,
+                timestamp,
+            })
+        }
+    }
+
+    fn route_as_path(&self, route: &str, timestamp: DateTime<Utc>) -> PathBuf {
+        let mut file_path = self.route_folder(route);
+        if self.track_timestamps {
+            file_path.push("index");
+            file_path.push(timestamp_to_string(timestamp));
+        } else {
+            file_path.push("index");
+        }
+        file_path.set_extension("html");
+        file_path
+    }
+
+    /// Remove every html file stored for the route
+    fn remove_route_files(&self, route: &str) -> std::io::Result<()> {
+        let mut file_path = self.route_folder(route);
+        if self.track_timestamps {
+            file_path.push("index");
+            for entry in std::fs::read_dir(file_path).into_iter().flatten().flatten() {
+                if ValidCachedPath::try_from_path(entry.path()).is_some() {
+                    remove_render(&entry.path())?;
+                }
+            }
+        } else {
+            file_path.push("index.html");
+            if file_path.exists() {
+                remove_render(&file_path)?;
             }
         }
-        let file = std::fs::File::create(file_path)?;
-        let mut file = std::io::BufWriter::new(file);
-        file.write_all(&data)?;
         Ok(())
     }
 
-    pub fn clear(&mut self) {
-        // clear the static directory of index.html files contained within folders
+    /// Call `f` with every file inside the folders of the static directory. The top level files are not managed by the cache
+    fn for_each_cached_file(&self, mut f: impl FnMut(&Path)) {
         for entry in std::fs::read_dir(&self.static_dir)
             .into_iter()
             .flatten()
@@ -64,94 +150,202 @@ impl FileSystemCache {
             if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
                 for entry in walkdir::WalkDir::new(entry.path()).into_iter().flatten() {
                     if entry.file_type().is_file() {
-                        if let Some(fnmae) = entry.file_name().to_str() {
-                            if fnmae.ends_with(".html") {
-                                if let Err(err) = std::fs::remove_file(entry.path()) {
-                                    tracing::error!("Failed to remove file: {}", err);
-                                }
-                            }
-                        }
+                        f(entry.path());
                     }
                 }
             }
         }
     }
 
-    pub fn invalidate(&mut self, route: &str) {
-        let file_path = self.find_file(route).unwrap().full_path;
-        if let Err(err) = std::fs::remove_file(file_path) {
-            tracing::error!("Failed to remove file: {}", err);
+    /// Recover the route from the path of a cached file with the default path mapping
+    fn path_as_route(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.static_dir).ok()?;
+        let mut segments: Vec<_> = relative
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<_>>()?;
+        if self.track_timestamps {
+            ValidCachedPath::try_from_path(path.to_path_buf())?;
+            segments.pop();
+            if segments.pop()? != "index" {
+                return None;
+            }
+        } else if segments.pop()? != "index.html" {
+            return None;
         }
+        if segments == [ROOT_ROUTE_FOLDER] {
+            return Some("/".to_string());
+        }
+        let variant = segments
+            .last()
+            .filter(|segment| segment.starts_with(VARIANT_SEPARATOR))
+            .copied();
+        if variant.is_some() {
+            segments.pop();
+        }
+        Some(format!(
+            "/{}{}",
+            segments.join("/"),
+            variant.unwrap_or_default()
+        ))
     }
+}
 
-    pub fn get(
-        &self,
-        route: &str,
-    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+impl RenderCache for FileSystemCache {
+    fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
         if let Some(file_path) = self.find_file(route) {
-            if let Some(freshness) = file_path.freshness(self.invalidate_after) {
-                if let Ok(file) = std::fs::File::open(file_path.full_path) {
-                    let mut file = std::io::BufReader::new(file);
-                    let mut cache_hit = Vec::new();
-                    std::io::copy(&mut file, &mut cache_hit)?;
-                    tracing::trace!("file cache hit {:?}", route);
-                    return Ok(Some((freshness, cache_hit)));
+            if let Ok(file) = std::fs::File::open(&file_path.full_path) {
+                let mut file = std::io::BufReader::new(file);
+                let mut cache_hit = Vec::new();
+                std::io::copy(&mut file, &mut cache_hit)?;
+                tracing::trace!("file cache hit {:?}", route);
+                let tags = read_tags(&file_path.full_path)?;
+                let max_age = read_max_age(&file_path.full_path)?;
+                let timestamp = file_path.timestamp.into();
+                let entry = match read_etag(&file_path.full_path)? {
+                    Some(etag) => CacheEntry::restore(timestamp, cache_hit, etag),
+                    None => CacheEntry::new(timestamp, cache_hit),
+                };
+                let mut entry = entry.with_tags(tags).with_max_age(max_age);
+                for encoding in ContentEncoding::ALL {
+                    match std::fs::read(encoded_path(&file_path.full_path, encoding)) {
+                        Ok(bytes) => entry = entry.with_encoding(encoding, bytes),
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(err) => return Err(err.into()),
+                    }
                 }
+                return Ok(Some(entry));
             }
         }
 
         Ok(None)
     }
 
-    fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
-        let mut file_path = (self.map_path)(route);
-        if let Some(deadline) = self.invalidate_after {
-            // find the first file that matches the route and is a html file
-            file_path.push("index");
-            if let Ok(dir) = std::fs::read_dir(file_path) {
-                for entry in dir.flatten() {
-                    if let Some(cached_path) = ValidCachedPath::try_from_path(entry.path()) {
-                        if let Ok(elapsed) = cached_path.timestamp.elapsed() {
-                            if elapsed < deadline {
-                                // The timestamp is valid, return the file
-                                return Some(cached_path);
-                            }
-                        }
-                        // if the timestamp is invalid or passed, delete the file
-                        if let Err(err) = std::fs::remove_file(entry.path()) {
-                            tracing::error!("Failed to remove file: {}", err);
-                        }
-                    }
-                }
-                None
-            } else {
-                None
+    fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        use std::io::Write;
+        // Remove any older renders of the route so they don't pile up in the index folder
+        if self.track_timestamps {
+            self.remove_route_files(route)?;
+        }
+        let file_path = self.route_as_path(route, entry.timestamp);
+        if let Some(parent) = file_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let tags_path = file_path.with_extension(TAGS_EXTENSION);
+        let max_age_path = file_path.with_extension(MAX_AGE_EXTENSION);
+        let etag_path = file_path.with_extension(ETAG_EXTENSION);
+        for encoding in ContentEncoding::ALL {
+            let encoded_path = encoded_path(&file_path, encoding);
+            match entry.encoded(encoding) {
+                Some(bytes) => std::fs::write(encoded_path, bytes)?,
+                None if encoded_path.exists() => std::fs::remove_file(encoded_path)?,
+                None => {}
+            }
+        }
+        let file = std::fs::File::create(file_path)?;
+        let mut file = std::io::BufWriter::new(file);
+        file.write_all(&entry.html)?;
+        if entry.tags.is_empty() {
+            if tags_path.exists() {
+                std::fs::remove_file(tags_path)?;
             }
         } else {
-            file_path.push("index.html");
-            file_path.exists().then_some({
-                ValidCachedPath {
-                    full_path: file_path,
-                    timestamp: SystemTime::now(),
+            std::fs::write(tags_path, entry.tags.join("\n"))?;
+        }
+        match entry.max_age {
+            Some(max_age) => std::fs::write(&max_age_path, max_age.as_secs().to_string())?,
+            None if max_age_path.exists() => std::fs::remove_file(&max_age_path)?,
+            None => {}
+        }
+        std::fs::write(etag_path, entry.etag())?;
+        Ok(())
+    }
+
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.remove_route_files(route)?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        // clear the static directory of index.html files contained within folders
+        self.for_each_cached_file(|path| {
+            let extension = path.extension().and_then(|ext| ext.to_str());
+            // Only remove compressed html so pre-compressed assets in the same folder are kept
+            let is_encoded = ContentEncoding::ALL.iter().any(|encoding| {
+                extension == Some(encoding.file_extension())
+                    && Path::new(path.file_stem().unwrap_or_default()).extension()
+                        == Some(std::ffi::OsStr::new("html"))
+            });
+            if matches!(
+                extension,
+                Some("html" | TAGS_EXTENSION | MAX_AGE_EXTENSION | ETAG_EXTENSION)
+            ) || is_encoded
+            {
+                if let Err(err) = std::fs::remove_file(path) {
+                    tracing::error!("Failed to remove file: {}", err);
                 }
-            })
+            }
+        });
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        if self.custom_map_path {
+            tracing::warn!("Routes stored with a custom path mapping cannot be listed");
         }
+        let mut routes = Vec::new();
+        self.for_each_cached_file(|path| {
+            if let Some(route) = self.path_as_route(path) {
+                routes.push(route);
+            }
+        });
+        routes.sort_unstable();
+        routes.dedup();
+        Ok(routes)
     }
 
-    fn route_as_path(&self, route: &str, timestamp: DateTime<Utc>) -> PathBuf {
-        let mut file_path = (self.map_path)(route);
-        if self.track_timestamps() {
-            file_path.push("index");
-            file_path.push(timestamp_to_string(timestamp));
-        } else {
-            file_path.push("index");
+    fn invalidate_tag(&mut self, tag: &str) -> Result<(), IncrementalRendererError> {
+        // Only the tag files need to be read, so this also works with a custom path mapping
+        let mut tagged = Vec::new();
+        self.for_each_cached_file(|path| {
+            if path.extension() == Some(std::ffi::OsStr::new(TAGS_EXTENSION)) {
+                match read_tags(path) {
+                    Ok(tags) if tags.iter().any(|t| t == tag) => {
+                        tagged.push(path.with_extension("html"));
+                    }
+                    Ok(_) => {}
+                    Err(err) => tracing::error!("Failed to read tags from {path:?}: {err}"),
+                }
+            }
+        });
+        for path in tagged {
+            remove_render(&path)?;
         }
-        file_path.set_extension("html");
-        file_path
+        Ok(())
     }
 
-    fn track_timestamps(&self) -> bool {
-        self.invalidate_after.is_some()
+    fn invalidate_variants(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        // Variants are only stored inside the folder of the route with the default path mapping
+        if self.custom_map_path {
+            return self.invalidate_prefix(&format!("{route}{VARIANT_SEPARATOR}"));
+        }
+        for entry in std::fs::read_dir((self.map_path)(route))
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let name = entry.file_name();
+            let Some(variant) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(VARIANT_SEPARATOR))
+            else {
+                continue;
+            };
+            self.remove_route_files(&crate::variant_key(route, variant))?;
+        }
+        Ok(())
     }
 }
 
@@ -172,15 +366,80 @@ impl ValidCachedPath {
             timestamp,
         })
     }
+}
+
+/// The folder inside the static directory that the renders of `/` are stored in
+const ROOT_ROUTE_FOLDER: &str = ".root";
+
+/// The extension of the file that stores the tags of a render
+const TAGS_EXTENSION: &str = "tags";
+
+/// The extension of the file that stores the maximum age of a render in seconds
+const MAX_AGE_EXTENSION: &str = "max-age";
+
+/// The extension of the file that stores the `ETag` of a render
+const ETAG_EXTENSION: &str = "etag";
+
+/// Read the tags stored next to a rendered html file
+fn read_tags(html_path: &Path) -> std::io::Result<Vec<String>> {
+    match std::fs::read_to_string(html_path.with_extension(TAGS_EXTENSION)) {
+        Ok(tags) => Ok(tags.lines().map(ToString::to_string).collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Read the maximum age stored next to a rendered html file
+fn read_max_age(html_path: &Path) -> std::io::Result<Option<std::time::Duration>> {
+    match std::fs::read_to_string(html_path.with_extension(MAX_AGE_EXTENSION)) {
+        Ok(max_age) => Ok(max_age
+            .trim()
+            .parse()
+            .ok()
+            .map(std::time::Duration::from_secs)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Read the `ETag` stored next to a rendered html file
+fn read_etag(html_path: &Path) -> std::io::Result<Option<String>> {
+    match std::fs::read_to_string(html_path.with_extension(ETAG_EXTENSION)) {
+        Ok(etag) => Ok(Some(etag)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
 
-    pub fn freshness(&self, max_age: Option<std::time::Duration>) -> Option<RenderFreshness> {
-        let age = self.timestamp.elapsed().ok()?.as_secs();
-        let max_age = max_age.map(|max_age| max_age.as_secs());
-        Some(RenderFreshness::new(age, max_age?, self.timestamp.into()))
+/// Get the path of a pre-compressed variant of a rendered html file
+fn encoded_path(html_path: &Path, encoding: ContentEncoding) -> PathBuf {
+    let mut path = html_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(encoding.file_extension());
+    PathBuf::from(path)
+}
+
+/// Remove a rendered html file and the tags and pre-compressed variants stored next to it
+fn remove_render(html_path: &Path) -> std::io::Result<()> {
+    let encoded = ContentEncoding::ALL.map(|encoding| encoded_path(html_path, encoding));
+    for path in [
+        html_path.to_path_buf(),
+        html_path.with_extension(TAGS_EXTENSION),
+        html_path.with_extension(MAX_AGE_EXTENSION),
+        html_path.with_extension(ETAG_EXTENSION),
+    ]
+    .into_iter()
+    .chain(encoded)
+    {
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
     }
+    Ok(())
 }
 
-fn decode_timestamp(timestamp: &str) -> Option<std::time::SystemTime> {
+fn decode_timestamp(timestamp: &str) -> Option<SystemTime> {
     let timestamp = u64::from_str_radix(timestamp, 16).ok()?;
     Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(timestamp))
 }
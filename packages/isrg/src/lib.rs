@@ -2,36 +2,104 @@
 
 #![allow(non_snake_case)]
 
+mod cache;
+mod compression;
 mod config;
 mod freshness;
 #[cfg(not(target_arch = "wasm32"))]
 mod fs_cache;
 mod memory_cache;
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
-use chrono::Utc;
+pub use cache::*;
+use chrono::{DateTime, Utc};
+pub use compression::*;
 pub use config::*;
 pub use freshness::*;
-
-use self::memory_cache::InMemoryCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use fs_cache::FileSystemCache;
+pub use memory_cache::{EvictionPolicy, InMemoryCache};
 
 /// A render that was cached from a previous render.
-pub struct CachedRender<'a> {
+pub struct CachedRender {
     /// The route that was rendered
     pub route: String,
     /// The freshness information for the rendered response
     pub freshness: RenderFreshness,
     /// The rendered response
-    pub response: &'a [u8],
+    pub response: Vec<u8>,
+    /// Pre-compressed variants of the response
+    pub encodings: Vec<(ContentEncoding, Vec<u8>)>,
+    /// A weak `ETag` derived from the response
+    pub etag: String,
+}
+
+impl CachedRender {
+    /// Pick the body to send for the value of an `Accept-Encoding` header. Returns the encoding of the body, or `None`
+    /// if the body is not compressed.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{ContentEncoding, IncrementalRenderer};
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/encoded".to_string(), "<html></html>").unwrap();
+    /// for pending in renderer.take_pending_compression() {
+    ///     renderer.finish_compression(pending.compress().unwrap()).unwrap();
+    /// }
+    /// let cached = renderer.get("/encoded").unwrap().unwrap();
+    /// let (encoding, _) = cached.body_for(Some("gzip, br"));
+    /// assert_eq!(encoding, Some(ContentEncoding::Brotli));
+    /// let (encoding, body) = cached.body_for(None);
+    /// assert_eq!((encoding, body), (None, b"<html></html>".as_slice()));
+    /// ```
+    pub fn body_for(&self, accept_encoding: Option<&str>) -> (Option<ContentEncoding>, &[u8]) {
+        let encoding = accept_encoding.and_then(|accept_encoding| {
+            ContentEncoding::negotiate(accept_encoding, self.encodings.iter().map(|(e, _)| *e))
+        });
+        let body = encoding
+            .and_then(|encoding| {
+                self.encodings
+                    .iter()
+                    .find(|(e, _)| *e == encoding)
+                    .map(|(_, bytes)| bytes.as_slice())
+            })
+            .unwrap_or(&self.response);
+        (encoding, body)
+    }
 }
 
 /// An incremental renderer.
 pub struct IncrementalRenderer {
-    pub(crate) memory_cache: InMemoryCache,
-    #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) file_system_cache: fs_cache::FileSystemCache,
+    pub(crate) cache: Box<dyn RenderCache>,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    precompress: bool,
+    /// Every render stored with [`IncrementalRenderer::cache_uncompressed`] that is still waiting for its compressed
+    /// variants
+    pending_compression: HashMap<String, PendingRender>,
+    /// Renders stored with [`IncrementalRenderer::cache`] that haven't been handed out for compression yet
+    queued_compression: Vec<PendingCompression>,
+}
+
+/// The parts of a render waiting for compression needed to tell if it is still current
+struct PendingRender {
+    timestamp: DateTime<Utc>,
+    tags: Vec<String>,
+}
+
+/// A render stored with [`IncrementalRenderer::cache_uncompressed`] that still needs to be compressed.
+pub struct PendingCompression {
+    route: String,
+    entry: CacheEntry,
+}
+
+impl PendingCompression {
+    /// Compress the render with every supported encoding. This is slow for large renders, so it should run on a
+    /// blocking thread without holding a lock on the renderer.
+    pub fn compress(mut self) -> std::io::Result<Self> {
+        self.entry.encodings = ContentEncoding::compress_all(&self.entry.html)?;
+        Ok(self)
+    }
 }
 
 impl IncrementalRenderer {
@@ -40,21 +108,133 @@ impl IncrementalRenderer {
         IncrementalRendererConfig::new()
     }
 
-    /// Remove a route from the cache.
+    /// Remove a route and every [variant](variant_key) of the route from the cache.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{variant_key, IncrementalRenderer};
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache(variant_key("/about", "lang=en"), "<html></html>").unwrap();
+    /// renderer.cache(variant_key("/about", "lang=fr"), "<html></html>").unwrap();
+    /// renderer.invalidate("/about");
+    /// assert!(renderer.get(&variant_key("/about", "lang=en")).unwrap().is_none());
+    /// assert!(renderer.get(&variant_key("/about", "lang=fr")).unwrap().is_none());
+    /// ```
     pub fn invalidate(&mut self, route: &str) {
-        self.memory_cache.invalidate(route);
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.invalidate(route);
+        // Drop compressions that are in flight so they can't store an invalidated render again
+        self.forget_pending_compression(|key, _| {
+            key.strip_prefix(route)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(VARIANT_SEPARATOR))
+        });
+        let result = self
+            .cache
+            .invalidate(route)
+            .and_then(|_| self.cache.invalidate_variants(route));
+        if let Err(err) = result {
+            tracing::error!("Failed to invalidate route {route:?}: {err}");
+        }
+    }
+
+    /// Remove the entry stored under exactly this key from the cache. Unlike [`Self::invalidate`], variants of the
+    /// route are kept.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{variant_key, IncrementalRenderer};
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/contact".to_string(), "<html></html>").unwrap();
+    /// renderer.cache(variant_key("/contact", "lang=en"), "<html></html>").unwrap();
+    /// renderer.invalidate_key("/contact");
+    /// assert!(renderer.get("/contact").unwrap().is_none());
+    /// assert!(renderer.get(&variant_key("/contact", "lang=en")).unwrap().is_some());
+    /// ```
+    pub fn invalidate_key(&mut self, key: &str) {
+        // Drop a compression that is in flight so it can't store an invalidated render again
+        self.forget_pending_compression(|pending, _| pending == key);
+        if let Err(err) = self.cache.invalidate(key) {
+            tracing::error!("Failed to invalidate key {key:?}: {err}");
+        }
+    }
+
+    /// Remove a single variant of a route from the cache.
+    pub fn invalidate_variant(&mut self, route: &str, variant: &str) {
+        // Drop a compression that is in flight so it can't store an invalidated render again
+        let key = variant_key(route, variant);
+        self.forget_pending_compression(|pending, _| pending == key);
+        if let Err(err) = self.cache.invalidate(&key) {
+            tracing::error!("Failed to invalidate variant {variant:?} of route {route:?}: {err}");
+        }
     }
 
     /// Remove all routes from the cache.
     pub fn invalidate_all(&mut self) {
-        self.memory_cache.clear();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.clear();
+        // Drop compressions that are in flight so they can't store an invalidated render again
+        self.pending_compression.clear();
+        if let Err(err) = self.cache.clear() {
+            tracing::error!("Failed to clear the cache: {err}");
+        }
+    }
+
+    /// Remove every route rendered with the tag from the cache.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache_with_tags("/products/42".to_string(), "<html></html>", ["product:42"]).unwrap();
+    /// renderer.invalidate_tag("product:42");
+    /// assert!(renderer.get("/products/42").unwrap().is_none());
+    /// ```
+    pub fn invalidate_tag(&mut self, tag: &str) {
+        // Drop compressions that are in flight so they can't store an invalidated render again
+        self.forget_pending_compression(|_, tags| tags.iter().any(|t| t == tag));
+        if let Err(err) = self.cache.invalidate_tag(tag) {
+            tracing::error!("Failed to invalidate tag {tag:?}: {err}");
+        }
+    }
+
+    /// Remove every route that starts with the prefix from the cache. For example, `/blog/` removes every route under `/blog/`.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/docs/intro".to_string(), "<html></html>").unwrap();
+    /// renderer.invalidate_prefix("/docs/");
+    /// assert!(renderer.get("/docs/intro").unwrap().is_none());
+    /// ```
+    pub fn invalidate_prefix(&mut self, prefix: &str) {
+        // Drop compressions that are in flight so they can't store an invalidated render again
+        self.forget_pending_compression(|key, _| key.starts_with(prefix));
+        if let Err(err) = self.cache.invalidate_prefix(prefix) {
+            tracing::error!("Failed to invalidate prefix {prefix:?}: {err}");
+        }
+    }
+
+    /// List all routes that are currently cached. Variants of a route are listed with their [variant key](variant_key).
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/index".to_string(), "<html></html>").unwrap();
+    /// assert!(renderer.cached_routes().unwrap().contains(&"/index".to_string()));
+    /// ```
+    pub fn cached_routes(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        self.cache.list()
     }
 
-    /// Cache a rendered response.
+    /// Get the metrics of the memory cache in front of the backend, like the number of cache hits and the number of
+    /// bytes stored.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/metrics".to_string(), "<html></html>").unwrap();
+    /// renderer.get("/metrics").unwrap();
+    /// assert_eq!(renderer.metrics().hits, 1);
+    /// ```
+    pub fn metrics(&self) -> CacheMetrics {
+        self.cache.metrics().unwrap_or_default()
+    }
+
+    /// Cache a rendered response. If precompression is enabled, the response is stored uncompressed and queued for
+    /// compression. Take the queued renders with [`Self::take_pending_compression`] to compress them.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
@@ -68,16 +248,135 @@ impl IncrementalRenderer {
         route: String,
         html: impl Into<Vec<u8>>,
     ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.cache_with_tags(route, html, std::iter::empty::<String>())
+    }
+
+    /// Cache a rendered response with tags that can be used to invalidate it later with [`Self::invalidate_tag`].
+    pub fn cache_with_tags<T: Into<String>>(
+        &mut self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+        tags: impl IntoIterator<Item = T>,
+    ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.cache_with_max_age(route, html, tags, None)
+    }
+
+    /// Cache a rendered response with tags and a maximum age that overrides [`IncrementalRendererConfig::invalidate_after`]
+    /// for this render.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # use std::time::Duration;
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// let freshness = renderer
+    ///     .cache_with_max_age("/".to_string(), "<html></html>", ["home"], Some(Duration::from_secs(30)))
+    ///     .unwrap();
+    /// assert_eq!(freshness.max_age(), Some(30));
+    /// ```
+    pub fn cache_with_max_age<T: Into<String>>(
+        &mut self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+        tags: impl IntoIterator<Item = T>,
+        max_age: Option<Duration>,
+    ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.queued_compression
+            .retain(|queued| queued.route != route);
+        let (freshness, pending) = self.cache_uncompressed(route, html, tags, max_age)?;
+        self.queued_compression.extend(pending);
+        Ok(freshness)
+    }
+
+    /// Take the renders stored with [`Self::cache`] that are waiting to be compressed. They can be compressed without
+    /// holding a lock on the renderer and stored with [`Self::finish_compression`].
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{ContentEncoding, IncrementalRenderer};
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// renderer.cache("/queued".to_string(), "<html></html>").unwrap();
+    /// let pending = renderer.take_pending_compression();
+    /// assert_eq!(pending.len(), 1);
+    /// for pending in pending {
+    ///     renderer.finish_compression(pending.compress().unwrap()).unwrap();
+    /// }
+    /// let cached = renderer.get("/queued").unwrap().unwrap();
+    /// assert_eq!(cached.body_for(Some("gzip")).0, Some(ContentEncoding::Gzip));
+    /// ```
+    pub fn take_pending_compression(&mut self) -> Vec<PendingCompression> {
+        std::mem::take(&mut self.queued_compression)
+    }
+
+    /// Cache a rendered response like [`Self::cache_with_max_age`], but leave compressing it to the caller. If
+    /// precompression is enabled, the render is returned as a [`PendingCompression`] that can be compressed without
+    /// holding a lock on the renderer and stored with [`Self::finish_compression`].
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::{ContentEncoding, IncrementalRenderer};
+    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// let (_, pending) = renderer
+    ///     .cache_uncompressed("/deferred".to_string(), "<html></html>", ["home"], None)
+    ///     .unwrap();
+    /// let cached = renderer.get("/deferred").unwrap().unwrap();
+    /// assert!(cached.encodings.is_empty());
+    ///
+    /// let pending = pending.unwrap().compress().unwrap();
+    /// renderer.finish_compression(pending).unwrap();
+    /// let cached = renderer.get("/deferred").unwrap().unwrap();
+    /// assert_eq!(cached.body_for(Some("br")).0, Some(ContentEncoding::Brotli));
+    /// ```
+    pub fn cache_uncompressed<T: Into<String>>(
+        &mut self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+        tags: impl IntoIterator<Item = T>,
+        max_age: Option<Duration>,
+    ) -> Result<(RenderFreshness, Option<PendingCompression>), IncrementalRendererError> {
         let timestamp = Utc::now();
-        let html = html.into();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache
-            .put(route.clone(), timestamp, html.clone())?;
-        self.memory_cache.put(route, timestamp, html);
-        Ok(RenderFreshness::created_at(
-            timestamp,
-            self.invalidate_after,
-        ))
+        let entry = CacheEntry::new(timestamp, html)
+            .with_tags(tags)
+            .with_max_age(max_age);
+        let pending = self.precompress.then(|| PendingCompression {
+            route: route.clone(),
+            entry: entry.clone(),
+        });
+        self.cache.put(&route, entry)?;
+        match &pending {
+            Some(pending) => {
+                let tags = pending.entry.tags.clone();
+                self.pending_compression
+                    .insert(route, PendingRender { timestamp, tags })
+            }
+            None => self.pending_compression.remove(&route),
+        };
+        Ok((self.freshness(timestamp, max_age), pending))
+    }
+
+    /// Store the compressed variants of a render cached with [`Self::cache_uncompressed`]. The variants are dropped if
+    /// the route was rendered again or invalidated since.
+    pub fn finish_compression(
+        &mut self,
+        pending: PendingCompression,
+    ) -> Result<(), IncrementalRendererError> {
+        let PendingCompression { route, entry } = pending;
+        let current = self.pending_compression.get(&route);
+        if current.map(|pending| pending.timestamp) != Some(entry.timestamp) {
+            return Ok(());
+        }
+        self.pending_compression.remove(&route);
+        self.cache.put(&route, entry)
+    }
+
+    /// Forget the renders waiting for compression that match an invalidation by their key and tags
+    fn forget_pending_compression(&mut self, invalidated: impl Fn(&str, &[String]) -> bool) {
+        self.pending_compression
+            .retain(|key, pending| !invalidated(key, &pending.tags));
+        self.queued_compression
+            .retain(|queued| !invalidated(&queued.route, &queued.entry.tags));
+    }
+
+    fn freshness(&self, timestamp: DateTime<Utc>, max_age: Option<Duration>) -> RenderFreshness {
+        RenderFreshness::created_at(timestamp, max_age.or(self.invalidate_after))
+            .with_stale_while_revalidate(self.stale_while_revalidate)
     }
 
     /// Try to get a cached response for a route.
@@ -93,56 +392,44 @@ impl IncrementalRenderer {
     /// assert_eq!(response.unwrap().response, b"<html><body>Hello world</body></html>");
     /// ```
     ///
+    /// If the route has expired but is still inside the [stale while revalidate](IncrementalRendererConfig::stale_while_revalidate)
+    /// window, the stale render is returned and [`RenderFreshness::is_stale`] is set. The caller is responsible for regenerating the route.
+    ///
     /// If the route is not cached, `None` is returned.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
     /// # let mut renderer = IncrementalRenderer::builder().build();
-    /// let route = "/index";
+    /// let route = "/not-cached";
     /// let response = renderer.get(route).unwrap();
     /// assert!(response.is_none());
     /// ```
-    pub fn get<'a>(
-        &'a mut self,
-        route: &str,
-    ) -> Result<Option<CachedRender<'a>>, IncrementalRendererError> {
-        let Self {
-            memory_cache,
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache,
-            ..
-        } = self;
-
-        #[allow(unused)]
-        enum FsGetError {
-            NotPresent,
-            Error(IncrementalRendererError),
-        }
-
-        // The borrow checker prevents us from simply using a match/if and returning early. Instead we need to use the more complex closure API
-        // non lexical lifetimes will make this possible (it works with polonius)
-        let or_insert = || {
-            // check the file cache
-            #[cfg(not(target_arch = "wasm32"))]
-            return match file_system_cache.get(route) {
-                Ok(Some((freshness, bytes))) => Ok((freshness.timestamp(), bytes)),
-                Ok(None) => Err(FsGetError::NotPresent),
-                Err(e) => Err(FsGetError::Error(e)),
-            };
-
-            #[allow(unreachable_code)]
-            Err(FsGetError::NotPresent)
+    pub fn get(&mut self, route: &str) -> Result<Option<CachedRender>, IncrementalRendererError> {
+        let Some(entry) = self.cache.get(route)? else {
+            return Ok(None);
         };
 
-        match memory_cache.try_get_or_insert(route, or_insert) {
-            Ok(Some((freshness, bytes))) => Ok(Some(CachedRender {
-                route: route.to_string(),
-                freshness,
-                response: bytes,
-            })),
-            Err(FsGetError::NotPresent) | Ok(None) => Ok(None),
-            Err(FsGetError::Error(e)) => Err(e),
+        // The cache entry is out of date, so we need to remove it.
+        if let Some(invalidate_after) = entry.max_age.or(self.invalidate_after) {
+            // If we can't convert to a std duration, the duration is negative and hasn't elapsed yet.
+            if let Ok(elapsed) = Utc::now().signed_duration_since(entry.timestamp).to_std() {
+                let stale_until =
+                    invalidate_after + self.stale_while_revalidate.unwrap_or_default();
+                if elapsed > stale_until {
+                    tracing::trace!("cache entry for {route:?} out of date");
+                    self.cache.invalidate(route)?;
+                    return Ok(None);
+                }
+            }
         }
+
+        Ok(Some(CachedRender {
+            route: route.to_string(),
+            freshness: self.freshness(entry.timestamp, entry.max_age),
+            etag: entry.etag,
+            response: entry.html,
+            encodings: entry.encodings,
+        }))
     }
 }
 
@@ -2,88 +2,234 @@
 
 #![allow(non_snake_case)]
 
-use chrono::offset::Utc;
-use chrono::DateTime;
-use rustc_hash::FxHasher;
-use std::{hash::BuildHasherDefault, num::NonZeroUsize};
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 
-use super::freshness::RenderFreshness;
+use crate::{CacheEntry, CacheMetrics, IncrementalRendererError, RenderCache};
 
-pub(crate) struct InMemoryCache {
-    #[allow(clippy::type_complexity)]
-    lru: Option<lru::LruCache<String, (DateTime<Utc>, Vec<u8>), BuildHasherDefault<FxHasher>>>,
-    invalidate_after: Option<std::time::Duration>,
+/// The strategy an [`InMemoryCache`] uses to pick which route to remove when it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EvictionPolicy {
+    /// Remove the route that was used least recently.
+    #[default]
+    Lru,
+    /// Remove the route that was used least often. Ties are broken by removing the route that was used least recently.
+    Lfu,
+}
+
+/// A [`RenderCache`] that keeps routes in memory.
+///
+/// The cache is bounded by a number of routes and optionally a number of bytes. Once either limit is reached, routes
+/// are removed according to the [`EvictionPolicy`].
+///
+/// ```rust
+/// # use dioxus_isrg::{CacheEntry, EvictionPolicy, InMemoryCache, RenderCache};
+/// let mut cache = InMemoryCache::new(100)
+///     .max_bytes(16)
+///     .eviction_policy(EvictionPolicy::Lfu);
+///
+/// cache.put("/a", CacheEntry::new(Default::default(), "aaaaaa")).unwrap();
+/// cache.get("/a").unwrap();
+/// cache.put("/b", CacheEntry::new(Default::default(), "bbbbbb")).unwrap();
+/// cache.put("/c", CacheEntry::new(Default::default(), "c")).unwrap();
+///
+/// // /b was used less often than /a, so it was removed to make room for /c
+/// assert_eq!(cache.list().unwrap(), vec!["/a".to_string(), "/c".to_string()]);
+/// let metrics = cache.metrics().unwrap();
+/// assert_eq!(metrics.evictions, 1);
+/// assert_eq!(metrics.bytes, 11);
+/// ```
+pub struct InMemoryCache {
+    entries: FxHashMap<String, Slot>,
+    /// The routes ordered by the rank they will be evicted in. The first route is evicted first.
+    order: BTreeMap<Rank, String>,
+    max_entries: usize,
+    max_bytes: Option<usize>,
+    policy: EvictionPolicy,
+    /// A counter that increases with every use of the cache to track how recently routes were used
+    clock: u64,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+/// The eviction rank of a route: (number of uses, last use). The number of uses is always zero with [`EvictionPolicy::Lru`]
+type Rank = (u64, u64);
+
+struct Slot {
+    entry: CacheEntry,
+    size: usize,
+    uses: u64,
+    last_used: u64,
 }
 
 impl InMemoryCache {
-    pub fn new(memory_cache_limit: usize, invalidate_after: Option<std::time::Duration>) -> Self {
+    /// Create a new in memory cache that holds at most `memory_cache_limit` routes. A limit of zero disables the cache.
+    pub fn new(memory_cache_limit: usize) -> Self {
         Self {
-            lru: NonZeroUsize::new(memory_cache_limit)
-                .map(|limit| lru::LruCache::with_hasher(limit, Default::default())),
-            invalidate_after,
+            entries: Default::default(),
+            order: Default::default(),
+            max_entries: memory_cache_limit,
+            max_bytes: None,
+            policy: EvictionPolicy::default(),
+            clock: 0,
+            bytes: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
         }
     }
 
-    pub fn clear(&mut self) {
-        if let Some(cache) = &mut self.lru {
-            cache.clear();
+    /// Limit the total size of the cached routes in bytes. Routes larger than the limit are never cached.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Set the policy used to pick which route to remove when the cache is full (default: [`EvictionPolicy::Lru`])
+    pub fn eviction_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn rank(&self, slot: &Slot) -> Rank {
+        match self.policy {
+            EvictionPolicy::Lru => (0, slot.last_used),
+            EvictionPolicy::Lfu => (slot.uses, slot.last_used),
         }
     }
 
-    pub fn put(&mut self, route: String, timestamp: DateTime<Utc>, data: Vec<u8>) {
-        if let Some(cache) = &mut self.lru {
-            cache.put(route, (timestamp, data));
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn insert(&mut self, route: String, slot: Slot) {
+        self.bytes += slot.size;
+        self.order.insert(self.rank(&slot), route.clone());
+        self.entries.insert(route, slot);
+    }
+
+    fn remove(&mut self, route: &str) -> Option<Slot> {
+        let slot = self.entries.remove(route)?;
+        self.order.remove(&self.rank(&slot));
+        self.bytes -= slot.size;
+        Some(slot)
+    }
+
+    fn is_full(&self) -> bool {
+        self.entries.len() > self.max_entries
+            || self
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes > max_bytes)
+    }
+
+    /// Evict routes until the cache is within its limits. The route that was just inserted is never evicted.
+    fn evict_to_fit(&mut self, inserted: &str) {
+        while self.is_full() {
+            let Some(route) = self
+                .order
+                .values()
+                .find(|route| route.as_str() != inserted)
+                .cloned()
+            else {
+                break;
+            };
+            tracing::trace!("evicting {:?} from the memory cache", route);
+            self.remove(&route);
+            self.evictions += 1;
         }
     }
+}
+
+/// The number of bytes a cached route takes up
+fn entry_size(route: &str, entry: &CacheEntry) -> usize {
+    route.len()
+        + entry.html.len()
+        + entry.tags.iter().map(String::len).sum::<usize>()
+        + entry
+            .encodings
+            .iter()
+            .map(|(_, bytes)| bytes.len())
+            .sum::<usize>()
+}
+
+impl RenderCache for InMemoryCache {
+    fn get(&mut self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        let Some(mut slot) = self.remove(route) else {
+            self.misses += 1;
+            return Ok(None);
+        };
+        tracing::trace!("memory cache hit {:?}", route);
+        self.hits += 1;
+        slot.uses += 1;
+        slot.last_used = self.tick();
+        let entry = slot.entry.clone();
+        self.insert(route.to_string(), slot);
+        Ok(Some(entry))
+    }
 
-    pub fn invalidate(&mut self, route: &str) {
-        if let Some(cache) = &mut self.lru {
-            cache.pop(route);
+    fn put(&mut self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        if self.max_entries == 0 {
+            return Ok(());
+        }
+        // Keep the number of uses so replacing a render doesn't reset its frequency
+        let uses = self.remove(route).map_or(0, |slot| slot.uses) + 1;
+        let size = entry_size(route, &entry);
+        if self.max_bytes.is_some_and(|max_bytes| size > max_bytes) {
+            tracing::trace!("{:?} is too large for the memory cache", route);
+            return Ok(());
         }
+        let slot = Slot {
+            entry,
+            size,
+            uses,
+            last_used: self.tick(),
+        };
+        self.insert(route.to_string(), slot);
+        self.evict_to_fit(route);
+        Ok(())
     }
 
-    pub fn try_get_or_insert<'a, F: FnOnce() -> Result<(DateTime<Utc>, Vec<u8>), E>, E>(
-        &'a mut self,
-        route: &str,
-        or_insert: F,
-    ) -> Result<Option<(RenderFreshness, &'a [u8])>, E> {
-        if let Some(memory_cache) = self.lru.as_mut() {
-            let (timestamp, _) = memory_cache.try_get_or_insert(route.to_string(), or_insert)?;
-
-            let now = Utc::now();
-            let elapsed = timestamp.signed_duration_since(now);
-            let age = elapsed.num_seconds();
-            // The cache entry is out of date, so we need to remove it.
-            if let Some(invalidate_after) = self.invalidate_after {
-                // If we can't convert to a std duration, the duration is negative and hasn't elapsed yet.
-                if let Ok(std_elapsed) = elapsed.to_std() {
-                    if std_elapsed > invalidate_after {
-                        tracing::trace!("memory cache out of date");
-                        memory_cache.pop(route);
-                        return Ok(None);
-                    }
-                }
-            }
-
-            // We need to reborrow because we may have invalidated the lifetime if the route was removed.
-            // We know it wasn't because we returned... but rust doesn't understand that.
-            let (timestamp, cache_hit) = memory_cache.get(route).unwrap();
-
-            return match self.invalidate_after {
-                Some(invalidate_after) => {
-                    tracing::trace!("memory cache hit");
-                    let max_age = invalidate_after.as_secs();
-                    let freshness = RenderFreshness::new(age as u64, max_age, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-                None => {
-                    tracing::trace!("memory cache hit");
-                    let freshness = RenderFreshness::new_age(age as u64, *timestamp);
-                    Ok(Some((freshness, cache_hit)))
-                }
-            };
+    fn invalidate(&mut self, route: &str) -> Result<(), IncrementalRendererError> {
+        self.remove(route);
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), IncrementalRendererError> {
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<String>, IncrementalRendererError> {
+        let mut routes: Vec<_> = self.entries.keys().cloned().collect();
+        routes.sort_unstable();
+        Ok(routes)
+    }
+
+    fn invalidate_tag(&mut self, tag: &str) -> Result<(), IncrementalRendererError> {
+        let tagged: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, slot)| slot.entry.has_tag(tag))
+            .map(|(route, _)| route.clone())
+            .collect();
+        for route in tagged {
+            self.remove(&route);
         }
+        Ok(())
+    }
 
-        Ok(None)
+    fn metrics(&self) -> Option<CacheMetrics> {
+        Some(CacheMetrics {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            bytes: self.bytes,
+            entries: self.entries.len(),
+        })
     }
 }
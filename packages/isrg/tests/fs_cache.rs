@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use dioxus_isrg::{CacheEntry, FileSystemCache, RenderCache};
+
+const TEMPLATE: &str = "<html><div id=\"main\"></div></html>";
+
+/// Create an empty static directory with the app template in it
+fn static_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dioxus-isrg-{name}-{}", std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), TEMPLATE).unwrap();
+    dir
+}
+
+fn root_route_is_cached_next_to_the_template(track_timestamps: bool) {
+    let dir = static_dir(&format!("root-{track_timestamps}"));
+    let mut cache = FileSystemCache::new(&dir).track_timestamps(track_timestamps);
+
+    cache
+        .put("/", CacheEntry::new(Utc::now(), "<html>home</html>"))
+        .unwrap();
+    let entry = cache.get("/").unwrap().expect("/ should be cached");
+    assert_eq!(entry.html, b"<html>home</html>");
+    assert_eq!(cache.list().unwrap(), ["/"]);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("index.html")).unwrap(),
+        TEMPLATE
+    );
+
+    cache.invalidate("/").unwrap();
+    assert!(cache.get("/").unwrap().is_none());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("index.html")).unwrap(),
+        TEMPLATE
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn root_route_does_not_overwrite_the_template() {
+    root_route_is_cached_next_to_the_template(false);
+}
+
+#[test]
+fn root_route_with_timestamps_does_not_overwrite_the_template() {
+    root_route_is_cached_next_to_the_template(true);
+}
+
+#[test]
+fn etag_is_loaded_from_the_cache() {
+    let dir = static_dir("etag");
+    let mut cache = FileSystemCache::new(&dir);
+    let entry = CacheEntry::restore(Utc::now(), "<html>post</html>", "W/\"stored\"");
+    cache.put("/post", entry).unwrap();
+
+    let mut cache = FileSystemCache::new(&dir);
+    let entry = cache.get("/post").unwrap().expect("/post should be cached");
+    assert_eq!(entry.etag(), "W/\"stored\"");
+
+    cache.invalidate("/post").unwrap();
+    assert!(!dir.join("post").join("index.etag").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,62 @@
+use dioxus_isrg::{ContentEncoding, InMemoryCache, IncrementalRenderer};
+
+fn renderer() -> IncrementalRenderer {
+    IncrementalRenderer::builder()
+        .render_cache(|| InMemoryCache::new(100))
+        .build()
+}
+
+/// Cache a route without compressing it and return a function that finishes the compression
+fn cache_pending(
+    renderer: &mut IncrementalRenderer,
+    route: &str,
+    tags: &[&str],
+) -> impl FnOnce(&mut IncrementalRenderer) {
+    let (_, pending) = renderer
+        .cache_uncompressed(
+            route.to_string(),
+            "<html></html>",
+            tags.iter().copied(),
+            None,
+        )
+        .unwrap();
+    let pending = pending.unwrap().compress().unwrap();
+    move |renderer| renderer.finish_compression(pending).unwrap()
+}
+
+fn is_compressed(renderer: &mut IncrementalRenderer, route: &str) -> bool {
+    renderer
+        .get(route)
+        .unwrap()
+        .is_some_and(|cached| cached.body_for(Some("br")).0 == Some(ContentEncoding::Brotli))
+}
+
+#[test]
+fn invalidation_only_drops_matching_compressions() {
+    let mut renderer = renderer();
+    let about = cache_pending(&mut renderer, "/about", &[]);
+    let about_us = cache_pending(&mut renderer, "/about-us", &[]);
+    let blog = cache_pending(&mut renderer, "/blog/post", &["post"]);
+    let home = cache_pending(&mut renderer, "/", &["home"]);
+
+    renderer.invalidate("/about");
+    renderer.invalidate_tag("post");
+    about(&mut renderer);
+    about_us(&mut renderer);
+    blog(&mut renderer);
+    home(&mut renderer);
+
+    assert!(renderer.get("/about").unwrap().is_none());
+    assert!(renderer.get("/blog/post").unwrap().is_none());
+    assert!(is_compressed(&mut renderer, "/about-us"));
+    assert!(is_compressed(&mut renderer, "/"));
+}
+
+#[test]
+fn invalidated_compression_is_not_stored() {
+    let mut renderer = renderer();
+    let docs = cache_pending(&mut renderer, "/docs/intro", &[]);
+    renderer.invalidate_prefix("/docs/");
+    docs(&mut renderer);
+    assert!(renderer.get("/docs/intro").unwrap().is_none());
+}
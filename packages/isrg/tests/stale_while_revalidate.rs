@@ -0,0 +1,103 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use dioxus_isrg::{CacheEntry, FileSystemCache, IncrementalRenderer, RenderCache};
+
+const INVALIDATE_AFTER: Duration = Duration::from_secs(60);
+const STALE_WHILE_REVALIDATE: Duration = Duration::from_secs(600);
+
+/// Create a renderer with a route that was rendered `age` ago
+fn renderer_with_render(name: &str, age: Duration) -> (IncrementalRenderer, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("dioxus-isrg-swr-{name}-{}", std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+    let timestamp = Utc::now() - chrono::Duration::from_std(age).unwrap();
+    FileSystemCache::new(&dir)
+        .track_timestamps(true)
+        .put("/post", CacheEntry::new(timestamp, "<html>post</html>"))
+        .unwrap();
+    let renderer = IncrementalRenderer::builder()
+        .static_dir(&dir)
+        .invalidate_after(INVALIDATE_AFTER)
+        .stale_while_revalidate(STALE_WHILE_REVALIDATE)
+        .build();
+    (renderer, dir)
+}
+
+#[test]
+fn fresh_render_is_not_stale() {
+    let (mut renderer, dir) = renderer_with_render("fresh", Duration::from_secs(10));
+    let cached = renderer
+        .get("/post")
+        .unwrap()
+        .expect("/post should be cached");
+    assert!(!cached.freshness.is_stale());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn stale_render_inside_the_window_is_served() {
+    let (mut renderer, dir) = renderer_with_render("stale", Duration::from_secs(90));
+    let cached = renderer
+        .get("/post")
+        .unwrap()
+        .expect("/post should be served stale");
+    assert_eq!(cached.response, b"<html>post</html>");
+    assert!(cached.freshness.is_stale());
+    assert_eq!(
+        cached.freshness.stale_while_revalidate(),
+        Some(STALE_WHILE_REVALIDATE.as_secs())
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn render_past_the_window_is_a_miss() {
+    let age = INVALIDATE_AFTER + STALE_WHILE_REVALIDATE + Duration::from_secs(10);
+    let (mut renderer, dir) = renderer_with_render("expired", age);
+    assert!(renderer.get("/post").unwrap().is_none());
+    assert!(renderer.cached_routes().unwrap().is_empty());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn stale_render_headers() {
+    let (mut renderer, dir) = renderer_with_render("headers", Duration::from_secs(90));
+    let cached = renderer.get("/post").unwrap().unwrap();
+    let mut headers = http::HeaderMap::new();
+    cached.freshness.write(&mut headers);
+
+    assert_eq!(
+        headers[http::header::CACHE_CONTROL],
+        "max-age=60, stale-while-revalidate=600"
+    );
+    // The age stays above the max age so caches know the response is stale
+    let age: u64 = headers[http::header::AGE]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        age >= 90,
+        "age {age} should include the time since the render"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn fresh_render_headers_without_a_window() {
+    let mut renderer = IncrementalRenderer::builder()
+        .render_cache(|| dioxus_isrg::InMemoryCache::new(10))
+        .build();
+    let freshness = renderer
+        .cache_with_max_age(
+            "/".to_string(),
+            "<html></html>",
+            ["home"],
+            Some(Duration::from_secs(30)),
+        )
+        .unwrap();
+    let mut headers = http::HeaderMap::new();
+    freshness.write(&mut headers);
+    assert_eq!(headers[http::header::CACHE_CONTROL], "max-age=30");
+    assert_eq!(headers[http::header::AGE], "0");
+}
@@ -0,0 +1,242 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    adapters::{index_page, shutdown_on_signal, websocket_path},
+    LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter,
+};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use actix_ws::{Message, MessageStream, Session};
+use dioxus_core::VirtualDom;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver};
+use futures_util::{Sink, Stream, StreamExt};
+
+/// Convert an actix-web websocket session into a `LiveViewSocket`.
+///
+/// The messages of the websocket are read on the current actix-web worker, so this must be called inside of the actix
+/// runtime, for example in a request handler:
+///
+/// ```rust, ignore
+/// async fn ws(req: HttpRequest, body: web::Payload, pool: web::Data<LiveViewPool>) -> actix_web::Result<HttpResponse> {
+///     let (response, session, stream) = actix_ws::handle(&req, body)?;
+///     let pool = pool.get_ref().clone();
+///     actix_web::rt::spawn(async move {
+///         _ = pool.launch(actix_socket(session, stream), app).await;
+///     });
+///     Ok(response)
+/// }
+/// ```
+pub fn actix_socket(session: Session, stream: MessageStream) -> impl LiveViewSocket {
+    // The message stream can't leave the worker, so forward the messages to the VirtualDom through a channel
+    let (tx, rx) = unbounded();
+    let mut pong = session.clone();
+    actix_web::rt::spawn(async move {
+        let mut stream = stream;
+        while let Some(message) = stream.next().await {
+            let message = match message {
+                Ok(Message::Text(text)) => Ok(text.as_bytes().to_vec()),
+                Ok(Message::Binary(bytes)) => Ok(bytes.to_vec()),
+                Ok(Message::Ping(bytes)) => {
+                    if pong.pong(&bytes).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(_) => Err(LiveViewError::SendingFailed),
+            };
+            if tx.unbounded_send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let tx = futures_util::sink::unfold(session, |mut session, message: Vec<u8>| async move {
+        session
+            .binary(message)
+            .await
+            .map_err(|_| LiveViewError::SendingFailed)?;
+        Ok::<_, LiveViewError>(session)
+    });
+
+    ActixSocket {
+        tx: Box::pin(tx),
+        rx,
+    }
+}
+
+/// The two halves of an actix-web websocket
+struct ActixSocket {
+    tx: Pin<Box<dyn Sink<Vec<u8>, Error = LiveViewError> + Send>>,
+    rx: UnboundedReceiver<Result<Vec<u8>, LiveViewError>>,
+}
+
+impl Stream for ActixSocket {
+    type Item = Result<Vec<u8>, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+impl Sink<Vec<u8>> for ActixSocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.tx.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        self.tx.as_mut().start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.tx.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.tx.as_mut().poll_close(cx)
+    }
+}
+
+/// The liveview routes of an actix-web server.
+///
+/// actix-web creates a new `App` for every worker, so the routes are collected here and added to each app with
+/// [`ActixLiveviewRouter::configure`]:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_liveview::{ActixLiveviewRouter, LiveviewRouter};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from actix-web" }
+/// }
+///
+/// # async fn serve() -> std::io::Result<()> {
+/// let liveview = ActixLiveviewRouter::create_default_liveview_router().with_app("/", app);
+/// actix_web::HttpServer::new(move || {
+///     let liveview = liveview.clone();
+///     actix_web::App::new().configure(move |cfg| liveview.configure(cfg))
+/// })
+/// .bind(("127.0.0.1", 3030))?
+/// .run()
+/// .await
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct ActixLiveviewRouter {
+    routes: Vec<ActixRoute>,
+    /// The pool of the routes that don't set their own pool
+    default_pool: Option<LiveViewPool>,
+}
+
+#[derive(Clone)]
+struct ActixRoute {
+    route: String,
+    pool: LiveViewPool,
+    app: Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+}
+
+impl ActixLiveviewRouter {
+    /// Add the pages and websockets of the liveview routes to an actix-web app.
+    pub fn configure(&self, cfg: &mut web::ServiceConfig) {
+        for route in &self.routes {
+            let ws_path = websocket_path(&route.route);
+            let page = index_page(&ws_path);
+
+            let pool = route.pool.clone();
+            let app = route.app.clone();
+            cfg.route(
+                &ws_path,
+                web::get().to(move |req: HttpRequest, body: web::Payload| {
+                    let pool = pool.clone();
+                    let app = app.clone();
+                    async move {
+                        let (response, session, stream) = actix_ws::handle(&req, body)?;
+                        actix_web::rt::spawn(async move {
+                            _ = pool
+                                .launch_virtualdom(actix_socket(session, stream), move || app())
+                                .await;
+                        });
+                        Ok::<_, actix_web::Error>(response)
+                    }
+                }),
+            );
+
+            // Add an extra catch all segment to the route
+            let pattern = match route.route.trim_matches('/') {
+                "" => "/{route:.*}".to_string(),
+                route => format!("/{route}/{{route:.*}}"),
+            };
+            cfg.route(
+                &pattern,
+                web::get().to(move || {
+                    let page = page.clone();
+                    async move { HttpResponse::Ok().content_type("text/html").body(page) }
+                }),
+            );
+        }
+    }
+}
+
+impl LiveviewRouter for ActixLiveviewRouter {
+    fn create_default_liveview_router() -> Self {
+        Self::default()
+    }
+
+    fn with_virtual_dom(
+        mut self,
+        route: &str,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        let pool = self
+            .default_pool
+            .get_or_insert_with(LiveViewPool::new)
+            .clone();
+        self.with_virtual_dom_in_pool(route, pool, app)
+    }
+
+    fn with_virtual_dom_in_pool(
+        mut self,
+        route: &str,
+        pool: LiveViewPool,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push(ActixRoute {
+            route: route.to_string(),
+            pool,
+            app: Arc::new(app),
+        });
+        self
+    }
+
+    async fn start(self, address: impl Into<std::net::SocketAddr>) {
+        let pools: Vec<_> = self.routes.iter().map(|route| route.pool.clone()).collect();
+        let server = HttpServer::new(move || {
+            let router = self.clone();
+            App::new().configure(move |cfg| router.configure(cfg))
+        })
+        // The sessions need to end before the server stops
+        .disable_signals()
+        .bind(address.into());
+        let result = match server {
+            Ok(server) => {
+                let server = server.run();
+                let handle = server.handle();
+                tokio::spawn(async move {
+                    shutdown_on_signal(pools).await;
+                    handle.stop(true).await;
+                });
+                server.await
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to start actix-web server: {}", err);
+        }
+    }
+}
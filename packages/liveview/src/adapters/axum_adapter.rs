@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::{interpreter_glue, LiveViewError, LiveViewSocket, LiveviewRouter};
+use crate::{
+    adapters::{index_page, shutdown_on_signal, websocket_path},
+    LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter,
+};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -33,6 +36,90 @@ async fn transform_tx(message: Vec<u8>) -> Result<Message, axum::Error> {
     Ok(Message::Binary(message.into()))
 }
 
+/// The liveview routes of an axum server and the pools that run them. The sessions of the pools are drained when the
+/// server started with [`LiveviewRouter::start`] stops.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_liveview::{AxumLiveviewRouter, LiveviewRouter};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from axum" }
+/// }
+///
+/// # async fn serve() {
+/// AxumLiveviewRouter::create_default_liveview_router()
+///     .with_app("/", app)
+///     .start(([127, 0, 0, 1], 3030))
+///     .await;
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct AxumLiveviewRouter {
+    router: Router,
+    pools: Vec<LiveViewPool>,
+    /// The pool of the routes that don't set their own pool
+    default_pool: Option<LiveViewPool>,
+}
+
+impl AxumLiveviewRouter {
+    /// Add liveview routes to an existing router.
+    pub fn new(router: Router) -> Self {
+        Self {
+            router,
+            ..Default::default()
+        }
+    }
+
+    /// Get the router with the liveview routes to serve it yourself. The sessions are not drained when that server
+    /// stops unless you shut down their pools.
+    pub fn into_router(self) -> Router {
+        self.router
+    }
+}
+
+impl LiveviewRouter for AxumLiveviewRouter {
+    fn create_default_liveview_router() -> Self {
+        Self::default()
+    }
+
+    fn with_virtual_dom(
+        mut self,
+        route: &str,
+        app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        let pool = self
+            .default_pool
+            .get_or_insert_with(LiveViewPool::new)
+            .clone();
+        self.with_virtual_dom_in_pool(route, pool, app)
+    }
+
+    fn with_virtual_dom_in_pool(
+        mut self,
+        route: &str,
+        pool: LiveViewPool,
+        app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        self.pools.push(pool.clone());
+        self.router = add_route(self.router, route, pool, app);
+        self
+    }
+
+    async fn start(self, address: impl Into<std::net::SocketAddr>) {
+        let listener = tokio::net::TcpListener::bind(address.into()).await.unwrap();
+        let server = axum::serve(listener, self.router.into_make_service())
+            .with_graceful_shutdown(shutdown_on_signal(self.pools));
+        if let Err(err) = server.await {
+            eprintln!("Failed to start axum server: {}", err);
+        }
+    }
+}
+
+/// Liveview routes added to a plain axum router. Every route without its own pool runs on a new pool.
+///
+/// The router can't keep track of the pools of its routes, so their sessions are not drained when the server stops.
+/// Use [`AxumLiveviewRouter`] to shut the server down gracefully.
 impl LiveviewRouter for Router {
     fn create_default_liveview_router() -> Self {
         Router::new()
@@ -43,39 +130,51 @@ impl LiveviewRouter for Router {
         route: &str,
         app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
     ) -> Self {
-        let view = crate::LiveViewPool::new();
-
-        let ws_path = format!("{}/ws", route.trim_start_matches('/'));
-        let title = crate::app_title();
-
-        let index_page_with_glue = move |glue: &str| {
-            Html(format!(
-                r#"
-        <!DOCTYPE html>
-        <html>
-            <head><title>{title}</title></head>
-            <body><div id="main"></div></body>
-            {glue}
-        </html>
-        "#,
-            ))
-        };
-
-        let app = Arc::new(app);
-        // Add an extra catch all segment to the route
-        let mut route = route.trim_matches('/').to_string();
-        if route.is_empty() {
-            route = "/{*route}".to_string();
-        } else {
-            route = format!("/{route}/{{*route}}");
+        add_route(self, route, LiveViewPool::new(), app)
+    }
+
+    fn with_virtual_dom_in_pool(
+        self,
+        route: &str,
+        pool: LiveViewPool,
+        app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        add_route(self, route, pool, app)
+    }
+
+    async fn start(self, address: impl Into<std::net::SocketAddr>) {
+        let listener = tokio::net::TcpListener::bind(address.into()).await.unwrap();
+        if let Err(err) = axum::serve(listener, self.into_make_service()).await {
+            eprintln!("Failed to start axum server: {}", err);
         }
+    }
+}
+
+/// Add the page and the websocket of a liveview route that runs on `pool` to the router
+fn add_route(
+    router: Router,
+    route: &str,
+    pool: LiveViewPool,
+    app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+) -> Router {
+    let ws_path = websocket_path(route);
 
-        self.route(
+    let app = Arc::new(app);
+    // Add an extra catch all segment to the route
+    let mut route = route.trim_matches('/').to_string();
+    if route.is_empty() {
+        route = "/{*route}".to_string();
+    } else {
+        route = format!("/{route}/{{*route}}");
+    }
+
+    router
+        .route(
             &ws_path,
             get(move |ws: WebSocketUpgrade| async move {
                 let app = app.clone();
                 ws.on_upgrade(move |socket| async move {
-                    _ = view
+                    _ = pool
                         .launch_virtualdom(axum_socket(socket), move || app())
                         .await;
                 })
@@ -83,14 +182,6 @@ impl LiveviewRouter for Router {
         )
         .route(
             &route,
-            get(move || async move { index_page_with_glue(&interpreter_glue(&ws_path)) }),
+            get(move || async move { Html(index_page(&ws_path)) }),
         )
-    }
-
-    async fn start(self, address: impl Into<std::net::SocketAddr>) {
-        let listener = tokio::net::TcpListener::bind(address.into()).await.unwrap();
-        if let Err(err) = axum::serve(listener, self.into_make_service()).await {
-            eprintln!("Failed to start axum server: {}", err);
-        }
-    }
 }
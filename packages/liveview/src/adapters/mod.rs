@@ -2,11 +2,23 @@ use std::future::Future;
 
 use dioxus_core::{Element, VirtualDom};
 
+use crate::LiveViewPool;
+
 #[cfg(feature = "axum")]
 pub mod axum_adapter;
 #[cfg(feature = "axum")]
 pub use axum_adapter::*;
 
+#[cfg(feature = "actix")]
+pub mod actix_adapter;
+#[cfg(feature = "actix")]
+pub use actix_adapter::*;
+
+#[cfg(feature = "tower")]
+pub mod tower_adapter;
+#[cfg(feature = "tower")]
+pub use tower_adapter::*;
+
 /// A trait for servers that can be used to host a LiveView app.
 pub trait LiveviewRouter {
     /// Create a new router.
@@ -27,6 +39,67 @@ pub trait LiveviewRouter {
         app: impl Fn() -> VirtualDom + Send + Sync + 'static,
     ) -> Self;
 
+    /// Add a liveview route to the server from a virtual dom that runs on `pool`.
+    ///
+    /// Use this to configure the sessions of the route, like how many can run at the same time. Routers that can't
+    /// run a route on a specific pool ignore `pool` and add the route with [`Self::with_virtual_dom`].
+    fn with_virtual_dom_in_pool(
+        self,
+        route: &str,
+        pool: LiveViewPool,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        _ = pool;
+        self.with_virtual_dom(route, app)
+    }
+
     /// Start the server on an address.
+    ///
+    /// Routers that keep track of the pools of their routes stop after a SIGTERM or ctrl-c once every session of
+    /// their pools ended.
     fn start(self, address: impl Into<std::net::SocketAddr>) -> impl Future<Output = ()>;
 }
+
+/// Wait for a SIGTERM or ctrl-c and shut down the pools of a server started with [`LiveviewRouter::start`]. Shutting
+/// down a pool twice is fine, so routes can share pools.
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub(crate) async fn shutdown_on_signal(pools: impl IntoIterator<Item = LiveViewPool>) {
+    crate::pool::shutdown_signal().await;
+    // Every pool tells its clients about the shutdown at once, then they drain together
+    let shutdowns: Vec<_> = pools
+        .into_iter()
+        .map(|pool| tokio::spawn(async move { pool.shutdown().await }))
+        .collect();
+    for shutdown in shutdowns {
+        _ = shutdown.await;
+    }
+}
+
+/// The path of the websocket of a liveview route
+#[cfg(any(feature = "axum", feature = "actix", feature = "tower"))]
+pub(crate) fn websocket_path(route: &str) -> String {
+    match route.trim_matches('/') {
+        "" => "/ws".to_string(),
+        route => format!("/{route}/ws"),
+    }
+}
+
+/// The page that loads the liveview app and connects to the websocket at `ws_path`
+#[cfg(any(feature = "axum", feature = "actix", feature = "tower"))]
+pub(crate) fn index_page(ws_path: &str) -> String {
+    let title = crate::app_title();
+    let glue = crate::interpreter_glue(ws_path);
+    format!(
+        r#"
+        <!DOCTYPE html>
+        <html>
+            <head><title>{title}</title></head>
+            <body><div id="main"></div></body>
+            {glue}
+        </html>
+        "#,
+    )
+}
@@ -0,0 +1,188 @@
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    adapters::{index_page, websocket_path},
+    LiveViewError, LiveViewPool, LiveViewSocket,
+};
+use bytes::Bytes;
+use dioxus_core::{Element, VirtualDom};
+use futures_util::{SinkExt, StreamExt};
+use http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use http_body_util::Full;
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+/// Convert a tungstenite websocket into a `LiveViewSocket`.
+pub fn tungstenite_socket<S>(ws: WebSocketStream<S>) -> impl LiveViewSocket
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    ws.filter_map(|message| async move { transform_rx(message) })
+        .with(transform_tx)
+        .sink_map_err(|_| LiveViewError::SendingFailed)
+}
+
+fn transform_rx(
+    message: Result<Message, tokio_tungstenite::tungstenite::Error>,
+) -> Option<Result<Vec<u8>, LiveViewError>> {
+    match message {
+        Ok(Message::Text(text)) => Some(Ok(text.as_bytes().to_vec())),
+        Ok(Message::Binary(bytes)) => Some(Ok(bytes.to_vec())),
+        // Pings are answered by tungstenite
+        Ok(_) => None,
+        Err(_) => Some(Err(LiveViewError::SendingFailed)),
+    }
+}
+
+async fn transform_tx(message: Vec<u8>) -> Result<Message, tokio_tungstenite::tungstenite::Error> {
+    Ok(Message::Binary(message.into()))
+}
+
+/// A [`tower::Service`] that serves a liveview app without depending on a web framework.
+///
+/// The service serves the page with the client glue for every path under the route and upgrades requests to
+/// `{route}/ws` to the websocket of the app. It works with any server built on hyper, like hyper itself or axum:
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_liveview::LiveViewService;
+/// use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from hyper" }
+/// }
+///
+/// # async fn serve() -> std::io::Result<()> {
+/// let service = LiveViewService::with_app("/", app);
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:3030").await?;
+/// loop {
+///     let (stream, _) = listener.accept().await?;
+///     let service = TowerToHyperService::new(service.clone());
+///     tokio::spawn(async move {
+///         _ = hyper::server::conn::http1::Builder::new()
+///             .serve_connection(TokioIo::new(stream), service)
+///             .with_upgrades()
+///             .await;
+///     });
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LiveViewService {
+    route: String,
+    pool: LiveViewPool,
+    app: Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+}
+
+impl LiveViewService {
+    /// Create a service that serves a new virtual dom to every client under the route.
+    pub fn new(route: &str, app: impl Fn() -> VirtualDom + Send + Sync + 'static) -> Self {
+        Self {
+            route: route.trim_matches('/').to_string(),
+            pool: LiveViewPool::new(),
+            app: Arc::new(app),
+        }
+    }
+
+    /// Create a service that serves a component to every client under the route.
+    pub fn with_app(route: &str, app: fn() -> Element) -> Self {
+        Self::new(route, move || VirtualDom::new(app))
+    }
+
+    /// Set the pool the virtual doms are run on.
+    pub fn with_pool(mut self, pool: LiveViewPool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    fn is_page(&self, path: &str) -> bool {
+        match path
+            .trim_start_matches('/')
+            .strip_prefix(self.route.as_str())
+        {
+            Some(rest) => self.route.is_empty() || rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Upgrade the request to a websocket and launch the app on it once the connection is upgraded
+    fn upgrade<B>(&self, request: &mut Request<B>) -> Response<Full<Bytes>> {
+        let headers = request.headers();
+        let is_upgrade = headers
+            .get(header::UPGRADE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+        let key = headers.get(header::SEC_WEBSOCKET_KEY);
+        let (true, Some(key)) = (is_upgrade, key) else {
+            return status(StatusCode::BAD_REQUEST);
+        };
+        let accept = derive_accept_key(key.as_bytes());
+
+        let on_upgrade = hyper::upgrade::on(request);
+        let pool = self.pool.clone();
+        let app = self.app.clone();
+        tokio::spawn(async move {
+            let Ok(upgraded) = on_upgrade.await else {
+                return;
+            };
+            let ws =
+                WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None).await;
+            _ = pool
+                .launch_virtualdom(tungstenite_socket(ws), move || app())
+                .await;
+        });
+
+        let mut response = status(StatusCode::SWITCHING_PROTOCOLS);
+        let headers = response.headers_mut();
+        headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+        headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+        if let Ok(accept) = HeaderValue::from_str(&accept) {
+            headers.insert(header::SEC_WEBSOCKET_ACCEPT, accept);
+        }
+        response
+    }
+}
+
+impl<B> tower::Service<Request<B>> for LiveViewService {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let ws_path = websocket_path(&self.route);
+        let response = if request.method() != Method::GET {
+            status(StatusCode::METHOD_NOT_ALLOWED)
+        } else if request.uri().path() == ws_path {
+            self.upgrade(&mut request)
+        } else if self.is_page(request.uri().path()) {
+            let mut response = Response::new(Full::new(Bytes::from(index_page(&ws_path))));
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            );
+            response
+        } else {
+            status(StatusCode::NOT_FOUND)
+        };
+        ready(Ok(response))
+    }
+}
+
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = status;
+    response
+}
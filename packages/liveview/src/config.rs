@@ -1,6 +1,6 @@
 use dioxus_core::{LaunchConfig, VirtualDom};
 
-use crate::LiveviewRouter;
+use crate::{LiveViewPool, LiveviewRouter};
 
 pub(crate) fn app_title() -> String {
     dioxus_cli_config::app_title().unwrap_or_else(|| "Dioxus Liveview App".to_string())
@@ -11,6 +11,7 @@ pub struct Config<R: LiveviewRouter> {
     router: R,
     address: std::net::SocketAddr,
     route: String,
+    pool: Option<LiveViewPool>,
 }
 
 impl<R: LiveviewRouter + 'static> LaunchConfig for Config<R> {}
@@ -21,6 +22,7 @@ impl<R: LiveviewRouter> Default for Config<R> {
             address: dioxus_cli_config::fullstack_address_or_localhost(),
             router: R::create_default_liveview_router(),
             route: "/".to_string(),
+            pool: None,
         }
     }
 }
@@ -32,18 +34,30 @@ impl<R: LiveviewRouter> Config<R> {
         self
     }
 
-    /// Create a new configuration for the LiveView server.
-    pub fn with_app(mut self, app: fn() -> dioxus_core::prelude::Element) -> Self {
-        self.router = self.router.with_app(&self.route, app);
+    /// Set the pool that runs the app. By default, the app runs on the default pool of the router.
+    ///
+    /// The pool is used by the apps added after this call.
+    pub fn with_pool(mut self, pool: LiveViewPool) -> Self {
+        self.pool = Some(pool);
         self
     }
 
+    /// Create a new configuration for the LiveView server.
+    pub fn with_app(self, app: fn() -> dioxus_core::prelude::Element) -> Self {
+        self.with_virtual_dom(move || VirtualDom::new(app))
+    }
+
     /// Create a new configuration for the LiveView server.
     pub fn with_virtual_dom(
         mut self,
         virtual_dom: impl Fn() -> VirtualDom + Send + Sync + 'static,
     ) -> Self {
-        self.router = self.router.with_virtual_dom(&self.route, virtual_dom);
+        self.router = match self.pool.clone() {
+            Some(pool) => self
+                .router
+                .with_virtual_dom_in_pool(&self.route, pool, virtual_dom),
+            None => self.router.with_virtual_dom(&self.route, virtual_dom),
+        };
         self
     }
 
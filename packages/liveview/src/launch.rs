@@ -1,7 +1,7 @@
 use dioxus_core::*;
 use std::any::Any;
 
-pub type Config = crate::Config<axum::Router>;
+pub type Config = crate::Config<crate::AxumLiveviewRouter>;
 
 /// Launches the WebView and runs the event loop, with configuration and root props.
 pub fn launch(
@@ -34,5 +34,6 @@ pub fn launch(
             .await;
     });
 
-    panic!("Launching a liveview app should never return")
+    // The server only stops after it shut down gracefully
+    std::process::exit(0)
 }
@@ -9,9 +9,11 @@ pub use adapters::*;
 mod element;
 pub mod pool;
 mod query;
+mod session;
 use dioxus_interpreter_js::NATIVE_JS;
 use futures_util::{SinkExt, StreamExt};
 pub use pool::*;
+pub use session::SessionMetrics;
 mod config;
 mod document;
 mod events;
@@ -31,6 +33,10 @@ impl<T> WebsocketRx for T where T: StreamExt<Item = Result<String, LiveViewError
 pub enum LiveViewError {
     #[error("Sending to client error")]
     SendingFailed,
+    #[error("The pool already runs as many sessions as it allows")]
+    SessionLimitReached,
+    #[error("The pool is shutting down")]
+    ShuttingDown,
 }
 
 fn handle_edits_code() -> String {
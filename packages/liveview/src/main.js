@@ -7,27 +7,103 @@ function main() {
   }
 }
 
+// The longest time to wait between attempts to reconnect to the server
+const MAX_RECONNECT_DELAY = 5000;
+
 class IPC {
   constructor(root) {
+    this.root = root;
+    // The token of the session on the server. The server keeps the session alive for a while after the websocket
+    // disconnects, so we can resume it when we reconnect
+    this.session = null;
+    // The server tells us when it ends the session. After a shutdown we connect to a new session once the server is
+    // back up
+    this.reconnectAfterClose = false;
+    this.reconnectDelay = 250;
+    // Edits and queries waiting for the next animation frame. Queries wait behind edits so they see the dom the
+    // server rendered before sending them
+    this.queue = [];
+    this.frameRequested = false;
+    this.createInterpreter();
+    this.connect();
+
+    // we ping every 30 seconds to keep the websocket alive
+    setInterval(() => this.postMessage("__ping__"), 30000);
+  }
+
+  createInterpreter() {
     window.interpreter = new NativeInterpreter();
-    window.interpreter.initialize(root);
+    window.interpreter.initialize(this.root);
     window.interpreter.liveview = true;
     window.interpreter.ipc = this;
-    const ws = new WebSocket(WS_ADDR);
-    ws.binaryType = "arraybuffer";
+  }
 
-    function ping() {
-      ws.send("__ping__");
+  // The server sends the whole dom again after we reconnect, so throw away the dom of the last connection
+  resetInterpreter() {
+    const interpreter = window.interpreter;
+    for (const name in interpreter.global) {
+      this.root.removeEventListener(name, interpreter.global[name].callback);
+    }
+    this.root.replaceChildren();
+    this.queue = [];
+    this.createInterpreter();
+  }
+
+  enqueue(item) {
+    this.queue.push(item);
+    if (!this.frameRequested) {
+      this.frameRequested = true;
+      requestAnimationFrame(() => this.flush());
+      // Animation frames are paused in hidden tabs, so keep applying edits on a timer there
+      setTimeout(() => this.flush(), 100);
+    }
+  }
+
+  // Apply everything the server sent since the last frame and acknowledge the edits. The server can hold edits back
+  // while too many of them are unacknowledged, so a slow tab doesn't fall behind
+  flush() {
+    if (!this.frameRequested) {
+      return;
+    }
+    this.frameRequested = false;
+    const queue = this.queue;
+    this.queue = [];
+    let edits = 0;
+    for (const item of queue) {
+      if (item.edits !== undefined) {
+        window.interpreter.run_from_bytes(item.edits);
+        edits++;
+      } else {
+        runQuery(item.query);
+      }
     }
+    if (edits > 0) {
+      this.postMessage(`__ack__:${edits}`);
+    }
+  }
+
+  connect() {
+    const reconnecting = this.ws !== undefined;
+    const ws = new WebSocket(WS_ADDR);
+    ws.binaryType = "arraybuffer";
 
     ws.onopen = () => {
-      // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      this.reconnectDelay = 250;
+      this.reconnectAfterClose = false;
+      if (reconnecting) {
+        this.resetInterpreter();
+      }
+      const params = this.session === null ? {} : { session: this.session };
+      ws.send(window.interpreter.serializeIpcMessage("initialize", params));
     };
 
-    ws.onerror = (err) => {
-      // todo: retry the connection
+    ws.onclose = () => {
+      // Only reconnect if the server can resume our session
+      if (this.session === null && !this.reconnectAfterClose) {
+        return;
+      }
+      setTimeout(() => this.connect(), this.reconnectDelay);
+      this.reconnectDelay = Math.min(this.reconnectDelay * 2, MAX_RECONNECT_DELAY);
     };
 
     ws.onmessage = (message) => {
@@ -37,7 +113,7 @@ class IPC {
       // The first byte tells the shim if this is a binary of text frame
       if (binaryFrame) {
         // binary frame
-        window.interpreter.run_from_bytes(messageData);
+        this.enqueue({ edits: messageData });
       } else {
         // text frame
 
@@ -50,7 +126,23 @@ class IPC {
           const event = JSON.parse(str);
           switch (event.type) {
             case "query":
-              Function("Eval", `"use strict";${event.data};`)();
+              if (this.queue.length > 0) {
+                this.enqueue({ query: event.data });
+              } else {
+                runQuery(event.data);
+              }
+              break;
+            case "session":
+              this.session = event.data;
+              break;
+            case "close":
+              // The session ended on the server, so it can't be resumed. The reason is "rejected", "idle" or
+              // "shutdown"
+              this.session = null;
+              this.reconnectAfterClose = event.data === "shutdown";
+              window.dispatchEvent(
+                new CustomEvent("dioxus-liveview-close", { detail: event.data })
+              );
               break;
           }
         }
@@ -61,8 +153,15 @@ class IPC {
   }
 
   postMessage(msg) {
-    this.ws.send(msg);
+    // Messages sent while we are disconnected are dropped
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
 
+function runQuery(query) {
+  Function("Eval", `"use strict";${query};`)();
+}
+
 main();
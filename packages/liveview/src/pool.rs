@@ -3,19 +3,42 @@ use crate::{
     element::LiveviewElement,
     events::SerializedHtmlEventConverter,
     query::{QueryEngine, QueryResult},
+    session::{
+        read_handshake, BoxedSocket, Handshake, NewSession, Reconnect, Registration,
+        SessionMetrics, Sessions, SharedMetrics,
+    },
     LiveViewError,
 };
 use dioxus_core::prelude::*;
 use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
 use dioxus_interpreter_js::MutationState;
-use futures_util::{pin_mut, SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt};
 use serde::Serialize;
 use std::{any::Any, rc::Rc, time::Duration};
-use tokio_util::task::LocalPoolHandle;
+use tokio::{
+    sync::{mpsc::UnboundedReceiver, oneshot},
+    time::Instant,
+};
+use tokio_util::{sync::CancellationToken, task::LocalPoolHandle};
+
+/// The shortest time between two edit frames by default. This is about one animation frame at 60fps.
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How many threads run the VirtualDoms of a pool by default.
+pub const DEFAULT_WORKERS: usize = 16;
+
+/// How long a new socket of a pool that resumes sessions waits for the handshake of the client glue before it starts
+/// a new session.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: Sessions,
+    session_grace_period: Duration,
+    max_sessions: Option<usize>,
+    options: SessionOptions,
+    shutdown: CancellationToken,
 }
 
 impl Default for LiveViewPool {
@@ -30,10 +53,108 @@ impl LiveViewPool {
         dioxus_html::set_event_converter(Box::new(SerializedHtmlEventConverter));
 
         LiveViewPool {
-            pool: LocalPoolHandle::new(16),
+            pool: LocalPoolHandle::new(DEFAULT_WORKERS),
+            sessions: Sessions::default(),
+            session_grace_period: Duration::ZERO,
+            max_sessions: None,
+            options: SessionOptions::default(),
+            shutdown: CancellationToken::new(),
         }
     }
 
+    /// Set how many threads run the VirtualDoms of the pool. Defaults to [`DEFAULT_WORKERS`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        assert!(workers > 0, "a LiveViewPool needs at least one worker");
+        self.pool = LocalPoolHandle::new(workers);
+        self
+    }
+
+    /// Set how many sessions can run at the same time, including the sessions kept alive for the grace period. There
+    /// is no limit by default.
+    ///
+    /// Clients that connect while the pool is full are told the session was rejected and the socket is closed.
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = Some(max_sessions);
+        self
+    }
+
+    /// End sessions when the client hasn't sent an event for the timeout. Sessions never time out by default.
+    ///
+    /// The client is told the session ended, so it doesn't try to resume it.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.options.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Set how long the VirtualDom of a session is kept alive after its socket disconnects. Sessions are not resumed by
+    /// default.
+    ///
+    /// When the client glue from [`interpreter_glue`](crate::interpreter_glue) reconnects within the grace period, it
+    /// resumes the session: the client receives the current dom and the app keeps its state instead of starting over.
+    /// A grace period of zero disables resuming sessions.
+    ///
+    /// With a grace period, a new socket waits a few seconds for the first message of the client before the session
+    /// starts. The client glue sends it as soon as it connects, but clients that wait for the server to send the dom
+    /// first should not be used with a grace period.
+    pub fn with_session_grace_period(mut self, grace_period: Duration) -> Self {
+        self.session_grace_period = grace_period;
+        self
+    }
+
+    /// Set how many edit frames can be sent to a client before it acknowledges them. Edits are sent without waiting
+    /// for acknowledgements by default, and a window of zero turns the window off again.
+    ///
+    /// The client glue acknowledges edits once it applies them. While the window is full, the VirtualDom keeps running
+    /// and rendering, but its edits are held back and sent to the client in a single frame once it catches up.
+    pub fn with_max_in_flight_edits(mut self, max_in_flight: usize) -> Self {
+        self.options.max_in_flight_edits = (max_in_flight > 0).then_some(max_in_flight);
+        self
+    }
+
+    /// Set the shortest time between two edit frames. Defaults to [`DEFAULT_FRAME_INTERVAL`].
+    ///
+    /// Everything that changes in the interval is rendered and sent to the client in one frame.
+    pub fn with_frame_interval(mut self, frame_interval: Duration) -> Self {
+        self.options.frame_interval = frame_interval;
+        self
+    }
+
+    /// The number of sessions running in the pool
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// The metrics of every session running in the pool
+    pub fn metrics(&self) -> Vec<SessionMetrics> {
+        self.sessions.metrics()
+    }
+
+    /// Tell every connected client the server is shutting down, end all sessions and wait until they stopped.
+    ///
+    /// Clients that connect after the pool started shutting down are turned away.
+    pub async fn shutdown(&self) {
+        self.shutdown.cancel();
+        self.sessions.drained().await;
+    }
+
+    /// Wait for a SIGTERM or ctrl-c and [`shutdown`](Self::shutdown) the pool.
+    ///
+    /// Pass this to the graceful shutdown of your server to let the sessions drain before it stops:
+    ///
+    /// ```rust, ignore
+    /// axum::serve(listener, app)
+    ///     .with_graceful_shutdown(async move { pool.shutdown_on_signal().await })
+    ///     .await
+    /// ```
+    pub async fn shutdown_on_signal(&self) {
+        shutdown_signal().await;
+        self.shutdown().await;
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -52,20 +173,100 @@ impl LiveViewPool {
             .await
     }
 
+    /// Launch a VirtualDom on the socket, or resume the session the client reconnects to.
+    ///
+    /// If the client resumes a session, `make_app` is not called and this returns when the socket disconnects.
     pub async fn launch_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
-            Ok(Ok(_)) => Ok(()),
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(LiveViewError::SendingFailed),
+        let mut ws: BoxedSocket = Box::pin(ws);
+        if self.shutdown.is_cancelled() {
+            close_socket(&mut ws, CloseReason::Shutdown).await;
+            return Err(LiveViewError::ShuttingDown);
+        }
+
+        let mut first_message = None;
+        if !self.session_grace_period.is_zero() {
+            // The client glue sends the token of its last session when it reconnects
+            let token = match tokio::time::timeout(HANDSHAKE_TIMEOUT, ws.next()).await {
+                Ok(Some(Ok(message))) => match read_handshake(&message) {
+                    Some(Handshake::Initialize { session }) => session,
+                    // Clients without the glue start with a regular message
+                    None => {
+                        first_message = Some(message);
+                        None
+                    }
+                },
+                Ok(Some(Err(err))) => return Err(err),
+                Ok(None) => return Ok(()),
+                // The client doesn't send a handshake, so it can't resume a session
+                Err(_) => None,
+            };
+            if let Some(token) = token {
+                match self.sessions.resume(&token, ws) {
+                    Ok(done) => return done.await.unwrap_or(Err(LiveViewError::SendingFailed)),
+                    // The session expired, so start a new one
+                    Err(returned) => ws = returned,
+                }
+            }
         }
+
+        let Some(NewSession {
+            registration,
+            reconnects,
+            metrics,
+        }) = self.sessions.create(self.max_sessions)
+        else {
+            close_socket(&mut ws, CloseReason::Rejected).await;
+            return Err(LiveViewError::SessionLimitReached);
+        };
+
+        let (done, done_rx) = oneshot::channel();
+        let options = self.options;
+        let shutdown = self.shutdown.clone();
+        let grace_period = self.session_grace_period;
+        // The session outlives the socket, so it isn't awaited
+        drop(self.pool.spawn_pinned(move || {
+            let session = Session::new(make_app(), options, metrics, shutdown);
+            run_resumable(
+                session,
+                ws,
+                first_message,
+                done,
+                registration,
+                reconnects,
+                grace_period,
+            )
+        }));
+        done_rx.await.unwrap_or(Err(LiveViewError::SendingFailed))
     }
 }
 
-/// A LiveViewSocket is a Sink and Stream of Strings that Dioxus uses to communicate with the client
+/// Wait for a SIGTERM or ctrl-c
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate()).expect("failed to listen for SIGTERM");
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = tokio::signal::ctrl_c() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// A LiveViewSocket is a Sink and Stream of bytes that Dioxus uses to communicate with the client
+///
+/// Every message sent to the client starts with a byte that tells the client glue how to read the rest: `0` for a
+/// JSON text frame and `1` for a batch of edits in the binary format of `dioxus-interpreter-js`. The messages should
+/// be sent as binary websocket messages.
 ///
 /// Most websockets from most HTTP frameworks can be converted into a LiveViewSocket using the appropriate adapter.
 ///
@@ -81,15 +282,16 @@ impl LiveViewPool {
 ///         .sink_map_err(|_| LiveViewError::SendingFailed)
 /// }
 ///
-/// fn transform_rx(message: Result<Message, axum::Error>) -> Result<String, LiveViewError> {
+/// fn transform_rx(message: Result<Message, axum::Error>) -> Result<Vec<u8>, LiveViewError> {
 ///     message
 ///         .map_err(|_| LiveViewError::SendingFailed)?
 ///         .into_text()
+///         .map(|s| s.as_str().into())
 ///         .map_err(|_| LiveViewError::SendingFailed)
 /// }
 ///
-/// async fn transform_tx(message: String) -> Result<Message, axum::Error> {
-///     Ok(Message::Text(message))
+/// async fn transform_tx(message: Vec<u8>) -> Result<Message, axum::Error> {
+///     Ok(Message::Binary(message.into()))
 /// }
 /// ```
 pub trait LiveViewSocket:
@@ -115,145 +317,471 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Bytes, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        dioxus_devtools::connect(move |template| _ = tx.send(template));
-        rx
-    };
-
-    let mut mutations = MutationState::default();
-
-    // Create the a proxy for query engine
-    let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
-    let query_engine = QueryEngine::new(query_tx);
-    vdom.runtime().on_scope(ScopeId::ROOT, || {
-        provide_context(query_engine.clone());
-        init_document();
-    });
-
-    // pin the futures so we can use select!
-    pin_mut!(ws);
-
-    if let Some(edits) = {
-        vdom.rebuild(&mut mutations);
-        take_edits(&mut mutations)
-    } {
-        // send the initial render to the client
-        ws.send(edits).await?;
-    }
-
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(Box<HtmlEvent>),
-        #[serde(rename = "query")]
-        Query(QueryResult),
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    let mut ws: BoxedSocket = Box::pin(ws);
+    let mut session = Session::new(
+        vdom,
+        SessionOptions::default(),
+        SharedMetrics::default(),
+        CancellationToken::new(),
+    );
+    session.rebuild(&mut ws).await?;
+
+    // Nobody can reconnect to this session
+    let (_, mut reconnects) = tokio::sync::mpsc::unbounded_channel();
+    match session.serve(&mut ws, &mut reconnects).await {
+        Disconnect::Closed(result) => result,
+        Disconnect::Replaced(_) | Disconnect::Ended => Ok(()),
     }
+}
+
+/// The event loop of a session that keeps running for the grace period after the socket disconnects. If a client
+/// reconnects in the grace period, it receives the current dom and takes over the session.
+async fn run_resumable(
+    mut session: Session,
+    mut ws: BoxedSocket,
+    mut first_message: Option<Vec<u8>>,
+    mut done: oneshot::Sender<Result<(), LiveViewError>>,
+    registration: Registration,
+    mut reconnects: UnboundedReceiver<Reconnect>,
+    grace_period: Duration,
+) {
+    // Without a grace period, the client never learns the token and can't reconnect
+    let resumable = !grace_period.is_zero();
+    let shutdown = session.shutdown.clone();
+    let mut resumed = false;
 
     loop {
-        #[cfg(all(feature = "devtools", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
-        #[cfg(not(all(feature = "devtools", debug_assertions)))]
-        let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+        let connected = async {
+            if resumable {
+                ws.send(text_frame(
+                    &serde_json::to_string(&ClientUpdate::Session(registration.token.clone()))
+                        .unwrap(),
+                ))
+                .await?;
+            }
+            match resumed {
+                true => session.replay(&mut ws).await?,
+                false => session.rebuild(&mut ws).await?,
+            }
+            // The message the client sent in place of the handshake
+            if let Some(message) = first_message.take() {
+                session.handle_message(&mut ws, &message).await?;
+            }
+            Ok(())
+        };
+        let disconnect = match connected.await {
+            Ok(()) => session.serve(&mut ws, &mut reconnects).await,
+            Err(err) => Disconnect::Closed(Err(err)),
+        };
 
-        tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(text_frame("__pong__")).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    let event = if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        Event::new(
-                                            Rc::new(PlatformEventData::new(Box::new(element))) as Rc<dyn Any>,
-                                            evt.bubbles,
-                                        )
-                                    } else {
-                                        Event::new(
-                                            evt.data.into_any(),
-                                            evt.bubbles,
-                                        )
-                                    };
-                                    vdom.runtime().handle_event(
-                                        &evt.name,
-                                        event,
-                                        evt.element,
-                                    );
-                                }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
+        let reconnect = match disconnect {
+            // The client reconnected before the server noticed the old socket is gone
+            Disconnect::Replaced(reconnect) => {
+                _ = done.send(Ok(()));
+                reconnect
+            }
+            // The session is removed before the socket is done, so the pool no longer counts it once the socket returns
+            Disconnect::Ended => {
+                registration.remove();
+                _ = done.send(Ok(()));
+                break;
+            }
+            // Without a grace period, nobody can resume the session
+            Disconnect::Closed(result) if !resumable => {
+                registration.remove();
+                _ = done.send(result);
+                break;
+            }
+            Disconnect::Closed(result) => {
+                _ = done.send(result);
+                session.metrics().connected = false;
+                let reconnect = tokio::select! {
+                    reconnect = tokio::time::timeout(grace_period, reconnects.recv()) => reconnect.ok().flatten(),
+                    _ = shutdown.cancelled() => None,
+                };
+                match reconnect {
+                    Some(reconnect) if !shutdown.is_cancelled() => reconnect,
+                    _ => {
+                        registration.remove();
+                        // A client may have reconnected right before the session was removed
+                        match reconnects.try_recv() {
+                            Ok(reconnect) if !shutdown.is_cancelled() => reconnect,
+                            Ok(mut reconnect) => {
+                                close_socket(&mut reconnect.ws, CloseReason::Shutdown).await;
+                                _ = reconnect.done.send(Err(LiveViewError::ShuttingDown));
+                                break;
                             }
+                            Err(_) => break,
                         }
                     }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {}
-                    None => return Ok(()),
                 }
             }
+        };
 
-            // handle any new queries
-            Some(query) = query_rx.recv() => {
-                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
-            }
+        ws = reconnect.ws;
+        done = reconnect.done;
+        session.metrics().connected = true;
+        resumed = true;
+    }
+}
 
-            Some(msg) = hot_reload_wait => {
-                #[cfg(all(feature = "devtools", debug_assertions))]
-                match msg {
-                    dioxus_devtools::DevserverMsg::HotReload(msg)=> {
-                        dioxus_devtools::apply_changes(&vdom, &msg);
-                    }
-                    dioxus_devtools::DevserverMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
-                    dioxus_devtools::DevserverMsg::FullReloadCommand
-                    | dioxus_devtools::DevserverMsg::FullReloadStart
-                    | dioxus_devtools::DevserverMsg::FullReloadFailed => {
-                        // usually only web gets this message - what are we supposed to do?
-                        // Maybe we could just binary patch ourselves in place without losing window state?
-                    },
-                    _ => {}
-                }
-                #[cfg(not(all(feature = "devtools", debug_assertions)))]
-                let () = msg;
-            }
+/// The settings a pool passes to its sessions
+#[derive(Clone, Copy, Debug)]
+struct SessionOptions {
+    /// How many edit frames can wait for an acknowledgement from the client, or `None` to never wait
+    max_in_flight_edits: Option<usize>,
+    /// The shortest time between two edit frames
+    frame_interval: Duration,
+    /// How long the session waits for an event before it ends
+    idle_timeout: Option<Duration>,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        Self {
+            max_in_flight_edits: None,
+            frame_interval: DEFAULT_FRAME_INTERVAL,
+            idle_timeout: None,
         }
+    }
+}
 
-        // wait for suspense to resolve in a 10ms window
-        tokio::select! {
-            _ = tokio::time::sleep(Duration::from_millis(10)) => {}
-            _ = vdom.wait_for_suspense() => {}
+/// Why [`Session::serve`] stopped serving a socket
+enum Disconnect {
+    /// The socket was closed or failed
+    Closed(Result<(), LiveViewError>),
+    /// The client reconnected with a new socket
+    Replaced(Reconnect),
+    /// The session timed out or the pool is shutting down. The client was told the session ended.
+    Ended,
+}
+
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum IpcMessage {
+    #[serde(rename = "user_event")]
+    Event(Box<HtmlEvent>),
+    #[serde(rename = "query")]
+    Query(QueryResult),
+}
+
+/// A VirtualDom and the state it shares with the sockets it is served to
+struct Session {
+    vdom: VirtualDom,
+    mutations: MutationState,
+    query_engine: QueryEngine,
+    query_rx: UnboundedReceiver<String>,
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    hot_reload_rx: UnboundedReceiver<dioxus_devtools::DevserverMsg>,
+    options: SessionOptions,
+    metrics: SharedMetrics,
+    shutdown: CancellationToken,
+    /// The edit frames sent to the current socket that the client hasn't acknowledged yet
+    in_flight: usize,
+    /// If edits were rendered while the client was behind and still need to be sent
+    edits_held: bool,
+    /// When the last edit frame was sent
+    last_frame: Instant,
+    /// When the client last sent an event
+    last_active: Instant,
+}
+
+impl Session {
+    fn new(
+        vdom: VirtualDom,
+        options: SessionOptions,
+        metrics: SharedMetrics,
+        shutdown: CancellationToken,
+    ) -> Self {
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        let hot_reload_rx = {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            dioxus_devtools::connect(move |template| _ = tx.send(template));
+            rx
+        };
+
+        // Create the a proxy for query engine
+        let (query_tx, query_rx) = tokio::sync::mpsc::unbounded_channel();
+        let query_engine = QueryEngine::new(query_tx);
+        vdom.runtime().on_scope(ScopeId::ROOT, || {
+            provide_context(query_engine.clone());
+            init_document();
+        });
+
+        Self {
+            vdom,
+            mutations: MutationState::default(),
+            query_engine,
+            query_rx,
+            #[cfg(all(feature = "devtools", debug_assertions))]
+            hot_reload_rx,
+            options,
+            metrics,
+            shutdown,
+            in_flight: 0,
+            edits_held: false,
+            last_frame: Instant::now(),
+            last_active: Instant::now(),
+        }
+    }
+
+    fn metrics(&self) -> std::sync::MutexGuard<'_, SessionMetrics> {
+        self.metrics.lock().expect("unpoisoned mutex")
+    }
+
+    /// Render the dirty scopes of the VirtualDom and record how long it took
+    fn render(&mut self) {
+        let started = std::time::Instant::now();
+        self.vdom.render_immediate(&mut self.mutations);
+        self.record_render(started.elapsed());
+    }
+
+    fn record_render(&self, render_time: Duration) {
+        let memory = self.vdom.approximate_memory_usage();
+        let mut metrics = self.metrics();
+        metrics.renders += 1;
+        metrics.render_time += render_time;
+        metrics.memory = memory;
+    }
+
+    /// The client sent an event or the result of a query
+    fn record_activity(&mut self, event: bool) {
+        self.last_active = Instant::now();
+        let mut metrics = self.metrics();
+        metrics.last_active = self.last_active.into_std();
+        if event {
+            metrics.events += 1;
         }
+    }
+
+    /// Send the initial render to the client
+    async fn rebuild(&mut self, ws: &mut BoxedSocket) -> Result<(), LiveViewError> {
+        self.in_flight = 0;
+        let started = std::time::Instant::now();
+        self.vdom.rebuild(&mut self.mutations);
+        self.record_render(started.elapsed());
+        self.send_edits(ws).await
+    }
 
-        // render the vdom
-        vdom.render_immediate(&mut mutations);
+    /// Send the current dom to a client that reconnected. The client starts with an empty dom and no templates.
+    async fn replay(&mut self, ws: &mut BoxedSocket) -> Result<(), LiveViewError> {
+        // Acknowledgements for the frames sent to the old socket will never arrive
+        self.in_flight = 0;
+        self.mutations = MutationState::default();
+        self.vdom.replay(&mut self.mutations);
+        self.send_edits(ws).await
+    }
 
-        if let Some(edits) = take_edits(&mut mutations) {
+    /// If the client acknowledged enough edit frames to send another one
+    fn window_open(&self) -> bool {
+        match self.options.max_in_flight_edits {
+            Some(max_in_flight) => self.in_flight < max_in_flight,
+            None => true,
+        }
+    }
+
+    /// Send the edits written since the last frame to the client
+    async fn send_edits(&mut self, ws: &mut BoxedSocket) -> Result<(), LiveViewError> {
+        if let Some(edits) = take_edits(&mut self.mutations) {
             ws.send(edits).await?;
+            self.edits_held = false;
+            self.in_flight += 1;
+            self.last_frame = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Handle a message from the client. Returns false if the message can't change the dom.
+    async fn handle_message(
+        &mut self,
+        ws: &mut BoxedSocket,
+        message: &[u8],
+    ) -> Result<bool, LiveViewError> {
+        // respond with a pong every ping to keep the websocket alive
+        if message == b"__ping__" {
+            ws.send(text_frame("__pong__")).await?;
+            return Ok(true);
+        }
+
+        // the client applied some edit frames
+        if let Some(count) = message.strip_prefix(ACK_PREFIX) {
+            let acknowledged = std::str::from_utf8(count)
+                .ok()
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(1);
+            self.in_flight = self.in_flight.saturating_sub(acknowledged);
+            return Ok(false);
+        }
+
+        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(message)) {
+            let event = match message {
+                IpcMessage::Event(evt) => {
+                    // Intercept the mounted event and insert a custom element type
+                    let event = if let EventData::Mounted = &evt.data {
+                        let element = LiveviewElement::new(evt.element, self.query_engine.clone());
+                        Event::new(
+                            Rc::new(PlatformEventData::new(Box::new(element))) as Rc<dyn Any>,
+                            evt.bubbles,
+                        )
+                    } else {
+                        Event::new(evt.data.into_any(), evt.bubbles)
+                    };
+                    self.vdom
+                        .runtime()
+                        .handle_event(&evt.name, event, evt.element);
+                    true
+                }
+                IpcMessage::Query(result) => {
+                    self.query_engine.send(result);
+                    false
+                }
+            };
+            self.record_activity(event);
+        }
+        Ok(true)
+    }
+
+    /// Handle events from the socket and send edits to it until it disconnects or is replaced by a new socket
+    async fn serve(
+        &mut self,
+        ws: &mut BoxedSocket,
+        reconnects: &mut UnboundedReceiver<Reconnect>,
+    ) -> Disconnect {
+        match self.serve_inner(ws, reconnects).await {
+            Ok(disconnect) => disconnect,
+            Err(err) => Disconnect::Closed(Err(err)),
+        }
+    }
+
+    async fn serve_inner(
+        &mut self,
+        ws: &mut BoxedSocket,
+        reconnects: &mut UnboundedReceiver<Reconnect>,
+    ) -> Result<Disconnect, LiveViewError> {
+        // Renders are delayed until the next frame, so everything that changes before then is sent in one frame
+        let mut next_render: Option<Instant> = None;
+
+        loop {
+            let idle_deadline = self
+                .options
+                .idle_timeout
+                .map(|timeout| self.last_active + timeout);
+            let vdom = &mut self.vdom;
+
+            #[cfg(all(feature = "devtools", debug_assertions))]
+            let hot_reload_wait = self.hot_reload_rx.recv();
+            #[cfg(not(all(feature = "devtools", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            tokio::select! {
+                // poll any futures or suspense
+                _ = vdom.wait_for_work(), if next_render.is_none() => {}
+
+                _ = tokio::time::sleep_until(next_render.unwrap_or_else(Instant::now)), if next_render.is_some() => {
+                    next_render = None;
+
+                    // wait for suspense to resolve in a 10ms window
+                    tokio::select! {
+                        _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                        _ = vdom.wait_for_suspense() => {}
+                    }
+
+                    // render the vdom. While the client is behind, the edits pile up and are sent in one frame once it
+                    // catches up.
+                    self.render();
+                    match self.window_open() {
+                        true => self.send_edits(ws).await?,
+                        false => self.edits_held = true,
+                    }
+                    continue;
+                }
+
+                evt = ws.next() => {
+                    match evt {
+                        Some(Ok(message)) => {
+                            // Acknowledgements only free up the window for the edits that were held back, they don't
+                            // change the dom
+                            if !self.handle_message(ws, &message).await? {
+                                if self.edits_held && self.window_open() {
+                                    self.send_edits(ws).await?;
+                                }
+                                continue;
+                            }
+                        }
+                        // log this I guess? when would we get an error here?
+                        Some(Err(_e)) => {}
+                        None => return Ok(Disconnect::Closed(Ok(()))),
+                    }
+                }
+
+                Some(reconnect) = reconnects.recv() => {
+                    return Ok(Disconnect::Replaced(reconnect));
+                }
+
+                _ = self.shutdown.cancelled() => {
+                    close_socket(ws, CloseReason::Shutdown).await;
+                    return Ok(Disconnect::Ended);
+                }
+
+                _ = tokio::time::sleep_until(idle_deadline.unwrap_or_else(Instant::now)), if idle_deadline.is_some() => {
+                    close_socket(ws, CloseReason::Idle).await;
+                    return Ok(Disconnect::Ended);
+                }
+
+                // handle any new queries
+                Some(query) = self.query_rx.recv() => {
+                    ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "devtools", debug_assertions))]
+                    match msg {
+                        dioxus_devtools::DevserverMsg::HotReload(msg)=> {
+                            dioxus_devtools::apply_changes(vdom, &msg);
+                        }
+                        dioxus_devtools::DevserverMsg::Shutdown => {
+                            std::process::exit(0);
+                        },
+                        dioxus_devtools::DevserverMsg::FullReloadCommand
+                        | dioxus_devtools::DevserverMsg::FullReloadStart
+                        | dioxus_devtools::DevserverMsg::FullReloadFailed => {
+                            // usually only web gets this message - what are we supposed to do?
+                            // Maybe we could just binary patch ourselves in place without losing window state?
+                        },
+                        _ => {}
+                    }
+                    #[cfg(not(all(feature = "devtools", debug_assertions)))]
+                    let () = msg;
+                }
+            }
+
+            // render in the next frame
+            next_render.get_or_insert_with(|| {
+                Instant::now().max(self.last_frame + self.options.frame_interval)
+            });
         }
     }
 }
 
+/// The client glue sends `__ack__:{count}` after it applies edit frames
+const ACK_PREFIX: &[u8] = b"__ack__:";
+
 fn text_frame(text: &str) -> Vec<u8> {
     let mut bytes = vec![0];
     bytes.extend(text.as_bytes());
     bytes
 }
 
+/// Tell the client why the session ended and close the socket
+async fn close_socket(ws: &mut BoxedSocket, reason: CloseReason) {
+    let update = serde_json::to_string(&ClientUpdate::Close(reason)).unwrap();
+    _ = ws.send(text_frame(&update)).await;
+    _ = ws.close().await;
+}
+
 fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
     // Add an extra one at the beginning to tell the shim this is a binary frame
     let mut bytes = vec![1];
@@ -266,4 +794,22 @@ fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
 enum ClientUpdate {
     #[serde(rename = "query")]
     Query(String),
+    /// The token the client sends to resume the session when it reconnects
+    #[serde(rename = "session")]
+    Session(String),
+    /// The session ended and can't be resumed
+    #[serde(rename = "close")]
+    Close(CloseReason),
+}
+
+/// Why the server ended a session
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+enum CloseReason {
+    /// The pool already runs as many sessions as it allows
+    Rejected,
+    /// The client didn't send an event for the idle timeout
+    Idle,
+    /// The pool is shutting down
+    Shutdown,
 }
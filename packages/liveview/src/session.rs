@@ -0,0 +1,202 @@
+use crate::{LiveViewError, LiveViewSocket};
+use futures_util::{Sink, Stream};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, oneshot, Notify};
+
+/// A [`LiveViewSocket`] that can be sent to the thread of a running session
+pub(crate) type BoxedSocket = Pin<Box<dyn SessionSocket>>;
+
+pub(crate) trait SessionSocket:
+    Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+impl<S: LiveViewSocket> SessionSocket for S {}
+
+/// A new socket for a session that is kept alive by the pool
+pub(crate) struct Reconnect {
+    pub ws: BoxedSocket,
+    /// Resolves when the socket is disconnected again
+    pub done: oneshot::Sender<Result<(), LiveViewError>>,
+}
+
+/// A snapshot of the activity of a session in a [`LiveViewPool`](crate::LiveViewPool)
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SessionMetrics {
+    /// When the session started
+    pub started: Instant,
+    /// When the client last sent an event or the result of a query
+    pub last_active: Instant,
+    /// If a client is connected to the session. Sessions without a client are kept alive for the grace period.
+    pub connected: bool,
+    /// How many events the clients of the session sent
+    pub events: u64,
+    /// How many times the VirtualDom rendered
+    pub renders: u64,
+    /// The total time the VirtualDom spent rendering
+    pub render_time: Duration,
+    /// The bytes used by the VirtualDom after the last render as estimated by
+    /// [`VirtualDom::approximate_memory_usage`](dioxus_core::VirtualDom::approximate_memory_usage)
+    pub memory: usize,
+}
+
+impl Default for SessionMetrics {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last_active: now,
+            connected: true,
+            events: 0,
+            renders: 0,
+            render_time: Duration::ZERO,
+            memory: 0,
+        }
+    }
+}
+
+/// The metrics of a session, shared between the session and its pool
+pub(crate) type SharedMetrics = Arc<Mutex<SessionMetrics>>;
+
+/// A session registered with a pool
+struct Entry {
+    reconnects: mpsc::UnboundedSender<Reconnect>,
+    metrics: SharedMetrics,
+}
+
+/// A new session returned by [`Sessions::create`]
+pub(crate) struct NewSession {
+    pub registration: Registration,
+    pub reconnects: mpsc::UnboundedReceiver<Reconnect>,
+    pub metrics: SharedMetrics,
+}
+
+/// Removes a session from its pool when it is dropped, even if the app panics
+pub(crate) struct Registration {
+    sessions: Sessions,
+    pub token: String,
+}
+
+impl Registration {
+    /// Stop accepting sockets for the session
+    pub(crate) fn remove(&self) {
+        self.sessions.remove(&self.token);
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// The sessions of a [`LiveViewPool`](crate::LiveViewPool) by their token
+#[derive(Clone, Default)]
+pub(crate) struct Sessions {
+    sessions: Arc<Mutex<HashMap<String, Entry>>>,
+    /// Notified when the last session is removed
+    drained: Arc<Notify>,
+}
+
+impl Sessions {
+    /// Create a new session with a random token and receive the sockets that reconnect to it. Returns `None` if there
+    /// are already `max_sessions` sessions.
+    pub(crate) fn create(&self, max_sessions: Option<usize>) -> Option<NewSession> {
+        let mut sessions = self.sessions.lock().expect("unpoisoned mutex");
+        if max_sessions.is_some_and(|max| sessions.len() >= max) {
+            return None;
+        }
+
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let (tx, reconnects) = mpsc::unbounded_channel();
+        let metrics = SharedMetrics::default();
+        sessions.insert(
+            token.clone(),
+            Entry {
+                reconnects: tx,
+                metrics: metrics.clone(),
+            },
+        );
+        Some(NewSession {
+            registration: Registration {
+                sessions: self.clone(),
+                token,
+            },
+            reconnects,
+            metrics,
+        })
+    }
+
+    /// Hand a socket to the running session with the token. Returns the socket back if there is no such session.
+    pub(crate) fn resume(
+        &self,
+        token: &str,
+        ws: BoxedSocket,
+    ) -> Result<oneshot::Receiver<Result<(), LiveViewError>>, BoxedSocket> {
+        let sessions = self.sessions.lock().expect("unpoisoned mutex");
+        let Some(session) = sessions.get(token) else {
+            return Err(ws);
+        };
+        let (done, done_rx) = oneshot::channel();
+        match session.reconnects.send(Reconnect { ws, done }) {
+            Ok(()) => Ok(done_rx),
+            Err(mpsc::error::SendError(reconnect)) => Err(reconnect.ws),
+        }
+    }
+
+    /// Stop accepting sockets for the session with the token
+    pub(crate) fn remove(&self, token: &str) {
+        let mut sessions = self.sessions.lock().expect("unpoisoned mutex");
+        sessions.remove(token);
+        if sessions.is_empty() {
+            self.drained.notify_waiters();
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.sessions.lock().expect("unpoisoned mutex").len()
+    }
+
+    pub(crate) fn metrics(&self) -> Vec<SessionMetrics> {
+        let sessions = self.sessions.lock().expect("unpoisoned mutex");
+        sessions
+            .values()
+            .map(|entry| entry.metrics.lock().expect("unpoisoned mutex").clone())
+            .collect()
+    }
+
+    /// Wait until every session is removed
+    pub(crate) async fn drained(&self) {
+        loop {
+            let drained = self.drained.notified();
+            if self.len() == 0 {
+                return;
+            }
+            drained.await;
+        }
+    }
+}
+
+/// The first message the client glue sends after it connects
+#[derive(Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+pub(crate) enum Handshake {
+    #[serde(rename = "initialize")]
+    Initialize {
+        /// The token of the session the client was connected to before
+        #[serde(default)]
+        session: Option<String>,
+    },
+}
+
+/// Read the handshake from the first message the client sent, or `None` if the message is something else
+pub(crate) fn read_handshake(message: &[u8]) -> Option<Handshake> {
+    serde_json::from_slice(message).ok()
+}
@@ -0,0 +1,175 @@
+//! Drive the actix-web and tower adapters with a real websocket client
+
+use dioxus::prelude::*;
+use dioxus_liveview::{
+    ActixLiveviewRouter, AxumLiveviewRouter, LiveViewPool, LiveViewService, LiveviewRouter,
+};
+use futures_util::{SinkExt, StreamExt};
+use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+fn app() -> Element {
+    rsx! { div { "hello from the server" } }
+}
+
+async fn serve_actix(liveview: ActixLiveviewRouter) -> SocketAddr {
+    let server = actix_web::HttpServer::new(move || {
+        let liveview = liveview.clone();
+        actix_web::App::new().configure(move |cfg| liveview.configure(cfg))
+    })
+    .workers(1)
+    .disable_signals()
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let address = server.addrs()[0];
+    tokio::spawn(server.run());
+    address
+}
+
+async fn serve_axum(liveview: AxumLiveviewRouter) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, liveview.into_router()).await });
+    address
+}
+
+async fn serve_tower(service: LiveViewService) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = TowerToHyperService::new(service.clone());
+            tokio::spawn(async move {
+                _ = hyper::server::conn::http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .with_upgrades()
+                    .await;
+            });
+        }
+    });
+    address
+}
+
+/// Send a GET request and read the whole response
+async fn get(address: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: {address}\r\nConnection: close\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Connect to the websocket of the app and return the session token if the session can be resumed and the first edits
+async fn connect(address: SocketAddr, session: Option<&str>) -> (Option<String>, Vec<u8>) {
+    let (mut ws, _) = connect_async(format!("ws://{address}/app/ws"))
+        .await
+        .unwrap();
+    let params = match session {
+        Some(session) => format!(r#"{{"session":"{session}"}}"#),
+        None => "{}".to_string(),
+    };
+    ws.send(Message::text(format!(
+        r#"{{"method":"initialize","params":{params}}}"#
+    )))
+    .await
+    .unwrap();
+
+    // A resumable session sends its token in a text frame first, then the dom in a binary frame
+    let mut token = None;
+    let edits = loop {
+        let frame = match ws.next().await.unwrap().unwrap() {
+            Message::Binary(bytes) => bytes.to_vec(),
+            Message::Text(text) => text.as_bytes().to_vec(),
+            _ => continue,
+        };
+        if frame[0] == 1 {
+            break frame;
+        }
+        let update: serde_json::Value = serde_json::from_slice(&frame[1..]).unwrap();
+        assert_eq!(update["type"], "session");
+        token = Some(update["data"].as_str().unwrap().to_string());
+    };
+    ws.close(None).await.unwrap();
+
+    (token, edits)
+}
+
+/// Check the app is served and return the session token of a new connection
+async fn assert_serves_app(address: SocketAddr) -> Option<String> {
+    let page = get(address, "/app/some/route").await;
+    assert!(page.starts_with("HTTP/1.1 200"), "{page}");
+    assert!(page.contains(r#"__dioxusGetWsUrl("/app/ws")"#));
+
+    let (token, edits) = connect(address, None).await;
+    let text = String::from_utf8_lossy(&edits);
+    assert!(text.contains("hello from the server"));
+    token
+}
+
+#[tokio::test]
+async fn actix_adapter() {
+    let liveview = ActixLiveviewRouter::create_default_liveview_router().with_app("/app", app);
+    let address = serve_actix(liveview).await;
+    let token = assert_serves_app(address).await;
+    assert!(token.is_none(), "sessions are not resumable by default");
+}
+
+#[tokio::test]
+async fn actix_route_runs_on_its_pool() {
+    let pool = LiveViewPool::new().with_workers(1);
+    let liveview = ActixLiveviewRouter::create_default_liveview_router().with_virtual_dom_in_pool(
+        "/app",
+        pool.clone(),
+        || VirtualDom::new(app),
+    );
+    let address = serve_actix(liveview).await;
+    connect(address, None).await;
+    assert_eq!(pool.session_count(), 1);
+}
+
+#[tokio::test]
+async fn axum_adapter() {
+    let liveview = AxumLiveviewRouter::create_default_liveview_router().with_app("/app", app);
+    let address = serve_axum(liveview).await;
+    let token = assert_serves_app(address).await;
+    assert!(token.is_none(), "sessions are not resumable by default");
+}
+
+#[tokio::test]
+async fn axum_route_runs_on_its_pool() {
+    let pool = LiveViewPool::new().with_workers(1);
+    let liveview = AxumLiveviewRouter::create_default_liveview_router().with_virtual_dom_in_pool(
+        "/app",
+        pool.clone(),
+        || VirtualDom::new(app),
+    );
+    let address = serve_axum(liveview).await;
+    connect(address, None).await;
+    assert_eq!(pool.session_count(), 1);
+}
+
+#[tokio::test]
+async fn tower_service() {
+    let pool = LiveViewPool::new().with_session_grace_period(Duration::from_secs(30));
+    let address = serve_tower(LiveViewService::with_app("/app", app).with_pool(pool)).await;
+    let token = assert_serves_app(address).await.unwrap();
+
+    // Reconnecting with the token resumes the same session
+    let (resumed, _) = connect(address, Some(&token)).await;
+    assert_eq!(resumed, Some(token));
+
+    assert!(get(address, "/other").await.starts_with("HTTP/1.1 404"));
+}
@@ -0,0 +1,441 @@
+use dioxus::prelude::*;
+use dioxus_liveview::{LiveViewError, LiveViewPool};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{Sink, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// The server end of an in memory socket
+struct TestSocket {
+    rx: UnboundedReceiver<Vec<u8>>,
+    tx: UnboundedSender<Vec<u8>>,
+}
+
+impl Stream for TestSocket {
+    type Item = Result<Vec<u8>, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx).map(|message| message.map(Ok))
+    }
+}
+
+impl Sink<Vec<u8>> for TestSocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        self.tx
+            .unbounded_send(item)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The client end of an in memory socket
+struct Client {
+    tx: UnboundedSender<Vec<u8>>,
+    rx: UnboundedReceiver<Vec<u8>>,
+    server: JoinHandle<Result<(), LiveViewError>>,
+}
+
+impl Client {
+    /// Connect to the pool and send the handshake of the client glue
+    fn connect(pool: &LiveViewPool, launches: Arc<AtomicUsize>, session: Option<&str>) -> Self {
+        Self::launch(
+            pool,
+            move || {
+                launches.fetch_add(1, Ordering::SeqCst);
+                VirtualDom::new(app)
+            },
+            session,
+        )
+    }
+
+    fn launch(
+        pool: &LiveViewPool,
+        make_app: impl FnOnce() -> VirtualDom + Send + 'static,
+        session: Option<&str>,
+    ) -> Self {
+        let client = Self::launch_without_handshake(pool, make_app);
+        let params = match session {
+            Some(session) => format!(r#"{{"session":"{session}"}}"#),
+            None => "{}".to_string(),
+        };
+        client
+            .tx
+            .unbounded_send(format!(r#"{{"method":"initialize","params":{params}}}"#).into_bytes())
+            .unwrap();
+        client
+    }
+
+    /// Connect to the pool like a client that doesn't use the client glue
+    fn launch_without_handshake(
+        pool: &LiveViewPool,
+        make_app: impl FnOnce() -> VirtualDom + Send + 'static,
+    ) -> Self {
+        let (tx, server_rx) = unbounded();
+        let (server_tx, rx) = unbounded();
+        let socket = TestSocket {
+            rx: server_rx,
+            tx: server_tx,
+        };
+        let pool = pool.clone();
+        let server = tokio::spawn(async move { pool.launch_virtualdom(socket, make_app).await });
+        Self { tx, rx, server }
+    }
+
+    /// Receive the session token and the first edits
+    async fn receive_session(&mut self) -> String {
+        let frame = self.rx.next().await.unwrap();
+        assert_eq!(frame[0], 0, "the session token is sent in a text frame");
+        let update: serde_json::Value = serde_json::from_slice(&frame[1..]).unwrap();
+        assert_eq!(update["type"], "session");
+
+        let edits = self.rx.next().await.unwrap();
+        assert_eq!(edits[0], 1, "the dom is sent in a binary frame");
+        assert!(edits.len() > 1);
+
+        update["data"].as_str().unwrap().to_string()
+    }
+
+    /// Receive the first edits of a session that can't be resumed
+    async fn receive_dom(&mut self) {
+        let edits = self.rx.next().await.unwrap();
+        assert_eq!(edits[0], 1, "the dom is sent without a session token");
+        assert!(edits.len() > 1);
+    }
+
+    /// Focus the div of the counter
+    fn focus(&self) {
+        let event = r#"{"method":"user_event","params":{"name":"focus","element":1,"data":{},"bubbles":false}}"#;
+        self.tx.unbounded_send(event.as_bytes().to_vec()).unwrap();
+    }
+
+    fn acknowledge(&self, frames: usize) {
+        self.tx
+            .unbounded_send(format!("__ack__:{frames}").into_bytes())
+            .unwrap();
+    }
+
+    /// Receive the reason the server ended the session
+    async fn receive_close(&mut self) -> String {
+        loop {
+            let frame = self.rx.next().await.unwrap();
+            if frame[0] != 0 {
+                continue;
+            }
+            let update: serde_json::Value = serde_json::from_slice(&frame[1..]).unwrap();
+            if update["type"] == "close" {
+                return update["data"].as_str().unwrap().to_string();
+            }
+        }
+    }
+
+    async fn disconnect(self) {
+        // Keep receiving until the server noticed the disconnect, so frames it is still sending don't fail
+        drop(self.tx);
+        self.server.await.unwrap().unwrap();
+        drop(self.rx);
+    }
+}
+
+fn app() -> Element {
+    rsx! { div { "hello" } }
+}
+
+/// A pool that keeps sessions alive after their socket disconnects
+fn resumable_pool() -> LiveViewPool {
+    LiveViewPool::new().with_session_grace_period(Duration::from_secs(30))
+}
+
+#[tokio::test]
+async fn reconnect_resumes_session() {
+    let pool = LiveViewPool::new().with_session_grace_period(Duration::from_secs(5));
+    let launches = Arc::new(AtomicUsize::new(0));
+
+    let mut client = Client::connect(&pool, launches.clone(), None);
+    let token = client.receive_session().await;
+    client.disconnect().await;
+
+    let mut client = Client::connect(&pool, launches.clone(), Some(&token));
+    assert_eq!(client.receive_session().await, token);
+    assert_eq!(launches.load(Ordering::SeqCst), 1);
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn expired_session_starts_over() {
+    let pool = LiveViewPool::new().with_session_grace_period(Duration::from_millis(10));
+    let launches = Arc::new(AtomicUsize::new(0));
+
+    let mut client = Client::connect(&pool, launches.clone(), None);
+    let token = client.receive_session().await;
+    client.disconnect().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = Client::connect(&pool, launches.clone(), Some(&token));
+    assert_ne!(client.receive_session().await, token);
+    assert_eq!(launches.load(Ordering::SeqCst), 2);
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn unknown_session_starts_over() {
+    let pool = resumable_pool();
+    let launches = Arc::new(AtomicUsize::new(0));
+
+    let mut client = Client::connect(&pool, launches.clone(), Some("unknown"));
+    assert_ne!(client.receive_session().await, "unknown");
+    assert_eq!(launches.load(Ordering::SeqCst), 1);
+    client.disconnect().await;
+}
+
+/// Receive the next edit frame, skipping text frames
+async fn receive_edits(rx: &mut UnboundedReceiver<Vec<u8>>) -> Vec<u8> {
+    loop {
+        let frame = rx.next().await.unwrap();
+        if frame[0] == 1 {
+            return frame;
+        }
+    }
+}
+
+/// Get the last count the counter rendered in an edit frame
+fn rendered_count(edits: &[u8]) -> u64 {
+    let text = String::from_utf8_lossy(edits);
+    let count = text.split("count: ").last().unwrap();
+    count
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+fn counter() -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! {
+        div { onfocus: move |_| count += 1, "count: {count}" }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn missing_handshake_starts_a_session() {
+    let pool = resumable_pool();
+    let mut client = Client::launch_without_handshake(&pool, || VirtualDom::new(counter));
+    client.receive_session().await;
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn sessions_without_a_grace_period_start_without_a_handshake() {
+    let pool = LiveViewPool::new();
+    let mut client = Client::launch_without_handshake(&pool, || VirtualDom::new(counter));
+    tokio::time::timeout(Duration::from_secs(1), client.receive_dom())
+        .await
+        .expect("the dom is sent without waiting for the client");
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn message_in_place_of_the_handshake_is_handled() {
+    let pool = resumable_pool().with_frame_interval(Duration::ZERO);
+    let mut client = Client::launch_without_handshake(&pool, || VirtualDom::new(counter));
+    client.focus();
+    client.receive_session().await;
+    assert_eq!(rendered_count(&receive_edits(&mut client.rx).await), 1);
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn edits_wait_for_acknowledgement() {
+    let pool = LiveViewPool::new()
+        .with_max_in_flight_edits(2)
+        .with_frame_interval(Duration::from_millis(5));
+    let mut client = Client::launch(&pool, || VirtualDom::new(counter), None);
+    client.receive_dom().await;
+
+    // The initial dom and one update fill the window
+    client.focus();
+    assert_eq!(rendered_count(&receive_edits(&mut client.rx).await), 1);
+    for _ in 0..20 {
+        client.focus();
+    }
+    let timeout = tokio::time::timeout(Duration::from_millis(100), receive_edits(&mut client.rx));
+    assert!(
+        timeout.await.is_err(),
+        "no edits are sent while the window is full"
+    );
+
+    // Every update made while the client was behind is sent in one frame
+    client.acknowledge(2);
+    assert_eq!(rendered_count(&receive_edits(&mut client.rx).await), 21);
+    let timeout = tokio::time::timeout(Duration::from_millis(100), receive_edits(&mut client.rx));
+    assert!(timeout.await.is_err());
+
+    client.disconnect().await;
+}
+
+/// A counter that counts up in a task
+fn ticker() -> Element {
+    let ticks = use_context::<Arc<AtomicUsize>>();
+    let mut count = use_signal(|| 0);
+    use_future(move || {
+        let ticks = ticks.clone();
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                ticks.fetch_add(1, Ordering::SeqCst);
+                count += 1;
+            }
+        }
+    });
+    rsx! { div { "count: {count}" } }
+}
+
+#[tokio::test]
+async fn tasks_run_while_the_window_is_full() {
+    let pool = LiveViewPool::new()
+        .with_max_in_flight_edits(1)
+        .with_frame_interval(Duration::from_millis(5));
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let mut client = Client::launch(
+        &pool,
+        {
+            let ticks = ticks.clone();
+            move || VirtualDom::new(ticker).with_root_context(ticks)
+        },
+        None,
+    );
+    client.receive_dom().await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let ticked = ticks.load(Ordering::SeqCst);
+    assert!(ticked > 5, "the task only ran {ticked} times");
+    let timeout = tokio::time::timeout(Duration::from_millis(20), receive_edits(&mut client.rx));
+    assert!(
+        timeout.await.is_err(),
+        "no edits are sent while the window is full"
+    );
+
+    client.acknowledge(1);
+    let count = rendered_count(&receive_edits(&mut client.rx).await);
+    assert!(count as usize >= ticked);
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn edits_are_sent_without_acknowledgements_by_default() {
+    let pool = LiveViewPool::new().with_frame_interval(Duration::ZERO);
+    let mut client = Client::launch(&pool, || VirtualDom::new(counter), None);
+    client.receive_dom().await;
+
+    for count in 1..=10 {
+        client.focus();
+        assert_eq!(rendered_count(&receive_edits(&mut client.rx).await), count);
+    }
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn sessions_over_the_limit_are_rejected() {
+    let pool = LiveViewPool::new().with_max_sessions(1);
+    let launches = Arc::new(AtomicUsize::new(0));
+
+    let mut first = Client::connect(&pool, launches.clone(), None);
+    first.receive_dom().await;
+
+    let mut second = Client::connect(&pool, launches.clone(), None);
+    assert_eq!(second.receive_close().await, "rejected");
+    assert!(matches!(
+        second.server.await.unwrap(),
+        Err(LiveViewError::SessionLimitReached)
+    ));
+    assert_eq!(launches.load(Ordering::SeqCst), 1);
+    assert_eq!(pool.session_count(), 1);
+    first.disconnect().await;
+}
+
+#[tokio::test]
+async fn idle_sessions_end() {
+    let pool = LiveViewPool::new().with_idle_timeout(Duration::from_millis(50));
+    let mut client = Client::launch(&pool, || VirtualDom::new(counter), None);
+    client.receive_dom().await;
+
+    // Events keep the session alive
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        client.focus();
+    }
+    assert_eq!(client.receive_close().await, "idle");
+    client.disconnect().await;
+    assert_eq!(pool.session_count(), 0);
+}
+
+#[tokio::test]
+async fn sessions_record_metrics() {
+    let pool = LiveViewPool::new().with_frame_interval(Duration::ZERO);
+    let mut client = Client::launch(&pool, || VirtualDom::new(counter), None);
+    client.receive_dom().await;
+
+    for _ in 0..3 {
+        client.focus();
+        receive_edits(&mut client.rx).await;
+        client.acknowledge(1);
+    }
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.len(), 1);
+    assert!(metrics[0].connected);
+    assert_eq!(metrics[0].events, 3);
+    // The initial render and one render for every event
+    assert_eq!(metrics[0].renders, 4);
+    assert!(metrics[0].memory > 0);
+    client.disconnect().await;
+}
+
+#[tokio::test]
+async fn shutdown_drains_sessions() {
+    let pool = resumable_pool();
+    let launches = Arc::new(AtomicUsize::new(0));
+
+    let mut client = Client::connect(&pool, launches.clone(), None);
+    client.receive_session().await;
+    let mut disconnected = Client::connect(&pool, launches.clone(), None);
+    disconnected.receive_session().await;
+    disconnected.disconnect().await;
+    assert_eq!(pool.session_count(), 2);
+
+    // Both the connected session and the session waiting for its client end
+    tokio::time::timeout(Duration::from_secs(5), pool.shutdown())
+        .await
+        .unwrap();
+    assert_eq!(client.receive_close().await, "shutdown");
+    assert_eq!(pool.session_count(), 0);
+    client.disconnect().await;
+
+    let mut late = Client::connect(&pool, launches.clone(), None);
+    assert_eq!(late.receive_close().await, "shutdown");
+    assert!(matches!(
+        late.server.await.unwrap(),
+        Err(LiveViewError::ShuttingDown)
+    ));
+}
@@ -78,6 +78,13 @@ impl DioxusNativeApplication {
                 dioxus_devtools::DevserverMsg::FullReloadStart => {}
                 dioxus_devtools::DevserverMsg::FullReloadFailed => {}
                 dioxus_devtools::DevserverMsg::FullReloadCommand => {}
+                dioxus_devtools::DevserverMsg::Inspect => {
+                    for window in self.inner.windows.values_mut() {
+                        let doc = window.downcast_doc_mut::<DioxusDocument>();
+                        let snapshot = dioxus_devtools::inspect(&doc.vdom);
+                        dioxus_devtools::send(dioxus_devtools::ClientMsg::Inspector(snapshot));
+                    }
+                }
                 _ => {}
             },
 
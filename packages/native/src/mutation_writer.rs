@@ -2,7 +2,8 @@
 use crate::{qual_name, trace, NodeId};
 use blitz_dom::{Attribute, BaseDocument, DocumentMutator};
 use dioxus_core::{
-    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+    AttributeValue, ElementId, PortalTarget, Template, TemplateAttribute, TemplateNode,
+    WriteMutations,
 };
 use rustc_hash::FxHashMap;
 
@@ -90,6 +91,42 @@ impl MutationWriter<'_> {
         let top_of_stack_node_id = *self.state.stack.last().unwrap();
         self.docm.node_at_path(top_of_stack_node_id, path)
     }
+
+    /// Find the node a portal target points to. Named targets are created at the end of the root the first time they
+    /// are used, and missing elements fall back to the root.
+    fn portal_target(&mut self, target: &PortalTarget) -> NodeId {
+        let root_id = self.state.element_to_node_id(ElementId(0));
+        match target {
+            PortalTarget::Named(name) => {
+                let attr = qual_name("data-dioxus-portal", None).local;
+                let existing = self.docm.doc.get_node(root_id).and_then(|root| {
+                    root.children.iter().copied().find(|child| {
+                        self.docm
+                            .doc
+                            .get_node(*child)
+                            .and_then(|n| n.attr(attr.clone()))
+                            == Some(name.as_str())
+                    })
+                });
+                existing.unwrap_or_else(|| {
+                    let attrs = vec![Attribute {
+                        name: qual_name("data-dioxus-portal", None),
+                        value: name.clone(),
+                    }];
+                    let node_id = self.docm.create_element(qual_name("div", None), attrs);
+                    self.docm.append_children(root_id, &[node_id]);
+                    node_id
+                })
+            }
+            PortalTarget::Element(id) => self
+                .docm
+                .doc
+                .nodes_to_id
+                .get(id)
+                .copied()
+                .unwrap_or(root_id),
+        }
+    }
 }
 
 impl WriteMutations for MutationWriter<'_> {
@@ -260,6 +297,54 @@ impl WriteMutations for MutationWriter<'_> {
     fn remove_event_listener(&mut self, _name: &'static str, _id: ElementId) {
         // node.remove_event_listener(name);
     }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize) {
+        trace!("mount_portal id:{} m:{}", id.0, m);
+        let child_node_ids = self.state.m_stack_nodes(m);
+
+        // Reuse the mount if the portal was already mounted. The id may point to a removed node if it was reclaimed
+        let mount_attr = qual_name("data-dioxus-portal-mount", None).local;
+        let existing = self.state.try_element_to_node_id(id).filter(|node_id| {
+            self.docm.node_has_parent(*node_id)
+                && self
+                    .docm
+                    .doc
+                    .get_node(*node_id)
+                    .is_some_and(|node| node.has_attr(mount_attr.clone()))
+        });
+        let mount_id = existing.unwrap_or_else(|| {
+            let attrs = vec![
+                Attribute {
+                    name: qual_name("data-dioxus-portal-mount", None),
+                    value: String::new(),
+                },
+                Attribute {
+                    name: qual_name("style", None),
+                    value: "display: contents".to_string(),
+                },
+                // Events that bubble out of the portal continue from the logical parent of the portal
+                Attribute {
+                    name: qual_name("data-dioxus-id", None),
+                    value: id.0.to_string(),
+                },
+            ];
+            let node_id = self.docm.create_element(qual_name("div", None), attrs);
+            self.set_id_mapping(node_id, id);
+            node_id
+        });
+
+        let target_id = self.portal_target(target);
+        if self
+            .docm
+            .doc
+            .get_node(mount_id)
+            .and_then(|node| node.parent)
+            != Some(target_id)
+        {
+            self.docm.append_children(target_id, &[mount_id]);
+        }
+        self.docm.append_children(mount_id, &child_node_ids);
+    }
 }
 
 fn create_template_node(docm: &mut DocumentMutator<'_>, node: &TemplateNode) -> NodeId {
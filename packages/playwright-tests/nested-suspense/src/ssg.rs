@@ -8,23 +8,19 @@ fn main() {
     dioxus::LaunchBuilder::new()
         .with_cfg(server_only! {
             ServeConfig::builder()
-                .incremental(
-                    IncrementalRendererConfig::new()
-                        .static_dir(
-                            std::env::current_exe()
-                                .unwrap()
-                                .parent()
-                                .unwrap()
-                                .join("public")
-                        )
-                        .clear_cache(false)
-                )
+                .static_site(StaticSiteConfig::<Route>::new())
                 .enable_out_of_order_streaming()
         })
         .launch(app);
 }
 
-#[server(endpoint = "static_routes")]
-async fn static_routes() -> Result<Vec<String>, ServerFnError> {
-    Ok(vec!["/".to_string()])
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+}
+
+#[component]
+fn Home() -> Element {
+    app()
 }
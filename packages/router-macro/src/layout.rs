@@ -11,6 +11,8 @@ pub struct LayoutId(pub usize);
 pub struct Layout {
     pub comp: Path,
     pub active_nests: Vec<NestId>,
+    pub loader: Option<Path>,
+    pub guard: Option<Path>,
 }
 
 impl Layout {
@@ -35,6 +37,29 @@ impl Layout {
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
 
-        Ok(Self { comp, active_nests })
+        // Then parse the optional loader and guard: `loader = load_fn, guard = guard_fn`
+        let mut loader = None;
+        let mut guard = None;
+        while input.parse::<syn::Token![,]>().is_ok() && !input.is_empty() {
+            let name: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            if name == "loader" {
+                loader = Some(input.parse()?);
+            } else if name == "guard" {
+                guard = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "expected `loader = ..` or `guard = ..`",
+                ));
+            }
+        }
+
+        Ok(Self {
+            comp,
+            active_nests,
+            loader,
+            guard,
+        })
     }
 }
@@ -10,7 +10,7 @@ use quote::{__private::Span, format_ident, quote, ToTokens};
 use redirect::Redirect;
 use route::{Route, RouteType};
 use segment::RouteSegment;
-use syn::{parse::ParseStream, parse_macro_input, Ident, Token, Type};
+use syn::{parse::ParseStream, parse_macro_input, Ident, LitStr, Token, Type};
 
 use proc_macro2::TokenStream as TokenStream2;
 
@@ -18,12 +18,14 @@ use crate::{layout::LayoutId, route_tree::ParseRouteTree};
 
 mod hash;
 mod layout;
+mod localized;
 mod nest;
 mod query;
 mod redirect;
 mod route;
 mod route_tree;
 mod segment;
+mod sitemap;
 
 /// Derives the Routable trait for an enum of routes
 ///
@@ -42,6 +44,43 @@ mod segment;
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
 /// 2. By the order they are defined in the enum
 ///
+/// Loaders:
+/// 1. Route loaders: `#[loader(load_fn)]` on a variant
+/// 2. Layout loaders: `#[layout(Component, loader = load_fn)]`
+///
+/// Loaders are async functions that take the route and return data that is read with `use_loader_data`. The loaders of
+/// every layout and the route are started in parallel when navigation begins. See `dioxus_router::loader::RouteLoader`.
+///
+/// Guards:
+/// 1. Route guards: `#[guard(guard_fn)]` on a variant
+/// 2. Layout guards: `#[layout(Component, guard = guard_fn)]`
+///
+/// Guards are async functions that take the route that is being navigated to and decide if the navigation continues.
+/// See `dioxus_router::guard::RouteGuard`.
+///
+/// Named outlets:
+/// 1. Route outlets: `#[outlet("name")]` on a variant
+///
+/// Routes with a named outlet render in the `Outlet { name: "name" }` of their layout when they are navigated to from
+/// a page that renders that outlet. The previous route keeps rendering in the default outlets, which can be used for
+/// modals and panes that are routed independently. If the outlet isn't rendered, like when the route is opened
+/// directly, the route renders in the default outlet. See `dioxus_router::components::Outlet`.
+///
+/// Scrolling:
+/// 1. Opt out of scroll restoration: `#[scroll(manual)]` on a variant
+///
+/// The router scrolls to the top of the page or the hash fragment when a route is pushed and restores the scroll
+/// position when going back or forward. Routes with `#[scroll(manual)]` handle scrolling themselves. See
+/// `dioxus_router::scroll`.
+///
+/// Localized paths:
+/// 1. Locales: `#[locales("en", "de")]` on the enum. The first locale is the default locale
+/// 2. Route paths in a locale: `#[localized("de", "/produkte/:id")]` on a variant, with the full path of the route
+///    including its nests and the same dynamic segments as the route
+///
+/// Paths can start with a locale prefix like `/de/produkte/1`. Paths without a prefix are in the default locale. See
+/// `dioxus_router::localization`.
+///
 /// All features:
 /// ```rust
 /// use dioxus::prelude::*;
@@ -51,6 +90,8 @@ mod segment;
 /// enum Route {
 ///     // Define routes with the route macro. If the name of the component is not the same as the variant, you can specify it as the second parameter
 ///     #[route("/", IndexComponent)]
+///     // Annotate the route in the sitemap generated by `dioxus_router::sitemap::Sitemap`. Use `#[sitemap(exclude)]` to leave it out
+///     #[sitemap(changefreq = "daily", priority = 1.0)]
 ///     Index {},
 ///     // Nests with parameters have types taken from child routes
 ///     // Everything inside the nest has the added parameter `user_id: usize`
@@ -241,7 +282,10 @@ mod segment;
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route, nest, end_nest, layout, end_layout, redirect, child, sitemap, loader, guard, outlet,
+        locales, localized, scroll
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -276,6 +320,7 @@ struct RouteEnum {
     nests: Vec<Nest>,
     layouts: Vec<Layout>,
     site_map: Vec<SiteMapSegment>,
+    locales: Vec<LitStr>,
 }
 
 impl RouteEnum {
@@ -400,7 +445,7 @@ impl RouteEnum {
                         Redirect::parse(input, nest_stack.clone(), endpoints.len())
                     };
                     let redirect = attr.parse_args_with(parser)?;
-                    endpoints.push(RouteEndpoint::Redirect(redirect));
+                    endpoints.push(RouteEndpoint::Redirect(Box::new(redirect)));
                 }
             }
 
@@ -414,7 +459,7 @@ impl RouteEnum {
             let mut segment = SiteMapSegment::new(&route.segments);
             if let RouteType::Child(child) = &route.ty {
                 let new_segment = SiteMapSegment {
-                    segment_type: SegmentType::Child(child.ty.clone()),
+                    segment_type: SegmentType::Child(Box::new(child.ty.clone())),
                     children: Vec::new(),
                 };
                 match &mut segment {
@@ -446,7 +491,7 @@ impl RouteEnum {
                 children.push(segment);
             }
 
-            endpoints.push(RouteEndpoint::Route(route));
+            endpoints.push(RouteEndpoint::Route(Box::new(route)));
         }
 
         // pop any remaining site map segments
@@ -467,12 +512,28 @@ impl RouteEnum {
             children.push(current);
         }
 
+        let locales = data
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("locales"))
+            .map(localized::parse_locales)
+            .transpose()?
+            .unwrap_or_default();
+        for endpoint in &endpoints {
+            if let RouteEndpoint::Route(route) = endpoint {
+                for localized in &route.localized {
+                    localized.validate(&locales, &route.full_path(&nests))?;
+                }
+            }
+        }
+
         let myself = Self {
             name: name.clone(),
             endpoints,
             nests,
             layouts,
             site_map,
+            locales,
         };
 
         Ok(myself)
@@ -507,6 +568,17 @@ impl RouteEnum {
         let name = &self.name;
 
         let error_name = format_ident!("{}MatchError", self.name);
+        // Map localized paths to the path of the route before parsing
+        let delocalize = (!self.locales.is_empty()).then(|| {
+            quote! {
+                let delocalized = dioxus_router::localization::delocalize(
+                    s,
+                    <Self as dioxus_router::routable::Routable>::LOCALES,
+                    <Self as dioxus_router::routable::Routable>::LOCALIZED_PATHS,
+                );
+                let s: &str = &delocalized;
+            }
+        });
         let tokens = tree.roots.iter().map(|&id| {
             let route = tree.get(id).unwrap();
             route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
@@ -525,6 +597,7 @@ impl RouteEnum {
                 type Err = dioxus_router::routable::RouteParseError<#error_name>;
 
                 fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    #delocalize
                     let route = s;
                     let (route, hash) = route.split_once('#').unwrap_or((route, ""));
                     let (route, query) = route.split_once('?').unwrap_or((route, ""));
@@ -668,20 +741,107 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut sitemap_matches = Vec::new();
+        let mut loaders_matches = Vec::new();
+        let mut has_loaders = self.layouts.iter().any(|layout| layout.loader.is_some());
+        let mut guards_matches = Vec::new();
+        let mut has_guards = self.layouts.iter().any(|layout| layout.guard.is_some());
+        let mut outlet_matches = Vec::new();
+        let mut has_outlets = false;
+        let mut manual_scroll_matches = Vec::new();
+        let mut has_manual_scroll = false;
+        let mut preload_matches = Vec::new();
+        let mut split_modules = Vec::new();
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
                 matches.push(route.routable_match(&self.layouts, &self.nests, name));
+                sitemap_matches.push(route.sitemap_match());
+                loaders_matches.push(route.loaders_match(&self.layouts));
+                has_loaders |= route.loader.is_some() || matches!(route.ty, RouteType::Child(_));
+                guards_matches.push(route.guards_match(&self.layouts));
+                has_guards |= route.guard.is_some() || matches!(route.ty, RouteType::Child(_));
+                outlet_matches.push(route.outlet_match());
+                preload_matches.push(route.preload_match(name));
+                split_modules.extend(route.split_module(name));
+                has_outlets |= route.outlet.is_some() || matches!(route.ty, RouteType::Child(_));
+                manual_scroll_matches.push(route.manual_scroll_match());
+                has_manual_scroll |= route.manual_scroll || matches!(route.ty, RouteType::Child(_));
             }
         }
 
+        // Only routes with loaders or child routes that may have loaders need to override the default loaders
+        let loaders_impl = has_loaders.then(|| {
+            quote! {
+                fn loaders(&self) -> Vec<dioxus_router::loader::RouteLoader> {
+                    match self {
+                        #(#loaders_matches)*
+                    }
+                }
+            }
+        });
+        let guards_impl = has_guards.then(|| {
+            quote! {
+                fn guards(&self) -> Vec<dioxus_router::guard::RouteGuard<Self>> {
+                    match self {
+                        #(#guards_matches)*
+                    }
+                }
+            }
+        });
+
+        let manual_scroll_impl = has_manual_scroll.then(|| {
+            quote! {
+                fn manual_scroll(&self) -> bool {
+                    match self {
+                        #(#manual_scroll_matches)*
+                    }
+                }
+            }
+        });
+
+        let localization_impl = (!self.locales.is_empty()).then(|| {
+            let locales = &self.locales;
+            let localized_paths = self.endpoints.iter().flat_map(|endpoint| match endpoint {
+                RouteEndpoint::Route(route) => {
+                    let path = route.full_path(&self.nests);
+                    route
+                        .localized
+                        .iter()
+                        .map(|localized| localized.to_tokens(&path))
+                        .collect()
+                }
+                RouteEndpoint::Redirect(_) => Vec::new(),
+            });
+            quote! {
+                const LOCALES: &'static [&'static str] = &[#(#locales,)*];
+                const LOCALIZED_PATHS: &'static [dioxus_router::localization::LocalizedPath] = &[
+                    #(#localized_paths,)*
+                ];
+            }
+        });
+
+        let outlet_impl = has_outlets.then(|| {
+            quote! {
+                fn outlet(&self) -> Option<&'static str> {
+                    match self {
+                        #(#outlet_matches)*
+                    }
+                }
+            }
+        });
+
         quote! {
+            #(#split_modules)*
+
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
                     #(#site_map,)*
                 ];
 
+                #localization_impl
+
                 fn render(&self, level: usize) -> dioxus_core::Element {
                     let myself = self.clone();
                     match (level, myself) {
@@ -689,14 +849,34 @@ impl RouteEnum {
                         _ => VNode::empty()
                     }
                 }
+
+                fn sitemap_annotations(&self) -> dioxus_router::sitemap::SitemapAnnotations {
+                    match self {
+                        #(#sitemap_matches)*
+                    }
+                }
+
+                #loaders_impl
+
+                #guards_impl
+
+                #outlet_impl
+
+                #manual_scroll_impl
+
+                fn preload(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()>>> {
+                    match self {
+                        #(#preload_matches)*
+                    }
+                }
             }
         }
     }
 }
 
 enum RouteEndpoint {
-    Route(Route),
-    Redirect(Redirect),
+    Route(Box<Route>),
+    Redirect(Box<Redirect>),
 }
 
 struct SiteMapSegment {
@@ -755,7 +935,7 @@ enum SegmentType {
     Static(String),
     Dynamic(String),
     CatchAll(String),
-    Child(Type),
+    Child(Box<Type>),
 }
 
 impl ToTokens for SegmentType {
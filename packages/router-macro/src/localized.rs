@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse::ParseStream, punctuated::Punctuated, LitStr, Token};
+
+/// The path of a route in a locale in `#[localized("de", "/produkte/:id")]`
+#[derive(Debug)]
+pub(crate) struct LocalizedAttr {
+    pub locale: LitStr,
+    pub path: LitStr,
+}
+
+impl LocalizedAttr {
+    pub(crate) fn parse(attr: &syn::Attribute) -> syn::Result<Self> {
+        attr.parse_args_with(|input: ParseStream| {
+            let locale = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let path: LitStr = input.parse()?;
+            let value = path.value();
+            if !value.starts_with('/') || value.contains(['?', '#']) {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "localized paths must start with `/` and can't contain a query or hash",
+                ));
+            }
+            Ok(Self { locale, path })
+        })
+    }
+
+    /// Check that the locale is one of the locales of the enum and the path has the same dynamic segments as the route
+    pub(crate) fn validate(&self, locales: &[LitStr], route: &str) -> syn::Result<()> {
+        if !locales
+            .iter()
+            .any(|locale| locale.value() == self.locale.value())
+        {
+            return Err(syn::Error::new_spanned(
+                &self.locale,
+                "the locale must be one of the locales in the `#[locales(..)]` attribute of the enum",
+            ));
+        }
+        let path = self.path.value();
+        let mut expected = dynamic_segments(route);
+        let mut found = dynamic_segments(&path);
+        expected.sort();
+        found.sort();
+        if expected != found {
+            return Err(syn::Error::new_spanned(
+                &self.path,
+                format!(
+                    "the localized path must have the same dynamic segments as the route `{route}`"
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create the `LocalizedPath` for the route with the full path template `route`
+    pub(crate) fn to_tokens(&self, route: &str) -> TokenStream2 {
+        let locale = &self.locale;
+        let path = &self.path;
+        quote! {
+            dioxus_router::localization::LocalizedPath {
+                locale: #locale,
+                route: #route,
+                path: #path,
+            }
+        }
+    }
+}
+
+/// Parse the locales of the enum in `#[locales("en", "de")]`
+pub(crate) fn parse_locales(attr: &syn::Attribute) -> syn::Result<Vec<LitStr>> {
+    let locales: Vec<LitStr> = attr
+        .parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?
+        .into_iter()
+        .collect();
+    if locales.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected at least one locale",
+        ));
+    }
+    for locale in &locales {
+        let value = locale.value();
+        if value.is_empty() || value.contains(['/', '?', '#']) {
+            return Err(syn::Error::new_spanned(
+                locale,
+                "locales can't be empty or contain `/`, `?` or `#`",
+            ));
+        }
+    }
+    Ok(locales)
+}
+
+/// The names of the dynamic and catch all segments of a path template
+fn dynamic_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix(':'))
+        .map(|name| name.trim_start_matches(".."))
+        .collect()
+}
@@ -5,7 +5,7 @@ use proc_macro2::TokenStream as TokenStream2;
 
 #[derive(Debug)]
 pub enum QuerySegment {
-    Single(FullQuerySegment),
+    Single(Box<FullQuerySegment>),
     Segments(Vec<QueryArgument>),
 }
 
@@ -66,10 +66,10 @@ impl QuerySegment {
                 ));
             };
 
-            Ok(QuerySegment::Single(FullQuerySegment {
+            Ok(QuerySegment::Single(Box::new(FullQuerySegment {
                 ident: query_ident,
                 ty,
-            }))
+            })))
         } else {
             let mut query_arguments = Vec::new();
             for segment in query.split('&') {
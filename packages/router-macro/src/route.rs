@@ -12,12 +12,14 @@ use proc_macro2::TokenStream as TokenStream2;
 use crate::hash::HashFragment;
 use crate::layout::Layout;
 use crate::layout::LayoutId;
+use crate::localized::LocalizedAttr;
 use crate::nest::Nest;
 use crate::nest::NestId;
 use crate::query::QuerySegment;
 use crate::segment::create_error_type;
 use crate::segment::parse_route_segments;
 use crate::segment::RouteSegment;
+use crate::sitemap::SitemapAttr;
 
 struct RouteArgs {
     route: LitStr,
@@ -60,6 +62,12 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub sitemap: Option<SitemapAttr>,
+    pub loader: Option<Path>,
+    pub guard: Option<Path>,
+    pub outlet: Option<LitStr>,
+    pub localized: Vec<LocalizedAttr>,
+    pub manual_scroll: bool,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -104,7 +112,7 @@ impl Route {
                             });
                             match child_field{
                                 Some(child) => {
-                                    ty = RouteType::Child(child.clone());
+                                    ty = RouteType::Child(Box::new(child.clone()));
                                 }
                                 None => {
                                     return Err(syn::Error::new_spanned(
@@ -146,6 +154,61 @@ impl Route {
             _ => Vec::new(),
         };
 
+        let sitemap = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("sitemap"))
+            .map(SitemapAttr::parse)
+            .transpose()?;
+
+        let loader = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("loader"))
+            .map(|attr| attr.parse_args::<Path>())
+            .transpose()?;
+
+        let guard = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("guard"))
+            .map(|attr| attr.parse_args::<Path>())
+            .transpose()?;
+
+        let outlet = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("outlet"))
+            .map(|attr| attr.parse_args::<LitStr>())
+            .transpose()?;
+
+        let localized = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("localized"))
+            .map(LocalizedAttr::parse)
+            .collect::<syn::Result<Vec<_>>>()?;
+        if !localized.is_empty() && matches!(ty, RouteType::Child(_)) {
+            return Err(syn::Error::new_spanned(
+                variant.clone(),
+                "Routable variants with a #[child(..)] attribute can't have localized paths",
+            ));
+        }
+
+        let manual_scroll = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("scroll"))
+            .map(|attr| {
+                let behavior = attr.parse_args::<Ident>()?;
+                match behavior == "manual" {
+                    true => Ok(true),
+                    false => Err(syn::Error::new_spanned(behavior, "expected `manual`")),
+                }
+            })
+            .transpose()?
+            .unwrap_or_default();
+
         let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -163,10 +226,157 @@ impl Route {
             hash,
             nests,
             layouts,
+            sitemap,
+            loader,
+            guard,
+            outlet,
+            localized,
+            manual_scroll,
             fields,
         })
     }
 
+    /// The path template of this route including the nests it is in, without the query and hash
+    pub(crate) fn full_path(&self, nests: &[Nest]) -> String {
+        let mut path: String = self
+            .nests
+            .iter()
+            .map(|id| nests[id.0].route.as_str())
+            .collect();
+        let route = &self.route[..self.route.find(['?', '#']).unwrap_or(self.route.len())];
+        path.push_str(route);
+        path
+    }
+
+    /// Match this route and return if it handles scrolling itself. Child routes use the child route.
+    pub(crate) fn manual_scroll_match(&self) -> TokenStream2 {
+        let name = &self.route_name;
+        match (self.manual_scroll, &self.ty) {
+            (true, _) => quote! {
+                Self::#name { .. } => true,
+            },
+            (false, RouteType::Child(field)) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::manual_scroll(#child),
+                }
+            }
+            (false, RouteType::Leaf { .. }) => quote! {
+                Self::#name { .. } => false,
+            },
+        }
+    }
+
+    /// Match this route and return the name of the outlet it renders in. Child routes without a named outlet use the outlet of the child route.
+    pub(crate) fn outlet_match(&self) -> TokenStream2 {
+        let name = &self.route_name;
+        match (&self.outlet, &self.ty) {
+            (Some(outlet), _) => quote! {
+                Self::#name { .. } => Some(#outlet),
+            },
+            (None, RouteType::Child(field)) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::outlet(#child),
+                }
+            }
+            (None, RouteType::Leaf { .. }) => quote! {
+                Self::#name { .. } => None,
+            },
+        }
+    }
+
+    /// Match this route and return the guards of its layouts followed by its own guard. Child routes add the guards of the child route.
+    pub(crate) fn guards_match(&self, layouts: &[Layout]) -> TokenStream2 {
+        let name = &self.route_name;
+        let guards = self
+            .layouts
+            .iter()
+            .filter_map(|id| layouts[id.0].guard.as_ref())
+            .chain(self.guard.as_ref());
+        let guards = quote! {
+            vec![#(dioxus_router::guard::RouteGuard::new(self.clone(), #guards),)*]
+        };
+
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => {
+                        let mut guards = #guards;
+                        // Redirects of the child guards are mapped back into this route
+                        let parent = self.clone();
+                        guards.extend(dioxus_router::routable::Routable::guards(#child).into_iter().map(|guard| {
+                            let parent = parent.clone();
+                            guard.map(move |__child| {
+                                let mut route = parent;
+                                if let Self::#name { #child, .. } = &mut route {
+                                    *#child = __child;
+                                }
+                                route
+                            })
+                        }));
+                        guards
+                    }
+                }
+            }
+            RouteType::Leaf { .. } => quote! {
+                Self::#name { .. } => #guards,
+            },
+        }
+    }
+
+    /// Match this route and return the loaders of its layouts followed by its own loader. Child routes add the loaders of the child route.
+    pub(crate) fn loaders_match(&self, layouts: &[Layout]) -> TokenStream2 {
+        let name = &self.route_name;
+        let loaders = self
+            .layouts
+            .iter()
+            .filter_map(|id| layouts[id.0].loader.as_ref())
+            .chain(self.loader.as_ref());
+        let loaders = quote! {
+            vec![#(dioxus_router::loader::RouteLoader::new(self.clone(), #loaders),)*]
+        };
+
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => {
+                        let mut loaders = #loaders;
+                        loaders.extend(dioxus_router::routable::Routable::loaders(#child));
+                        loaders
+                    }
+                }
+            }
+            RouteType::Leaf { .. } => quote! {
+                Self::#name { .. } => #loaders,
+            },
+        }
+    }
+
+    /// Match this route and return its sitemap annotations. Child routes without annotations use the annotations of the child route.
+    pub(crate) fn sitemap_match(&self) -> TokenStream2 {
+        let name = &self.route_name;
+        match (&self.sitemap, &self.ty) {
+            (Some(sitemap), _) => {
+                let annotations = sitemap.annotations();
+                quote! {
+                    Self::#name { .. } => #annotations,
+                }
+            }
+            (None, RouteType::Child(field)) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::sitemap_annotations(#child),
+                }
+            }
+            (None, RouteType::Leaf { .. }) => quote! {
+                Self::#name { .. } => dioxus_router::sitemap::SitemapAnnotations::default(),
+            },
+        }
+    }
+
     pub(crate) fn display_match(&self, nests: &[Nest]) -> TokenStream2 {
         let name = &self.route_name;
         let dynamic_segments = self.dynamic_segments();
@@ -286,15 +496,8 @@ impl Route {
                 we should think about restructuring the router macro completely since its codegen
                 makes up nearly 30-40% of the binary size in the dioxus docsite.
                 */
-                use sha2::Digest;
                 let dynamic_segments_receiver = self.dynamic_segments();
-                let dynamic_segments_from_route_ = self.dynamic_segments();
-                let dynamic_segments_from_route__ = self.dynamic_segments();
-                    let unique_identifier = base16::encode_lower(
-                    &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
-                );
-                let module_name = format_ident!("module{}{unique_identifier}", name).to_string();
-                let comp_name = format_ident!("route{}{unique_identifier}", name);
+                let module_fn = self.split_module_ident();
 
                 quote! {
                     #[allow(unused)]
@@ -302,28 +505,14 @@ impl Route {
                         dioxus::config_macros::maybe_wasm_split! {
                             if wasm_split {
                                 {
-                                    fn #comp_name(args: #router_name) -> Element {
-                                        match args {
-                                            #router_name::#name { #(#dynamic_segments_from_route_,)* } => {
-                                                rsx! {
-                                                    #component {
-                                                        #(#dynamic_segments_from_route__: #dynamic_segments_from_route__,)*
-                                                    }
-                                                }
-                                            }
-                                            _ => unreachable!()
-                                        }
-                                    }
-
-
-
                                     #[component]
                                     fn LoaderInner(args: NoPartialEq<#router_name>) -> Element {
-                                        static MODULE: wasm_split::LazyLoader<#router_name, Element> =
-                                            wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
+                                        let module = #module_fn()
+                                            .downcast_ref::<wasm_split::LazyLoader<#router_name, Element>>()
+                                            .unwrap();
 
-                                        use_resource(|| async move { MODULE.load().await }).suspend()?;
-                                        MODULE.call(args.0).unwrap()
+                                        use_resource(move || async move { module.load().await }).suspend()?;
+                                        module.call(args.0).unwrap()
                                     }
 
                                     struct NoPartialEq<T>(T);
@@ -370,6 +559,106 @@ impl Route {
         tokens
     }
 
+    /// A unique identifier for the split module of this route
+    fn split_identifier(&self) -> String {
+        use sha2::Digest;
+        let name = &self.route_name;
+        base16::encode_lower(
+            &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
+        )
+    }
+
+    /// The name of the function that returns the lazy loader of the split module of this route
+    fn split_module_ident(&self) -> Ident {
+        format_ident!(
+            "__split_module{}{}",
+            self.route_name,
+            self.split_identifier()
+        )
+    }
+
+    /// Create the function that returns the lazy loader of the split module of this leaf route. The loader is shared
+    /// between rendering and preloading the route. Without the `wasm-split` feature, the function is unused.
+    pub(crate) fn split_module(&self, router_name: &Ident) -> Option<TokenStream2> {
+        let RouteType::Leaf { component } = &self.ty else {
+            return None;
+        };
+        let name = &self.route_name;
+        let dynamic_segments = self.dynamic_segments();
+        let dynamic_segments_ = self.dynamic_segments();
+        let unique_identifier = self.split_identifier();
+        let module_name = format_ident!("module{}{unique_identifier}", name).to_string();
+        let comp_name = format_ident!("route{}{unique_identifier}", name);
+        let module_fn = self.split_module_ident();
+        let component = quote_spanned! { name.span() =>
+            #component
+        };
+
+        Some(quote! {
+            #[allow(non_snake_case, dead_code)]
+            fn #module_fn() -> &'static dyn std::any::Any {
+                dioxus::config_macros::maybe_wasm_split! {
+                    if wasm_split {
+                        {
+                            fn #comp_name(args: #router_name) -> Element {
+                                match args {
+                                    #router_name::#name { #(#dynamic_segments,)* } => {
+                                        rsx! {
+                                            #component {
+                                                #(#dynamic_segments_: #dynamic_segments_,)*
+                                            }
+                                        }
+                                    }
+                                    _ => unreachable!()
+                                }
+                            }
+
+                            static MODULE: wasm_split::LazyLoader<#router_name, Element> =
+                                wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
+                            &MODULE
+                        }
+                    } else {
+                        { &() }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Match this route and return a future that loads the split module of the route. Child routes preload the child route.
+    pub(crate) fn preload_match(&self, router_name: &Ident) -> TokenStream2 {
+        let name = &self.route_name;
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::preload(#child),
+                }
+            }
+            RouteType::Leaf { .. } => {
+                let module_fn = self.split_module_ident();
+                quote! {
+                    Self::#name { .. } => {
+                        dioxus::config_macros::maybe_wasm_split! {
+                            if wasm_split {
+                                {
+                                    let module = #module_fn()
+                                        .downcast_ref::<wasm_split::LazyLoader<#router_name, Element>>()
+                                        .unwrap();
+                                    Box::pin(async move {
+                                        module.load().await;
+                                    })
+                                }
+                            } else {
+                                { Box::pin(std::future::ready(())) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         self.fields.iter().map(|(name, _)| {
             quote! {#name}
@@ -463,6 +752,6 @@ impl Route {
 
 #[derive(Debug)]
 pub(crate) enum RouteType {
-    Child(Field),
+    Child(Box<Field>),
     Leaf { component: Path },
 }
@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{LitFloat, LitStr};
+
+/// The change frequencies allowed by the sitemap protocol
+const CHANGE_FREQUENCIES: &[&str] = &[
+    "always", "hourly", "daily", "weekly", "monthly", "yearly", "never",
+];
+
+/// The annotations of a route in `#[sitemap(changefreq = "weekly", priority = 0.8, lastmod = "2024-01-01", exclude)]`
+#[derive(Debug, Default)]
+pub(crate) struct SitemapAttr {
+    lastmod: Option<LitStr>,
+    changefreq: Option<String>,
+    priority: Option<f32>,
+    exclude: bool,
+}
+
+impl SitemapAttr {
+    pub(crate) fn parse(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut sitemap = Self::default();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("exclude") {
+                sitemap.exclude = true;
+            } else if meta.path.is_ident("lastmod") {
+                sitemap.lastmod = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("changefreq") {
+                let changefreq: LitStr = meta.value()?.parse()?;
+                let value = changefreq.value();
+                if !CHANGE_FREQUENCIES.contains(&value.as_str()) {
+                    return Err(syn::Error::new_spanned(
+                        changefreq,
+                        format!(
+                            "changefreq must be one of {}",
+                            CHANGE_FREQUENCIES.join(", ")
+                        ),
+                    ));
+                }
+                sitemap.changefreq = Some(value);
+            } else if meta.path.is_ident("priority") {
+                let priority: LitFloat = meta.value()?.parse()?;
+                let value: f32 = priority.base10_parse()?;
+                if !(0.0..=1.0).contains(&value) {
+                    return Err(syn::Error::new_spanned(
+                        priority,
+                        "priority must be between 0.0 and 1.0",
+                    ));
+                }
+                sitemap.priority = Some(value);
+            } else {
+                return Err(
+                    meta.error("expected one of `lastmod`, `changefreq`, `priority` or `exclude`")
+                );
+            }
+            Ok(())
+        })?;
+        Ok(sitemap)
+    }
+
+    /// Create the `SitemapAnnotations` for the route
+    pub(crate) fn annotations(&self) -> TokenStream2 {
+        let lastmod = match &self.lastmod {
+            Some(lastmod) => quote! { Some(#lastmod) },
+            None => quote! { None },
+        };
+        let changefreq = match &self.changefreq {
+            Some(changefreq) => {
+                let mut variant = changefreq.clone();
+                variant[..1].make_ascii_uppercase();
+                let variant = format_ident!("{}", variant);
+                quote! { Some(dioxus_router::sitemap::ChangeFrequency::#variant) }
+            }
+            None => quote! { None },
+        };
+        let priority = match self.priority {
+            Some(priority) => quote! { Some(#priority) },
+            None => quote! { None },
+        };
+        let exclude = self.exclude;
+
+        quote! {
+            dioxus_router::sitemap::SitemapAnnotations {
+                lastmod: #lastmod,
+                changefreq: #changefreq,
+                priority: #priority,
+                exclude: #exclude,
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use dioxus_lib::prelude::*;
 use tracing::error;
 
 use crate::navigation::NavigationTarget;
+use crate::prefetch::Prefetch;
 use crate::utils::use_router_internal::use_router_internal;
 
 /// The properties for a [`Link`].
@@ -43,6 +44,14 @@ pub struct LinkProps {
     /// 3. If `onclick_only` is [`true`], only the provided `onclick` handler will be executed.
     pub onclick_only: bool,
 
+    /// When the route the link points to is prefetched. See [`Prefetch`].
+    ///
+    /// Only internal links that don't open in a new tab are prefetched. Prefetching starts the loaders of the route,
+    /// loads the code of the route if it is split with the `wasm-split` feature and, in fullstack apps, warms the
+    /// `use_server_future`s of the route.
+    #[props(default)]
+    pub prefetch: Prefetch,
+
     /// The rel attribute for the generated HTML anchor tag.
     ///
     /// For external `a`s, this defaults to `noopener noreferrer`.
@@ -65,6 +74,7 @@ impl Debug for LinkProps {
             .field("new_tab", &self.new_tab)
             .field("onclick", &self.onclick.as_ref().map(|_| "onclick is set"))
             .field("onclick_only", &self.onclick_only)
+            .field("prefetch", &self.prefetch)
             .field("rel", &self.rel)
             .finish()
     }
@@ -135,10 +145,11 @@ pub fn Link(props: LinkProps) -> Element {
     let LinkProps {
         active_class,
         children,
-        attributes,
+        mut attributes,
         new_tab,
         onclick,
         onclick_only,
+        prefetch,
         rel,
         to,
         class,
@@ -159,9 +170,9 @@ pub fn Link(props: LinkProps) -> Element {
     };
 
     let current_url = router.full_route_string();
-    let href = match &to {
-        NavigationTarget::Internal(url) => url.clone(),
-        NavigationTarget::External(route) => route.clone(),
+    let href = match router.localize(to.clone()) {
+        NavigationTarget::Internal(url) => url,
+        NavigationTarget::External(route) => route,
     };
     // Add the history's prefix to the href for use in the rsx
     let full_href = router.prefix().unwrap_or_default() + &href;
@@ -195,6 +206,48 @@ pub fn Link(props: LinkProps) -> Element {
 
     let do_default = onclick.is_none() || !onclick_only;
 
+    let prefetch = match is_router_nav {
+        true => prefetch,
+        false => Prefetch::None,
+    };
+    if prefetch == Prefetch::Hover {
+        let to = to.clone();
+        let prefetch = move || router.prefetch(to.clone());
+        let on_hover = prefetch.clone();
+        attributes.push(dioxus_elements::events::onmouseenter(move |_| on_hover()));
+        attributes.push(dioxus_elements::events::onfocus(move |_| prefetch()));
+    }
+    // The link is found by this attribute to observe when it scrolls into view
+    let prefetch_id = current_scope_id().ok().map(|id| id.0.to_string());
+    if prefetch == Prefetch::Visible {
+        if let Some(id) = prefetch_id.clone() {
+            attributes.push(Attribute::new("data-dioxus-prefetch", id, None, false));
+        }
+    }
+
+    let onmounted = {
+        let to = to.clone();
+        move |event| {
+            match prefetch {
+                Prefetch::Eager => router.prefetch(to.clone()),
+                Prefetch::Visible => {
+                    if let Some(id) = prefetch_id.clone() {
+                        let to = to.clone();
+                        spawn(async move {
+                            if wait_until_visible(&id).await {
+                                router.prefetch(to);
+                            }
+                        });
+                    }
+                }
+                Prefetch::None | Prefetch::Hover => {}
+            }
+            if let Some(handler) = props.onmounted {
+                handler.call(event);
+            }
+        }
+    };
+
     let action = move |event: MouseEvent| {
         // Only handle events without modifiers
         if !event.modifiers().is_empty() {
@@ -221,12 +274,6 @@ pub fn Link(props: LinkProps) -> Element {
         }
     };
 
-    let onmounted = move |event| {
-        if let Some(handler) = props.onmounted {
-            handler.call(event);
-        }
-    };
-
     // In liveview, we need to prevent the default action if the user clicks on the link with modifiers
     // in javascript. The prevent_default method is not available in the liveview renderer because
     // event handlers are handled over a websocket.
@@ -253,3 +300,22 @@ pub fn Link(props: LinkProps) -> Element {
         }
     }
 }
+
+/// Wait until the link with the given prefetch id scrolls into the viewport.
+async fn wait_until_visible(id: &str) -> bool {
+    let mut eval = dioxus_lib::document::eval(&format!(
+        r#"
+        const link = document.querySelector('[data-dioxus-prefetch="{id}"]');
+        if (link) {{
+            const observer = new IntersectionObserver((entries) => {{
+                if (entries.some((entry) => entry.isIntersecting)) {{
+                    observer.disconnect();
+                    dioxus.send(true);
+                }}
+            }});
+            observer.observe(link);
+        }}
+        "#
+    ));
+    eval.recv::<bool>().await.unwrap_or(false)
+}
@@ -69,6 +69,55 @@ use dioxus_lib::prelude::*;
 /// # vdom.rebuild_in_place();
 /// # assert_eq!(dioxus_ssr::render(&vdom), "<h1>App</h1><p>Child</p>");
 /// ```
-pub fn Outlet<R: Routable + Clone>() -> Element {
-    OutletContext::<R>::render()
+pub fn Outlet<R: Routable + Clone>(props: OutletProps) -> Element {
+    OutletContext::<R>::render(props.name)
+}
+
+/// The props for [`Outlet`].
+#[derive(Props, Clone, PartialEq)]
+pub struct OutletProps {
+    /// The name of the outlet. Named outlets render the routes with a matching `#[outlet(..)]` attribute while the
+    /// previous route keeps rendering in the default outlets. The name must not change after the outlet is created.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// #[derive(Clone, Routable, PartialEq, Debug)]
+    /// #[rustfmt::skip]
+    /// enum Route {
+    ///     #[layout(Frame)]
+    ///         #[route("/")]
+    ///         Gallery {},
+    ///         // Opening a photo from the gallery renders it in the modal outlet over the gallery.
+    ///         // Opening the URL directly renders the photo as a normal page
+    ///         #[route("/photo/:id")]
+    ///         #[outlet("modal")]
+    ///         Photo { id: usize },
+    /// }
+    ///
+    /// #[component]
+    /// fn Frame() -> Element {
+    ///     rsx! {
+    ///         Outlet::<Route> {}
+    ///         Outlet::<Route> { name: "modal" }
+    ///     }
+    /// }
+    ///
+    /// #[component]
+    /// fn Gallery() -> Element {
+    ///     rsx! { Link { to: Route::Photo { id: 1 }, "Open the first photo" } }
+    /// }
+    ///
+    /// #[component]
+    /// fn Photo(id: usize) -> Element {
+    ///     let background = router().background::<Route>();
+    ///     rsx! {
+    ///         div { class: if background.is_some() { "modal" } else { "page" },
+    ///             "Photo {id}"
+    ///             // Going back closes the modal and shows the gallery
+    ///             GoBackButton { "Close" }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub name: Option<String>,
 }
@@ -1,6 +1,8 @@
 use dioxus_lib::prelude::*;
 
 use crate::{
+    contexts::LoaderContext,
+    localization,
     prelude::{provide_router_context, Outlet},
     routable::Routable,
     router_cfg::RouterConfig,
@@ -40,8 +42,68 @@ impl<R: Clone> PartialEq for RouterProps<R> {
 pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
-    use_hook(|| {
-        provide_router_context(RouterContext::new(props.config.call(())));
+    let router = use_hook(|| {
+        let router = RouterContext::new(props.config.call(()));
+        provide_router_context(router);
+        router
+    });
+
+    // Start the loaders of the whole layout chain as soon as the route changes. Only the data of the first route
+    // is serialized for hydration
+    let hydrate = use_hook(|| std::rc::Rc::new(std::cell::Cell::new(true)));
+    let loaders = use_hook(|| provide_context(LoaderContext::new(router)));
+
+    // The server futures of a prefetched route start with the results they had while the route rendered in the
+    // background. This needs to happen before the loaders take the route out of the prefetch cache
+    #[cfg(feature = "streaming")]
+    {
+        let replayed = use_hook(|| std::rc::Rc::new(std::cell::RefCell::new(None::<String>)));
+        let current = router.current::<R>().to_string();
+        if replayed.borrow().as_ref() != Some(&current) {
+            router
+                .take_prefetched_server_futures(&current)
+                .unwrap_or_default()
+                .replay();
+            replayed.replace(Some(current));
+        }
+    }
+
+    // If the current route renders in a named outlet, the named outlet loads its data and the loaders of the
+    // background route keep their data
+    let route = router
+        .background::<R>()
+        .or_else(|| router.full_route_string().parse::<R>().ok());
+    if let Some(route) = route {
+        loaders.load(&route, hydrate.replace(false));
+    }
+
+    // Link the current route in every locale for search engines. Head elements can't be changed after they are
+    // rendered, so the links of the first route are rendered with the page and replaced with a script once the
+    // route changes
+    let current_alternates = move || {
+        let prefix = router.prefix().unwrap_or_default();
+        router
+            .full_route_string()
+            .parse::<R>()
+            .map(|route| localization::alternates(&route, &prefix))
+            .unwrap_or_default()
+    };
+    let alternates = use_hook(current_alternates);
+    let linked_alternates =
+        use_hook(|| std::rc::Rc::new(std::cell::RefCell::new(alternates.clone())));
+    use_effect(move || {
+        let current = current_alternates();
+        if *linked_alternates.borrow() != current {
+            _ = dioxus_lib::document::eval(&localization::alternates_script(&current));
+            linked_alternates.replace(current);
+        }
+    });
+
+    // Scroll once the new route rendered. The background route of the entry is only rendered once the first
+    // render was hydrated
+    use_effect(move || {
+        router.finish_first_render();
+        router.scroll();
     });
 
     #[cfg(feature = "streaming")]
@@ -53,5 +115,69 @@ pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
         provide_context(OutletContext::<R>::new());
     });
 
-    rsx! { Outlet::<R> {} }
+    // Prefetched routes render in the background to record the results of their server futures
+    #[cfg(feature = "streaming")]
+    let prefetched = rsx! {
+        for (key, server_futures) in router.prefetched_routes() {
+            if let Ok(route) = key.parse::<R>() {
+                PrefetchedRoute::<R> { key: "{key}", route, server_futures }
+            }
+        }
+    };
+    #[cfg(not(feature = "streaming"))]
+    let prefetched = VNode::empty();
+
+    rsx! {
+        for (hreflang, href) in alternates {
+            dioxus_lib::document::Link { key: "{hreflang}", rel: "alternate", hreflang, href }
+        }
+        Outlet::<R> {}
+        {prefetched}
+    }
+}
+
+#[cfg(feature = "streaming")]
+#[derive(Props, Clone)]
+struct PrefetchedRouteProps<R: Clone + 'static> {
+    route: R,
+    server_futures: dioxus_fullstack_hooks::PrefetchedServerFutures,
+}
+
+#[cfg(feature = "streaming")]
+impl<R: Clone> PartialEq for PrefetchedRouteProps<R> {
+    fn eq(&self, other: &Self) -> bool {
+        // Each prefetch of a route records its server futures separately
+        self.server_futures == other.server_futures
+    }
+}
+
+/// Render a prefetched route inside of a suspense boundary that never resolves. The route is never mounted and its
+/// effects never run, but its server futures run and record their results.
+#[cfg(feature = "streaming")]
+#[allow(non_snake_case)]
+fn PrefetchedRoute<R: Routable + Clone>(props: PrefetchedRouteProps<R>) -> Element {
+    use crate::prelude::{outlet::OutletContext, RouterContext};
+
+    use_hook(|| {
+        props.server_futures.record();
+        provide_context(OutletContext::<R>::prefetched(props.route.clone()));
+        let router = consume_context::<RouterContext>();
+        provide_context(LoaderContext::new(router)).load_prefetched(&props.route);
+    });
+
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| VNode::empty(),
+            Unresolved {}
+            Outlet::<R> {}
+        }
+    }
+}
+
+/// A component that stays suspended so the suspense boundary around a prefetched route never resolves
+#[cfg(feature = "streaming")]
+#[component]
+fn Unresolved() -> Element {
+    let task = use_hook(|| spawn(std::future::pending::<()>()));
+    Err(suspend(task).unwrap_err())
 }
@@ -0,0 +1,156 @@
+use std::{
+    any::{Any, TypeId},
+    rc::Rc,
+};
+
+use dioxus_lib::prelude::*;
+use futures_util::FutureExt;
+
+use crate::{
+    loader::LoaderFuture, prefetch::PrefetchedLoader, prelude::RouterContext, routable::Routable,
+};
+
+struct LoaderSlot {
+    type_id: TypeId,
+    /// The task of the loader or `None` if the data was available when the loader started
+    task: Option<Task>,
+    data: Option<Rc<dyn Any>>,
+}
+
+#[derive(Default)]
+struct LoaderState {
+    /// The route the loaders were started for
+    route: Option<String>,
+    /// Increased every time the loaders are restarted so loaders of a previous route don't overwrite the new data
+    generation: usize,
+    slots: Vec<LoaderSlot>,
+}
+
+/// The data of the loaders in the layout chain of the current route. This is provided by the [`Router`](crate::components::Router).
+#[derive(Clone, Copy)]
+pub(crate) struct LoaderContext {
+    router: RouterContext,
+    state: CopyValue<LoaderState>,
+    /// Written when the state changes outside of a render so the components that read loader data rerun
+    changed: Signal<()>,
+}
+
+impl LoaderContext {
+    pub(crate) fn new(router: RouterContext) -> Self {
+        Self {
+            router,
+            state: CopyValue::new(LoaderState::default()),
+            changed: Signal::new(()),
+        }
+    }
+
+    /// Start every loader of the route in parallel if the route is different from the route that was loaded last.
+    ///
+    /// If `hydrate` is true, the data of the loaders is serialized into the page on the server and read back on the client.
+    /// Otherwise, the loaders of a prefetched route are taken from the prefetch cache of the router.
+    pub(crate) fn load<R: Routable>(&self, route: &R, hydrate: bool) {
+        let key = route.to_string();
+        if self.state.peek().route.as_deref() == Some(key.as_str()) {
+            return;
+        }
+
+        let prefetched = match hydrate {
+            true => None,
+            false => self.router.take_prefetched(&key),
+        };
+        self.start(route, key, prefetched, hydrate);
+    }
+
+    /// Start the loaders of a route that is rendered while it is prefetched with the loaders in the prefetch cache
+    /// without taking them out of the cache.
+    #[cfg(feature = "streaming")]
+    pub(crate) fn load_prefetched<R: Routable>(&self, route: &R) {
+        let key = route.to_string();
+        let prefetched = self.router.prefetched_loaders(&key);
+        self.start(route, key, prefetched, false);
+    }
+
+    fn start<R: Routable>(
+        &self,
+        route: &R,
+        key: String,
+        prefetched: Option<Vec<PrefetchedLoader>>,
+        hydrate: bool,
+    ) {
+        let loaders: Vec<(TypeId, LoaderFuture)> = match prefetched {
+            Some(loaders) => loaders
+                .into_iter()
+                .map(|(type_id, future)| (type_id, Box::pin(future) as LoaderFuture))
+                .collect(),
+            None => route
+                .loaders()
+                .into_iter()
+                .map(|loader| (loader.type_id, (loader.start)(hydrate)))
+                .collect(),
+        };
+
+        let mut state = self.state;
+        let mut changed = self.changed;
+        let generation = state.peek().generation + 1;
+        let slots: Vec<_> = loaders
+            .into_iter()
+            .enumerate()
+            .map(|(index, (type_id, mut future))| {
+                // Loaders with data that is already available, like hydrated data, resolve before the route renders
+                if let Some(data) = (&mut future).now_or_never() {
+                    return LoaderSlot {
+                        type_id,
+                        task: None,
+                        data: Some(data),
+                    };
+                }
+                let task = spawn(async move {
+                    let data = future.await;
+                    let mut state = state.write();
+                    if state.generation == generation {
+                        state.slots[index].data = Some(data);
+                        changed.set(());
+                    }
+                });
+                LoaderSlot {
+                    type_id,
+                    task: Some(task),
+                    data: None,
+                }
+            })
+            .collect();
+
+        // This runs while the router renders, so the components that read the state are only notified once the
+        // render is done
+        {
+            let mut state = state.write();
+            for slot in state.slots.drain(..) {
+                if let (None, Some(task)) = (&slot.data, slot.task) {
+                    task.cancel();
+                }
+            }
+            state.route = Some(key);
+            state.generation = generation;
+            state.slots = slots;
+        }
+        queue_effect(move || changed.set(()));
+    }
+
+    /// Get the data of the loader closest to the route that returns `T`. Returns the task of the loader if it is still running.
+    pub(crate) fn get<T: Clone + 'static>(&self) -> Option<Result<T, Task>> {
+        self.changed.read();
+        let state = self.state.read();
+        let slot = state
+            .slots
+            .iter()
+            .rev()
+            .find(|slot| slot.type_id == TypeId::of::<T>())?;
+        Some(match &slot.data {
+            Some(data) => Ok(data
+                .downcast_ref::<T>()
+                .expect("loader data has the type of its loader")
+                .clone()),
+            None => Err(slot.task.expect("loaders without data have a task")),
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{de::DeserializeOwned, Serialize};
+
 use crate::prelude::{ExternalNavigationFailure, NavigationTarget, RouterContext};
 
 /// Acquire the navigator without subscribing to updates.
@@ -61,4 +63,62 @@ impl Navigator {
     ) -> Option<ExternalNavigationFailure> {
         self.0.replace(target)
     }
+
+    /// Push a new location and attach `state` to the new entry of the history.
+    ///
+    /// The state can be read with [`Navigator::current_state`] when the user navigates back to the entry:
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Post(id: usize) -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable, Debug, PartialEq)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Posts {},
+    ///     #[route("/post/:id")]
+    ///     Post { id: usize },
+    /// }
+    ///
+    /// #[component]
+    /// fn Posts() -> Element {
+    ///     let navigator = use_navigator();
+    ///     // Restore the filter when the user navigates back to the list
+    ///     let mut filter = use_signal(|| navigator.current_state::<String>().unwrap_or_default());
+    ///
+    ///     rsx! {
+    ///         input { value: "{filter}", oninput: move |event| filter.set(event.value()) }
+    ///         button {
+    ///             onclick: move |_| {
+    ///                 navigator.replace_with_state(Route::Posts {}, &filter());
+    ///                 navigator.push(Route::Post { id: 1 });
+    ///             },
+    ///             "Open the first post"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: &impl Serialize,
+    ) -> Option<ExternalNavigationFailure> {
+        self.0.push_with_state(target, state)
+    }
+
+    /// Replace the current location and attach `state` to the entry of the history.
+    pub fn replace_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: &impl Serialize,
+    ) -> Option<ExternalNavigationFailure> {
+        self.0.replace_with_state(target, state)
+    }
+
+    /// Get the state attached to the current entry of the history. This method is reactive.
+    ///
+    /// Returns [`None`] if the entry has no state or the state can't be deserialized into `T`.
+    pub fn current_state<T: DeserializeOwned>(&self) -> Option<T> {
+        self.0.current_state()
+    }
 }
@@ -1,6 +1,10 @@
+use std::rc::Rc;
+
 use dioxus_lib::prelude::*;
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{
+    contexts::LoaderContext, routable::Routable, utils::use_router_internal::use_router_internal,
+};
 
 /// A context that manages nested routing levels for outlet components.
 ///
@@ -13,6 +17,10 @@ use crate::{routable::Routable, utils::use_router_internal::use_router_internal}
 #[derive(Clone, Default)]
 pub struct OutletContext<R> {
     current_level: usize,
+    /// If this outlet is inside of a named outlet. Outlets inside of a named outlet render the current route instead of the background route
+    in_named_outlet: bool,
+    /// The route that is rendered while it is prefetched. Outlets of a prefetched route render this route instead of the current route
+    prefetched: Option<Rc<R>>,
     _marker: std::marker::PhantomData<R>,
 }
 
@@ -21,6 +29,8 @@ impl<R> OutletContext<R> {
     pub fn new() -> Self {
         Self {
             current_level: 0,
+            in_named_outlet: false,
+            prefetched: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -29,23 +39,61 @@ impl<R> OutletContext<R> {
     pub fn next(&self) -> Self {
         Self {
             current_level: self.current_level + 1,
+            in_named_outlet: self.in_named_outlet,
+            prefetched: self.prefetched.clone(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Creates a new outlet context that renders a route while it is prefetched
+    #[cfg(feature = "streaming")]
+    pub(crate) fn prefetched(route: R) -> Self {
+        Self {
+            prefetched: Some(Rc::new(route)),
+            ..Self::new()
+        }
+    }
+
     /// Returns the current nesting level of this outlet
     pub fn level(&self) -> usize {
         self.current_level
     }
 
-    pub(crate) fn render() -> Element
+    pub(crate) fn render(name: Option<String>) -> Element
     where
         R: Routable + Clone,
     {
         let router = use_router_internal().expect("Outlet must be inside of a router");
         let outlet: OutletContext<R> = use_outlet_context();
         let current_level = outlet.level();
-        provide_context(outlet.next());
+        provide_context(Self {
+            in_named_outlet: outlet.in_named_outlet || name.is_some(),
+            ..outlet.next()
+        });
+
+        // A prefetched route renders outside of the history, so it ignores named outlets and the background route
+        if let Some(route) = &outlet.prefetched {
+            return match name {
+                Some(_) => VNode::empty(),
+                None => route.render(current_level),
+            };
+        }
+
+        // Named outlets load the data of their route separately from the background route
+        let named = use_hook(|| {
+            name.clone().map(|name| {
+                router.add_named_outlet(name.clone());
+                (name, provide_context(LoaderContext::new(router)))
+            })
+        });
+        use_drop({
+            let named = named.clone();
+            move || {
+                if let Some((name, _)) = named {
+                    router.remove_named_outlet(&name);
+                }
+            }
+        });
 
         if let Some(error) = router.render_error() {
             return if current_level == 0 {
@@ -55,7 +103,22 @@ impl<R> OutletContext<R> {
             };
         }
 
-        router.current::<R>().render(current_level)
+        let current = router.current::<R>();
+        let background = router.background::<R>();
+        match named {
+            // Named outlets only render routes of the outlet that have a background route
+            Some((name, loaders)) => match background {
+                Some(_) if current.outlet() == Some(name.as_str()) => {
+                    loaders.load(&current, false);
+                    current.render(current_level)
+                }
+                _ => VNode::empty(),
+            },
+            None => match background {
+                Some(background) if !outlet.in_named_outlet => background.render(current_level),
+                _ => current.render(current_level),
+            },
+        }
     }
 }
 
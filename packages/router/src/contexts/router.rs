@@ -2,15 +2,27 @@ use std::{
     collections::HashSet,
     error::Error,
     fmt::Display,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
     sync::{Arc, Mutex},
 };
 
 use dioxus_history::history;
 use dioxus_lib::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    components::child_router::consume_child_route_mapping, navigation::NavigationTarget,
-    prelude::SiteMapSegment, routable::Routable, router_cfg::RouterConfig,
+    components::child_router::consume_child_route_mapping,
+    guard::{NavigationDecision, RouteGuard},
+    hooks::PendingNavigation,
+    localization::{self, LocalizedPath},
+    navigation::NavigationTarget,
+    prefetch::{PrefetchCache, PrefetchedLoader, RoutePrefetch},
+    prelude::SiteMapSegment,
+    routable::Routable,
+    router_cfg::RouterConfig,
+    scroll::{Scroll, ScrollAction, ScrollPosition, SCROLL_SCRIPT},
 };
 
 /// An error that is thrown when the router fails to parse a route
@@ -61,6 +73,59 @@ pub(crate) type RoutingCallback<R> =
     Arc<dyn Fn(GenericRouterContext<R>) -> Option<NavigationTarget<R>>>;
 pub(crate) type AnyRoutingCallback = Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>;
 
+/// A guard set with [`RouterConfig::guard`] that is called with the path of the current route and the route that is
+/// being navigated to.
+pub(crate) type NavigationGuard<R> =
+    Arc<dyn Fn(String, R) -> Pin<Box<dyn Future<Output = NavigationDecision<R>>>>>;
+/// A function that gets the guards of a navigation from the current route to another route.
+type AnyNavigationGuards = Rc<dyn Fn(&str, &str) -> Vec<RouteGuard<String>>>;
+
+/// The number of times guards can redirect a single navigation before it is cancelled
+const MAX_GUARD_REDIRECTS: usize = 16;
+
+/// A navigation that has not been committed yet.
+#[derive(Clone)]
+pub(crate) enum Navigation {
+    /// Push a target with the serialized state of the new entry
+    Push(NavigationTarget, Option<serde_json::Value>),
+    /// Replace the current entry with a target and the serialized state of the new entry
+    Replace(NavigationTarget, Option<serde_json::Value>),
+    Back,
+    Forward,
+}
+
+/// The state the router stores in each entry of the history
+#[derive(Serialize, Deserialize, Default)]
+struct EntryState {
+    /// The state set with [`RouterContext::push_with_state`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    state: Option<serde_json::Value>,
+    /// The route that renders in the default outlets while the route of the entry renders in its named outlet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    /// The key of the scroll position of the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scroll_key: Option<u64>,
+    /// The scroll position the page had when it last stopped scrolling while the entry was active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scroll: Option<ScrollPosition>,
+}
+
+impl EntryState {
+    fn current() -> Self {
+        history()
+            .current_state()
+            .and_then(|state| serde_json::from_str(&state).ok())
+            .unwrap_or_default()
+    }
+}
+
+struct BlockerEntry {
+    id: usize,
+    should_block: Rc<dyn Fn() -> bool>,
+    pending: Signal<Option<PendingNavigation>>,
+}
+
 struct RouterContextInner {
     unresolved_error: Option<ExternalNavigationFailure>,
 
@@ -71,7 +136,42 @@ struct RouterContextInner {
 
     internal_route: fn(&str) -> bool,
 
+    /// Get the loaders of a route and a future that loads its code
+    prefetch_of: fn(&str) -> Option<RoutePrefetch>,
+    prefetch_cache: PrefetchCache,
+
+    /// Get the name of the outlet a route renders in
+    outlet_of: fn(&str) -> Option<&'static str>,
+    /// The names of the named outlets that are currently rendered
+    named_outlets: Vec<String>,
+    /// The server doesn't know the background route of the entry, so the first render ignores it to match the
+    /// page that is being hydrated
+    first_render: bool,
+
+    /// Check if a route handles scrolling itself
+    manual_scroll_of: fn(&str) -> bool,
+    /// How to scroll after the next route change. Going back and forward restores the scroll position
+    pending_scroll: Option<ScrollAction>,
+    /// The scroll key of the entry the page was last scrolled for
+    scroll_key: Option<u64>,
+    next_scroll_key: u64,
+    /// The task that stores the scroll positions the page reports for the current entry
+    scroll_task: Option<Task>,
+
     site_map: &'static [SiteMapSegment],
+
+    locales: &'static [&'static str],
+    localized_paths: &'static [LocalizedPath],
+
+    guards: AnyNavigationGuards,
+    /// Increased every time a navigation starts so guards of an older navigation don't commit it
+    navigation_generation: usize,
+
+    blockers: Vec<Rc<BlockerEntry>>,
+    next_blocker_id: usize,
+
+    /// The scope of the router. Guards run in this scope so they can access the history of the router
+    scope: ScopeId,
 }
 
 impl RouterContextInner {
@@ -112,6 +212,7 @@ impl RouterContext {
     pub(crate) fn new<R: Routable + 'static>(cfg: RouterConfig<R>) -> Self {
         let subscribers = Arc::new(Mutex::new(HashSet::new()));
         let mapping = consume_child_route_mapping();
+        let config_guards = cfg.guards;
 
         let myself = RouterContextInner {
             unresolved_error: None,
@@ -138,7 +239,50 @@ impl RouterContext {
 
             internal_route: |route| R::from_str(route).is_ok(),
 
+            prefetch_of: |route| {
+                R::from_str(route)
+                    .ok()
+                    .map(|route| (route.loaders(), route.preload()))
+            },
+            prefetch_cache: PrefetchCache::new(cfg.prefetch_cache_size),
+
+            outlet_of: |route| R::from_str(route).ok().and_then(|route| route.outlet()),
+            named_outlets: Vec::new(),
+            first_render: true,
+
+            manual_scroll_of: |route| R::from_str(route).is_ok_and(|route| route.manual_scroll()),
+            pending_scroll: None,
+            scroll_key: None,
+            next_scroll_key: EntryState::current().scroll_key.map_or(0, |key| key + 1),
+            scroll_task: None,
+
             site_map: R::SITE_MAP,
+
+            locales: R::LOCALES,
+            localized_paths: R::LOCALIZED_PATHS,
+
+            guards: Rc::new(move |from, to| {
+                let Ok(to) = R::from_str(to) else {
+                    return Vec::new();
+                };
+                let mut guards = Vec::new();
+                for guard in &config_guards {
+                    let guard = guard.clone();
+                    let from = from.to_string();
+                    guards.push(RouteGuard::new(to.clone(), move |to| guard(from, to)));
+                }
+                guards.extend(to.guards());
+                guards
+                    .into_iter()
+                    .map(|guard| guard.map(|route| route.to_string()))
+                    .collect()
+            }),
+            navigation_generation: 0,
+
+            blockers: Vec::new(),
+            next_blocker_id: 0,
+
+            scope: current_scope_id().unwrap_or(ScopeId::ROOT),
         };
 
         let history = history();
@@ -156,9 +300,15 @@ impl RouterContext {
 
         // If the current route is different from the one in the browser, replace the current route
         let current_route: R = myself.current();
+        let current_path = match localization::locale_prefix(&history.current_route(), R::LOCALES) {
+            Some(locale) => current_route.localized(locale),
+            None => current_route.to_string(),
+        };
 
-        if current_route.to_string() != history.current_route() {
+        if current_path != history.current_route() {
             myself.replace(current_route);
+            // The server normalizes the route the same way, so this is still the render that is hydrated
+            myself.inner.write_unchecked().first_render = true;
         }
 
         myself
@@ -186,86 +336,450 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
-        history().go_back();
-        self.change_route();
+        self.navigate(Navigation::Back);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
-        history().go_forward();
-        self.change_route();
+        self.navigate(Navigation::Forward);
     }
 
     pub(crate) fn push_any(&self, target: NavigationTarget) -> Option<ExternalNavigationFailure> {
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => history().push(p),
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
-
-        self.change_route()
+        self.navigate(Navigation::Push(target, None))
     }
 
     /// Push a new location.
     ///
-    /// The previous location will be available to go back to.
+    /// The previous location will be available to go back to. If the new location has guards, the navigation is
+    /// committed once every guard allowed it.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.push(p)
-                }
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
+        self.navigate(Navigation::Push(target.into(), None))
+    }
 
-        self.change_route()
+    /// Push a new location and attach `state` to the new entry of the history.
+    ///
+    /// The state can be read with [`RouterContext::current_state`] while the entry is active, for example after the
+    /// user navigated back to it. The state is dropped if the navigation is redirected by a guard.
+    pub fn push_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: &impl Serialize,
+    ) -> Option<ExternalNavigationFailure> {
+        self.navigate(Navigation::Push(target.into(), serialize_state(state)))
     }
 
     /// Replace the current location.
     ///
-    /// The previous location will **not** be available to go back to.
+    /// The previous location will **not** be available to go back to. If the new location has guards, the navigation
+    /// is committed once every guard allowed it.
     pub fn replace(
         &self,
         target: impl Into<NavigationTarget>,
     ) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut state = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.replace(p)
+        self.navigate(Navigation::Replace(target.into(), None))
+    }
+
+    /// Replace the current location and attach `state` to the entry of the history.
+    ///
+    /// This can also be used to update the state of the current entry by replacing it with the current route.
+    pub fn replace_with_state(
+        &self,
+        target: impl Into<NavigationTarget>,
+        state: &impl Serialize,
+    ) -> Option<ExternalNavigationFailure> {
+        self.navigate(Navigation::Replace(target.into(), serialize_state(state)))
+    }
+
+    /// Get the state attached to the current entry of the history with [`RouterContext::push_with_state`] or
+    /// [`RouterContext::replace_with_state`]. This method is reactive.
+    ///
+    /// Returns [`None`] if the entry has no state or the state can't be deserialized into `T`.
+    pub fn current_state<T: DeserializeOwned>(&self) -> Option<T> {
+        let inner = self.inner.read();
+        inner.subscribe_to_current_context();
+        let state = EntryState::current().state?;
+        serde_json::from_value(state)
+            .inspect_err(|err| tracing::warn!("Failed to deserialize the history state: {err}"))
+            .ok()
+    }
+
+    /// The route that renders in the default outlets while the current route renders in its named
+    /// [`Outlet`](crate::components::Outlet). This method is reactive.
+    ///
+    /// Returns [`None`] if the current route renders in the default outlet, for example because it doesn't have a
+    /// named outlet or it was opened directly. The first render of the router always returns [`None`] so it
+    /// matches the page rendered on the server.
+    pub fn background<R: Routable>(&self) -> Option<R> {
+        let inner = self.inner.read();
+        inner.subscribe_to_current_context();
+        if inner.first_render {
+            return None;
+        }
+        drop(inner);
+        let background = EntryState::current().background?;
+        self.parse_route(&background).ok()
+    }
+
+    fn navigate(&self, navigation: Navigation) -> Option<ExternalNavigationFailure> {
+        if self.block(Some(PendingNavigation::Navigate(navigation.clone()))) {
+            return None;
+        }
+        self.navigate_unblocked(navigation)
+    }
+
+    /// Run the guards of a navigation without checking the navigation blockers
+    pub(crate) fn navigate_unblocked(
+        &self,
+        navigation: Navigation,
+    ) -> Option<ExternalNavigationFailure> {
+        self.guard(navigation, 0)
+    }
+
+    fn guard(&self, navigation: Navigation, redirects: usize) -> Option<ExternalNavigationFailure> {
+        let navigation = match navigation {
+            Navigation::Push(target, state) => Navigation::Push(self.localize(target), state),
+            Navigation::Replace(target, state) => Navigation::Replace(self.localize(target), state),
+            navigation => navigation,
+        };
+        // Moving through the history runs the guards of the entry it moves to if the history knows its route
+        let to = match &navigation {
+            Navigation::Push(NavigationTarget::Internal(to), _)
+            | Navigation::Replace(NavigationTarget::Internal(to), _) => Some(to.clone()),
+            Navigation::Back => history().back_route(),
+            Navigation::Forward => history().forward_route(),
+            _ => None,
+        };
+        let guards = match to {
+            Some(to) => {
+                let guards = self.inner.peek().guards.clone();
+                guards(&history().current_route(), &to)
+            }
+            None => Vec::new(),
+        };
+
+        let generation = {
+            let mut inner = self.inner.write_unchecked();
+            inner.navigation_generation += 1;
+            inner.navigation_generation
+        };
+
+        // Commit navigation without guards right away
+        if guards.is_empty() {
+            return self.commit(navigation);
+        }
+
+        let myself = *self;
+        let scope = self.inner.peek().scope;
+        scope.spawn(async move {
+            for guard in guards {
+                let decision = guard.run().await;
+                // Another navigation started while the guard was running
+                if myself.inner.peek().navigation_generation != generation {
+                    return;
                 }
-                NavigationTarget::External(e) => return state.external(e),
+                match decision {
+                    NavigationDecision::Allow => {}
+                    NavigationDecision::Cancel => return,
+                    NavigationDecision::Redirect(target) => {
+                        if redirects >= MAX_GUARD_REDIRECTS {
+                            tracing::error!("Navigation guards redirected more than {MAX_GUARD_REDIRECTS} times. Cancelling the navigation to {target:?}");
+                            return;
+                        }
+                        let navigation = match navigation {
+                            Navigation::Replace(..) => Navigation::Replace(target, None),
+                            _ => Navigation::Push(target, None),
+                        };
+                        myself.guard(navigation, redirects + 1);
+                        return;
+                    }
+                }
+            }
+            myself.commit(navigation);
+        });
+
+        None
+    }
+
+    fn commit(&self, navigation: Navigation) -> Option<ExternalNavigationFailure> {
+        // Navigating replaces the page that was hydrated
+        self.inner.write_unchecked().first_render = false;
+        let history = history();
+        match navigation {
+            Navigation::Push(NavigationTarget::Internal(p), state) => {
+                self.inner.write_unchecked().pending_scroll = Some(ScrollAction::Push);
+                history.push_with_state(p.clone(), self.entry_state(&p, state));
+            }
+            Navigation::Replace(NavigationTarget::Internal(p), state) => {
+                self.inner.write_unchecked().pending_scroll = Some(ScrollAction::Replace);
+                history.replace_with_state(p.clone(), self.entry_state(&p, state));
             }
+            Navigation::Push(NavigationTarget::External(e), _)
+            | Navigation::Replace(NavigationTarget::External(e), _) => {
+                return self.inner.write_unchecked().external(e)
+            }
+            Navigation::Back => history.go_back(),
+            Navigation::Forward => history.go_forward(),
         }
 
         self.change_route()
     }
 
-    /// The route that is currently active.
-    pub fn current<R: Routable>(&self) -> R {
-        let absolute_route = self.full_route_string();
-        // If this is a child route, map the absolute route to the child route before parsing
-        let mapping = consume_child_route_mapping::<R>();
-        let route = match mapping.as_ref() {
-            Some(mapping) => mapping
-                .parse_route_from_root_route(&absolute_route)
-                .ok_or_else(|| "Failed to parse route".to_string()),
+    /// Create the serialized state of a new entry for the route `to`.
+    fn entry_state(&self, to: &str, state: Option<serde_json::Value>) -> String {
+        let background = self.background_of(to);
+        let scroll_key = Some(self.new_scroll_key());
+        serde_json::to_string(&EntryState {
+            state,
+            background,
+            scroll_key,
+            scroll: None,
+        })
+        .unwrap_or_default()
+    }
+
+    fn new_scroll_key(&self) -> u64 {
+        let mut inner = self.inner.write_unchecked();
+        let key = inner.next_scroll_key;
+        inner.next_scroll_key += 1;
+        key
+    }
+
+    /// Render the background route of the current entry after the first render was hydrated.
+    pub(crate) fn finish_first_render(&self) {
+        let mut inner = self.inner.write_unchecked();
+        if std::mem::take(&mut inner.first_render) && EntryState::current().background.is_some() {
+            inner.update_subscribers();
+        }
+    }
+
+    /// Scroll the page after the current route rendered. See [`scroll`](crate::scroll).
+    pub(crate) fn scroll(&self) {
+        let history = history();
+        if !history.scroll_restoration() {
+            return;
+        }
+        let route = self.full_route_string();
+        let mut entry = EntryState::current();
+        // Entries that were not created by the router don't have a scroll key yet
+        let key = match entry.scroll_key {
+            Some(key) => key,
             None => {
-                R::from_str(&absolute_route).map_err(|err| format!("Failed to parse route {err}"))
+                let key = self.new_scroll_key();
+                entry.scroll_key = Some(key);
+                if let Ok(state) = serde_json::to_string(&entry) {
+                    history.replace_with_state(route.clone(), state);
+                }
+                key
+            }
+        };
+
+        let mut inner = self.inner.write_unchecked();
+        if inner.scroll_key.replace(key) == Some(key) {
+            return;
+        }
+        let action = inner.pending_scroll.take();
+        // Routes that render in a named outlet keep the scroll position of the background route
+        let action = match entry.background.is_some() || (inner.manual_scroll_of)(&route) {
+            true => ScrollAction::Manual,
+            false => action.unwrap_or(ScrollAction::Restore),
+        };
+        let scroll = Scroll::new(&route, action, entry.scroll);
+
+        let mut eval = dioxus_lib::document::eval(SCROLL_SCRIPT);
+        if eval.send(scroll).is_err() {
+            return;
+        }
+        let myself = *self;
+        let task = inner.scope.push_future(async move {
+            while let Ok(position) = eval.recv::<ScrollPosition>().await {
+                myself.remember_scroll(key, position);
+            }
+        });
+        if let Some(task) = std::mem::replace(&mut inner.scroll_task, task) {
+            task.cancel();
+        }
+    }
+
+    /// Store the scroll position of the entry with the scroll key `key` if it is still the current entry
+    fn remember_scroll(&self, key: u64, position: ScrollPosition) {
+        let history = history();
+        let mut entry = EntryState::current();
+        if entry.scroll_key != Some(key) {
+            return;
+        }
+        entry.scroll = Some(position);
+        if let Ok(state) = serde_json::to_string(&entry) {
+            history.replace_with_state(history.current_route(), state);
+        }
+    }
+
+    /// Get the route that should keep rendering in the default outlets when navigating to `to`.
+    fn background_of(&self, to: &str) -> Option<String> {
+        let inner = self.inner.peek();
+        let outlet = (inner.outlet_of)(to)?;
+        // Routes render in the default outlet if their named outlet isn't rendered
+        if !inner.named_outlets.iter().any(|name| name == outlet) {
+            return None;
+        }
+
+        // Navigating between routes of named outlets keeps the current background
+        if let Some(background) = EntryState::current().background {
+            return Some(background);
+        }
+
+        // A route of the same outlet that was opened directly renders in the default outlet, so it can't be the background
+        let current = history().current_route();
+        ((inner.outlet_of)(&current) != Some(outlet)).then_some(current)
+    }
+
+    /// Start loading the data and code of a route before it is navigated to. The data is kept in a bounded cache
+    /// until the route is navigated to. See [`Prefetch`](crate::prefetch::Prefetch).
+    ///
+    /// Prefetching the current route or a route that is already prefetched does nothing.
+    pub fn prefetch(&self, target: impl Into<NavigationTarget>) {
+        let NavigationTarget::Internal(route) = target.into() else {
+            return;
+        };
+        if route == history().current_route() {
+            return;
+        }
+
+        let mut inner = self.inner.write_unchecked();
+        if inner.prefetch_cache.contains(&route) {
+            return;
+        }
+        let Some((loaders, preload)) = (inner.prefetch_of)(&route) else {
+            return;
+        };
+        let scope = inner.scope;
+        scope.spawn(preload);
+        inner.prefetch_cache.insert(route, loaders, scope);
+    }
+
+    /// Take the loaders of a prefetched route out of the prefetch cache
+    pub(crate) fn take_prefetched(&self, route: &str) -> Option<Vec<PrefetchedLoader>> {
+        self.inner.write_unchecked().prefetch_cache.take(route)
+    }
+
+    /// Get the loaders of a prefetched route without taking them out of the prefetch cache
+    #[cfg(feature = "streaming")]
+    pub(crate) fn prefetched_loaders(&self, route: &str) -> Option<Vec<PrefetchedLoader>> {
+        self.inner.peek().prefetch_cache.loaders(route)
+    }
+
+    /// Take the results of the server futures that were recorded while a route was prefetched
+    #[cfg(feature = "streaming")]
+    pub(crate) fn take_prefetched_server_futures(
+        &self,
+        route: &str,
+    ) -> Option<dioxus_fullstack_hooks::PrefetchedServerFutures> {
+        self.inner
+            .write_unchecked()
+            .prefetch_cache
+            .take_server_futures(route)
+    }
+
+    /// The prefetched routes that render in the background to record the results of their server futures
+    #[cfg(feature = "streaming")]
+    pub(crate) fn prefetched_routes(
+        &self,
+    ) -> Vec<(String, dioxus_fullstack_hooks::PrefetchedServerFutures)> {
+        self.inner.peek().prefetch_cache.rendering()
+    }
+
+    /// Register a named outlet that is rendered
+    pub(crate) fn add_named_outlet(&self, name: String) {
+        self.inner.write_unchecked().named_outlets.push(name);
+    }
+
+    pub(crate) fn remove_named_outlet(&self, name: &str) {
+        let mut inner = self.inner.write_unchecked();
+        if let Some(index) = inner.named_outlets.iter().position(|outlet| outlet == name) {
+            inner.named_outlets.swap_remove(index);
+        }
+    }
+
+    /// Register a function that blocks navigation while it returns `true`. Blocked navigation is stored in `pending`.
+    pub(crate) fn add_blocker(
+        &self,
+        should_block: Rc<dyn Fn() -> bool>,
+        pending: Signal<Option<PendingNavigation>>,
+    ) -> usize {
+        let mut inner = self.inner.write_unchecked();
+        let id = inner.next_blocker_id;
+        inner.next_blocker_id += 1;
+        inner.blockers.push(Rc::new(BlockerEntry {
+            id,
+            should_block,
+            pending,
+        }));
+
+        // Block navigation outside of the router, like the back button of the browser, while any blocker is registered
+        if inner.blockers.len() == 1 {
+            let myself = *self;
+            history().set_navigation_blocker(Some(Rc::new(move |retry| {
+                myself.block(retry.map(PendingNavigation::Retry))
+            })));
+        }
+        id
+    }
+
+    pub(crate) fn remove_blocker(&self, id: usize) {
+        let mut inner = self.inner.write_unchecked();
+        inner.blockers.retain(|blocker| blocker.id != id);
+        if inner.blockers.is_empty() {
+            history().set_navigation_blocker(None);
+        }
+    }
+
+    /// Check if any blocker blocks navigation. The navigation is stored in the first blocker that blocks it.
+    fn block(&self, navigation: Option<PendingNavigation>) -> bool {
+        let blockers = self.inner.peek().blockers.clone();
+        match blockers.iter().find(|blocker| (blocker.should_block)()) {
+            Some(blocker) => {
+                if let Some(navigation) = navigation {
+                    let mut pending = blocker.pending;
+                    pending.set(Some(navigation));
+                }
+                true
             }
+            None => false,
+        }
+    }
+
+    /// The locale of the current route. This method is reactive.
+    ///
+    /// Returns [`None`] if the [`Routable`] enum has no locales. See [`localization`](crate::localization).
+    pub fn locale(&self) -> Option<&'static str> {
+        let locales = self.inner.read().locales;
+        localization::locale_of(&self.full_route_string(), locales)
+    }
+
+    /// Format an internal target in the locale of the current route, if the current route has a locale prefix and
+    /// the target doesn't.
+    pub(crate) fn localize(&self, target: NavigationTarget) -> NavigationTarget {
+        let NavigationTarget::Internal(route) = target else {
+            return target;
+        };
+        let (locales, paths) = {
+            let inner = self.inner.peek();
+            (inner.locales, inner.localized_paths)
         };
+        let current = history().current_route();
+        match localization::locale_prefix(&current, locales) {
+            Some(locale) if localization::locale_prefix(&route, locales).is_none() => {
+                NavigationTarget::Internal(localization::localize(&route, locale, locales, paths))
+            }
+            _ => NavigationTarget::Internal(route),
+        }
+    }
 
-        match route {
+    /// The route that is currently active.
+    pub fn current<R: Routable>(&self) -> R {
+        let absolute_route = self.full_route_string();
+        match self.parse_route(&absolute_route) {
             Ok(route) => route,
             Err(err) => {
                 throw_error(ParseRouteError { message: err });
@@ -274,6 +788,19 @@ impl RouterContext {
         }
     }
 
+    fn parse_route<R: Routable>(&self, absolute_route: &str) -> Result<R, String> {
+        // If this is a child route, map the absolute route to the child route before parsing
+        let mapping = consume_child_route_mapping::<R>();
+        match mapping.as_ref() {
+            Some(mapping) => mapping
+                .parse_route_from_root_route(absolute_route)
+                .ok_or_else(|| "Failed to parse route".to_string()),
+            None => {
+                R::from_str(absolute_route).map_err(|err| format!("Failed to parse route {err}"))
+            }
+        }
+    }
+
     /// The full route that is currently active. If this is called from inside a child router, this will always return the parent's view of the route.
     pub fn full_route_string(&self) -> String {
         let inner = self.inner.read();
@@ -338,6 +865,13 @@ impl RouterContext {
     }
 }
 
+/// Serialize the state of a history entry. Returns `None` and logs an error if the state can't be serialized.
+fn serialize_state(state: &impl Serialize) -> Option<serde_json::Value> {
+    serde_json::to_value(state)
+        .inspect_err(|err| tracing::error!("Failed to serialize the history state: {err}"))
+        .ok()
+}
+
 /// This context is set to the RouterConfig on_update method
 pub struct GenericRouterContext<R> {
     inner: RouterContext,
@@ -0,0 +1,112 @@
+//! Navigation guards that run before the router commits a navigation.
+
+use std::{future::Future, pin::Pin};
+
+use crate::navigation::NavigationTarget;
+
+type GuardFuture<R> = Pin<Box<dyn Future<Output = NavigationDecision<R>>>>;
+
+/// What the router should do with a navigation after a guard ran.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NavigationDecision<R> {
+    /// Continue with the navigation.
+    Allow,
+    /// Stay on the current route.
+    Cancel,
+    /// Navigate to another target instead. The guards of the new target run before it is committed.
+    Redirect(NavigationTarget<R>),
+}
+
+impl<R> NavigationDecision<R> {
+    /// Map the route of a redirect to another route type.
+    pub fn map<P>(self, f: impl FnOnce(R) -> P) -> NavigationDecision<P> {
+        match self {
+            NavigationDecision::Allow => NavigationDecision::Allow,
+            NavigationDecision::Cancel => NavigationDecision::Cancel,
+            NavigationDecision::Redirect(NavigationTarget::Internal(route)) => {
+                NavigationDecision::Redirect(NavigationTarget::Internal(f(route)))
+            }
+            NavigationDecision::Redirect(NavigationTarget::External(url)) => {
+                NavigationDecision::Redirect(NavigationTarget::External(url))
+            }
+        }
+    }
+}
+
+/// A guard of a route or layout that has not been started yet. This is created by the
+/// [`Routable`](dioxus_router_macro::Routable) macro for every `#[guard(..)]` attribute in the layout chain of a route.
+///
+/// Guards take the route that is being navigated to and run before [`Navigator::push`](crate::prelude::Navigator::push)
+/// and [`Navigator::replace`](crate::prelude::Navigator::replace) commit. Going back and forward with the
+/// [`Navigator`](crate::prelude::Navigator) runs the guards of the entry it moves to if the history knows its route. The guards of the layouts run first, followed
+/// by the guard of the route. The first guard that doesn't allow the navigation decides what happens:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_router::guard::NavigationDecision;
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/login")]
+///     Login {},
+///     #[layout(AdminFrame, guard = require_admin)]
+///         #[route("/admin/:page")]
+///         #[guard(valid_page)]
+///         Admin { page: usize },
+/// }
+///
+/// # async fn is_admin() -> bool { false }
+/// async fn require_admin(_: Route) -> NavigationDecision<Route> {
+///     match is_admin().await {
+///         true => NavigationDecision::Allow,
+///         false => NavigationDecision::Redirect(Route::Login {}.into()),
+///     }
+/// }
+///
+/// async fn valid_page(route: Route) -> NavigationDecision<Route> {
+///     match route {
+///         Route::Admin { page } if page > 100 => NavigationDecision::Cancel,
+///         _ => NavigationDecision::Allow,
+///     }
+/// }
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Login() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn AdminFrame() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin(page: usize) -> Element { VNode::empty() }
+/// ```
+pub struct RouteGuard<R> {
+    start: Box<dyn FnOnce() -> GuardFuture<R>>,
+}
+
+impl<R: 'static> RouteGuard<R> {
+    /// Create a guard that calls `guard` with the route when it is started.
+    pub fn new<F>(route: R, guard: impl FnOnce(R) -> F + 'static) -> Self
+    where
+        F: Future<Output = NavigationDecision<R>> + 'static,
+    {
+        Self {
+            start: Box::new(move || Box::pin(guard(route))),
+        }
+    }
+
+    /// Map the redirects of the guard to another route type. This is used to run the guards of child routes.
+    pub fn map<P: 'static>(self, f: impl FnOnce(R) -> P + 'static) -> RouteGuard<P> {
+        RouteGuard {
+            start: Box::new(move || {
+                let future = (self.start)();
+                Box::pin(async move { future.await.map(f) })
+            }),
+        }
+    }
+
+    /// Run the guard
+    pub(crate) async fn run(self) -> NavigationDecision<R> {
+        (self.start)().await
+    }
+}
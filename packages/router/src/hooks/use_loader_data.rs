@@ -0,0 +1,45 @@
+use dioxus_lib::prelude::*;
+
+use crate::contexts::LoaderContext;
+
+/// A hook that reads the data of a loader in the layout chain of the current route.
+///
+/// The [`Router`](crate::components::Router) starts the loaders of every layout and the route in parallel when
+/// navigation begins. This hook suspends until the loader that returns `T` finishes. If several loaders in the layout
+/// chain return `T`, the data of the loader closest to the route is returned. See
+/// [`RouteLoader`](crate::loader::RouteLoader) for how loaders are declared.
+///
+/// # Errors
+/// - Returns a suspended error while the loader is running.
+/// - Returns an error if no loader of the current route returns `T`.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::components::Router) component.
+#[must_use = "Consider using `?` to suspend the component until the data is loaded"]
+pub fn use_loader_data<T: Clone + 'static>() -> Result<T, RenderError> {
+    let loaders = use_hook(|| {
+        try_consume_context::<LoaderContext>()
+            .expect("`use_loader_data` must be called in a descendant of a Router component")
+    });
+
+    match loaders.get::<T>() {
+        Some(Ok(data)) => Ok(data),
+        Some(Err(task)) => Err(suspend(task).unwrap_err()),
+        None => Err(MissingLoaderError(std::any::type_name::<T>()).into()),
+    }
+}
+
+#[derive(Debug)]
+struct MissingLoaderError(&'static str);
+
+impl std::fmt::Display for MissingLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The current route has no loader that returns `{}`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for MissingLoaderError {}
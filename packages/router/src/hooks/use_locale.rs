@@ -0,0 +1,52 @@
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that provides the locale of the current route. This hook is reactive.
+///
+/// The locale is taken from the locale prefix of the current path. Paths without a prefix are in the default locale.
+/// Returns [`None`] if the [`Routable`](crate::routable::Routable) enum has no `#[locales(..)]`. See
+/// [`localization`](crate::localization).
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::components::Router) component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_history::{History, MemoryHistory};
+/// # use dioxus_router::components::HistoryProvider;
+/// # use std::rc::Rc;
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// #[locales("en", "de")]
+/// enum Route {
+///     #[route("/products/:id")]
+///     #[localized("de", "/produkte/:id")]
+///     Product { id: usize },
+/// }
+///
+/// #[component]
+/// fn Product(id: usize) -> Element {
+///     let locale = use_locale().unwrap_or("en");
+///     rsx! { "{locale}" }
+/// }
+/// #
+/// # #[component]
+/// # fn App() -> Element {
+/// #     rsx! {
+/// #         HistoryProvider {
+/// #             history: move |_| Rc::new(MemoryHistory::with_initial_path("/de/produkte/1")) as Rc<dyn History>,
+/// #             Router::<Route> {}
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # vdom.rebuild_in_place();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "de");
+/// ```
+#[must_use]
+pub fn use_locale() -> Option<&'static str> {
+    match use_router_internal() {
+        Some(router) => router.locale(),
+        None => panic!("`use_locale` must be called in a descendant of a Router component"),
+    }
+}
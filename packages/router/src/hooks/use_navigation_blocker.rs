@@ -0,0 +1,95 @@
+use std::rc::Rc;
+
+use dioxus_lib::prelude::*;
+
+use crate::{
+    contexts::Navigation, prelude::RouterContext, utils::use_router_internal::use_router_internal,
+};
+
+/// A navigation that was stopped by a [`NavigationBlocker`].
+#[derive(Clone)]
+pub(crate) enum PendingNavigation {
+    /// A navigation started by the router
+    Navigate(Navigation),
+    /// A navigation outside of the router, like the back button of the browser, that can be retried
+    Retry(Rc<dyn Fn()>),
+}
+
+/// A handle to a navigation blocker created with [`use_navigation_blocker`].
+#[derive(Clone, Copy)]
+pub struct NavigationBlocker {
+    router: RouterContext,
+    pending: Signal<Option<PendingNavigation>>,
+}
+
+impl NavigationBlocker {
+    /// Check if a navigation is waiting for [`Self::proceed`] or [`Self::reset`]. This method is reactive.
+    pub fn is_blocked(&self) -> bool {
+        self.pending.read().is_some()
+    }
+
+    /// Continue with the blocked navigation. The guards of the navigation still run.
+    pub fn proceed(&self) {
+        let mut pending = self.pending;
+        match pending.take() {
+            Some(PendingNavigation::Navigate(navigation)) => {
+                self.router.navigate_unblocked(navigation);
+            }
+            Some(PendingNavigation::Retry(retry)) => retry(),
+            None => {}
+        }
+    }
+
+    /// Stay on the current route and forget the blocked navigation.
+    pub fn reset(&self) {
+        let mut pending = self.pending;
+        pending.set(None);
+    }
+}
+
+/// A hook that blocks navigation away from the current route while `should_block` returns `true`, for example while a
+/// form has unsaved changes.
+///
+/// Blocked navigation through the router, the back and forward buttons of the browser and links is stored until
+/// [`NavigationBlocker::proceed`] or [`NavigationBlocker::reset`] is called, so you can ask the user what to do.
+/// While navigation is blocked on the web, the browser also asks the user before the page is closed or reloaded.
+///
+/// `should_block` is only captured once, so read any state it depends on from signals.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::components::Router) component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[component]
+/// fn EditProfile() -> Element {
+///     let mut name = use_signal(String::new);
+///     let mut dirty = use_signal(|| false);
+///     let blocker = use_navigation_blocker(move || dirty());
+///
+///     rsx! {
+///         input {
+///             value: "{name}",
+///             oninput: move |event| {
+///                 name.set(event.value());
+///                 dirty.set(true);
+///             },
+///         }
+///         if blocker.is_blocked() {
+///             p { "You have unsaved changes. Leave anyway?" }
+///             button { onclick: move |_| blocker.proceed(), "Leave" }
+///             button { onclick: move |_| blocker.reset(), "Stay" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_navigation_blocker(should_block: impl Fn() -> bool + 'static) -> NavigationBlocker {
+    let router = use_router_internal()
+        .expect("`use_navigation_blocker` must be called in a descendant of a Router component");
+    let pending = use_signal(|| None);
+    let id = use_hook(|| router.add_blocker(Rc::new(should_block), pending));
+    use_drop(move || router.remove_blocker(id));
+
+    NavigationBlocker { router, pending }
+}
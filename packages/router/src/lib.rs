@@ -5,8 +5,14 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod guard;
+pub mod loader;
+pub mod localization;
 pub mod navigation;
+pub mod prefetch;
 pub mod routable;
+pub mod scroll;
+pub mod sitemap;
 
 /// Components interacting with the router.
 pub mod components {
@@ -33,6 +39,8 @@ pub mod components {
 }
 
 mod contexts {
+    pub(crate) mod loader;
+    pub(crate) use loader::LoaderContext;
     pub(crate) mod navigator;
     pub(crate) mod outlet;
     pub use outlet::{use_outlet_context, OutletContext};
@@ -54,6 +62,15 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_loader_data;
+    pub use use_loader_data::*;
+
+    mod use_navigation_blocker;
+    pub use use_navigation_blocker::*;
+
+    mod use_locale;
+    pub use use_locale::*;
 }
 
 pub use hooks::router;
@@ -61,14 +78,16 @@ pub use hooks::router;
 /// A collection of useful items most applications might need.
 pub mod prelude {
     pub use crate::components::{
-        GoBackButton, GoForwardButton, HistoryButtonProps, Link, LinkProps, Outlet, Router,
-        RouterProps,
+        GoBackButton, GoForwardButton, HistoryButtonProps, Link, LinkProps, Outlet, OutletProps,
+        Router, RouterProps,
     };
     pub use crate::contexts::*;
     pub use crate::hooks::*;
     pub use crate::navigation::*;
+    pub use crate::prefetch::Prefetch;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
+    pub use crate::sitemap::Sitemap;
     pub use dioxus_router_macro::Routable;
 
     #[doc(hidden)]
@@ -0,0 +1,91 @@
+//! Data loaders that run for a route before it renders.
+
+use std::{any::Any, any::TypeId, future::Future, pin::Pin, rc::Rc};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub(crate) type LoaderFuture = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// A loader of a route or layout that has not been started yet. This is created by the
+/// [`Routable`](dioxus_router_macro::Routable) macro for every `#[loader(..)]` attribute in the layout chain of a route.
+///
+/// Loaders take the route they load data for and return data that can be read with
+/// [`use_loader_data`](crate::hooks::use_loader_data):
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[nest("/user/:user_id")]
+///         // Layout loaders run in parallel with the loader of the route
+///         #[layout(UserFrame, loader = load_user)]
+///             #[route("/posts/:post_id")]
+///             #[loader(load_post)]
+///             Post { user_id: usize, post_id: usize },
+/// }
+///
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct User { name: String }
+///
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct BlogPost { title: String }
+///
+/// async fn load_user(route: Route) -> User {
+///     let Route::Post { user_id, .. } = route;
+///     User { name: format!("user {user_id}") }
+/// }
+///
+/// async fn load_post(route: Route) -> BlogPost {
+///     let Route::Post { post_id, .. } = route;
+///     BlogPost { title: format!("post {post_id}") }
+/// }
+///
+/// #[component]
+/// fn UserFrame(user_id: usize) -> Element {
+///     let user = use_loader_data::<User>()?;
+///     rsx! {
+///         h1 { "{user.name}" }
+///         Outlet::<Route> {}
+///     }
+/// }
+///
+/// #[component]
+/// fn Post(user_id: usize, post_id: usize) -> Element {
+///     let post = use_loader_data::<BlogPost>()?;
+///     rsx! { h2 { "{post.title}" } }
+/// }
+/// ```
+pub struct RouteLoader {
+    pub(crate) type_id: TypeId,
+    pub(crate) start: Box<dyn FnOnce(bool) -> LoaderFuture>,
+}
+
+impl RouteLoader {
+    /// Create a loader that calls `loader` with the route when it is started.
+    ///
+    /// The data is serialized into the page when the route is rendered on the server, so the loader doesn't run
+    /// again on the client during hydration.
+    pub fn new<R, T, F>(route: R, loader: impl FnOnce(R) -> F + 'static) -> Self
+    where
+        R: 'static,
+        T: Serialize + DeserializeOwned + Clone + 'static,
+        F: Future<Output = T> + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<T>(),
+            start: Box::new(move |hydrate| {
+                #[cfg(feature = "streaming")]
+                let future = match hydrate {
+                    true => dioxus_fullstack_hooks::serialized_future(move || loader(route)),
+                    false => Box::pin(loader(route)),
+                };
+                #[cfg(not(feature = "streaming"))]
+                let future = {
+                    _ = hydrate;
+                    loader(route)
+                };
+                Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> })
+            }),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Localized paths and locale prefixes of routes.
+//!
+//! The locales of a [`Routable`](crate::routable::Routable) enum are set with the `#[locales(..)]` attribute and the
+//! path of a route in a locale with the `#[localized(..)]` attribute:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! #[derive(Routable, Clone, PartialEq, Debug)]
+//! // The first locale is the default locale
+//! #[locales("en", "de")]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     #[route("/products/:id")]
+//!     #[localized("de", "/produkte/:id")]
+//!     Product { id: usize },
+//! }
+//! # #[component]
+//! # fn Home() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Product(id: usize) -> Element { VNode::empty() }
+//!
+//! // Paths start with an optional locale prefix. Paths without a prefix are in the default locale
+//! assert_eq!("/de/produkte/1".parse::<Route>().unwrap(), Route::Product { id: 1 });
+//! assert_eq!("/en/products/1".parse::<Route>().unwrap(), Route::Product { id: 1 });
+//! assert_eq!("/products/1".parse::<Route>().unwrap(), Route::Product { id: 1 });
+//!
+//! // Display formats the route without a locale
+//! assert_eq!(Route::Product { id: 1 }.to_string(), "/products/1");
+//! assert_eq!(Route::Product { id: 1 }.localized("de"), "/de/produkte/1");
+//! // Routes without a localized path keep their path in every locale
+//! assert_eq!(Route::Home {}.localized("de"), "/de");
+//! ```
+//!
+//! While a path with a locale prefix is active, the router keeps navigation in that locale: links and navigation to a
+//! route are formatted in the active locale. The active locale can be read with
+//! [`use_locale`](crate::hooks::use_locale). To switch the locale, navigate to [`Routable::localized`](crate::routable::Routable::localized).
+//!
+//! During server side rendering, the [`Router`](crate::components::Router) links the current page in every locale
+//! with [`hreflang` alternates](https://developers.google.com/search/docs/specialty/international/localized-versions)
+//! in the head of the page.
+
+use std::borrow::Cow;
+
+use crate::routable::Routable;
+
+/// The path of a route in a locale, set with the `#[localized(..)]` attribute of the
+/// [`Routable`](dioxus_router_macro::Routable) macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalizedPath {
+    /// The locale of the path
+    pub locale: &'static str,
+    /// The path template of the route, including the nests it is in. For example `/products/:id`
+    pub route: &'static str,
+    /// The path template of the route in the locale. For example `/produkte/:id`
+    pub path: &'static str,
+}
+
+/// Get the locale in the prefix of a path, if it starts with one of the locales.
+pub fn locale_prefix(path: &str, locales: &[&'static str]) -> Option<&'static str> {
+    let first = path.strip_prefix('/')?.split(['/', '?', '#']).next()?;
+    locales.iter().copied().find(|locale| *locale == first)
+}
+
+/// Get the locale of a path. Paths without a locale prefix are in the default locale, which is the first locale.
+///
+/// Returns [`None`] if there are no locales.
+pub fn locale_of(path: &str, locales: &[&'static str]) -> Option<&'static str> {
+    locale_prefix(path, locales).or_else(|| locales.first().copied())
+}
+
+/// Map a path with an optional locale prefix to the path of the route without a locale. This is called by the
+/// [`Routable`](dioxus_router_macro::Routable) macro before the path is parsed.
+pub fn delocalize<'a>(
+    path: &'a str,
+    locales: &[&'static str],
+    paths: &[LocalizedPath],
+) -> Cow<'a, str> {
+    let Some(locale) = locale_of(path, locales) else {
+        return Cow::Borrowed(path);
+    };
+    let path = match locale_prefix(path, locales) {
+        Some(prefix) => &path[prefix.len() + 1..],
+        None => path,
+    };
+    let (route, suffix) = split_suffix(path);
+
+    let translated = paths
+        .iter()
+        .filter(|localized| localized.locale == locale)
+        .find_map(|localized| {
+            let captures = match_template(localized.path, route)?;
+            Some(fill_template(localized.route, &captures))
+        });
+    match translated {
+        Some(route) => Cow::Owned(route + suffix),
+        None if route.is_empty() => Cow::Owned(format!("/{suffix}")),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// Map the path of a route without a locale to the path in a locale, prefixed with the locale.
+///
+/// Returns the path unchanged if the locale is not one of the locales.
+pub fn localize(
+    path: &str,
+    locale: &str,
+    locales: &[&'static str],
+    paths: &[LocalizedPath],
+) -> String {
+    let Some(locale) = locales.iter().find(|l| **l == locale) else {
+        return path.to_string();
+    };
+    let (route, suffix) = split_suffix(path);
+
+    let translated = paths
+        .iter()
+        .filter(|localized| localized.locale == *locale)
+        .find_map(|localized| {
+            let captures = match_template(localized.route, route)?;
+            Some(fill_template(localized.path, &captures))
+        })
+        .unwrap_or_else(|| route.to_string());
+    match translated.as_str() {
+        "/" | "" => format!("/{locale}{suffix}"),
+        _ => format!("/{locale}{translated}{suffix}"),
+    }
+}
+
+/// The `hreflang` and `href` of the route in every locale, and the route without a locale as the `x-default` alternate.
+pub(crate) fn alternates<R: Routable>(route: &R, prefix: &str) -> Vec<(String, String)> {
+    if R::LOCALES.is_empty() {
+        return Vec::new();
+    }
+    R::LOCALES
+        .iter()
+        .map(|locale| {
+            (
+                locale.to_string(),
+                format!("{prefix}{}", route.localized(locale)),
+            )
+        })
+        .chain(std::iter::once((
+            "x-default".to_string(),
+            format!("{prefix}{route}"),
+        )))
+        .collect()
+}
+
+/// Create the script that replaces the alternate links of the previous route in the head of the page with
+/// `alternates`. Only links with the `hreflang` of one of the alternates are replaced.
+pub(crate) fn alternates_script(alternates: &[(String, String)]) -> String {
+    let alternates = serde_json::to_string(alternates).unwrap_or_else(|_| "[]".to_string());
+    format!(
+        r#"
+        const alternates = {alternates};
+        const hreflangs = new Set(alternates.map(([hreflang]) => hreflang));
+        for (const link of document.head.querySelectorAll('link[rel="alternate"][hreflang]')) {{
+            if (hreflangs.has(link.getAttribute("hreflang"))) {{
+                link.remove();
+            }}
+        }}
+        for (const [hreflang, href] of alternates) {{
+            const link = document.createElement("link");
+            link.rel = "alternate";
+            link.hreflang = hreflang;
+            link.href = href;
+            document.head.appendChild(link);
+        }}
+        "#
+    )
+}
+
+/// Split a path into the route and the query and hash
+fn split_suffix(path: &str) -> (&str, &str) {
+    path.split_at(path.find(['?', '#']).unwrap_or(path.len()))
+}
+
+/// Match a route against a path template and capture the dynamic segments by name
+fn match_template<'a>(template: &'a str, route: &str) -> Option<Vec<(&'a str, String)>> {
+    let mut captures = Vec::new();
+    let mut segments = route.split('/').filter(|segment| !segment.is_empty());
+    for part in template.split('/').filter(|part| !part.is_empty()) {
+        if let Some(name) = part.strip_prefix(":..") {
+            captures.push((name, segments.by_ref().collect::<Vec<_>>().join("/")));
+        } else if let Some(name) = part.strip_prefix(':') {
+            captures.push((name, segments.next()?.to_string()));
+        } else {
+            let segment = segments.next()?;
+            let decoded = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+            if decoded != part {
+                return None;
+            }
+        }
+    }
+    segments.next().is_none().then_some(captures)
+}
+
+/// Fill the dynamic segments of a path template with captured segments
+fn fill_template(template: &str, captures: &[(&str, String)]) -> String {
+    let capture = |name: &str| {
+        captures
+            .iter()
+            .find(|(capture, _)| *capture == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    };
+    let mut path = String::new();
+    for part in template.split('/').filter(|part| !part.is_empty()) {
+        let segment = match part.strip_prefix(":..") {
+            Some(name) => capture(name),
+            None => match part.strip_prefix(':') {
+                Some(name) => capture(name),
+                None => part,
+            },
+        };
+        if !segment.is_empty() {
+            path.push('/');
+            path.push_str(segment);
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    path
+}
@@ -0,0 +1,191 @@
+//! Prefetching the data and code of routes before they are navigated to.
+
+use std::{any::TypeId, collections::VecDeque, future::Future, pin::Pin};
+
+use dioxus_lib::prelude::*;
+use futures_util::future::{FutureExt, Shared};
+
+use crate::loader::{LoaderFuture, RouteLoader};
+
+/// When a [`Link`](crate::components::Link) prefetches the route it links to.
+///
+/// Prefetching starts the loaders of the route (see [`RouteLoader`]) and loads the code of the route if it is split
+/// into a separate chunk with the `wasm-split` feature. The data of the loaders is kept in a bounded cache until the
+/// route is navigated to, so the route can render without waiting for its loaders again. The size of the cache can
+/// be changed with [`RouterConfig::prefetch_cache_size`](crate::prelude::RouterConfig::prefetch_cache_size).
+///
+/// With the `streaming` feature that fullstack apps enable, the prefetched route also renders in the background
+/// without being mounted. The results of its `use_server_future`s are kept with the route and used once it is
+/// navigated to. Components of the background render see the current route in hooks like
+/// [`use_route`](crate::hooks::use_route), but render the prefetched route in their outlets.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/post/:id")]
+///     #[loader(load_post)]
+///     Post { id: usize },
+/// }
+///
+/// # #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// # struct BlogPost { title: String }
+/// # async fn load_post(route: Route) -> BlogPost { BlogPost { title: route.to_string() } }
+/// #[component]
+/// fn Home() -> Element {
+///     rsx! {
+///         // The post starts loading when the link is hovered
+///         Link { to: Route::Post { id: 1 }, prefetch: Prefetch::Hover, "Read the first post" }
+///     }
+/// }
+/// # #[component]
+/// # fn Post(id: usize) -> Element { VNode::empty() }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Prefetch {
+    /// Don't prefetch the route.
+    #[default]
+    None,
+    /// Prefetch the route when the pointer enters the link or the link is focused.
+    Hover,
+    /// Prefetch the route when the link is scrolled into the viewport.
+    Visible,
+    /// Prefetch the route as soon as the link is mounted.
+    Eager,
+}
+
+/// The future of a prefetched loader that can be awaited by the prefetch task and the route once it is navigated to.
+pub(crate) type PrefetchedLoader = (TypeId, Shared<LoaderFuture>);
+
+/// The loaders of a route and a future that loads its code.
+pub(crate) type RoutePrefetch = (Vec<RouteLoader>, Pin<Box<dyn Future<Output = ()>>>);
+
+/// The default number of routes in the prefetch cache.
+pub(crate) const DEFAULT_PREFETCH_CACHE_SIZE: usize = 16;
+
+struct PrefetchedRoute {
+    route: String,
+    loaders: Vec<PrefetchedLoader>,
+    tasks: Vec<Task>,
+    /// The results of the server futures of the route while it renders in the background. This is taken once the
+    /// route is navigated to
+    #[cfg(feature = "streaming")]
+    server_futures: Option<dioxus_fullstack_hooks::PrefetchedServerFutures>,
+}
+
+/// A bounded cache of prefetched routes. The route that was prefetched first is dropped when the cache is full.
+pub(crate) struct PrefetchCache {
+    capacity: usize,
+    routes: VecDeque<PrefetchedRoute>,
+    /// Written when routes are added to or dropped from the cache so the router renders the prefetched routes
+    #[cfg(feature = "streaming")]
+    changed: Signal<()>,
+}
+
+impl PrefetchCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            routes: VecDeque::new(),
+            #[cfg(feature = "streaming")]
+            changed: Signal::new_in_scope((), ScopeId::ROOT),
+        }
+    }
+
+    pub(crate) fn contains(&self, route: &str) -> bool {
+        self.routes
+            .iter()
+            .any(|prefetched| prefetched.route == route)
+    }
+
+    /// Start the loaders of a route in the given scope and keep their data until the route is taken from the cache.
+    pub(crate) fn insert(&mut self, route: String, loaders: Vec<RouteLoader>, scope: ScopeId) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.routes.len() >= self.capacity {
+            if let Some(oldest) = self.routes.pop_front() {
+                oldest.cancel();
+            }
+        }
+
+        let loaders: Vec<PrefetchedLoader> = loaders
+            .into_iter()
+            .map(|loader| (loader.type_id, (loader.start)(false).shared()))
+            .collect();
+        let tasks = loaders
+            .iter()
+            .filter_map(|(_, future)| scope.push_future(future.clone().map(drop)))
+            .collect();
+        self.routes.push_back(PrefetchedRoute {
+            route,
+            loaders,
+            tasks,
+            #[cfg(feature = "streaming")]
+            server_futures: Some(Default::default()),
+        });
+        #[cfg(feature = "streaming")]
+        self.changed.set(());
+    }
+
+    /// Get the loaders of a prefetched route without taking them out of the cache.
+    #[cfg(feature = "streaming")]
+    pub(crate) fn loaders(&self, route: &str) -> Option<Vec<PrefetchedLoader>> {
+        self.routes
+            .iter()
+            .find(|prefetched| prefetched.route == route)
+            .map(|prefetched| prefetched.loaders.clone())
+    }
+
+    /// Take the recorded server futures of a prefetched route. The route stops rendering in the background.
+    #[cfg(feature = "streaming")]
+    pub(crate) fn take_server_futures(
+        &mut self,
+        route: &str,
+    ) -> Option<dioxus_fullstack_hooks::PrefetchedServerFutures> {
+        self.routes
+            .iter_mut()
+            .find(|prefetched| prefetched.route == route)?
+            .server_futures
+            .take()
+    }
+
+    /// The routes that render in the background to record their server futures. This method is reactive.
+    #[cfg(feature = "streaming")]
+    pub(crate) fn rendering(
+        &self,
+    ) -> Vec<(String, dioxus_fullstack_hooks::PrefetchedServerFutures)> {
+        self.changed.read();
+        self.routes
+            .iter()
+            .filter_map(|prefetched| {
+                let server_futures = prefetched.server_futures.clone()?;
+                Some((prefetched.route.clone(), server_futures))
+            })
+            .collect()
+    }
+
+    /// Take the loaders of a prefetched route out of the cache.
+    pub(crate) fn take(&mut self, route: &str) -> Option<Vec<PrefetchedLoader>> {
+        let index = self
+            .routes
+            .iter()
+            .position(|prefetched| prefetched.route == route)?;
+        let prefetched = self.routes.remove(index)?;
+        // The loaders keep running as long as the route awaits them
+        for task in &prefetched.tasks {
+            task.cancel();
+        }
+        Some(prefetched.loaders)
+    }
+}
+
+impl PrefetchedRoute {
+    fn cancel(self) {
+        for task in self.tasks {
+            task.cancel();
+        }
+    }
+}
@@ -3,9 +3,14 @@
 #![allow(non_snake_case)]
 use dioxus_lib::prelude::*;
 
+use crate::{
+    guard::RouteGuard, loader::RouteLoader, localization::LocalizedPath,
+    sitemap::SitemapAnnotations,
+};
+
 use std::iter::FlatMap;
 use std::slice::Iter;
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, future::Future, pin::Pin, str::FromStr};
 
 /// An error that occurs when parsing a route.
 #[derive(Debug, PartialEq)]
@@ -729,6 +734,15 @@ pub trait Routable: FromStr<Err: Display> + Display + Clone + 'static {
     /// The error that can occur when parsing a route.
     const SITE_MAP: &'static [SiteMapSegment];
 
+    /// The locales of the routes, set with the `#[locales(..)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro. The first locale is the default locale. See
+    /// [`localization`](crate::localization).
+    const LOCALES: &'static [&'static str] = &[];
+
+    /// The paths of the routes in each locale, set with the `#[localized(..)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro.
+    const LOCALIZED_PATHS: &'static [LocalizedPath] = &[];
+
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
@@ -837,6 +851,58 @@ pub trait Routable: FromStr<Err: Display> + Display + Clone + 'static {
         Self::from_str(&new_route).ok()
     }
 
+    /// Get the loaders of every layout and the route this route renders, set with the `#[loader(..)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro. See [`RouteLoader`].
+    fn loaders(&self) -> Vec<RouteLoader> {
+        Vec::new()
+    }
+
+    /// Get the guards of every layout and the route this route renders, set with the `#[guard(..)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro. See [`RouteGuard`].
+    fn guards(&self) -> Vec<RouteGuard<Self>> {
+        Vec::new()
+    }
+
+    /// Get the name of the [`Outlet`](crate::components::Outlet) this route renders in, set with the `#[outlet(..)]`
+    /// attribute of the [`Routable`](dioxus_router_macro::Routable) macro. Routes without a named outlet render in the
+    /// default outlet.
+    fn outlet(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Check if the route handles scrolling itself, set with the `#[scroll(manual)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro. Otherwise the router scrolls the page when the route is
+    /// navigated to. See [`scroll`](crate::scroll).
+    fn manual_scroll(&self) -> bool {
+        false
+    }
+
+    /// Format the route in a locale, prefixed with the locale. Routes without a path in the locale keep their path.
+    ///
+    /// Returns the route unchanged if the locale is not one of [`Self::LOCALES`]. See
+    /// [`localization`](crate::localization).
+    fn localized(&self, locale: &str) -> String {
+        crate::localization::localize(
+            &self.to_string(),
+            locale,
+            Self::LOCALES,
+            Self::LOCALIZED_PATHS,
+        )
+    }
+
+    /// Load the code of the route ahead of time. Routes are only split into separate chunks that need to be loaded if
+    /// the `wasm-split` feature is enabled, otherwise this does nothing. This is used to prefetch routes, see
+    /// [`Prefetch`](crate::prefetch::Prefetch).
+    fn preload(&self) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(std::future::ready(()))
+    }
+
+    /// Get the annotations of this route in the sitemap, set with the `#[sitemap(..)]` attribute of the
+    /// [`Routable`](dioxus_router_macro::Routable) macro. See [`Sitemap`](crate::sitemap::Sitemap).
+    fn sitemap_annotations(&self) -> SitemapAnnotations {
+        SitemapAnnotations::default()
+    }
+
     /// Returns a flattened version of [`Self::SITE_MAP`].
     fn flatten_site_map<'a>() -> SiteMapFlattened<'a> {
         Self::SITE_MAP.iter().flat_map(SiteMapSegment::flatten)
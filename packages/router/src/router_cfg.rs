@@ -1,6 +1,9 @@
-use crate::{components::FailureExternalNavigation, prelude::*};
+use crate::{
+    components::FailureExternalNavigation, guard::NavigationDecision,
+    prefetch::DEFAULT_PREFETCH_CACHE_SIZE, prelude::*,
+};
 use dioxus_lib::prelude::*;
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 /// Global configuration options for the router.
 ///
@@ -29,6 +32,8 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) guards: Vec<NavigationGuard<R>>,
+    pub(crate) prefetch_cache_size: usize,
 }
 
 impl<R> Default for RouterConfig<R> {
@@ -36,6 +41,8 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: FailureExternalNavigation,
             on_update: None,
+            guards: Vec::new(),
+            prefetch_cache_size: DEFAULT_PREFETCH_CACHE_SIZE,
         }
     }
 }
@@ -67,6 +74,33 @@ where
         }
     }
 
+    /// Add a guard that runs before every navigation with [`Navigator::push`], [`Navigator::replace`],
+    /// [`Navigator::go_back`] or [`Navigator::go_forward`].
+    ///
+    /// The guard is called with the path of the current route and the route that is being navigated to. The current
+    /// path is not parsed since it may not be a route of `R`, like a page that was not found. Guards added here run
+    /// before the guards of the layouts and route set with the `#[guard(..)]` attribute. See
+    /// [`RouteGuard`](crate::guard::RouteGuard).
+    pub fn guard<F>(mut self, guard: impl Fn(String, R) -> F + 'static) -> Self
+    where
+        F: Future<Output = NavigationDecision<R>> + 'static,
+    {
+        self.guards
+            .push(Arc::new(move |from, to| Box::pin(guard(from, to))));
+        self
+    }
+
+    /// The number of prefetched routes the router keeps until they are navigated to. When the cache is full, the
+    /// route that was prefetched first is dropped. See [`Prefetch`](crate::prefetch::Prefetch).
+    ///
+    /// Defaults to 16.
+    pub fn prefetch_cache_size(self, size: usize) -> Self {
+        Self {
+            prefetch_cache_size: size,
+            ..self
+        }
+    }
+
     /// A component to render when an external navigation fails.
     ///
     /// Defaults to a router-internal component called [`FailureExternalNavigation`]
@@ -0,0 +1,186 @@
+//! Scroll restoration and hash fragment scrolling.
+//!
+//! The router scrolls the page after the route changes. This works the same way on every platform that can evaluate
+//! JavaScript, like the web, liveview and desktop:
+//! - Pushing a route scrolls to the element with the id of the hash fragment of the route, or to the top of the page
+//! - Replacing a route only scrolls to the hash fragment of the route
+//! - Going back or forward restores the scroll position the page had when it was left
+//!
+//! The scroll position of an entry is stored in the state of the entry in the history, so it is restored after the
+//! page is reloaded as well. Histories can opt out of scrolling with
+//! [`History::scroll_restoration`](dioxus_history::History::scroll_restoration), like
+//! `WebHistory::new(prefix, false)` on the web.
+//!
+//! Routes can opt out with the `#[scroll(manual)]` attribute of the [`Routable`](dioxus_router_macro::Routable)
+//! macro to handle scrolling themselves:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! #[derive(Routable, Clone, PartialEq, Debug)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     // The feed keeps its scroll position when it is opened
+//!     #[route("/feed")]
+//!     #[scroll(manual)]
+//!     Feed {},
+//! }
+//! # #[component]
+//! # fn Home() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Feed() -> Element { VNode::empty() }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// How the router scrolls after the route changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ScrollAction {
+    /// A route was pushed: scroll to the hash fragment or the top of the page
+    Push,
+    /// A route was replaced: scroll to the hash fragment
+    Replace,
+    /// The history moved back or forward: restore the scroll position of the entry, or scroll like a push if the
+    /// position is unknown
+    Restore,
+    /// The route handles scrolling itself
+    Manual,
+}
+
+/// A scroll position of the page.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ScrollPosition {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+/// Where [`SCROLL_SCRIPT`] scrolls the page once the route rendered.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct Scroll {
+    /// The position the page is restored to
+    position: Option<ScrollPosition>,
+    /// The id of the element that is scrolled into view if there is no position to restore
+    fragment: Option<String>,
+    /// Scroll to the top of the page if there is no position to restore and no element to scroll to
+    to_top: bool,
+}
+
+impl Scroll {
+    /// Find out how to scroll after the history moved to an entry with the route `route`. `position` is the
+    /// scroll position that was stored in the entry.
+    pub(crate) fn new(route: &str, action: ScrollAction, position: Option<ScrollPosition>) -> Self {
+        let fragment = match action {
+            ScrollAction::Manual => None,
+            _ => route.split_once('#').map(|(_, fragment)| {
+                percent_encoding::percent_decode_str(fragment)
+                    .decode_utf8_lossy()
+                    .into_owned()
+            }),
+        };
+        Self {
+            position: position.filter(|_| action == ScrollAction::Restore),
+            fragment,
+            to_top: matches!(action, ScrollAction::Push | ScrollAction::Restore),
+        }
+    }
+}
+
+/// The script that scrolls the page. It receives a [`Scroll`] and then sends the [`ScrollPosition`] of the page
+/// every time the user stops scrolling, so the router can store it in the current entry.
+pub(crate) const SCROLL_SCRIPT: &str = r#"
+    const scroll = window.__dioxusScroll ??= (() => {
+        const scroll = { report: null, release: null, timeout: null };
+        if ("scrollRestoration" in history) {
+            history.scrollRestoration = "manual";
+        }
+        window.addEventListener("scroll", () => {
+            clearTimeout(scroll.timeout);
+            scroll.timeout = setTimeout(() => scroll.report?.(), 100);
+        }, { passive: true });
+        return scroll;
+    })();
+    // Don't report the scroll position while the new route is rendered and close the script of the previous entry
+    scroll.report = null;
+    scroll.release?.();
+    const { position, fragment, to_top } = await dioxus.recv();
+    requestAnimationFrame(() => {
+        const target = fragment === null ? null : document.getElementById(fragment);
+        if (position) {
+            window.scrollTo(position.x, position.y);
+        } else if (target) {
+            target.scrollIntoView();
+        } else if (to_top) {
+            window.scrollTo(0, 0);
+        }
+        scroll.report = () => dioxus.send({ x: window.scrollX, y: window.scrollY });
+    });
+    // The channel to the router closes when the script returns, so keep it open until the next entry is scrolled
+    await new Promise((resolve) => scroll.release = resolve);
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITION: ScrollPosition = ScrollPosition { x: 0.0, y: 120.0 };
+
+    #[test]
+    fn push_scrolls_to_the_fragment_or_top() {
+        assert_eq!(
+            Scroll::new(
+                "/docs#getting%20started",
+                ScrollAction::Push,
+                Some(POSITION)
+            ),
+            Scroll {
+                position: None,
+                fragment: Some("getting started".to_string()),
+                to_top: true,
+            }
+        );
+        assert_eq!(
+            Scroll::new("/docs", ScrollAction::Push, None),
+            Scroll {
+                position: None,
+                fragment: None,
+                to_top: true,
+            }
+        );
+    }
+
+    #[test]
+    fn replace_only_scrolls_to_the_fragment() {
+        assert_eq!(
+            Scroll::new("/docs#install", ScrollAction::Replace, Some(POSITION)),
+            Scroll {
+                position: None,
+                fragment: Some("install".to_string()),
+                to_top: false,
+            }
+        );
+    }
+
+    #[test]
+    fn restore_uses_the_stored_position() {
+        assert_eq!(
+            Scroll::new("/docs#install", ScrollAction::Restore, Some(POSITION)),
+            Scroll {
+                position: Some(POSITION),
+                fragment: Some("install".to_string()),
+                to_top: true,
+            }
+        );
+    }
+
+    #[test]
+    fn manual_routes_dont_scroll() {
+        assert_eq!(
+            Scroll::new("/feed#latest", ScrollAction::Manual, Some(POSITION)),
+            Scroll {
+                position: None,
+                fragment: None,
+                to_top: false,
+            }
+        );
+    }
+}
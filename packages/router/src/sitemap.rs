@@ -0,0 +1,271 @@
+//! Generate a [sitemap](https://www.sitemaps.org/protocol.html) and `robots.txt` from a [`Routable`] enum.
+
+use std::{collections::HashSet, fmt::Write, sync::Arc};
+
+use crate::routable::Routable;
+
+type RouteGenerator<R> = Arc<dyn Fn() -> Vec<R> + Send + Sync>;
+type LastModified<R> = Arc<dyn Fn(&R) -> Option<String> + Send + Sync>;
+
+/// How often the page of a route is likely to change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFrequency {
+    /// The page changes every time it is accessed
+    Always,
+    /// The page changes every hour
+    Hourly,
+    /// The page changes every day
+    Daily,
+    /// The page changes every week
+    Weekly,
+    /// The page changes every month
+    Monthly,
+    /// The page changes every year
+    Yearly,
+    /// The page is archived and never changes
+    Never,
+}
+
+impl ChangeFrequency {
+    /// The value of the frequency in a sitemap.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        }
+    }
+}
+
+/// The sitemap annotations of a route variant. These are set with the `#[sitemap(..)]` attribute of the
+/// [`Routable`](dioxus_router_macro::Routable) macro:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Drafts() -> Element { VNode::empty() }
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     #[sitemap(changefreq = "daily", priority = 1.0, lastmod = "2024-05-01")]
+///     Home {},
+///     #[route("/drafts")]
+///     #[sitemap(exclude)]
+///     Drafts {},
+/// }
+///
+/// assert!(Route::Drafts {}.sitemap_annotations().exclude);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SitemapAnnotations {
+    /// The date the page was last modified in the W3C datetime format, like `2024-05-01`
+    pub lastmod: Option<&'static str>,
+    /// How often the page is likely to change
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to other pages on the site between 0.0 and 1.0
+    pub priority: Option<f32>,
+    /// Leave the route out of the sitemap. Static routes are also disallowed in [`Sitemap::robots_txt`]
+    pub exclude: bool,
+}
+
+/// A single url in a sitemap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    /// The absolute url of the page
+    pub loc: String,
+    /// The date the page was last modified
+    pub lastmod: Option<String>,
+    /// How often the page is likely to change
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to other pages on the site
+    pub priority: Option<f32>,
+}
+
+/// A builder for the sitemap of a [`Routable`] enum.
+///
+/// Every static route in [`Routable::SITE_MAP`] is included automatically. Routes with dynamic segments need to be
+/// listed with [`Self::dynamic_routes`]. Routes marked with `#[sitemap(exclude)]` are skipped, and static routes marked
+/// with it are disallowed in the `robots.txt`.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_router::sitemap::Sitemap;
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Route {
+///     #[route("/")]
+///     #[sitemap(changefreq = "daily", priority = 1.0)]
+///     Home {},
+///     #[route("/blog/:id")]
+///     #[sitemap(changefreq = "monthly", priority = 0.5)]
+///     Blog { id: usize },
+///     #[route("/login")]
+///     #[sitemap(exclude)]
+///     Login {},
+/// }
+/// # #[component]
+/// # fn Home() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Blog(id: usize) -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Login() -> Element { VNode::empty() }
+///
+/// let sitemap = Sitemap::<Route>::new("https://example.com")
+///     .dynamic_routes(|| (1..=2).map(|id| Route::Blog { id }))
+///     .lastmod(|route| match route {
+///         Route::Blog { id } => Some(format!("2024-05-0{id}")),
+///         _ => None,
+///     });
+///
+/// let xml = sitemap.to_xml();
+/// assert!(xml.contains("<loc>https://example.com/</loc>"));
+/// assert!(xml.contains("<loc>https://example.com/blog/2</loc>\n    <lastmod>2024-05-02</lastmod>"));
+/// assert!(!xml.contains("/login"));
+/// assert_eq!(
+///     sitemap.robots_txt(),
+///     "User-agent: *\nAllow: /\nDisallow: /login\n\nSitemap: https://example.com/sitemap.xml\n"
+/// );
+/// ```
+pub struct Sitemap<R: Routable> {
+    base_url: String,
+    dynamic_routes: Vec<RouteGenerator<R>>,
+    lastmod: Option<LastModified<R>>,
+}
+
+impl<R: Routable> Clone for Sitemap<R> {
+    fn clone(&self) -> Self {
+        Self {
+            base_url: self.base_url.clone(),
+            dynamic_routes: self.dynamic_routes.clone(),
+            lastmod: self.lastmod.clone(),
+        }
+    }
+}
+
+impl<R: Routable> Sitemap<R> {
+    /// Create a sitemap for the site hosted at `base_url`, like `https://example.com`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            dynamic_routes: Vec::new(),
+            lastmod: None,
+        }
+    }
+
+    /// Add a generator for routes with dynamic segments. The generator is called every time the sitemap is built.
+    pub fn dynamic_routes<I: IntoIterator<Item = R>>(
+        mut self,
+        generator: impl Fn() -> I + Send + Sync + 'static,
+    ) -> Self {
+        self.dynamic_routes
+            .push(Arc::new(move || generator().into_iter().collect()));
+        self
+    }
+
+    /// Set a function that returns the last modified date of a route, like the date a blog post was edited. If the
+    /// function returns `None`, the `lastmod` of the `#[sitemap(..)]` attribute is used.
+    pub fn lastmod(
+        mut self,
+        lastmod: impl Fn(&R) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.lastmod = Some(Arc::new(lastmod));
+        self
+    }
+
+    /// Get every url in the sitemap in the order of the site map followed by the dynamic routes.
+    pub fn entries(&self) -> Vec<SitemapEntry> {
+        let routes = R::static_routes()
+            .into_iter()
+            .chain(self.dynamic_routes.iter().flat_map(|generator| generator()));
+
+        let mut seen = HashSet::new();
+        routes
+            .filter_map(|route| {
+                let annotations = route.sitemap_annotations();
+                if annotations.exclude {
+                    return None;
+                }
+                let path = route.to_string();
+                let (path, _) = path.split_once('#').unwrap_or((&path, ""));
+                let loc = format!("{}{}", self.base_url, path);
+                if !seen.insert(loc.clone()) {
+                    return None;
+                }
+                let lastmod = self
+                    .lastmod
+                    .as_ref()
+                    .and_then(|lastmod| lastmod(&route))
+                    .or_else(|| annotations.lastmod.map(ToString::to_string));
+                Some(SitemapEntry {
+                    loc,
+                    lastmod,
+                    changefreq: annotations.changefreq,
+                    priority: annotations.priority,
+                })
+            })
+            .collect()
+    }
+
+    /// Render the sitemap as a `sitemap.xml` document.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in self.entries() {
+            xml.push_str("  <url>\n");
+            _ = writeln!(xml, "    <loc>{}</loc>", escape_xml(&entry.loc));
+            if let Some(lastmod) = &entry.lastmod {
+                _ = writeln!(xml, "    <lastmod>{}</lastmod>", escape_xml(lastmod));
+            }
+            if let Some(changefreq) = entry.changefreq {
+                _ = writeln!(xml, "    <changefreq>{}</changefreq>", changefreq.as_str());
+            }
+            if let Some(priority) = entry.priority {
+                _ = writeln!(xml, "    <priority>{}</priority>", priority);
+            }
+            xml.push_str("  </url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+
+    /// Render a `robots.txt` that allows every crawler and points to the `sitemap.xml` at the root of the site. Static
+    /// routes marked with `#[sitemap(exclude)]` are disallowed.
+    pub fn robots_txt(&self) -> String {
+        let mut robots = String::from("User-agent: *\nAllow: /\n");
+        let mut seen = HashSet::new();
+        for route in R::static_routes() {
+            if !route.sitemap_annotations().exclude {
+                continue;
+            }
+            let path = route.to_string();
+            let (path, _) = path.split_once('#').unwrap_or((&path, ""));
+            if seen.insert(path.to_string()) {
+                _ = writeln!(robots, "Disallow: {path}");
+            }
+        }
+        _ = write!(robots, "\nSitemap: {}/sitemap.xml\n", self.base_url);
+        robots
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
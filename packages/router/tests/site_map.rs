@@ -51,3 +51,84 @@ fn with_class() {
         ],
     );
 }
+
+#[test]
+fn sitemap() {
+    use dioxus_router::sitemap::{ChangeFrequency, Sitemap, SitemapEntry};
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum ChildRoute {
+        #[route("/")]
+        #[sitemap(changefreq = "weekly", priority = 0.5)]
+        ChildRoot {},
+        #[route("/:name")]
+        #[sitemap(lastmod = "2024-01-01")]
+        Post { name: String },
+    }
+
+    #[derive(Routable, Clone, PartialEq, Debug)]
+    enum Route {
+        #[route("/")]
+        Root {},
+        #[route("/private")]
+        #[sitemap(exclude)]
+        Private {},
+        #[child("/blog")]
+        Blog { child: ChildRoute },
+    }
+
+    #[component]
+    fn Root() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Private() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn ChildRoot() -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Post(name: String) -> Element {
+        unimplemented!()
+    }
+
+    let sitemap = Sitemap::<Route>::new("https://example.com/").dynamic_routes(|| {
+        ["a&b", "a&b"].map(|name| Route::Blog {
+            child: ChildRoute::Post {
+                name: name.to_string(),
+            },
+        })
+    });
+
+    assert_eq!(
+        sitemap.entries(),
+        vec![
+            SitemapEntry {
+                loc: "https://example.com/".to_string(),
+                lastmod: None,
+                changefreq: None,
+                priority: None,
+            },
+            SitemapEntry {
+                loc: "https://example.com/blog/".to_string(),
+                lastmod: None,
+                changefreq: Some(ChangeFrequency::Weekly),
+                priority: Some(0.5),
+            },
+            SitemapEntry {
+                loc: "https://example.com/blog/a&b".to_string(),
+                lastmod: Some("2024-01-01".to_string()),
+                changefreq: None,
+                priority: None,
+            },
+        ]
+    );
+    assert!(sitemap
+        .to_xml()
+        .contains("<loc>https://example.com/blog/a&amp;b</loc>"));
+}
@@ -0,0 +1,153 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use dioxus_router::guard::NavigationDecision;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/login")]
+    Login {},
+    #[layout(AdminFrame, guard = require_login)]
+        #[route("/admin")]
+        Admin {},
+    #[end_layout]
+    #[route("/closed")]
+    #[guard(cancel)]
+    Closed {},
+    #[route("/form")]
+    Form {},
+}
+
+async fn require_login(_: Route) -> NavigationDecision<Route> {
+    tokio::task::yield_now().await;
+    NavigationDecision::Redirect(Route::Login {}.into())
+}
+
+async fn cancel(_: Route) -> NavigationDecision<Route> {
+    NavigationDecision::Cancel
+}
+
+#[component]
+fn App(path: Route, navigate_to: Route) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            Navigate { to: navigate_to }
+        }
+    }
+}
+
+#[component]
+fn Navigate(to: Route) -> Element {
+    use_effect(move || {
+        root_router().unwrap().push(to.clone());
+    });
+    rsx! { Router::<Route> {} }
+}
+
+#[component]
+fn BackApp(path: Route, navigate_to: Route) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            NavigateBack { to: navigate_to }
+        }
+    }
+}
+
+#[component]
+fn NavigateBack(to: Route) -> Element {
+    use_effect(move || {
+        let router = root_router().unwrap();
+        router.push(to.clone());
+        router.go_back();
+    });
+    rsx! { Router::<Route> {} }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Login() -> Element {
+    rsx! { "Login" }
+}
+
+#[component]
+fn AdminFrame() -> Element {
+    rsx! { Outlet::<Route> {} }
+}
+
+#[component]
+fn Admin() -> Element {
+    rsx! { "Admin" }
+}
+
+#[component]
+fn Closed() -> Element {
+    rsx! { "Closed" }
+}
+
+#[component]
+fn Form() -> Element {
+    let blocker = use_navigation_blocker(|| true);
+    use_effect(move || {
+        // Leave the form once the navigation to the home page was blocked
+        if blocker.is_blocked() {
+            blocker.proceed();
+        }
+    });
+    rsx! { "Form" }
+}
+
+async fn render(path: Route, navigate_to: Route) -> String {
+    render_dom(VirtualDom::new_with_props(
+        App,
+        AppProps { path, navigate_to },
+    ))
+    .await
+}
+
+async fn render_dom(mut vdom: VirtualDom) -> String {
+    vdom.rebuild_in_place();
+    for _ in 0..10 {
+        _ = tokio::time::timeout(std::time::Duration::from_millis(50), vdom.wait_for_work()).await;
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    dioxus_ssr::render(&vdom)
+}
+
+#[tokio::test]
+async fn guard_redirects() {
+    assert_eq!(render(Route::Home {}, Route::Admin {}).await, "Login");
+}
+
+#[tokio::test]
+async fn guard_cancels() {
+    assert_eq!(render(Route::Home {}, Route::Closed {}).await, "Home");
+}
+
+#[tokio::test]
+async fn blocker_proceeds() {
+    assert_eq!(render(Route::Form {}, Route::Home {}).await, "Home");
+}
+
+#[tokio::test]
+async fn guard_runs_when_going_back() {
+    let vdom = VirtualDom::new_with_props(
+        BackApp,
+        BackAppProps {
+            path: Route::Closed {},
+            navigate_to: Route::Home {},
+        },
+    );
+    assert_eq!(render_dom(vdom).await, "Home");
+}
@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    List {},
+    #[route("/item/:id")]
+    Item { id: usize },
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::default()) as Rc<dyn History>,
+            Navigate {}
+        }
+    }
+}
+
+#[component]
+fn Navigate() -> Element {
+    use_effect(move || {
+        let router = root_router().unwrap();
+        router.replace_with_state(Route::List {}, &"unread");
+        router.push(Route::Item { id: 1 });
+        router.go_back();
+    });
+    rsx! { Router::<Route> {} }
+}
+
+#[component]
+fn List() -> Element {
+    let filter = router().current_state::<String>().unwrap_or_default();
+    rsx! { "List {filter}" }
+}
+
+#[component]
+fn Item(id: usize) -> Element {
+    let filter = router().current_state::<String>();
+    rsx! { "Item {id} {filter:?}" }
+}
+
+#[tokio::test]
+async fn state_is_restored_after_going_back() {
+    let mut vdom = VirtualDom::new(App);
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "List unread");
+}
@@ -0,0 +1,82 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[nest("/user/:user_id")]
+        #[layout(UserFrame, loader = load_user)]
+            #[route("/posts/:post_id")]
+            #[loader(load_post)]
+            Post { user_id: usize, post_id: usize },
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct User(String);
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct BlogPost(String);
+
+/// Wait until both loaders started. This never finishes if the loaders run one after the other.
+async fn wait_for_both_loaders() {
+    STARTED.fetch_add(1, Ordering::SeqCst);
+    while STARTED.load(Ordering::SeqCst) < 2 {
+        tokio::task::yield_now().await;
+    }
+}
+
+async fn load_user(route: Route) -> User {
+    wait_for_both_loaders().await;
+    let Route::Post { user_id, .. } = route;
+    User(format!("user {user_id}"))
+}
+
+async fn load_post(route: Route) -> BlogPost {
+    wait_for_both_loaders().await;
+    let Route::Post { post_id, .. } = route;
+    BlogPost(format!("post {post_id}"))
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(Route::Post { user_id: 1, post_id: 2 })) as Rc<dyn History>,
+            Router::<Route> {}
+        }
+    }
+}
+
+#[component]
+fn UserFrame(user_id: usize) -> Element {
+    let user = use_loader_data::<User>()?;
+    rsx! {
+        h1 { "{user.0}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Post(user_id: usize, post_id: usize) -> Element {
+    let post = use_loader_data::<BlogPost>()?;
+    rsx! {
+        h2 { "{post.0}" }
+    }
+}
+
+#[tokio::test]
+async fn loaders_run_in_parallel() {
+    let mut vdom = VirtualDom::new(App);
+    vdom.rebuild_in_place();
+    tokio::time::timeout(std::time::Duration::from_secs(5), vdom.wait_for_suspense())
+        .await
+        .expect("loaders should run in parallel");
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>user 1</h1><h2>post 2</h2>");
+}
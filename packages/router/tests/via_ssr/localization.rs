@@ -0,0 +1,188 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::document::{Document, Eval, LinkProps, NoOpDocument};
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+/// The alternate links the router renders into the head and the scripts it evaluates
+#[derive(Default, PartialEq)]
+struct Head {
+    alternates: Vec<(String, String)>,
+    scripts: Vec<String>,
+}
+
+/// A document that records the changes the router makes to the head
+struct HeadRecorder(Rc<RefCell<Head>>);
+
+impl Document for HeadRecorder {
+    fn eval(&self, js: String) -> Eval {
+        self.0.borrow_mut().scripts.push(js);
+        NoOpDocument.eval(String::new())
+    }
+
+    fn create_link(&self, props: LinkProps) {
+        if let (Some(hreflang), Some(href)) = (props.hreflang, props.href) {
+            self.0.borrow_mut().alternates.push((hreflang, href));
+        }
+    }
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[locales("en", "de")]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/shop")]
+        #[route("/products/:id?:color")]
+        #[localized("de", "/laden/produkte/:id")]
+        Product { id: usize, color: String },
+}
+
+#[component]
+fn App(path: String, navigate_to: Option<Route>, head: Rc<RefCell<Head>>) -> Element {
+    use_hook(|| provide_context(Rc::new(HeadRecorder(head.clone())) as Rc<dyn Document>));
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            Navigate { to: navigate_to }
+        }
+    }
+}
+
+#[component]
+fn Navigate(to: Option<Route>) -> Element {
+    use_effect(move || {
+        if let Some(to) = to.clone() {
+            root_router().unwrap().push(to);
+        }
+    });
+    rsx! { Router::<Route> {} }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Product(id: usize, color: String) -> Element {
+    let locale = use_locale().unwrap();
+    let path = router().full_route_string();
+    rsx! {
+        p { "{locale} {id} {color} {path}" }
+        Link { to: Route::Product { id: id + 1, color: String::new() }, "Next" }
+    }
+}
+
+async fn render(path: &str, navigate_to: Option<Route>) -> String {
+    render_with_head(path, navigate_to).await.0
+}
+
+async fn render_with_head(path: &str, navigate_to: Option<Route>) -> (String, Head) {
+    let head = Rc::new(RefCell::new(Head::default()));
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: path.to_string(),
+            navigate_to,
+            head: head.clone(),
+        },
+    );
+    vdom.rebuild_in_place();
+    for _ in 0..3 {
+        _ = tokio::time::timeout(std::time::Duration::from_millis(50), vdom.wait_for_work()).await;
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    let html = dioxus_ssr::render(&vdom);
+    drop(vdom);
+    (html, head.take())
+}
+
+fn alternate_scripts(head: &Head) -> Vec<&String> {
+    head.scripts
+        .iter()
+        .filter(|script| script.contains("x-default"))
+        .collect()
+}
+
+#[test]
+fn parse_and_format_localized_paths() {
+    let route = Route::Product {
+        id: 1,
+        color: "red".to_string(),
+    };
+    assert_eq!(route.to_string(), "/shop/products/1?color=red");
+    assert_eq!(route.localized("de"), "/de/laden/produkte/1?color=red");
+    assert_eq!(route.localized("en"), "/en/shop/products/1?color=red");
+    assert_eq!(Route::Home {}.localized("de"), "/de");
+    for path in [
+        "/de/laden/produkte/1?color=red",
+        "/en/shop/products/1?color=red",
+        "/shop/products/1?color=red",
+    ] {
+        assert_eq!(path.parse::<Route>().unwrap(), route);
+    }
+    assert_eq!("/de".parse::<Route>().unwrap(), Route::Home {});
+}
+
+#[tokio::test]
+async fn links_keep_the_locale() {
+    assert_eq!(
+        render("/de/laden/produkte/1", None).await,
+        r#"<p>de 1  /de/laden/produkte/1?color=</p><a href="/de/laden/produkte/2?color=">Next</a>"#
+    );
+}
+
+#[tokio::test]
+async fn default_locale_without_prefix() {
+    assert_eq!(
+        render("/shop/products/1", None).await,
+        r#"<p>en 1  /shop/products/1?color=</p><a href="/shop/products/2?color=">Next</a>"#
+    );
+}
+
+#[tokio::test]
+async fn navigation_keeps_the_locale() {
+    let to = Route::Product {
+        id: 3,
+        color: "blue".to_string(),
+    };
+    assert_eq!(
+        render("/de/laden/produkte/1", Some(to)).await,
+        r#"<p>de 3 blue /de/laden/produkte/3?color=blue</p><a href="/de/laden/produkte/4?color=">Next</a>"#
+    );
+}
+
+#[tokio::test]
+async fn alternates_link_the_first_route() {
+    let (_, head) = render_with_head("/de/laden/produkte/1", None).await;
+    assert_eq!(
+        head.alternates,
+        [
+            ("en", "/en/shop/products/1?color="),
+            ("de", "/de/laden/produkte/1?color="),
+            ("x-default", "/shop/products/1?color="),
+        ]
+        .map(|(hreflang, href)| (hreflang.to_string(), href.to_string()))
+    );
+    assert!(alternate_scripts(&head).is_empty());
+}
+
+#[tokio::test]
+async fn alternates_follow_the_current_route() {
+    let to = Route::Product {
+        id: 3,
+        color: "blue".to_string(),
+    };
+    let (_, head) = render_with_head("/de/laden/produkte/1", Some(to)).await;
+    // The links of the first route stay in the head until the script replaces them
+    assert_eq!(head.alternates.len(), 3);
+    let scripts = alternate_scripts(&head);
+    assert_eq!(scripts.len(), 1);
+    let alternates = r#"[["en","/en/shop/products/3?color=blue"],["de","/de/laden/produkte/3?color=blue"],["x-default","/shop/products/3?color=blue"]]"#;
+    assert!(scripts[0].contains(alternates));
+}
@@ -1,5 +1,14 @@
+mod guards;
+mod history_state;
 mod link;
+mod loaders;
+mod localization;
+mod named_outlets;
 mod navigation;
 mod outlet;
+mod prefetch;
+#[cfg(feature = "streaming")]
+mod prefetch_server_futures;
 mod redirect;
+mod scroll;
 mod without_index;
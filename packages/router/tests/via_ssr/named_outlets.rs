@@ -0,0 +1,180 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Frame)]
+        #[route("/")]
+        Gallery {},
+        #[route("/photo/:id")]
+        #[outlet("modal")]
+        Photo { id: usize },
+        #[nest("/inbox")]
+            #[layout(Inbox)]
+                #[route("/")]
+                Messages {},
+                #[route("/:id")]
+                #[outlet("detail")]
+                Message { id: usize },
+}
+
+#[component]
+fn App(path: Route, navigate_to: Vec<Route>, back: bool) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+            Navigate { to: navigate_to, back }
+        }
+    }
+}
+
+#[component]
+fn Navigate(to: Vec<Route>, back: bool) -> Element {
+    use_effect(move || {
+        let router = root_router().unwrap();
+        for route in to.clone() {
+            router.push(route);
+        }
+        if back {
+            router.go_back();
+        }
+    });
+    rsx! { Router::<Route> {} }
+}
+
+#[component]
+fn Frame() -> Element {
+    rsx! {
+        Outlet::<Route> {}
+        Outlet::<Route> { name: "modal" }
+    }
+}
+
+#[component]
+fn Gallery() -> Element {
+    rsx! { "Gallery" }
+}
+
+#[component]
+fn Photo(id: usize) -> Element {
+    let mode = match router().background::<Route>() {
+        Some(_) => "modal",
+        None => "page",
+    };
+    rsx! { "Photo {id} {mode}" }
+}
+
+#[component]
+fn Inbox() -> Element {
+    rsx! {
+        Outlet::<Route> {}
+        Outlet::<Route> { name: "detail" }
+    }
+}
+
+#[component]
+fn Messages() -> Element {
+    rsx! { "Messages" }
+}
+
+#[component]
+fn Message(id: usize) -> Element {
+    rsx! { "Message {id}" }
+}
+
+fn render(path: Route, navigate_to: Vec<Route>, back: bool) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path,
+            navigate_to,
+            back,
+        },
+    );
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+    dioxus_ssr::render(&vdom)
+}
+
+#[test]
+fn modal_renders_over_previous_route() {
+    assert_eq!(
+        render(Route::Gallery {}, vec![Route::Photo { id: 1 }], false),
+        "GalleryPhoto 1 modal"
+    );
+}
+
+#[test]
+fn modal_keeps_background_between_routes_of_the_outlet() {
+    assert_eq!(
+        render(
+            Route::Gallery {},
+            vec![Route::Photo { id: 1 }, Route::Photo { id: 2 }],
+            false
+        ),
+        "GalleryPhoto 2 modal"
+    );
+}
+
+#[test]
+fn modal_route_opened_directly_renders_as_page() {
+    assert_eq!(
+        render(Route::Photo { id: 1 }, vec![], false),
+        "Photo 1 page"
+    );
+}
+
+#[test]
+fn going_back_closes_modal() {
+    assert_eq!(
+        render(Route::Gallery {}, vec![Route::Photo { id: 1 }], true),
+        "Gallery"
+    );
+}
+
+#[test]
+fn panes_render_independently() {
+    assert_eq!(
+        render(
+            Route::Messages {},
+            vec![Route::Message { id: 1 }, Route::Message { id: 2 }],
+            false
+        ),
+        "MessagesMessage 2"
+    );
+    assert_eq!(
+        render(Route::Messages {}, vec![Route::Message { id: 1 }], true),
+        "Messages"
+    );
+}
+
+#[component]
+fn ReloadedApp() -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| {
+                // A modal entry restored from the browser history after a reload
+                let history = MemoryHistory::with_initial_path(Route::Photo { id: 1 });
+                history.replace_with_state("/photo/1".to_string(), r#"{"background":"/"}"#.to_string());
+                Rc::new(history) as Rc<dyn History>
+            },
+            Router::<Route> {}
+        }
+    }
+}
+
+#[test]
+fn reloaded_modal_hydrates_as_page_before_rendering_background() {
+    let mut vdom = VirtualDom::new(ReloadedApp);
+    vdom.rebuild_in_place();
+    // The server doesn't know the background route, so the first render must match the page it rendered
+    assert_eq!(dioxus_ssr::render(&vdom), "Photo 1 page");
+
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "GalleryPhoto 1 modal");
+}
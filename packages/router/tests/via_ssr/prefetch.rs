@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+/// How often the loader ran for each post. Every test uses its own posts.
+static LOADED: Mutex<Option<HashMap<usize, usize>>> = Mutex::new(None);
+
+fn times_loaded(id: usize) -> usize {
+    LOADED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|loaded| loaded.get(&id).copied())
+        .unwrap_or_default()
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/post/:id")]
+    #[loader(load_post)]
+    Post { id: usize },
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct BlogPost(String);
+
+async fn load_post(route: Route) -> BlogPost {
+    let Route::Post { id } = route else {
+        unreachable!()
+    };
+    *LOADED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .entry(id)
+        .or_default() += 1;
+    BlogPost(format!("post {id}"))
+}
+
+#[component]
+fn App(prefetch: Vec<usize>, navigate_to: usize, cache_size: usize) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(Route::Home {})) as Rc<dyn History>,
+            Navigate { prefetch, to: navigate_to }
+            Router::<Route> {
+                config: move |_| RouterConfig::default().prefetch_cache_size(cache_size),
+            }
+        }
+    }
+}
+
+#[component]
+fn Navigate(prefetch: Vec<usize>, to: usize) -> Element {
+    use_effect(move || {
+        let router = root_router().unwrap();
+        let prefetch = prefetch.clone();
+        spawn(async move {
+            for id in prefetch {
+                router.prefetch(Route::Post { id });
+                // Let the prefetched loader finish
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            router.push(Route::Post { id: to });
+        });
+    });
+    VNode::empty()
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Post(id: usize) -> Element {
+    let post = use_loader_data::<BlogPost>()?;
+    rsx! { "{post.0}" }
+}
+
+async fn render(prefetch: Vec<usize>, navigate_to: usize, cache_size: usize) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            prefetch,
+            navigate_to,
+            cache_size,
+        },
+    );
+    vdom.rebuild_in_place();
+    for _ in 0..10 {
+        _ = tokio::time::timeout(std::time::Duration::from_millis(50), vdom.wait_for_work()).await;
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    dioxus_ssr::render(&vdom)
+}
+
+#[tokio::test]
+async fn prefetched_data_is_used() {
+    assert_eq!(render(vec![1], 1, 16).await, "post 1");
+    assert_eq!(times_loaded(1), 1);
+}
+
+#[tokio::test]
+async fn oldest_prefetch_is_evicted() {
+    assert_eq!(render(vec![2, 3], 2, 1).await, "post 2");
+    assert_eq!(times_loaded(2), 2);
+    assert_eq!(times_loaded(3), 1);
+}
+
+#[tokio::test]
+async fn prefetch_runs_loaders() {
+    assert_eq!(render(vec![4], 5, 16).await, "post 5");
+    assert_eq!(times_loaded(4), 1);
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_fullstack_hooks::use_server_future;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+
+/// How often the server future and the effects of each article ran. Every test uses its own articles.
+static FETCHED: Mutex<Option<HashMap<usize, usize>>> = Mutex::new(None);
+static MOUNTED: Mutex<Option<HashMap<usize, usize>>> = Mutex::new(None);
+
+fn count(counter: &Mutex<Option<HashMap<usize, usize>>>, id: usize) -> usize {
+    counter
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|counts| counts.get(&id).copied())
+        .unwrap_or_default()
+}
+
+fn increment(counter: &Mutex<Option<HashMap<usize, usize>>>, id: usize) {
+    *counter
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .entry(id)
+        .or_default() += 1;
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/article/:id")]
+    Article { id: usize },
+}
+
+async fn fetch_article(id: usize) -> String {
+    increment(&FETCHED, id);
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    format!("article {id}")
+}
+
+#[component]
+fn App(prefetch: usize, navigate: bool) -> Element {
+    rsx! {
+        HistoryProvider {
+            history: move |_| Rc::new(MemoryHistory::with_initial_path(Route::Home {})) as Rc<dyn History>,
+            Navigate { prefetch, navigate }
+            Router::<Route> {}
+        }
+    }
+}
+
+#[component]
+fn Navigate(prefetch: usize, navigate: bool) -> Element {
+    use_effect(move || {
+        let router = root_router().unwrap();
+        spawn(async move {
+            router.prefetch(Route::Article { id: prefetch });
+            // Let the prefetched server future finish
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            if navigate {
+                router.push(Route::Article { id: prefetch });
+            }
+        });
+    });
+    VNode::empty()
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Article(id: usize) -> Element {
+    let article = use_server_future(move || fetch_article(id))?;
+    use_effect(move || increment(&MOUNTED, id));
+    rsx! { "{article().unwrap_or_default()}" }
+}
+
+async fn render(prefetch: usize, navigate: bool) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { prefetch, navigate });
+    vdom.rebuild_in_place();
+    for _ in 0..10 {
+        _ = tokio::time::timeout(std::time::Duration::from_millis(20), vdom.wait_for_work()).await;
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    dioxus_ssr::render(&vdom)
+}
+
+#[tokio::test]
+async fn prefetched_server_futures_are_used() {
+    assert_eq!(render(1, true).await, "article 1");
+    assert_eq!(count(&FETCHED, 1), 1);
+    assert_eq!(count(&MOUNTED, 1), 1);
+}
+
+#[tokio::test]
+async fn prefetched_route_is_not_mounted() {
+    assert_eq!(render(2, false).await, "Home");
+    assert_eq!(count(&FETCHED, 2), 1);
+    assert_eq!(count(&MOUNTED, 2), 0);
+}
@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use dioxus::document::{Document, Eval, EvalError, Evaluator};
+use dioxus::prelude::*;
+use dioxus::signals::Owner;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use serde_json::{json, Value};
+
+/// The scroll plans the router sent to the page and the scroll positions the page reports back
+#[derive(Default)]
+struct Page {
+    plans: Vec<Value>,
+    reports: VecDeque<Value>,
+    waker: Option<Waker>,
+    /// A scope inside of the history provider to navigate from
+    scope: Option<ScopeId>,
+}
+
+impl PartialEq for Page {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// A document that records the scroll plans of the router instead of running its scripts
+struct PageRecorder {
+    page: Rc<RefCell<Page>>,
+    owner: Owner,
+}
+
+impl Document for PageRecorder {
+    fn eval(&self, _: String) -> Eval {
+        Eval::new(
+            self.owner
+                .insert(Box::new(RecordedScript(self.page.clone())) as Box<dyn Evaluator>),
+        )
+    }
+}
+
+struct RecordedScript(Rc<RefCell<Page>>);
+
+impl Evaluator for RecordedScript {
+    fn poll_join(&mut self, _: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        Poll::Pending
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        let mut page = self.0.borrow_mut();
+        match page.reports.pop_front() {
+            Some(report) => Poll::Ready(Ok(report)),
+            None => {
+                page.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn send(&self, data: Value) -> Result<(), EvalError> {
+        self.0.borrow_mut().plans.push(data);
+        Ok(())
+    }
+}
+
+/// A history that leaves scrolling to the platform
+struct NoScrollHistory(MemoryHistory);
+
+impl History for NoScrollHistory {
+    fn current_route(&self) -> String {
+        self.0.current_route()
+    }
+    fn go_back(&self) {
+        self.0.go_back()
+    }
+    fn go_forward(&self) {
+        self.0.go_forward()
+    }
+    fn push(&self, route: String) {
+        self.0.push(route)
+    }
+    fn replace(&self, path: String) {
+        self.0.replace(path)
+    }
+    fn push_with_state(&self, route: String, state: String) {
+        self.0.push_with_state(route, state)
+    }
+    fn replace_with_state(&self, path: String, state: String) {
+        self.0.replace_with_state(path, state)
+    }
+    fn current_state(&self) -> Option<String> {
+        self.0.current_state()
+    }
+    fn scroll_restoration(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/docs#:section")]
+    Docs { section: String },
+    #[route("/feed")]
+    #[scroll(manual)]
+    Feed {},
+}
+
+#[component]
+fn App(page: Rc<RefCell<Page>>, scroll_restoration: bool) -> Element {
+    use_hook(|| {
+        provide_context(Rc::new(PageRecorder {
+            page: page.clone(),
+            owner: Owner::default(),
+        }) as Rc<dyn Document>)
+    });
+    rsx! {
+        HistoryProvider {
+            history: move |_| {
+                let history = MemoryHistory::with_initial_path(Route::Home {});
+                match scroll_restoration {
+                    true => Rc::new(history) as Rc<dyn History>,
+                    false => Rc::new(NoScrollHistory(history)) as Rc<dyn History>,
+                }
+            },
+            Navigator { page }
+            Router::<Route> {}
+        }
+    }
+}
+
+#[component]
+fn Navigator(page: Rc<RefCell<Page>>) -> Element {
+    use_hook(|| page.borrow_mut().scope = current_scope_id().ok());
+    VNode::empty()
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Docs(section: String) -> Element {
+    rsx! { "Docs" }
+}
+
+#[component]
+fn Feed() -> Element {
+    rsx! { "Feed" }
+}
+
+struct TestPage {
+    vdom: VirtualDom,
+    page: Rc<RefCell<Page>>,
+}
+
+impl TestPage {
+    fn new(scroll_restoration: bool) -> Self {
+        let page = Rc::new(RefCell::new(Page::default()));
+        let mut vdom = VirtualDom::new_with_props(
+            App,
+            AppProps {
+                page: page.clone(),
+                scroll_restoration,
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut test = Self { vdom, page };
+        test.settle();
+        test
+    }
+
+    /// Run the effects and tasks of the last change
+    fn settle(&mut self) {
+        for _ in 0..4 {
+            self.vdom.render_immediate(&mut NoOpMutations);
+        }
+    }
+
+    fn navigate(&mut self, navigate: impl FnOnce(RouterContext)) {
+        self.in_scope(|| navigate(root_router().unwrap()));
+        self.settle();
+    }
+
+    fn in_scope<O>(&self, f: impl FnOnce() -> O) -> O {
+        let scope = self.page.borrow().scope.unwrap();
+        self.vdom.in_runtime(|| scope.in_runtime(f))
+    }
+
+    /// Report a scroll position from the page like the scroll script does when the user stops scrolling
+    fn scroll_to(&mut self, y: f64) {
+        let waker = {
+            let mut page = self.page.borrow_mut();
+            page.reports.push_back(json!({ "x": 0.0, "y": y }));
+            page.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        self.settle();
+    }
+
+    fn plans(&self) -> Vec<Value> {
+        self.page.borrow().plans.clone()
+    }
+}
+
+fn plan(position: Option<f64>, fragment: Option<&str>, to_top: bool) -> Value {
+    json!({
+        "position": position.map(|y| json!({ "x": 0.0, "y": y })),
+        "fragment": fragment,
+        "to_top": to_top,
+    })
+}
+
+#[test]
+fn push_scrolls_to_top_or_fragment() {
+    let mut page = TestPage::new(true);
+    page.navigate(|router| {
+        router.push(Route::Docs {
+            section: "install".to_string(),
+        });
+    });
+    page.navigate(|router| {
+        router.push(Route::Home {});
+    });
+    assert_eq!(
+        page.plans(),
+        [
+            plan(None, None, true),
+            plan(None, Some("install"), true),
+            plan(None, None, true),
+        ]
+    );
+}
+
+#[test]
+fn back_restores_the_reported_position() {
+    let mut page = TestPage::new(true);
+    page.scroll_to(300.0);
+    page.navigate(|router| {
+        router.push(Route::Docs {
+            section: String::new(),
+        });
+    });
+    page.scroll_to(50.0);
+    page.navigate(|router| router.go_back());
+    page.navigate(|router| router.go_forward());
+    assert_eq!(
+        page.plans(),
+        [
+            plan(None, None, true),
+            plan(None, None, true),
+            plan(Some(300.0), None, true),
+            plan(Some(50.0), None, true),
+        ]
+    );
+}
+
+#[test]
+fn positions_are_stored_in_the_history_state() {
+    let mut page = TestPage::new(true);
+    page.scroll_to(120.0);
+    let state = page
+        .in_scope(|| dioxus_history::history().current_state())
+        .unwrap();
+    let state: Value = serde_json::from_str(&state).unwrap();
+    assert_eq!(state["scroll"], json!({ "x": 0.0, "y": 120.0 }));
+}
+
+#[test]
+fn manual_routes_dont_scroll() {
+    let mut page = TestPage::new(true);
+    page.navigate(|router| {
+        router.push(Route::Feed {});
+    });
+    assert_eq!(
+        page.plans(),
+        [plan(None, None, true), plan(None, None, false)]
+    );
+}
+
+#[test]
+fn histories_can_opt_out_of_scrolling() {
+    let mut page = TestPage::new(false);
+    page.navigate(|router| {
+        router.push(Route::Docs {
+            section: "install".to_string(),
+        });
+    });
+    page.navigate(|router| router.go_back());
+    assert!(page.plans().is_empty());
+}
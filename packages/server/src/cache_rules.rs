@@ -0,0 +1,343 @@
+//! Per-route caching rules and cache keys for server side rendered pages.
+
+use http::{header::HeaderName, request::Parts, HeaderValue};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::{sync::Arc, time::Duration};
+
+/// How a rendered page should be cached.
+///
+/// Rules are set per route with [`ServeConfigBuilder::cache_rule`](crate::ServeConfigBuilder::cache_rule) or for a
+/// single render with [`DioxusServerContext::set_cache_rule`](crate::DioxusServerContext::set_cache_rule).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheRule {
+    /// Never store the page in the incremental cache and send `Cache-Control: no-store`.
+    Never,
+    /// Keep the page in the incremental cache and allow clients to cache it for the duration.
+    MaxAge(Duration),
+}
+
+/// A route pattern like `/blog/*`. Each `*` segment matches any single segment, and a trailing `*` matches the rest
+/// of the path.
+#[derive(Debug, Clone)]
+struct RoutePattern {
+    segments: Vec<String>,
+}
+
+impl RoutePattern {
+    fn new(pattern: &str) -> Self {
+        Self {
+            segments: path_segments(pattern).map(ToString::to_string).collect(),
+        }
+    }
+
+    fn matches(&self, route: &str) -> bool {
+        let mut route = path_segments(route);
+        for (i, segment) in self.segments.iter().enumerate() {
+            let is_last = i == self.segments.len() - 1;
+            match route.next() {
+                Some(_) if segment == "*" && is_last => return true,
+                Some(route_segment) if segment == "*" || segment == route_segment => {}
+                _ => return false,
+            }
+        }
+        route.next().is_none()
+    }
+}
+
+fn path_segments(route: &str) -> impl Iterator<Item = &str> {
+    let (path, _) = route.split_once(['?', '#']).unwrap_or((route, ""));
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// The caching rules of every route in the order they were added
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CacheRules {
+    rules: Vec<(RoutePattern, CacheRule)>,
+}
+
+impl CacheRules {
+    pub(crate) fn push(&mut self, pattern: &str, rule: CacheRule) {
+        self.rules.push((RoutePattern::new(pattern), rule));
+    }
+
+    /// Get the rule of the first pattern that matches the route
+    pub(crate) fn rule_for(&self, route: &str) -> Option<CacheRule> {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern.matches(route))
+            .map(|(_, rule)| *rule)
+    }
+}
+
+/// The characters that are escaped in variant keys so they are safe to use as a file name
+const VARIANT_ESCAPE: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+
+type VariantFn = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// The parts of a request that select which variant of a page is cached and served, like the language or theme.
+///
+/// Each variant of a route is cached separately. Invalidating a route removes every variant of the route. When a cache
+/// key is set, only query parameters listed with [`Self::query`] are cached. Requests with any other query parameter
+/// are rendered without the cache since the page may depend on it.
+///
+/// ```rust, no_run
+/// # fn app() -> Element { todo!() }
+/// use dioxus::prelude::*;
+///
+/// LaunchBuilder::new()
+///     .with_cfg(server_only! {
+///         ServeConfigBuilder::default()
+///             .incremental(IncrementalRendererConfig::default())
+///             .cache_key(
+///                 CacheKey::new()
+///                     .header(http::header::ACCEPT_LANGUAGE)
+///                     .cookie("theme")
+///                     .custom("device", |parts| {
+///                         let user_agent = parts.headers.get(http::header::USER_AGENT)?.to_str().ok()?;
+///                         Some(if user_agent.contains("Mobile") { "mobile" } else { "desktop" }.to_string())
+///                     })
+///                     .vary(http::header::USER_AGENT),
+///             )
+///     })
+///     .launch(app);
+/// ```
+#[derive(Clone, Default)]
+pub struct CacheKey {
+    headers: Vec<HeaderName>,
+    cookies: Vec<String>,
+    query: Vec<String>,
+    custom: Vec<(String, VariantFn)>,
+    vary: Vec<HeaderName>,
+}
+
+impl CacheKey {
+    /// Create a new cache key that only depends on the route.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache a separate variant for each value of a request header. The header is added to the `Vary` header of the response.
+    pub fn header(mut self, name: HeaderName) -> Self {
+        self.vary.push(name.clone());
+        self.headers.push(name);
+        self
+    }
+
+    /// Cache a separate variant for each value of a cookie. `Cookie` is added to the `Vary` header of the response.
+    pub fn cookie(mut self, name: impl Into<String>) -> Self {
+        if !self.vary.contains(&http::header::COOKIE) {
+            self.vary.push(http::header::COOKIE);
+        }
+        self.cookies.push(name.into());
+        self
+    }
+
+    /// Cache a separate variant for each value of a query parameter.
+    pub fn query(mut self, name: impl Into<String>) -> Self {
+        self.query.push(name.into());
+        self
+    }
+
+    /// Cache a separate variant for each value returned by a function of the request. Use [`Self::vary`] to add the headers
+    /// the function reads to the `Vary` header of the response.
+    pub fn custom(
+        mut self,
+        name: impl Into<String>,
+        variant: impl Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.custom.push((name.into(), Arc::new(variant)));
+        self
+    }
+
+    /// Add a header to the `Vary` header of the response without using it in the cache key.
+    pub fn vary(mut self, name: HeaderName) -> Self {
+        if !self.vary.contains(&name) {
+            self.vary.push(name);
+        }
+        self
+    }
+
+    /// Get the variant of the request. Each part is written as `{kind}.{name}={value}` with the value percent encoded.
+    fn variant(&self, route: &str, parts: &Parts) -> String {
+        let header = |name: &HeaderName| {
+            let values: Vec<_> = parts
+                .headers
+                .get_all(name)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect();
+            (!values.is_empty()).then(|| values.join(","))
+        };
+        let cookie = |name: &str| {
+            parts
+                .headers
+                .get_all(http::header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|cookies| cookies.split(';'))
+                .find_map(|cookie| {
+                    let (key, value) = cookie.trim().split_once('=')?;
+                    (key == name).then(|| value.to_string())
+                })
+        };
+        let query = route.split_once('?').map(|(_, query)| query).unwrap_or("");
+        let query_param = |name: &str| {
+            query.split('&').find_map(|param| {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                (key == name).then(|| value.to_string())
+            })
+        };
+
+        let parts = self
+            .headers
+            .iter()
+            .map(|name| ("h", name.as_str(), header(name)))
+            .chain(
+                self.cookies
+                    .iter()
+                    .map(|name| ("c", name.as_str(), cookie(name))),
+            )
+            .chain(
+                self.query
+                    .iter()
+                    .map(|name| ("q", name.as_str(), query_param(name))),
+            )
+            .chain(
+                self.custom
+                    .iter()
+                    .map(|(name, variant)| ("x", name.as_str(), variant(parts))),
+            );
+
+        let mut variant = String::new();
+        for (kind, name, value) in parts {
+            let Some(value) = value else {
+                continue;
+            };
+            if !variant.is_empty() {
+                variant.push('&');
+            }
+            let name = utf8_percent_encode(name, VARIANT_ESCAPE);
+            let value = utf8_percent_encode(&value, VARIANT_ESCAPE);
+            variant += &format!("{kind}.{name}={value}");
+        }
+        variant
+    }
+
+    /// Strip a request down to the parts the cache key reads. Revalidating a route renders it with these parts so
+    /// cookies, credentials and other headers of the request that found the stale entry never end up in the shared
+    /// cache.
+    pub(crate) fn shared_headers(&self, parts: &Parts) -> http::HeaderMap {
+        let mut headers = http::HeaderMap::new();
+        let varied =
+            self.headers.iter().chain(&self.vary).filter(|name| {
+                **name != http::header::COOKIE && **name != http::header::AUTHORIZATION
+            });
+        for name in varied {
+            if headers.contains_key(name) {
+                continue;
+            }
+            for value in parts.headers.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+
+        // Only keep the cookies the key varies on
+        let cookies: Vec<_> = parts
+            .headers
+            .get_all(http::header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .map(str::trim)
+            .filter(|cookie| {
+                let name = cookie.split_once('=').map_or(*cookie, |(name, _)| name);
+                self.cookies.iter().any(|listed| listed == name)
+            })
+            .collect();
+        if let Ok(cookies) = HeaderValue::from_str(&cookies.join("; ")) {
+            if !cookies.is_empty() {
+                headers.insert(http::header::COOKIE, cookies);
+            }
+        }
+        headers
+    }
+
+    /// Get the key a route is cached under for a request or `None` if the route has a query parameter that is not
+    /// part of the key
+    pub(crate) fn key_for(&self, route: &str, parts: &Parts) -> Option<String> {
+        let (path, query) = route.split_once('?').unwrap_or((route, ""));
+        let unlisted = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| param.split_once('=').map_or(param, |(key, _)| key))
+            .any(|key| !self.query.iter().any(|listed| listed == key));
+        if unlisted {
+            return None;
+        }
+        Some(dioxus_isrg::variant_key(path, &self.variant(route, parts)))
+    }
+
+    /// Get the value of the `Vary` header for responses rendered with this key
+    pub(crate) fn vary_header(&self) -> Option<HeaderValue> {
+        let vary: Vec<_> = self.vary.iter().map(HeaderName::as_str).collect();
+        if vary.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&vary.join(", ")).ok()
+    }
+}
+
+#[test]
+fn route_patterns() {
+    let mut rules = CacheRules::default();
+    rules.push("/", CacheRule::MaxAge(Duration::from_secs(30)));
+    rules.push("/account/*", CacheRule::Never);
+    rules.push("/blog/*", CacheRule::MaxAge(Duration::from_secs(3600)));
+    rules.push("/users/*/avatar", CacheRule::Never);
+
+    assert_eq!(
+        rules.rule_for("/?page=2"),
+        Some(CacheRule::MaxAge(Duration::from_secs(30)))
+    );
+    assert_eq!(
+        rules.rule_for("/account/settings/email"),
+        Some(CacheRule::Never)
+    );
+    assert_eq!(
+        rules.rule_for("/blog/hello-world"),
+        Some(CacheRule::MaxAge(Duration::from_secs(3600)))
+    );
+    assert_eq!(rules.rule_for("/blog"), None);
+    assert_eq!(rules.rule_for("/users/1/avatar"), Some(CacheRule::Never));
+    assert_eq!(rules.rule_for("/users/1/posts"), None);
+}
+
+#[test]
+fn cache_key_variants() {
+    let key = CacheKey::new()
+        .header(http::header::ACCEPT_LANGUAGE)
+        .cookie("theme")
+        .query("page");
+    let (parts, _) = http::Request::builder()
+        .header(http::header::ACCEPT_LANGUAGE, "en-US")
+        .header(http::header::COOKIE, "session=abc; theme=dark mode")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    assert_eq!(
+        key.key_for("/blog?page=2", &parts).as_deref(),
+        Some("/blog#h.accept-language=en-US&c.theme=dark%20mode&q.page=2")
+    );
+    assert_eq!(
+        CacheKey::new().key_for("/blog", &parts).as_deref(),
+        Some("/blog")
+    );
+    // Query parameters that are not part of the key could change the page, so those requests are not cached
+    assert_eq!(key.key_for("/blog?page=2&utm=ad", &parts), None);
+    assert_eq!(CacheKey::new().key_for("/blog?page=2", &parts), None);
+    assert_eq!(
+        key.vary_header().unwrap(),
+        HeaderValue::from_static("accept-language, cookie")
+    );
+}
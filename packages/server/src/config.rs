@@ -22,6 +22,10 @@ pub struct ServeConfigBuilder {
     pub(crate) context_providers:
         Vec<Arc<dyn Fn() -> Box<dyn std::any::Any> + Send + Sync + 'static>>,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) cache_invalidation: Option<CacheInvalidationEndpoint>,
+    pub(crate) cache_rules: crate::cache_rules::CacheRules,
+    pub(crate) cache_key: Option<crate::CacheKey>,
+    pub(crate) static_site: Option<Arc<dyn crate::ssg::StaticSiteRoutes>>,
 }
 
 impl LaunchConfig for ServeConfigBuilder {}
@@ -36,6 +40,10 @@ impl ServeConfigBuilder {
             incremental: None,
             context_providers: Default::default(),
             streaming_mode: StreamingMode::default(),
+            cache_invalidation: None,
+            cache_rules: Default::default(),
+            cache_key: None,
+            static_site: None,
         }
     }
 
@@ -57,6 +65,98 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Set how pages matching a route pattern are cached. Each `*` segment in the pattern matches any single segment and a
+    /// trailing `*` matches the rest of the path. If several patterns match a route, the rule that was added first is used.
+    ///
+    /// Routes without a rule use the [`IncrementalRendererConfig`](dioxus_isrg::IncrementalRendererConfig) passed to [`Self::incremental`].
+    /// Individual renders can override the rule with [`DioxusServerContext::set_cache_rule`](crate::DioxusServerContext::set_cache_rule).
+    ///
+    /// ```rust, no_run
+    /// # fn app() -> Element { todo!() }
+    /// use dioxus::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         ServeConfigBuilder::default()
+    ///             .incremental(IncrementalRendererConfig::default())
+    ///             .cache_rule("/", CacheRule::MaxAge(Duration::from_secs(30)))
+    ///             .cache_rule("/blog/*", CacheRule::MaxAge(Duration::from_secs(60 * 60)))
+    ///             .cache_rule("/account/*", CacheRule::Never)
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn cache_rule(mut self, pattern: impl AsRef<str>, rule: crate::CacheRule) -> Self {
+        self.cache_rules.push(pattern.as_ref(), rule);
+        self
+    }
+
+    /// Set the parts of the request that select which variant of a page is cached, like the `Accept-Language` header or a
+    /// theme cookie. See [`CacheKey`](crate::CacheKey) for more details.
+    pub fn cache_key(mut self, cache_key: crate::CacheKey) -> Self {
+        self.cache_key = Some(cache_key);
+        self
+    }
+
+    /// Register an HTTP endpoint under `path` that removes pages from the incremental cache. Every request must send
+    /// an `Authorization: Bearer {token}` header. The endpoint accepts `POST` requests to:
+    /// - `{path}/route/{route}` to remove a single route
+    /// - `{path}/prefix/{prefix}` to remove every route that starts with the prefix
+    /// - `{path}/tag/{tag}` to remove every route rendered with the tag
+    /// - `{path}/all` to remove every route
+    ///
+    /// ```rust, no_run
+    /// # fn app() -> Element { todo!() }
+    /// use dioxus::prelude::*;
+    ///
+    /// LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         ServeConfigBuilder::default()
+    ///             .incremental(IncrementalRendererConfig::default())
+    ///             .cache_invalidation_endpoint("/__invalidate", std::env::var("INVALIDATION_TOKEN").unwrap())
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn cache_invalidation_endpoint(
+        mut self,
+        path: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        self.cache_invalidation = Some(CacheInvalidationEndpoint {
+            path: path.into(),
+            token: token.into(),
+        });
+        self
+    }
+
+    /// Configure the routes that are rendered when the site is statically generated with `dx bundle --ssg`.
+    ///
+    /// ```rust, no_run
+    /// # fn app() -> Element { todo!() }
+    /// # #[component]
+    /// # fn Home() -> Element { todo!() }
+    /// use dioxus::prelude::*;
+    ///
+    /// #[derive(Routable, Clone, PartialEq)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Home {},
+    /// }
+    ///
+    /// LaunchBuilder::new()
+    ///     .with_cfg(server_only! {
+    ///         ServeConfigBuilder::default().static_site(StaticSiteConfig::<Route>::new())
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn static_site<R: dioxus_router::prelude::Routable>(
+        mut self,
+        cfg: crate::StaticSiteConfig<R>,
+    ) -> Self {
+        self.static_site = Some(Arc::new(cfg));
+        self
+    }
+
     /// Set the contents of the index.html file to be served. (precedence over index_path)
     pub fn index_html(mut self, index_html: String) -> Self {
         self.index_html = Some(index_html);
@@ -384,6 +484,10 @@ impl ServeConfigBuilder {
             incremental: self.incremental,
             context_providers,
             streaming_mode: self.streaming_mode,
+            cache_invalidation: self.cache_invalidation,
+            cache_rules: self.cache_rules,
+            cache_key: self.cache_key,
+            static_site: self.static_site,
         })
     }
 }
@@ -476,7 +580,7 @@ fn load_index_html(contents: String, root_id: &'static str) -> IndexHtml {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub(crate) struct IndexHtml {
     pub(crate) head_before_title: String,
     pub(crate) head_after_title: String,
@@ -486,6 +590,13 @@ pub(crate) struct IndexHtml {
     pub(crate) after_closing_body_tag: String,
 }
 
+/// An authenticated endpoint that removes pages from the incremental cache
+#[derive(Clone)]
+pub(crate) struct CacheInvalidationEndpoint {
+    pub(crate) path: String,
+    pub(crate) token: String,
+}
+
 /// The streaming mode to use while rendering the page
 #[derive(Clone, Copy, Default, PartialEq)]
 pub enum StreamingMode {
@@ -507,6 +618,10 @@ pub struct ServeConfig {
     #[allow(unused)]
     pub(crate) context_providers: ContextProviders,
     pub(crate) streaming_mode: StreamingMode,
+    pub(crate) cache_invalidation: Option<CacheInvalidationEndpoint>,
+    pub(crate) cache_rules: crate::cache_rules::CacheRules,
+    pub(crate) cache_key: Option<crate::CacheKey>,
+    pub(crate) static_site: Option<Arc<dyn crate::ssg::StaticSiteRoutes>>,
 }
 
 impl LaunchConfig for ServeConfig {}
@@ -62,6 +62,8 @@ pub struct DioxusServerContext {
     response_parts: Arc<RwLock<http::response::Parts>>,
     pub(crate) parts: Arc<RwLock<http::request::Parts>>,
     response_sent: Arc<std::sync::atomic::AtomicBool>,
+    cache_tags: Arc<RwLock<Vec<String>>>,
+    cache_rule: Arc<RwLock<Option<crate::CacheRule>>>,
 }
 
 enum ContextType {
@@ -89,6 +91,8 @@ impl Default for DioxusServerContext {
             )),
             parts: Arc::new(RwLock::new(http::request::Request::new(()).into_parts().0)),
             response_sent: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            cache_tags: Default::default(),
+            cache_rule: Default::default(),
         }
     }
 }
@@ -109,6 +113,8 @@ mod server_fn_impl {
                     http::response::Response::new(()).into_parts().0,
                 )),
                 response_sent: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                cache_tags: Default::default(),
+                cache_rule: Default::default(),
             }
         }
 
@@ -123,6 +129,8 @@ mod server_fn_impl {
                     http::response::Response::new(()).into_parts().0,
                 )),
                 response_sent: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                cache_tags: Default::default(),
+                cache_rule: Default::default(),
             }
         }
 
@@ -381,6 +389,61 @@ mod server_fn_impl {
             T::from_request(self).await
         }
 
+        /// Attach a tag to the current render. If incremental rendering is enabled, the tag is stored with the cached page and
+        /// every page with the tag can be removed from the cache with [`SSRState::invalidate_tag`](crate::SSRState::invalidate_tag).
+        ///
+        /// # Example
+        ///
+        /// ```rust, no_run
+        /// # use dioxus::prelude::*;
+        /// #[component]
+        /// fn Product(id: u32) -> Element {
+        ///     #[cfg(feature = "server")]
+        ///     server_context().add_cache_tag(format!("product:{id}"));
+        ///     rsx! { "Product {id}" }
+        /// }
+        /// ```
+        pub fn add_cache_tag(&self, tag: impl Into<String>) {
+            let tag = tag.into();
+            let mut tags = self.cache_tags.write();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        /// Get the tags attached to the current render with [`Self::add_cache_tag`]
+        pub fn cache_tags(&self) -> Vec<String> {
+            self.cache_tags.read().clone()
+        }
+
+        /// Override the [`CacheRule`](crate::CacheRule) of the route for the current render. This takes precedence over
+        /// the rules set with [`ServeConfigBuilder::cache_rule`](crate::ServeConfigBuilder::cache_rule).
+        ///
+        /// # Example
+        ///
+        /// ```rust, no_run
+        /// # use dioxus::prelude::*;
+        /// # fn logged_in_user() -> Option<String> { None }
+        /// #[component]
+        /// fn Home() -> Element {
+        ///     let user = logged_in_user();
+        ///     // Pages rendered for a logged in user should never be shared with other users
+        ///     #[cfg(feature = "server")]
+        ///     if user.is_some() {
+        ///         server_context().set_cache_rule(CacheRule::Never);
+        ///     }
+        ///     rsx! { "Welcome {user:?}" }
+        /// }
+        /// ```
+        pub fn set_cache_rule(&self, rule: crate::CacheRule) {
+            *self.cache_rule.write() = Some(rule);
+        }
+
+        /// Get the [`CacheRule`](crate::CacheRule) set for the current render with [`Self::set_cache_rule`]
+        pub fn cache_rule(&self) -> Option<crate::CacheRule> {
+            *self.cache_rule.read()
+        }
+
         /// Copy the response parts to a response and mark this server context as sent
         pub(crate) fn send_response<B>(&self, response: &mut http::response::Response<B>) {
             self.response_sent
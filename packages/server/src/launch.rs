@@ -87,6 +87,30 @@ async fn serve_server(
         })
         .unwrap();
 
+    // If the CLI is generating a static site, render every route into the output directory and exit instead of serving
+    if let Some(out_dir) = dioxus_cli_config::ssg_out_dir() {
+        let report =
+            crate::generate_static_site(&cfg, move || VirtualDom::new(original_root), &out_dir)
+                .await;
+        match report {
+            Ok(report) => {
+                println!(
+                    "Generated {} routes ({} unchanged)",
+                    report.rendered.len() + report.skipped.len(),
+                    report.skipped.len()
+                );
+                for (route, err) in &report.failed {
+                    eprintln!("Failed to render {route}: {err}");
+                }
+                std::process::exit(if report.is_success() { 0 } else { 1 });
+            }
+            Err(err) => {
+                eprintln!("Failed to generate static site: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Get the address the server should run on. If the CLI is running, the CLI proxies fullstack into the main address
     // and we use the generated address the CLI gives us
     let address = dioxus_cli_config::fullstack_address_or_localhost();
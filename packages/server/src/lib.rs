@@ -58,9 +58,11 @@
 pub mod config;
 pub mod context;
 
+mod cache_rules;
 mod document;
 mod render;
 mod server;
+mod ssg;
 mod streaming;
 
 pub(crate) use config::*;
@@ -69,6 +71,8 @@ pub use crate::config::{ServeConfig, ServeConfigBuilder};
 pub use crate::context::Axum;
 pub use crate::render::{FullstackHTMLTemplate, SSRState};
 pub use crate::server::*;
+pub use crate::ssg::{generate_static_site, StaticSiteConfig, StaticSiteError, StaticSiteReport};
+pub use cache_rules::{CacheKey, CacheRule};
 pub use config::*;
 pub use context::{
     extract, server_context, with_server_context, DioxusServerContext, FromContext,
@@ -84,6 +88,7 @@ pub use launch::{launch, launch_cfg};
 
 /// Re-export commonly used items
 pub mod prelude {
+    pub use crate::cache_rules::{CacheKey, CacheRule};
     pub use crate::config::{ServeConfig, ServeConfigBuilder};
     pub use crate::context::Axum;
     pub use crate::context::{
@@ -92,6 +97,7 @@ pub mod prelude {
     };
     pub use crate::render::{FullstackHTMLTemplate, SSRState};
     pub use crate::server::*;
+    pub use crate::ssg::StaticSiteConfig;
     pub use dioxus_isrg::{IncrementalRenderer, IncrementalRendererConfig};
 }
 
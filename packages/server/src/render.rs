@@ -1,5 +1,5 @@
 //! A shared pool of renderers for efficient server side rendering.
-use crate::{document::ServerDocument, ProvideServerContext, ServeConfig};
+use crate::{document::ServerDocument, CacheRule, ProvideServerContext, ServeConfig};
 use crate::{
     streaming::{Mount, StreamingRenderer},
     DioxusServerContext,
@@ -12,9 +12,14 @@ use dioxus_lib::document::Document;
 use dioxus_lib::prelude::dioxus_core::DynamicNode;
 use dioxus_router::prelude::ParseRouteError;
 use dioxus_ssr::Renderer;
-use futures_channel::mpsc::Sender;
 use futures_util::{Stream, StreamExt};
-use std::{collections::HashMap, fmt::Write, future::Future, rc::Rc, sync::Arc, sync::RwLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    future::Future,
+    rc::Rc,
+    sync::{Arc, Mutex, RwLock},
+};
 use tokio::task::JoinHandle;
 
 use crate::StreamingMode;
@@ -63,9 +68,55 @@ pub enum SSRError {
     Routing(ParseRouteError),
 }
 
+/// Get the key a route is stored under in the incremental cache for the current request or `None` if the request
+/// can't be cached
+fn cache_key(
+    cfg: &ServeConfig,
+    route: &str,
+    server_context: &DioxusServerContext,
+) -> Option<String> {
+    match &cfg.cache_key {
+        Some(cache_key) => cache_key.key_for(route, &server_context.request_parts()),
+        None => Some(route.to_string()),
+    }
+}
+
+/// Create the server context a stale route is regenerated with. The render is shared with every request that hits
+/// the cache, so it only sees the parts of the request the cache key reads instead of the cookies, credentials and
+/// response of the request that found the stale entry.
+fn revalidation_context(
+    cfg: &ServeConfig,
+    server_context: &DioxusServerContext,
+) -> DioxusServerContext {
+    let parts = server_context.request_parts();
+    let mut request = http::Request::builder()
+        .method(http::Method::GET)
+        .uri(parts.uri.clone())
+        .version(parts.version)
+        .body(())
+        .unwrap();
+    if let Some(cache_key) = &cfg.cache_key {
+        *request.headers_mut() = cache_key.shared_headers(&parts);
+    }
+    drop(parts);
+    let context = DioxusServerContext::new(request.into_parts().0);
+    crate::server::add_server_context(&context, &cfg.context_providers);
+    context
+}
+
+/// A route rendered by the [`SSRState`]
+pub(crate) enum RenderedRoute<S> {
+    /// The route was found in the incremental cache
+    Cached(CachedRender),
+    /// The route is being rendered into the stream
+    Streaming(RenderFreshness, S),
+}
+
 struct SsrRendererPool {
     renderers: RwLock<Vec<Renderer>>,
     incremental_cache: Option<RwLock<dioxus_isrg::IncrementalRenderer>>,
+    /// Routes with a stale cache entry that are currently being regenerated in the background
+    revalidating: Arc<Mutex<HashSet<String>>>,
 }
 
 impl SsrRendererPool {
@@ -77,35 +128,30 @@ impl SsrRendererPool {
         Self {
             renderers,
             incremental_cache: incremental.map(|cache| RwLock::new(cache.build())),
+            revalidating: Default::default(),
         }
     }
 
-    /// Look for a cached route in the incremental cache and send it into the render channel if it exists
-    fn check_cached_route(
-        &self,
-        route: &str,
-        render_into: &mut Sender<Result<String, dioxus_isrg::IncrementalRendererError>>,
-    ) -> Option<RenderFreshness> {
+    /// Run a function with the incremental cache if incremental rendering is enabled
+    fn with_incremental(&self, f: impl FnOnce(&mut dioxus_isrg::IncrementalRenderer)) {
+        if let Some(incremental) = &self.incremental_cache {
+            if let Ok(mut incremental) = incremental.write() {
+                f(&mut incremental);
+            }
+        }
+    }
+
+    /// Look for a cached route in the incremental cache
+    fn check_cached_route(&self, route: &str) -> Option<CachedRender> {
         if let Some(incremental) = &self.incremental_cache {
             if let Ok(mut incremental) = incremental.write() {
                 match incremental.get(route) {
-                    Ok(Some(cached_render)) => {
-                        let CachedRender {
-                            freshness,
-                            response,
-                            ..
-                        } = cached_render;
-                        _ = render_into.start_send(String::from_utf8(response.to_vec()).map_err(
-                            |err| dioxus_isrg::IncrementalRendererError::Other(Box::new(err)),
-                        ));
-                        return Some(freshness);
-                    }
+                    Ok(cached_render) => return cached_render,
                     Err(e) => {
                         tracing::error!(
                             "Failed to get route \"{route}\" from incremental cache: {e}"
                         );
                     }
-                    _ => {}
                 }
             }
         }
@@ -114,12 +160,128 @@ impl SsrRendererPool {
 
     /// Render a virtual dom into a stream. This method will return immediately and continue streaming the result in the background
     /// The streaming is canceled when the stream the function returns is dropped
+    ///
+    /// If the route is cached, the cached render is returned instead. Stale cache entries are returned immediately and
+    /// regenerated in the background.
     async fn render_to(
         self: Arc<Self>,
         cfg: &ServeConfig,
         route: String,
         virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
         server_context: &DioxusServerContext,
+    ) -> Result<
+        RenderedRoute<impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>>,
+        SSRError,
+    > {
+        // before we even spawn anything, we can check synchronously if we have the route cached
+        let cacheable = cfg.cache_rules.rule_for(&route) != Some(CacheRule::Never);
+        let cache_key = cache_key(cfg, &route, server_context).filter(|_| cacheable);
+        if let Some(cached_render) = cache_key.and_then(|key| self.check_cached_route(&key)) {
+            if cached_render.freshness.is_stale() {
+                self.revalidate(cfg, route, virtual_dom_factory, server_context);
+            }
+            return Ok(RenderedRoute::Cached(cached_render));
+        }
+
+        let (freshness, stream) = self
+            .render_uncached(cfg, route, virtual_dom_factory, server_context)
+            .await?;
+        Ok(RenderedRoute::Streaming(freshness, stream))
+    }
+
+    /// Regenerate a stale route in the background. Only one regeneration runs for each route at a time.
+    fn revalidate(
+        self: Arc<Self>,
+        cfg: &ServeConfig,
+        route: String,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+    ) {
+        /// Removes the route from the set of regenerating routes when the regeneration finishes or fails
+        struct RevalidationGuard {
+            revalidating: Arc<Mutex<HashSet<String>>>,
+            route: String,
+        }
+
+        impl Drop for RevalidationGuard {
+            fn drop(&mut self) {
+                if let Ok(mut revalidating) = self.revalidating.lock() {
+                    revalidating.remove(&self.route);
+                }
+            }
+        }
+
+        let Some(cache_key) = cache_key(cfg, &route, server_context) else {
+            return;
+        };
+        match self.revalidating.lock() {
+            Ok(mut revalidating) => {
+                if !revalidating.insert(cache_key.clone()) {
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
+
+        tracing::trace!("Regenerating stale route {cache_key:?} in the background");
+        let guard = RevalidationGuard {
+            revalidating: self.revalidating.clone(),
+            route: cache_key,
+        };
+        let cfg = cfg.clone();
+        let server_context = revalidation_context(&cfg, server_context);
+        spawn_platform(move || async move {
+            let _guard = guard;
+            match self
+                .render_uncached(&cfg, route.clone(), virtual_dom_factory, &server_context)
+                .await
+            {
+                // The render is only written to the cache once the stream finishes, so we need to drive it to completion
+                Ok((_, mut stream)) => while stream.next().await.is_some() {},
+                Err(SSRError::Incremental(err)) => {
+                    tracing::error!("Failed to regenerate route {route:?}: {err}");
+                }
+                Err(SSRError::Routing(err)) => {
+                    tracing::error!("Failed to regenerate route {route:?}: {err}");
+                }
+            }
+        });
+    }
+
+    /// Compress a render that was stored in the incremental cache on a blocking thread and store the compressed
+    /// variants once they are ready.
+    fn compress_in_background(self: Arc<Self>, pending: dioxus_isrg::PendingCompression) {
+        let compress = move || {
+            let pending = match pending.compress() {
+                Ok(pending) => pending,
+                Err(err) => {
+                    tracing::error!("Failed to compress cached route: {err}");
+                    return;
+                }
+            };
+            let Some(incremental) = &self.incremental_cache else {
+                return;
+            };
+            if let Ok(mut incremental) = incremental.write() {
+                if let Err(err) = incremental.finish_compression(pending) {
+                    tracing::error!("Failed to store compressed route: {err}");
+                }
+            }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        tokio::task::spawn_blocking(compress);
+        #[cfg(target_arch = "wasm32")]
+        compress();
+    }
+
+    /// Render a virtual dom into a stream without checking the incremental cache.
+    async fn render_uncached(
+        self: Arc<Self>,
+        cfg: &ServeConfig,
+        route: String,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
     ) -> Result<
         (
             RenderFreshness,
@@ -160,20 +322,10 @@ impl SsrRendererPool {
 
         let (initial_result_tx, initial_result_rx) = futures_channel::oneshot::channel();
 
-        // before we even spawn anything, we can check synchronously if we have the route cached
-        if let Some(freshness) = self.check_cached_route(&route, &mut into) {
-            return Ok((
-                freshness,
-                ReceiverWithDrop {
-                    receiver: rx,
-                    cancel_task: None,
-                },
-            ));
-        }
-
         let wrapper = FullstackHTMLTemplate { cfg: cfg.clone() };
 
         let server_context = server_context.clone();
+        let render_context = server_context.clone();
         let mut renderer = self
             .renderers
             .write()
@@ -183,28 +335,12 @@ impl SsrRendererPool {
 
         let myself = self.clone();
         let streaming_mode = cfg.streaming_mode;
+        let route_cache_rule = cfg.cache_rules.rule_for(&route);
+        let cache_key = cache_key(cfg, &route, &server_context);
 
         let create_render_future = move || async move {
-            let mut virtual_dom = virtual_dom_factory();
-            let document = Rc::new(ServerDocument::default());
-            virtual_dom.provide_root_context(document.clone());
-            // If there is a base path, trim the base path from the route and add the base path formatting to the
-            // history provider
-            let history;
-            if let Some(base_path) = base_path() {
-                let base_path = base_path.trim_matches('/');
-                let base_path = format!("/{base_path}");
-                let route = route.strip_prefix(&base_path).unwrap_or(&route);
-                history =
-                    dioxus_history::MemoryHistory::with_initial_path(route).with_prefix(base_path);
-            } else {
-                history = dioxus_history::MemoryHistory::with_initial_path(&route);
-            }
-
-            let streaming_context = in_root_scope(&virtual_dom, StreamingContext::new);
-            virtual_dom.provide_root_context(Rc::new(history) as Rc<dyn dioxus_history::History>);
-            virtual_dom.provide_root_context(document.clone() as Rc<dyn Document>);
-            virtual_dom.provide_root_context(streaming_context);
+            let (mut virtual_dom, streaming_context) =
+                create_virtual_dom(virtual_dom_factory, &route);
 
             // rebuild the virtual dom
             virtual_dom.rebuild_in_place();
@@ -236,37 +372,13 @@ impl SsrRendererPool {
             }
 
             // check if there are any errors
-            let errors = virtual_dom.in_runtime(|| {
-                let error_context: ErrorContext = ScopeId::APP
-                    .consume_context()
-                    .expect("The root should be under an error boundary");
-                let errors = error_context.errors();
-                errors.to_vec()
-            });
-            if errors.is_empty() {
+            match render_errors(&virtual_dom) {
                 // If routing was successful, we can return a 200 status and render into the stream
-                _ = initial_result_tx.send(Ok(()));
-            } else {
-                // If there was an error while routing, return the error with a 400 status
-                // Return a routing error if any of the errors were a routing error
-                let routing_error = errors.iter().find_map(|err| err.downcast().cloned());
-                if let Some(routing_error) = routing_error {
-                    _ = initial_result_tx.send(Err(SSRError::Routing(routing_error)));
+                Ok(()) => _ = initial_result_tx.send(Ok(())),
+                Err(err) => {
+                    _ = initial_result_tx.send(Err(err));
                     return;
                 }
-                #[derive(thiserror::Error, Debug)]
-                #[error("{0}")]
-                pub struct ErrorWhileRendering(String);
-                let mut all_errors = String::new();
-                for error in errors {
-                    all_errors += &error.to_string();
-                    all_errors += "\n"
-                }
-                let error = ErrorWhileRendering(all_errors);
-                _ = initial_result_tx.send(Err(SSRError::Incremental(
-                    IncrementalRendererError::Other(Box::new(error)),
-                )));
-                return;
             }
 
             let mut pre_body = String::new();
@@ -363,7 +475,14 @@ impl SsrRendererPool {
             }
 
             // If incremental rendering is enabled, add the new render to the cache without the streaming bits
-            if let Some(incremental) = &self.incremental_cache {
+            let mut pending_compression = None;
+            let cache_rule = render_context.cache_rule().or(route_cache_rule);
+            let incremental = self
+                .incremental_cache
+                .as_ref()
+                .filter(|_| cache_rule != Some(CacheRule::Never))
+                .zip(cache_key);
+            if let Some((incremental, cache_key)) = incremental {
                 let mut cached_render = String::new();
                 if let Err(err) = wrapper.render_head(&mut cached_render, &virtual_dom) {
                     throw_error!(err);
@@ -377,8 +496,22 @@ impl SsrRendererPool {
                 }
                 cached_render.push_str(&post_streaming);
 
+                let max_age = match cache_rule {
+                    Some(CacheRule::MaxAge(max_age)) => Some(max_age),
+                    _ => None,
+                };
+                // Only store the render while the cache is locked. Compressing it is slow, so that waits until the
+                // response is finished
                 if let Ok(mut incremental) = incremental.write() {
-                    let _ = incremental.cache(route, cached_render);
+                    match incremental.cache_uncompressed(
+                        cache_key,
+                        cached_render,
+                        render_context.cache_tags(),
+                        max_age,
+                    ) {
+                        Ok((_, pending)) => pending_compression = pending,
+                        Err(err) => tracing::error!("Failed to cache route: {err}"),
+                    }
                 }
             }
 
@@ -386,10 +519,15 @@ impl SsrRendererPool {
 
             renderer.reset_render_components();
             myself.renderers.write().unwrap().push(renderer);
+
+            if let Some(pending) = pending_compression {
+                myself.compress_in_background(pending);
+            }
         };
 
-        let join_handle = spawn_platform(move || {
-            ProvideServerContext::new(create_render_future(), server_context)
+        let join_handle = spawn_platform({
+            let server_context = server_context.clone();
+            move || ProvideServerContext::new(create_render_future(), server_context)
         });
 
         // Wait for the initial result which determines the status code
@@ -397,8 +535,21 @@ impl SsrRendererPool {
             SSRError::Incremental(IncrementalRendererError::Other(Box::new(err)))
         })??;
 
+        // The cache rule may have been changed while rendering the initial chunk
+        let freshness = match server_context.cache_rule().or(route_cache_rule) {
+            Some(CacheRule::MaxAge(max_age)) => RenderFreshness::now(Some(max_age)),
+            Some(CacheRule::Never) => {
+                server_context.headers_mut().insert(
+                    http::header::CACHE_CONTROL,
+                    http::HeaderValue::from_static("no-store"),
+                );
+                RenderFreshness::now(None)
+            }
+            None => RenderFreshness::now(None),
+        };
+
         Ok((
-            RenderFreshness::now(None),
+            freshness,
             ReceiverWithDrop {
                 receiver: rx,
                 cancel_task: Some(join_handle),
@@ -407,6 +558,131 @@ impl SsrRendererPool {
     }
 }
 
+impl SsrRendererPool {
+    /// Render a route to a complete html document after every suspense boundary has resolved. The document does not
+    /// contain any streaming placeholders.
+    ///
+    /// If `allow_routing_errors` is set, the document is returned even if the route failed to parse so the router's
+    /// error page can be rendered.
+    async fn render_static(
+        self: Arc<Self>,
+        cfg: &ServeConfig,
+        route: String,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        allow_routing_errors: bool,
+    ) -> Result<String, SSRError> {
+        let wrapper = FullstackHTMLTemplate { cfg: cfg.clone() };
+        let server_context = server_context.clone();
+        let mut renderer = self
+            .renderers
+            .write()
+            .unwrap()
+            .pop()
+            .unwrap_or_else(pre_renderer);
+        let myself = self.clone();
+
+        let create_render_future = move || async move {
+            let result: Result<String, SSRError> = async {
+                let (mut virtual_dom, _) = create_virtual_dom(virtual_dom_factory, &route);
+                virtual_dom.rebuild_in_place();
+                virtual_dom.wait_for_suspense().await;
+
+                match render_errors(&virtual_dom) {
+                    Err(SSRError::Routing(_)) if allow_routing_errors => {}
+                    result => result?,
+                }
+
+                let mut html = String::new();
+                wrapper
+                    .render_head(&mut html, &virtual_dom)
+                    .map_err(SSRError::Incremental)?;
+                renderer.reset_hydration();
+                renderer
+                    .render_to(&mut html, &virtual_dom)
+                    .map_err(|err| SSRError::Incremental(err.into()))?;
+                wrapper
+                    .render_after_main(&mut html, &virtual_dom)
+                    .map_err(SSRError::Incremental)?;
+                wrapper
+                    .render_after_body(&mut html)
+                    .map_err(SSRError::Incremental)?;
+                Ok(html)
+            }
+            .await;
+
+            // Return the renderer to the pool even if the route failed to render
+            myself.renderers.write().unwrap().push(renderer);
+            result
+        };
+
+        spawn_platform(move || ProvideServerContext::new(create_render_future(), server_context))
+            .await
+            .map_err(|err| SSRError::Incremental(IncrementalRendererError::Other(Box::new(err))))?
+    }
+}
+
+/// Create a virtual dom for a route with the document, history and streaming context the server renderer needs
+fn create_virtual_dom(
+    virtual_dom_factory: impl FnOnce() -> VirtualDom,
+    route: &str,
+) -> (VirtualDom, StreamingContext) {
+    let virtual_dom = virtual_dom_factory();
+    let document = Rc::new(ServerDocument::default());
+    virtual_dom.provide_root_context(document.clone());
+    // If there is a base path, trim the base path from the route and add the base path formatting to the
+    // history provider
+    let history;
+    if let Some(base_path) = base_path() {
+        let base_path = base_path.trim_matches('/');
+        let base_path = format!("/{base_path}");
+        let route = route.strip_prefix(&base_path).unwrap_or(route);
+        history = dioxus_history::MemoryHistory::with_initial_path(route).with_prefix(base_path);
+    } else {
+        history = dioxus_history::MemoryHistory::with_initial_path(route);
+    }
+
+    let streaming_context = in_root_scope(&virtual_dom, StreamingContext::new);
+    virtual_dom.provide_root_context(Rc::new(history) as Rc<dyn dioxus_history::History>);
+    virtual_dom.provide_root_context(document.clone() as Rc<dyn Document>);
+    virtual_dom.provide_root_context(streaming_context);
+
+    (virtual_dom, streaming_context)
+}
+
+/// Check if any errors were thrown to the root error boundary while rendering
+fn render_errors(virtual_dom: &VirtualDom) -> Result<(), SSRError> {
+    let errors = virtual_dom.in_runtime(|| {
+        let error_context: ErrorContext = ScopeId::APP
+            .consume_context()
+            .expect("The root should be under an error boundary");
+        let errors = error_context.errors();
+        errors.to_vec()
+    });
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    // If there was an error while routing, return the error with a 400 status
+    // Return a routing error if any of the errors were a routing error
+    let routing_error = errors.iter().find_map(|err| err.downcast().cloned());
+    if let Some(routing_error) = routing_error {
+        return Err(SSRError::Routing(routing_error));
+    }
+    #[derive(thiserror::Error, Debug)]
+    #[error("{0}")]
+    pub struct ErrorWhileRendering(String);
+    let mut all_errors = String::new();
+    for error in errors {
+        all_errors += &error.to_string();
+        all_errors += "\n"
+    }
+    let error = ErrorWhileRendering(all_errors);
+    Err(SSRError::Incremental(IncrementalRendererError::Other(
+        Box::new(error),
+    )))
+}
+
 /// Create the streaming render component callback. It will keep track of what scopes are mounted to what pending
 /// suspense boundaries in the DOM.
 ///
@@ -538,6 +814,10 @@ fn take_from_scope(context: &HydrationContext, vdom: &VirtualDom, scope: ScopeId
                 take_from_vnode(context, vdom, &node);
             }
         }
+        // Portals render their children in place on the client, even though they are mounted somewhere else
+        if let Some((_, node)) = scope.portal() {
+            take_from_vnode(context, vdom, &node);
+        }
         if let Some(node) = scope.try_root_node() {
             take_from_vnode(context, vdom, node);
         }
@@ -577,6 +857,66 @@ impl SSRState {
         }
     }
 
+    /// Remove a route from the incremental cache.
+    pub fn invalidate(&self, route: &str) {
+        self.renderers
+            .with_incremental(|cache| cache.invalidate(route));
+    }
+
+    /// Remove the entry stored under exactly this key from the incremental cache, like a route without
+    /// [variants](crate::CacheKey) or a single variant of a route. This is cheaper than [`Self::invalidate`].
+    pub fn invalidate_key(&self, key: &str) {
+        self.renderers
+            .with_incremental(|cache| cache.invalidate_key(key));
+    }
+
+    /// Remove every route rendered with the tag from the incremental cache. Tags are attached during rendering with
+    /// [`DioxusServerContext::add_cache_tag`].
+    pub fn invalidate_tag(&self, tag: &str) {
+        self.renderers
+            .with_incremental(|cache| cache.invalidate_tag(tag));
+    }
+
+    /// Remove every route that starts with the prefix from the incremental cache.
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        self.renderers
+            .with_incremental(|cache| cache.invalidate_prefix(prefix));
+    }
+
+    /// Remove every route from the incremental cache.
+    pub fn invalidate_all(&self) {
+        self.renderers
+            .with_incremental(|cache| cache.invalidate_all());
+    }
+
+    /// Get the metrics of the incremental cache or `None` if incremental rendering is disabled.
+    pub fn cache_metrics(&self) -> Option<dioxus_isrg::CacheMetrics> {
+        let incremental = self.renderers.incremental_cache.as_ref()?;
+        let incremental = incremental.read().ok()?;
+        Some(incremental.metrics())
+    }
+
+    /// Render the application to a complete HTML document without streaming or caching. This is used for static site generation.
+    pub(crate) async fn render_static(
+        &self,
+        route: String,
+        cfg: &ServeConfig,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        allow_routing_errors: bool,
+    ) -> Result<String, SSRError> {
+        self.renderers
+            .clone()
+            .render_static(
+                cfg,
+                route,
+                virtual_dom_factory,
+                server_context,
+                allow_routing_errors,
+            )
+            .await
+    }
+
     /// Render the application to HTML.
     pub async fn render<'a>(
         &'a self,
@@ -590,6 +930,36 @@ impl SSRState {
             impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>,
         ),
         SSRError,
+    > {
+        match self
+            .render_route(route, cfg, virtual_dom_factory, server_context)
+            .await?
+        {
+            RenderedRoute::Cached(cached_render) => {
+                let html = String::from_utf8(cached_render.response)
+                    .map_err(|err| IncrementalRendererError::Other(Box::new(err)));
+                Ok((
+                    cached_render.freshness,
+                    futures_util::future::Either::Left(futures_util::stream::once(async { html })),
+                ))
+            }
+            RenderedRoute::Streaming(freshness, stream) => {
+                Ok((freshness, futures_util::future::Either::Right(stream)))
+            }
+        }
+    }
+
+    /// Render a route like [`Self::render`], but return cached routes with their pre-compressed variants instead of
+    /// streaming them.
+    pub(crate) async fn render_route<'a>(
+        &'a self,
+        route: String,
+        cfg: &'a ServeConfig,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &'a DioxusServerContext,
+    ) -> Result<
+        RenderedRoute<impl Stream<Item = Result<String, dioxus_isrg::IncrementalRendererError>>>,
+        SSRError,
     > {
         self.renderers
             .clone()
@@ -1,4 +1,7 @@
-use crate::{render::SSRError, with_server_context, DioxusServerContext, SSRState, ServeConfig};
+use crate::{
+    render::{RenderedRoute, SSRError},
+    with_server_context, DioxusServerContext, SSRState, ServeConfig,
+};
 use crate::{ContextProviders, ProvideServerContext};
 use axum::body;
 use axum::extract::State;
@@ -8,8 +11,9 @@ use axum::{
     http::{Request, Response, StatusCode},
     response::IntoResponse,
 };
+use dioxus_isrg::{etag_matches, CachedRender};
 use dioxus_lib::prelude::{Element, VirtualDom};
-use http::header::*;
+use http::{header::*, HeaderMap};
 use server_fn::ServerFnTraitObj;
 use std::sync::Arc;
 
@@ -123,6 +127,7 @@ where
             .register_server_functions_with_context(cfg.context_providers.clone());
 
         let ssr_state = SSRState::new(&cfg);
+        let server = register_cache_invalidation(server, &cfg, &ssr_state);
 
         server.fallback(
             get(render_handler)
@@ -221,6 +226,7 @@ where
         let server = self.register_server_functions_with_context(cfg.context_providers.clone());
 
         let ssr_state = SSRState::new(&cfg);
+        let server = register_cache_invalidation(server, &cfg, &ssr_state);
 
         server.fallback(
             get(render_handler)
@@ -251,6 +257,110 @@ where
     }
 }
 
+/// Register the cache invalidation endpoint configured with [`ServeConfigBuilder::cache_invalidation_endpoint`](crate::ServeConfigBuilder::cache_invalidation_endpoint)
+fn register_cache_invalidation<S>(
+    router: Router<S>,
+    cfg: &ServeConfig,
+    ssr_state: &SSRState,
+) -> Router<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    use axum::extract::Path;
+
+    let Some(endpoint) = &cfg.cache_invalidation else {
+        return router;
+    };
+    if cfg.incremental.is_none() {
+        tracing::warn!(
+            "A cache invalidation endpoint was registered, but incremental rendering is disabled"
+        );
+    }
+
+    #[derive(Clone)]
+    struct InvalidationState {
+        token: Arc<str>,
+        ssr_state: SSRState,
+    }
+
+    impl InvalidationState {
+        fn authorized(&self, headers: &HeaderMap) -> bool {
+            let Some(token) = headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+            else {
+                return false;
+            };
+            // Compare every byte so the time taken doesn't leak how much of the token matched
+            token.len() == self.token.len()
+                && token
+                    .bytes()
+                    .zip(self.token.bytes())
+                    .fold(0, |diff, (a, b)| diff | (a ^ b))
+                    == 0
+        }
+
+        fn invalidate(&self, headers: &HeaderMap, f: impl FnOnce(&SSRState)) -> StatusCode {
+            if !self.authorized(headers) {
+                return StatusCode::UNAUTHORIZED;
+            }
+            f(&self.ssr_state);
+            StatusCode::NO_CONTENT
+        }
+    }
+
+    let state = InvalidationState {
+        token: endpoint.token.as_str().into(),
+        ssr_state: ssr_state.clone(),
+    };
+    let base = endpoint.path.trim_end_matches('/');
+
+    router
+        .route(
+            &format!("{base}/all"),
+            post(
+                |State(state): State<InvalidationState>, headers: HeaderMap| async move {
+                    state.invalidate(&headers, |ssr| ssr.invalidate_all())
+                },
+            )
+            .with_state(state.clone()),
+        )
+        .route(
+            &format!("{base}/route/{{*route}}"),
+            post(
+                |State(state): State<InvalidationState>,
+                 headers: HeaderMap,
+                 Path(route): Path<String>| async move {
+                    state.invalidate(&headers, |ssr| ssr.invalidate(&format!("/{route}")))
+                },
+            )
+            .with_state(state.clone()),
+        )
+        .route(
+            &format!("{base}/prefix/{{*prefix}}"),
+            post(
+                |State(state): State<InvalidationState>,
+                 headers: HeaderMap,
+                 Path(prefix): Path<String>| async move {
+                    state.invalidate(&headers, |ssr| ssr.invalidate_prefix(&format!("/{prefix}")))
+                },
+            )
+            .with_state(state.clone()),
+        )
+        .route(
+            &format!("{base}/tag/{{tag}}"),
+            post(
+                |State(state): State<InvalidationState>,
+                 headers: HeaderMap,
+                 Path(tag): Path<String>| async move {
+                    state.invalidate(&headers, |ssr| ssr.invalidate_tag(&tag))
+                },
+            )
+            .with_state(state),
+        )
+}
+
 pub type AxumServerFn = ServerFnTraitObj<http::Request<Body>, http::Response<Body>>;
 
 pub(crate) fn collect_raw_server_fns() -> Vec<&'static AxumServerFn> {
@@ -432,6 +542,9 @@ pub async fn render_handler(
         .path_and_query()
         .ok_or(StatusCode::BAD_REQUEST)?
         .to_string();
+    let header_str = |name| parts.headers.get(name).and_then(|v| v.to_str().ok());
+    let accept_encoding = header_str(ACCEPT_ENCODING).map(ToString::to_string);
+    let if_none_match = header_str(IF_NONE_MATCH).map(ToString::to_string);
     let parts: Arc<parking_lot::RwLock<http::request::Parts>> =
         Arc::new(parking_lot::RwLock::new(parts));
     // Create the server context with info from the request
@@ -440,12 +553,24 @@ pub async fn render_handler(
     add_server_context(&server_context, &state.config.context_providers);
 
     match ssr_state
-        .render(url, cfg, build_virtual_dom, &server_context)
+        .render_route(url, cfg, build_virtual_dom, &server_context)
         .await
     {
-        Ok((freshness, rx)) => {
+        Ok(RenderedRoute::Cached(cached_render)) => {
+            let mut response = cached_response(
+                &cached_render,
+                accept_encoding.as_deref(),
+                if_none_match.as_deref(),
+            );
+            cached_render.freshness.write(response.headers_mut());
+            append_vary(cfg, response.headers_mut());
+            server_context.send_response(&mut response);
+            Ok(response)
+        }
+        Ok(RenderedRoute::Streaming(freshness, rx)) => {
             let mut response = axum::response::Html::from(Body::from_stream(rx)).into_response();
             freshness.write(response.headers_mut());
+            append_vary(cfg, response.headers_mut());
             server_context.send_response(&mut response);
             Result::<http::Response<axum::body::Body>, StatusCode>::Ok(response)
         }
@@ -463,6 +588,46 @@ pub async fn render_handler(
     }
 }
 
+/// Build the response for a cached render. The pre-compressed variant that matches the `Accept-Encoding` header is
+/// sent, or a `304 Not Modified` if the client already has the render.
+fn cached_response(
+    cached_render: &CachedRender,
+    accept_encoding: Option<&str>,
+    if_none_match: Option<&str>,
+) -> Response<axum::body::Body> {
+    let not_modified =
+        if_none_match.is_some_and(|if_none_match| etag_matches(if_none_match, &cached_render.etag));
+    let mut response = if not_modified {
+        Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+            .unwrap()
+    } else {
+        let (encoding, body) = cached_render.body_for(accept_encoding);
+        let mut response = axum::response::Html::from(body.to_vec()).into_response();
+        if let Some(encoding) = encoding {
+            response.headers_mut().insert(
+                CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.as_str()),
+            );
+        }
+        response
+    };
+    let headers = response.headers_mut();
+    headers.insert(VARY, HeaderValue::from_static("accept-encoding"));
+    if let Ok(etag) = HeaderValue::from_str(&cached_render.etag) {
+        headers.insert(ETAG, etag);
+    }
+    response
+}
+
+/// Add the headers the cache key depends on to the `Vary` header
+fn append_vary(cfg: &ServeConfig, headers: &mut HeaderMap) {
+    if let Some(vary) = cfg.cache_key.as_ref().and_then(|key| key.vary_header()) {
+        headers.append(VARY, vary);
+    }
+}
+
 fn report_err<E: std::fmt::Display>(e: E) -> Response<axum::body::Body> {
     Response::builder()
         .status(StatusCode::INTERNAL_SERVER_ERROR)
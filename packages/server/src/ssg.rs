@@ -0,0 +1,434 @@
+//! In process static site generation driven by the [`Routable::SITE_MAP`] of the app.
+
+use crate::{add_server_context, render::SSRError, DioxusServerContext, SSRState, ServeConfig};
+use dioxus_lib::prelude::VirtualDom;
+use dioxus_router::prelude::Routable;
+use futures_util::StreamExt;
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the file in the output directory that records what each route was rendered from
+const MANIFEST_FILE: &str = ".dioxus-ssg-manifest";
+
+type RouteGenerator<R> = Arc<dyn Fn() -> Vec<R> + Send + Sync>;
+type RouteFingerprint<R> = Arc<dyn Fn(&R) -> String + Send + Sync>;
+
+/// The configuration for generating a static site from a [`Routable`] enum with `dx bundle --ssg`.
+///
+/// Every static route in [`Routable::SITE_MAP`] is rendered automatically. Routes with dynamic segments need to be
+/// listed with [`Self::dynamic_routes`].
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # #[component]
+/// # fn Home() -> Element { todo!() }
+/// # #[component]
+/// # fn Blog(id: u32) -> Element { todo!() }
+/// # fn app() -> Element { todo!() }
+/// #[derive(Routable, Clone, PartialEq)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/blog/:id")]
+///     Blog { id: u32 },
+/// }
+///
+/// LaunchBuilder::new()
+///     .with_cfg(server_only! {
+///         ServeConfig::builder().static_site(
+///             StaticSiteConfig::<Route>::new()
+///                 .dynamic_routes(|| (1..=10).map(|id| Route::Blog { id }))
+///         )
+///     })
+///     .launch(app);
+/// ```
+pub struct StaticSiteConfig<R: Routable> {
+    dynamic_routes: Vec<RouteGenerator<R>>,
+    fingerprint: Option<RouteFingerprint<R>>,
+    not_found_path: String,
+    _route: PhantomData<fn() -> R>,
+}
+
+impl<R: Routable> Default for StaticSiteConfig<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Routable> StaticSiteConfig<R> {
+    /// Create a new static site configuration that renders every static route of `R`.
+    pub fn new() -> Self {
+        Self {
+            dynamic_routes: Vec::new(),
+            fingerprint: None,
+            not_found_path: "/404".to_string(),
+            _route: PhantomData,
+        }
+    }
+
+    /// Add a generator for routes with dynamic segments. The generator is called once every time the site is generated.
+    pub fn dynamic_routes<I: IntoIterator<Item = R>>(
+        mut self,
+        generator: impl Fn() -> I + Send + Sync + 'static,
+    ) -> Self {
+        self.dynamic_routes
+            .push(Arc::new(move || generator().into_iter().collect()));
+        self
+    }
+
+    /// Set a function that describes the data a route renders, like the last modified time of a blog post.
+    ///
+    /// Routes are only rendered again on the next build if their fingerprint, the server executable or the index.html template changed.
+    pub fn fingerprint(
+        mut self,
+        fingerprint: impl Fn(&R) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.fingerprint = Some(Arc::new(fingerprint));
+        self
+    }
+
+    /// Set the path that is rendered into `404.html` (default: `/404`). If the path doesn't match any route, the
+    /// error page of the router is rendered.
+    pub fn not_found_path(mut self, path: impl Into<String>) -> Self {
+        self.not_found_path = path.into();
+        self
+    }
+}
+
+/// A route that should be statically generated
+pub(crate) struct StaticRoute {
+    pub(crate) route: String,
+    pub(crate) fingerprint: Option<String>,
+}
+
+/// A type erased [`StaticSiteConfig`]
+pub(crate) trait StaticSiteRoutes: Send + Sync {
+    /// Get every route that should be generated
+    fn routes(&self) -> Vec<StaticRoute>;
+
+    /// Get the path that should be rendered into the 404 page
+    fn not_found_path(&self) -> &str;
+}
+
+impl<R: Routable> StaticSiteRoutes for StaticSiteConfig<R> {
+    fn routes(&self) -> Vec<StaticRoute> {
+        let routes = R::static_routes()
+            .into_iter()
+            .chain(self.dynamic_routes.iter().flat_map(|generator| generator()));
+
+        let mut seen = std::collections::HashSet::new();
+        routes
+            .filter_map(|route| {
+                let path = route.to_string();
+                seen.insert(path.clone()).then(|| StaticRoute {
+                    fingerprint: self.fingerprint.as_ref().map(|f| f(&route)),
+                    route: path,
+                })
+            })
+            .collect()
+    }
+
+    fn not_found_path(&self) -> &str {
+        &self.not_found_path
+    }
+}
+
+/// The result of generating a static site
+#[derive(Debug, Default)]
+pub struct StaticSiteReport {
+    /// The routes that were rendered
+    pub rendered: Vec<String>,
+    /// The routes that were skipped because their inputs didn't change since the last build
+    pub skipped: Vec<String>,
+    /// The routes that failed to render with the error that occurred
+    pub failed: Vec<(String, String)>,
+}
+
+impl StaticSiteReport {
+    /// Check if every route was generated successfully
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// An error that prevented the static site from being generated
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum StaticSiteError {
+    /// The server config does not contain a [`StaticSiteConfig`]
+    #[error("Static site generation is not configured. Add a `StaticSiteConfig` with `ServeConfigBuilder::static_site`")]
+    NotConfigured,
+    /// An IO error occurred while writing the site
+    #[error("IoError: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Render every route configured with [`ServeConfigBuilder::static_site`](crate::ServeConfigBuilder::static_site) in process
+/// and write the result into `out_dir`.
+///
+/// Each route is written into `{route}/index.html` and the not found page is written into `404.html`. Routes whose inputs
+/// didn't change since the last generation into the same directory are skipped.
+pub async fn generate_static_site(
+    cfg: &ServeConfig,
+    build_virtual_dom: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    out_dir: &Path,
+) -> Result<StaticSiteReport, StaticSiteError> {
+    let static_site = cfg
+        .static_site
+        .clone()
+        .ok_or(StaticSiteError::NotConfigured)?;
+
+    // The cache is not used for static generation, the output directory is the cache
+    let mut render_cfg = cfg.clone();
+    render_cfg.incremental = None;
+    let render_cfg = &render_cfg;
+    let ssr_state = &SSRState::new(render_cfg);
+    let build_virtual_dom = Arc::new(build_virtual_dom);
+
+    std::fs::create_dir_all(out_dir)?;
+    let previous_manifest = Manifest::load(out_dir);
+    let build_hash = build_hash(cfg);
+
+    let render = |route: String, allow_routing_errors: bool| {
+        let build_virtual_dom = build_virtual_dom.clone();
+        let context_providers = render_cfg.context_providers.clone();
+        async move {
+            let parts = http::Request::builder()
+                .uri(&route)
+                .body(())
+                .unwrap_or_default()
+                .into_parts()
+                .0;
+            let server_context = DioxusServerContext::new(parts);
+            add_server_context(&server_context, &render_cfg.context_providers);
+            ssr_state
+                .render_static(
+                    route,
+                    render_cfg,
+                    move || {
+                        let mut vdom = build_virtual_dom();
+                        for state in context_providers.as_slice() {
+                            vdom.insert_any_root_context(state());
+                        }
+                        vdom
+                    },
+                    &server_context,
+                    allow_routing_errors,
+                )
+                .await
+        }
+    };
+
+    let mut report = StaticSiteReport::default();
+    let mut manifest = Manifest::default();
+
+    let parallelism = std::thread::available_parallelism().map_or(4, usize::from);
+    let mut renders = futures_util::stream::iter(static_site.routes())
+        .map(|StaticRoute { route, fingerprint }| {
+            let input = hash_of(&(build_hash, &route, &fingerprint));
+            let path = route_output_path(out_dir, &route);
+            let previous = previous_manifest.entries.get(&route).copied();
+            async move {
+                let Some(path) = path else {
+                    let err = format!("The route {route} escapes the output directory");
+                    return (route, Err(err), ManifestEntry::default());
+                };
+                // Skip the route if it was rendered from the same inputs and the output wasn't touched since
+                if let Some(previous) = previous.filter(|previous| previous.input == input) {
+                    let unchanged =
+                        std::fs::read(&path).is_ok_and(|html| hash_of(&html) == previous.output);
+                    if unchanged {
+                        return (route, Ok(None), previous);
+                    }
+                }
+                match render(route.clone(), false).await {
+                    Ok(html) => {
+                        let entry = ManifestEntry {
+                            input,
+                            output: hash_of(&html.as_bytes()),
+                        };
+                        let written = write_file(&path, &html).map_err(|err| err.to_string());
+                        (route, written.map(|_| Some(html)), entry)
+                    }
+                    Err(err) => (
+                        route,
+                        Err(ssr_error_to_string(err)),
+                        ManifestEntry::default(),
+                    ),
+                }
+            }
+        })
+        .buffer_unordered(parallelism);
+
+    while let Some((route, result, entry)) = renders.next().await {
+        match result {
+            Ok(Some(_)) => {
+                tracing::debug!("Rendered {route} for SSG");
+                manifest.entries.insert(route.clone(), entry);
+                report.rendered.push(route);
+            }
+            Ok(None) => {
+                tracing::debug!("Skipped unchanged {route} for SSG");
+                manifest.entries.insert(route.clone(), entry);
+                report.skipped.push(route);
+            }
+            Err(err) => {
+                tracing::error!("Failed to render {route} for SSG: {err}");
+                report.failed.push((route, err));
+            }
+        }
+    }
+    drop(renders);
+
+    // Remove pages of routes that no longer exist
+    for route in previous_manifest.entries.keys() {
+        if !manifest.entries.contains_key(route) && !report.failed.iter().any(|(r, _)| r == route) {
+            let Some(path) = route_output_path(out_dir, route) else {
+                continue;
+            };
+            if let Err(err) = std::fs::remove_file(&path) {
+                tracing::warn!("Failed to remove stale page {path:?}: {err}");
+            }
+        }
+    }
+
+    let not_found_path = static_site.not_found_path().to_string();
+    match render(not_found_path.clone(), true).await {
+        Ok(html) => write_file(&out_dir.join("404.html"), &html)?,
+        Err(err) => report
+            .failed
+            .push((not_found_path, ssr_error_to_string(err))),
+    }
+
+    manifest.save(out_dir)?;
+
+    Ok(report)
+}
+
+/// Get the path of the html file a route is written to. Returns `None` if a segment of the route would escape its
+/// directory once decoded.
+fn route_output_path(out_dir: &Path, route: &str) -> Option<PathBuf> {
+    let (route, _) = route.split_once('#').unwrap_or((route, ""));
+    let (route, _) = route.split_once('?').unwrap_or((route, ""));
+    let mut path = out_dir.to_path_buf();
+    for segment in route.split('/').filter(|segment| !segment.is_empty()) {
+        let segment = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+        // Every segment must stay a single plain directory name after decoding
+        let mut components = Path::new(segment.as_ref()).components();
+        let plain = matches!(components.next(), Some(std::path::Component::Normal(_)))
+            && components.next().is_none();
+        if !plain || segment.contains(['/', '\\']) {
+            return None;
+        }
+        path.push(segment.as_ref());
+    }
+    Some(path.join("index.html"))
+}
+
+fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+fn ssr_error_to_string(err: SSRError) -> String {
+    match err {
+        SSRError::Incremental(err) => err.to_string(),
+        SSRError::Routing(err) => format!("Failed to parse route: {err}"),
+    }
+}
+
+/// A hash of everything shared between all routes that affects the rendered html
+fn build_hash(cfg: &ServeConfig) -> u64 {
+    let executable = std::env::current_exe().and_then(std::fs::read);
+    if let Err(err) = &executable {
+        tracing::warn!("Failed to read the server executable. Every route will be rendered: {err}");
+    }
+    let executable = executable.unwrap_or_else(|_| {
+        // Make the hash unique so nothing is skipped
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_le_bytes()
+            .to_vec()
+    });
+    hash_of(&(executable, &cfg.index))
+}
+
+fn hash_of(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Clone, Copy, Default)]
+struct ManifestEntry {
+    input: u64,
+    output: u64,
+}
+
+/// A record of the inputs and output of every route from the last generation. Each line is `{route}\t{input}\t{output}`
+#[derive(Default)]
+struct Manifest {
+    entries: HashMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    fn load(out_dir: &Path) -> Self {
+        let entries = std::fs::read_to_string(out_dir.join(MANIFEST_FILE))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split('\t');
+                let route = parts.next()?.to_string();
+                let input = u64::from_str_radix(parts.next()?, 16).ok()?;
+                let output = u64::from_str_radix(parts.next()?, 16).ok()?;
+                Some((route, ManifestEntry { input, output }))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    fn save(&self, out_dir: &Path) -> std::io::Result<()> {
+        let mut contents = String::new();
+        for (route, entry) in &self.entries {
+            contents += &format!("{route}\t{:x}\t{:x}\n", entry.input, entry.output);
+        }
+        std::fs::write(out_dir.join(MANIFEST_FILE), contents)
+    }
+}
+
+#[test]
+fn route_output_paths() {
+    let out_dir = Path::new("public");
+    assert_eq!(
+        route_output_path(out_dir, "/").as_deref(),
+        Some(Path::new("public/index.html"))
+    );
+    assert_eq!(
+        route_output_path(out_dir, "/blog/hello%20world?page=2#top").as_deref(),
+        Some(Path::new("public/blog/hello world/index.html"))
+    );
+}
+
+#[test]
+fn route_output_paths_stay_in_out_dir() {
+    let out_dir = Path::new("public");
+    for route in [
+        "/..",
+        "/blog/..%2F..%2Fetc",
+        "/blog/%2Fetc%2Fpasswd",
+        "/blog/..%5C..%5Cetc",
+        "/blog/%2E%2E",
+        "/blog/.",
+    ] {
+        assert_eq!(route_output_path(out_dir, route), None, "{route}");
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use dioxus_isrg::{CacheEntry, FileSystemCache, RenderCache};
+use dioxus_server::{DioxusServerContext, IncrementalRendererConfig, SSRState, ServeConfig};
+
+const INVALIDATE_AFTER: Duration = Duration::from_secs(60);
+
+/// Holds the render of the app until the test releases it
+#[derive(Clone)]
+struct Gate(Arc<tokio::sync::Semaphore>);
+
+fn app() -> Element {
+    let gate = use_context::<Gate>();
+    let released = use_resource(move || {
+        let gate = gate.clone();
+        async move { _ = gate.0.acquire().await }
+    });
+    released.suspend()?;
+    rsx! { "fresh post" }
+}
+
+/// Create a static directory with a render of `/post` that is past its max age but inside the stale window
+fn static_dir_with_stale_render() -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("dioxus-server-revalidation-{}", std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+    let timestamp = chrono::Utc::now() - chrono::Duration::seconds(90);
+    FileSystemCache::new(&dir)
+        .track_timestamps(true)
+        .put(
+            "/post",
+            CacheEntry::new(timestamp, "<html>stale post</html>"),
+        )
+        .unwrap();
+    dir
+}
+
+fn server_context() -> DioxusServerContext {
+    let request = http::Request::get("/post").body(()).unwrap();
+    DioxusServerContext::new(request.into_parts().0)
+}
+
+/// Wait until the condition is true or panic after a few seconds
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+    for _ in 0..500 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("timed out waiting for the condition");
+}
+
+#[tokio::test]
+async fn stale_route_is_revalidated_once() {
+    let dir = static_dir_with_stale_render();
+    let cfg = ServeConfig::builder()
+        .incremental(
+            IncrementalRendererConfig::new()
+                .static_dir(&dir)
+                .invalidate_after(INVALIDATE_AFTER)
+                .stale_while_revalidate(Duration::from_secs(600)),
+        )
+        .build()
+        .unwrap();
+    let state = SSRState::new(&cfg);
+
+    // Every regeneration is suspended until the test lets it finish
+    let renders = Arc::new(AtomicUsize::new(0));
+    let gate = Gate(Arc::new(tokio::sync::Semaphore::new(0)));
+    let factory = || {
+        let renders = renders.clone();
+        let gate = gate.clone();
+        move || {
+            renders.fetch_add(1, Ordering::SeqCst);
+            VirtualDom::new(app).with_root_context(gate)
+        }
+    };
+
+    for _ in 0..3 {
+        let context = server_context();
+        let Ok((freshness, _)) = state
+            .render("/post".to_string(), &cfg, factory(), &context)
+            .await
+        else {
+            panic!("failed to render the stale route");
+        };
+        assert!(freshness.is_stale());
+    }
+
+    wait_until(|| renders.load(Ordering::SeqCst) > 0).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+    // Once the regeneration finishes the fresh render is served without regenerating it again
+    gate.0.close();
+    let mut fresh = false;
+    for _ in 0..500 {
+        let context = server_context();
+        let Ok((freshness, _)) = state
+            .render("/post".to_string(), &cfg, factory(), &context)
+            .await
+        else {
+            panic!("failed to render the route");
+        };
+        if !freshness.is_stale() {
+            fresh = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(fresh, "the route was never regenerated");
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,144 @@
+//! Keep track of the signals and memos in the app so devtools can show what subscribes to them. Signals are only
+//! tracked in debug builds with the `devtools` feature.
+
+use dioxus_core::prelude::{ReactiveContext, ScopeId};
+use generational_box::GenerationalBoxId;
+use std::panic::Location;
+#[cfg(all(feature = "devtools", debug_assertions))]
+use {
+    dioxus_core::prelude::Runtime,
+    std::{
+        cell::RefCell,
+        collections::HashSet,
+        rc::{Rc, Weak},
+        sync::{self, Arc, Mutex},
+    },
+};
+
+/// A snapshot of a signal or memo, made with [`inspect_signals`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct SignalInfo {
+    /// The id of the signal
+    pub id: GenerationalBoxId,
+    /// The type of the value in the signal
+    pub type_name: &'static str,
+    /// Where the signal was created
+    pub created_at: Option<&'static Location<'static>>,
+    /// The scope the signal was created in
+    pub origin_scope: ScopeId,
+    /// The reactive contexts that rerun when the signal changes, like components that read it while rendering
+    pub subscribers: Vec<ReactiveContext>,
+    /// The reactive context a memo recomputes in. Every value the memo reads is subscribed to it. This is `None` for
+    /// signals.
+    pub reactive_context: Option<ReactiveContext>,
+}
+
+#[cfg(all(feature = "devtools", debug_assertions))]
+struct Entry {
+    id: GenerationalBoxId,
+    type_name: &'static str,
+    created_at: Option<&'static Location<'static>>,
+    origin_scope: ScopeId,
+    runtime: Weak<Runtime>,
+    // The subscribers are dropped with the signal, so a dead pointer means the signal was dropped
+    subscribers: sync::Weak<Mutex<HashSet<ReactiveContext>>>,
+    reactive_context: Option<ReactiveContext>,
+}
+
+#[cfg(all(feature = "devtools", debug_assertions))]
+thread_local! {
+    static SIGNALS: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(all(feature = "devtools", debug_assertions))]
+/// Remember a signal that was created in the current runtime. Signals created outside of a runtime are ignored.
+pub(crate) fn register(
+    id: GenerationalBoxId,
+    type_name: &'static str,
+    created_at: Option<&'static Location<'static>>,
+    origin_scope: ScopeId,
+    subscribers: &Arc<Mutex<HashSet<ReactiveContext>>>,
+) {
+    let Ok(runtime) = Runtime::current() else {
+        return;
+    };
+    SIGNALS.with_borrow_mut(|signals| {
+        // Forget the signals that were dropped before the list grows
+        if signals.len() == signals.capacity() {
+            signals.retain(|entry| entry.subscribers.strong_count() > 0);
+        }
+        signals.push(Entry {
+            id,
+            type_name,
+            created_at,
+            origin_scope,
+            runtime: Rc::downgrade(&runtime),
+            subscribers: Arc::downgrade(subscribers),
+            reactive_context: None,
+        });
+    });
+}
+
+#[cfg(all(feature = "devtools", debug_assertions))]
+/// Mark the signal with the id as the value of a memo that recomputes in the reactive context
+pub(crate) fn register_memo(id: GenerationalBoxId, reactive_context: ReactiveContext) {
+    SIGNALS.with_borrow_mut(|signals| {
+        if let Some(entry) = signals.iter_mut().rev().find(|entry| entry.id == id) {
+            entry.reactive_context = Some(reactive_context);
+        }
+    });
+}
+
+/// Get every signal and memo in the current runtime that hasn't been dropped, in the order they were created.
+///
+/// Signals are only tracked in debug builds with the `devtools` feature. Otherwise, this is always empty.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_signals::inspect_signals;
+/// fn app() -> Element {
+///     let count = use_signal(|| 0);
+///     rsx! { "{count}" }
+/// }
+///
+/// let dom = VirtualDom::prebuilt(app);
+/// let signals = dom.in_runtime(inspect_signals);
+/// # if cfg!(all(feature = "devtools", debug_assertions)) {
+/// // The app subscribed to the count while it rendered
+/// assert_eq!(signals[0].type_name, "i32");
+/// assert_eq!(signals[0].subscribers.len(), 1);
+/// # }
+/// ```
+pub fn inspect_signals() -> Vec<SignalInfo> {
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    {
+        let Ok(runtime) = Runtime::current() else {
+            return Vec::new();
+        };
+        let runtime = Rc::downgrade(&runtime);
+        SIGNALS.with_borrow(|signals| {
+            signals
+                .iter()
+                .filter(|entry| entry.runtime.ptr_eq(&runtime))
+                .filter_map(|entry| {
+                    let subscribers = entry.subscribers.upgrade()?;
+                    let subscribers = subscribers.lock().unwrap().iter().copied().collect();
+                    Some(SignalInfo {
+                        id: entry.id,
+                        type_name: entry.type_name,
+                        created_at: entry.created_at,
+                        origin_scope: entry.origin_scope,
+                        subscribers,
+                        reactive_context: entry.reactive_context,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    #[cfg(not(all(feature = "devtools", debug_assertions)))]
+    {
+        Vec::new()
+    }
+}
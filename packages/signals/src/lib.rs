@@ -25,6 +25,9 @@ pub use memo::*;
 mod global;
 pub use global::*;
 
+mod inspect;
+pub use inspect::*;
+
 mod impls;
 
 pub use generational_box::{
@@ -75,6 +75,8 @@ impl<T: 'static> Memo<T> {
             callback: recompute,
         });
         let state: Signal<T> = Signal::new_with_caller(value, location);
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        crate::inspect::register_memo(state.id(), rc);
 
         let memo = Memo {
             inner: state,
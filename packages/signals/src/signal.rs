@@ -2,6 +2,7 @@ use crate::{default_impl, fmt_impls, write_impls, Global};
 use crate::{read::*, write::*, CopyValue, GlobalMemo, GlobalSignal, ReadableRef};
 use crate::{Memo, WritableRef};
 use dioxus_core::prelude::*;
+use dioxus_core::SubscriptionSource;
 use generational_box::{AnyStorage, BorrowResult, Storage, SyncStorage, UnsyncStorage};
 use std::sync::Arc;
 use std::{
@@ -155,6 +156,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
                 value,
             }),
         }
+        .register()
     }
 
     /// Creates a new Signal with an explicit caller. Signals are a Copy state management solution with automatic dependency tracking.
@@ -180,6 +182,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
                 caller,
             ),
         }
+        .register()
     }
 
     /// Create a new Signal without an owner. This will leak memory if you don't manually drop it.
@@ -193,6 +196,7 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
                 caller,
             ),
         }
+        .register()
     }
 
     /// Create a new signal with a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
@@ -219,6 +223,20 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
                 caller,
             ),
         }
+        .register()
+    }
+
+    /// Remember the signal so devtools can inspect it with [`crate::inspect_signals`]
+    fn register(self) -> Self {
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        crate::inspect::register(
+            self.id(),
+            std::any::type_name::<T>(),
+            self.inner.value().created_at(),
+            self.origin_scope(),
+            &self.inner.read().subscribers,
+        );
+        self
     }
 
     /// Point to another signal. This will subscribe the other signal to all subscribers of this signal.
@@ -249,7 +267,12 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
             // We cannot hold the subscribers lock while calling mark_dirty, because mark_dirty can run user code which may cause a new subscriber to be added. If we hold the lock, we will deadlock.
             #[allow(clippy::mutable_key_type)]
             let mut subscribers = std::mem::take(&mut *inner.subscribers.lock().unwrap());
-            subscribers.retain(|reactive_context| reactive_context.mark_dirty());
+            // Tell the profiler which signal caused the subscribers to rerun
+            let source = SubscriptionSource {
+                id: self.id(),
+                created_at: self.inner.value().created_at(),
+            };
+            subscribers.retain(|reactive_context| reactive_context.mark_dirty_from(source));
             // Extend the subscribers list instead of overwriting it in case a subscriber is added while reactive contexts are marked dirty
             inner.subscribers.lock().unwrap().extend(subscribers);
         }
@@ -0,0 +1,78 @@
+#![allow(non_snake_case)]
+//! Signals and memos are tracked in debug builds with the devtools feature so devtools can show what subscribes to them
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_signals::{inspect_signals, SignalInfo};
+
+/// The signals created in this file. Rsx also creates global signals for hot reloading.
+fn signals_in_test(dom: &VirtualDom) -> Vec<SignalInfo> {
+    dom.in_runtime(inspect_signals)
+        .into_iter()
+        .filter(|signal| signal.created_at.unwrap().file() == file!())
+        .collect()
+}
+
+#[test]
+fn signals_and_their_subscribers_are_tracked() {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let mut dom = VirtualDom::new(|| {
+        let count = use_signal(|| 0);
+        let doubled = use_memo(move || count() * 2);
+        let _unread = use_signal(|| "unread");
+        rsx! { "{doubled}" }
+    });
+    dom.rebuild(&mut NoOpMutations);
+
+    let scopes = dom.inspect_scopes();
+    let root = scopes.iter().find(|scope| scope.name == "root").unwrap();
+    let signals = signals_in_test(&dom);
+    assert_eq!(signals.len(), 3);
+
+    let [count, doubled, unread] = &signals[..] else {
+        unreachable!()
+    };
+    assert_eq!(count.type_name, "i32");
+    assert_eq!(count.origin_scope, root.id);
+    assert!(count.reactive_context.is_none());
+
+    // The memo recomputes when the count changes, and the component rerenders when the memo changes
+    let memo_context = doubled.reactive_context.unwrap();
+    assert!(count.subscribers == [memo_context]);
+    assert!(doubled.subscribers == [root.reactive_context]);
+
+    assert_eq!(unread.type_name, "&str");
+    assert!(unread.subscribers.is_empty());
+}
+
+#[test]
+fn dropped_signals_are_forgotten() {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let mut dom = VirtualDom::new(|| {
+        let mut show = use_signal(|| true);
+        use_hook(move || spawn(async move { show.set(false) }));
+        rsx! {
+            if show() {
+                Child {}
+            }
+        }
+    });
+    dom.rebuild(&mut NoOpMutations);
+    assert_eq!(signals_in_test(&dom).len(), 2);
+
+    dom.render_immediate(&mut NoOpMutations);
+    let signals = signals_in_test(&dom);
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].type_name, "bool");
+}
+
+#[component]
+fn Child() -> Element {
+    let value = use_signal(|| 1_u64);
+    rsx! { "{value}" }
+}
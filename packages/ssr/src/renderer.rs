@@ -24,6 +24,9 @@ pub struct Renderer {
 
     /// The current dynamic node id for hydration
     dynamic_node_id: usize,
+
+    /// The portals we found while rendering. They are written after the rest of the document
+    portals: Vec<ScopeId>,
 }
 
 impl Renderer {
@@ -66,7 +69,49 @@ impl Renderer {
         dom: &VirtualDom,
     ) -> std::fmt::Result {
         self.reset_hydration();
-        self.render_scope(buf, dom, ScopeId::ROOT)
+        self.portals.clear();
+        self.render_scope(buf, dom, ScopeId::ROOT)?;
+        self.render_portals(buf, dom)
+    }
+
+    /// Write the children of every portal we found at the end of the document. The client removes these nodes before
+    /// hydration and mounts the portals again in their targets, so we don't write any hydration ids for them.
+    fn render_portals<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+    ) -> std::fmt::Result {
+        let pre_render = std::mem::replace(&mut self.pre_render, false);
+        let result = self.render_queued_portals(buf, dom);
+        self.pre_render = pre_render;
+        result
+    }
+
+    fn render_queued_portals<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+    ) -> std::fmt::Result {
+        // Rendering a portal may discover more portals nested inside of it
+        while !self.portals.is_empty() {
+            for scope in std::mem::take(&mut self.portals) {
+                let Some((target, children)) = dom.get_scope(scope).and_then(|s| s.portal()) else {
+                    continue;
+                };
+                let (attribute, target) = match &target {
+                    PortalTarget::Named(name) => ("data-dioxus-ssr-portal", name),
+                    PortalTarget::Element(id) => ("data-dioxus-ssr-portal-element", id),
+                };
+                write!(
+                    buf,
+                    "<div {attribute}=\"{}\">",
+                    askama_escape::escape(target, askama_escape::Html)
+                )?;
+                self.render_template(buf, dom, &children, true)?;
+                write!(buf, "</div>")?;
+            }
+        }
+        Ok(())
     }
 
     /// Render an element to a string
@@ -101,10 +146,22 @@ impl Renderer {
         dom: &VirtualDom,
         scope: ScopeId,
     ) -> std::fmt::Result {
-        let node = dom.get_scope(scope).unwrap().root_node();
-        self.render_template(buf, dom, node, true)?;
+        let scope = dom.get_scope(scope).unwrap();
+        self.render_component_scope(buf, dom, scope, true)
+    }
 
-        Ok(())
+    fn render_component_scope<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+        scope: &ScopeState,
+        parent_escaped: bool,
+    ) -> std::fmt::Result {
+        // A portal only renders a placeholder in place. The children are rendered with the rest of the portals
+        if scope.portal().is_some() {
+            self.portals.push(scope.id());
+        }
+        self.render_template(buf, dom, scope.root_node(), parent_escaped)
     }
 
     fn render_template<W: Write + ?Sized>(
@@ -177,8 +234,7 @@ impl Renderer {
                                 render_components(self, &mut buf, dom, scope_id)?;
                             } else {
                                 let scope = node.mounted_scope(*index, template, dom).unwrap();
-                                let node = scope.root_node();
-                                self.render_template(buf, dom, node, escaped)?
+                                self.render_component_scope(buf, dom, scope, escaped)?
                             }
                         }
                         DynamicNode::Text(text) => {
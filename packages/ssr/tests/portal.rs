@@ -0,0 +1,37 @@
+use dioxus::prelude::*;
+
+#[test]
+fn portals_are_rendered_after_the_document() {
+    fn app() -> Element {
+        rsx! {
+            div { "before" Portal { target: "modals", p { "modal" } } "after" }
+            Portal { target: PortalTarget::element("sidebar"), "sidebar" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<div>beforeafter</div><div data-dioxus-ssr-portal="modals"><p>modal</p></div><div data-dioxus-ssr-portal-element="sidebar">sidebar</div>"#
+    );
+}
+
+#[test]
+fn portals_do_not_take_hydration_ids() {
+    fn app() -> Element {
+        let text = "dynamic";
+        rsx! {
+            div { Portal { "{text}" } "{text}" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        r#"<div data-node-hydration="0"><!--placeholder1--><!--node-id2-->dynamic<!--#--></div><div data-dioxus-ssr-portal="default">dynamic</div>"#
+    );
+}
@@ -0,0 +1,638 @@
+//! An in-memory DOM tree that the mutations of a VirtualDom are written to
+
+use dioxus_core::{
+    AttributeValue, ElementId, PortalTarget, Template, TemplateAttribute, TemplateNode,
+    WriteMutations,
+};
+use rustc_hash::FxHashMap;
+use slab::Slab;
+use std::fmt::Write;
+
+/// A node in the DOM of a [`TestDom`](crate::TestDom)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    /// The slots of removed nodes are reused. The generation tells apart the nodes that lived in the same slot.
+    generation: u64,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum NodeKind {
+    /// The node the app is mounted to
+    Root,
+    Element {
+        tag: &'static str,
+        attributes: Vec<(&'static str, String)>,
+        /// Attributes in the `style` namespace, like `color: "red"`
+        styles: Vec<(&'static str, String)>,
+        listeners: Vec<&'static str>,
+    },
+    Text(String),
+    Placeholder,
+    /// The node a portal appends its children to. It is transparent in the html of the tree.
+    PortalMount,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Node {
+    pub kind: NodeKind,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+    /// The id the VirtualDom knows this node by
+    pub element: Option<ElementId>,
+    /// The generation of the [`NodeId`] of this node
+    generation: u64,
+}
+
+/// The DOM of a [`TestDom`](crate::TestDom). Mutations from the VirtualDom are applied to it like a renderer would apply
+/// them to the real DOM.
+pub(crate) struct Tree {
+    nodes: Slab<Node>,
+    /// The generation of the next node that is inserted
+    next_generation: u64,
+    root: NodeId,
+    /// The stack of the mutation stack machine
+    stack: Vec<NodeId>,
+    /// The node of every ElementId
+    elements: Vec<Option<NodeId>>,
+    /// The roots of every template we loaded. The nodes are cloned when the template is loaded.
+    templates: FxHashMap<Template, Vec<NodeId>>,
+}
+
+impl Tree {
+    pub(crate) fn new() -> Self {
+        let mut tree = Self {
+            nodes: Slab::new(),
+            next_generation: 0,
+            root: NodeId {
+                index: 0,
+                generation: 0,
+            },
+            stack: Vec::new(),
+            elements: Vec::new(),
+            templates: FxHashMap::default(),
+        };
+        let root = tree.insert(NodeKind::Root);
+        tree.root = root;
+        tree.stack.push(root);
+        tree.map_element(root, ElementId(0));
+        tree
+    }
+
+    pub(crate) fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub(crate) fn node(&self, id: NodeId) -> &Node {
+        self.get(id)
+            .unwrap_or_else(|| panic!("{id:?} was removed from the dom"))
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        self.get_mut(id)
+            .unwrap_or_else(|| panic!("{id:?} was removed from the dom"))
+    }
+
+    fn get(&self, id: NodeId) -> Option<&Node> {
+        self.nodes
+            .get(id.index)
+            .filter(|node| node.generation == id.generation)
+    }
+
+    fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes
+            .get_mut(id.index)
+            .filter(|node| node.generation == id.generation)
+    }
+
+    /// Check if the node is still part of the tree
+    pub(crate) fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// The node the VirtualDom mounted to the ElementId
+    pub(crate) fn element(&self, id: ElementId) -> NodeId {
+        self.elements
+            .get(id.0)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| panic!("no node is mounted to {id:?}"))
+    }
+
+    /// The nearest element the VirtualDom knows about, starting at the node itself. Like a browser renderer, only
+    /// elements with listeners are event targets.
+    pub(crate) fn nearest_element(&self, mut id: NodeId) -> Option<ElementId> {
+        loop {
+            let node = self.node(id);
+            if let (NodeKind::Element { listeners, .. }, Some(element)) = (&node.kind, node.element)
+            {
+                if !listeners.is_empty() {
+                    return Some(element);
+                }
+            }
+            // Events in a portal bubble from the portal mount to the logical parent of the portal inside of the
+            // VirtualDom instead of the portal target
+            if let NodeKind::PortalMount = node.kind {
+                return node.element;
+            }
+            id = node.parent?;
+        }
+    }
+
+    /// Every node below the node in document order, not including the node itself
+    pub(crate) fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<NodeId> = self.node(id).children.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            descendants.push(id);
+            stack.extend(self.node(id).children.iter().rev());
+        }
+        descendants
+    }
+
+    /// The text content of the node and all of its descendants
+    pub(crate) fn text_content(&self, id: NodeId) -> String {
+        let mut text = String::new();
+        if let NodeKind::Text(value) = &self.node(id).kind {
+            text.push_str(value);
+        }
+        for id in self.descendants(id) {
+            if let NodeKind::Text(value) = &self.node(id).kind {
+                text.push_str(value);
+            }
+        }
+        text
+    }
+
+    pub(crate) fn attribute(&self, id: NodeId, name: &str) -> Option<&str> {
+        match &self.node(id).kind {
+            NodeKind::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(attribute, _)| *attribute == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Write the html of the node and its descendants
+    pub(crate) fn write_html(&self, id: NodeId, buf: &mut String) {
+        let node = self.node(id);
+        match &node.kind {
+            NodeKind::Root | NodeKind::PortalMount => {
+                for child in &node.children {
+                    self.write_html(*child, buf);
+                }
+            }
+            NodeKind::Element {
+                tag,
+                attributes,
+                styles,
+                ..
+            } => {
+                let mut inner_html = None;
+                _ = write!(buf, "<{tag}");
+                for (name, value) in attributes {
+                    if *name == "dangerous_inner_html" {
+                        inner_html = Some(value);
+                        continue;
+                    }
+                    _ = write!(
+                        buf,
+                        " {name}=\"{}\"",
+                        askama_escape::escape(value, askama_escape::Html)
+                    );
+                }
+                if !styles.is_empty() {
+                    buf.push_str(" style=\"");
+                    for (name, value) in styles {
+                        _ = write!(
+                            buf,
+                            "{name}:{};",
+                            askama_escape::escape(value, askama_escape::Html)
+                        );
+                    }
+                    buf.push('"');
+                }
+
+                if node.children.is_empty() && inner_html.is_none() && is_void_element(tag) {
+                    buf.push_str("/>");
+                    return;
+                }
+                buf.push('>');
+                if let Some(inner_html) = inner_html {
+                    buf.push_str(inner_html);
+                }
+                for child in &node.children {
+                    self.write_html(*child, buf);
+                }
+                _ = write!(buf, "</{tag}>");
+            }
+            NodeKind::Text(text) => {
+                let raw = node.parent.is_some_and(|parent| {
+                    matches!(
+                        self.node(parent).kind,
+                        NodeKind::Element {
+                            tag: "script" | "style",
+                            ..
+                        }
+                    )
+                });
+                match raw {
+                    true => buf.push_str(text),
+                    false => {
+                        _ = write!(buf, "{}", askama_escape::escape(text, askama_escape::Html))
+                    }
+                }
+            }
+            // Placeholders only mark where nodes can be inserted later
+            NodeKind::Placeholder => {}
+        }
+    }
+
+    fn insert(&mut self, kind: NodeKind) -> NodeId {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let index = self.nodes.insert(Node {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            element: None,
+            generation,
+        });
+        NodeId { index, generation }
+    }
+
+    fn map_element(&mut self, id: NodeId, element: ElementId) {
+        if self.elements.len() <= element.0 {
+            self.elements.resize(element.0 + 1, None);
+        }
+        // The VirtualDom reuses the ids of removed nodes
+        if let Some(old) = self.elements[element.0].replace(id) {
+            if let Some(node) = self.get_mut(old) {
+                node.element = None;
+            }
+        }
+        self.node_mut(id).element = Some(element);
+    }
+
+    fn push_new(&mut self, id: NodeId, element: ElementId) {
+        self.map_element(id, element);
+        self.stack.push(id);
+    }
+
+    fn pop_nodes(&mut self, m: usize) -> Vec<NodeId> {
+        self.stack.split_off(self.stack.len() - m)
+    }
+
+    /// Find the child of the node on the top of the stack by its path
+    fn load_child(&self, path: &[u8]) -> NodeId {
+        let mut id = *self.stack.last().expect("the stack is never empty");
+        for index in path {
+            id = self.node(id).children[*index as usize];
+        }
+        id
+    }
+
+    /// Remove the node from its parent
+    fn detach(&mut self, id: NodeId) {
+        if let Some(parent) = self.node_mut(id).parent.take() {
+            self.node_mut(parent).children.retain(|child| *child != id);
+        }
+    }
+
+    /// Insert the nodes into the children of the parent at the index
+    fn attach(&mut self, parent: NodeId, index: usize, nodes: &[NodeId]) {
+        for node in nodes {
+            self.detach(*node);
+            self.node_mut(*node).parent = Some(parent);
+        }
+        let children = &mut self.node_mut(parent).children;
+        children.splice(index..index, nodes.iter().copied());
+    }
+
+    /// Insert the nodes next to the anchor
+    fn insert_next_to(&mut self, anchor: NodeId, nodes: &[NodeId], after: bool) {
+        let parent = self.node(anchor).parent.expect("the anchor is mounted");
+        // Detach the nodes first so the index of the anchor doesn't shift
+        for node in nodes {
+            self.detach(*node);
+        }
+        let index = self
+            .node(parent)
+            .children
+            .iter()
+            .position(|child| *child == anchor)
+            .expect("the anchor is a child of its parent");
+        self.attach(parent, index + after as usize, nodes);
+    }
+
+    /// Find the node a portal target points to. Named targets are created at the end of the root the first time they
+    /// are used, and missing elements fall back to the root.
+    fn portal_target(&mut self, target: &PortalTarget) -> NodeId {
+        match target {
+            PortalTarget::Named(name) => {
+                let existing = self.node(self.root).children.iter().copied().find(|child| {
+                    self.attribute(*child, "data-dioxus-portal") == Some(name.as_str())
+                });
+                existing.unwrap_or_else(|| {
+                    let node = self.insert(NodeKind::Element {
+                        tag: "div",
+                        attributes: vec![("data-dioxus-portal", name.clone())],
+                        styles: Vec::new(),
+                        listeners: Vec::new(),
+                    });
+                    let index = self.node(self.root).children.len();
+                    self.attach(self.root, index, &[node]);
+                    node
+                })
+            }
+            PortalTarget::Element(id) => self
+                .descendants(self.root)
+                .into_iter()
+                .find(|node| self.attribute(*node, "id") == Some(id.as_str()))
+                .unwrap_or(self.root),
+        }
+    }
+
+    /// Remove the node and its descendants from the tree
+    fn free(&mut self, id: NodeId) {
+        self.detach(id);
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = self.nodes.remove(id.index);
+            if let Some(element) = node.element {
+                if self.elements[element.0] == Some(id) {
+                    self.elements[element.0] = None;
+                }
+            }
+            stack.extend(node.children);
+        }
+    }
+
+    fn deep_clone(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let kind = node.kind.clone();
+        let children = node.children.clone();
+        let clone = self.insert(kind);
+        for child in children {
+            let child = self.deep_clone(child);
+            self.node_mut(child).parent = Some(clone);
+            self.node_mut(clone).children.push(child);
+        }
+        clone
+    }
+
+    fn create_template_node(&mut self, node: &TemplateNode) -> NodeId {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let mut attributes = Vec::new();
+                let mut styles = Vec::new();
+                for attr in attrs.iter() {
+                    if let TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } = attr
+                    {
+                        match *namespace {
+                            Some("style") => styles.push((*name, value.to_string())),
+                            _ => attributes.push((*name, value.to_string())),
+                        }
+                    }
+                }
+                let id = self.insert(NodeKind::Element {
+                    tag,
+                    attributes,
+                    styles,
+                    listeners: Vec::new(),
+                });
+                let children: Vec<_> = children
+                    .iter()
+                    .map(|child| self.create_template_node(child))
+                    .collect();
+                self.attach(id, 0, &children);
+                id
+            }
+            TemplateNode::Text { text } => self.insert(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.insert(NodeKind::Placeholder),
+        }
+    }
+}
+
+impl WriteMutations for Tree {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.element(id);
+        let nodes = self.pop_nodes(m);
+        let index = self.node(parent).children.len();
+        self.attach(parent, index, &nodes);
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.load_child(path);
+        self.map_element(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.insert(NodeKind::Placeholder);
+        self.push_new(node, id);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.insert(NodeKind::Text(value.to_string()));
+        self.push_new(node, id);
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        if !self.templates.contains_key(&template) {
+            let roots = template
+                .roots
+                .iter()
+                .map(|root| self.create_template_node(root))
+                .collect();
+            self.templates.insert(template, roots);
+        }
+        let root = self.templates[&template][index];
+        let node = self.deep_clone(root);
+        self.push_new(node, id);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        let old = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_next_to(old, &nodes, false);
+        self.free(old);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        // The new nodes are above the node the path starts from on the stack
+        let nodes = self.pop_nodes(m);
+        let old = self.load_child(path);
+        self.insert_next_to(old, &nodes, false);
+        self.free(old);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        let anchor = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_next_to(anchor, &nodes, true);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        let anchor = self.element(id);
+        let nodes = self.pop_nodes(m);
+        self.insert_next_to(anchor, &nodes, false);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        let node = self.element(id);
+        let NodeKind::Element {
+            attributes, styles, ..
+        } = &mut self.node_mut(node).kind
+        else {
+            return;
+        };
+
+        let value = match value {
+            AttributeValue::Text(value) if is_boolean_attribute(name) && !is_truthy(value) => None,
+            AttributeValue::Text(value) => Some(value.clone()),
+            AttributeValue::Bool(false) | AttributeValue::None => None,
+            AttributeValue::Bool(true) => Some("true".to_string()),
+            AttributeValue::Int(value) => Some(value.to_string()),
+            AttributeValue::Float(value) => Some(value.to_string()),
+            // Listeners and other values only exist in the VirtualDom
+            _ => return,
+        };
+        let attributes = match ns {
+            Some("style") => styles,
+            _ => attributes,
+        };
+        let existing = attributes
+            .iter()
+            .position(|(attribute, _)| *attribute == name);
+        match (existing, value) {
+            (Some(index), Some(value)) => attributes[index].1 = value,
+            (None, Some(value)) => attributes.push((name, value)),
+            (Some(index), None) => _ = attributes.remove(index),
+            (None, None) => {}
+        }
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        let node = self.element(id);
+        if let NodeKind::Text(text) = &mut self.node_mut(node).kind {
+            *text = value.to_string();
+        }
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.element(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.node_mut(node).kind {
+            listeners.push(name);
+        }
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.element(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.node_mut(node).kind {
+            if let Some(index) = listeners.iter().position(|listener| *listener == name) {
+                listeners.remove(index);
+            }
+        }
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.free(node);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.stack.push(node);
+    }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize) {
+        let nodes = self.pop_nodes(m);
+        let mount = match self.elements.get(id.0).copied().flatten() {
+            Some(mount) if matches!(self.node(mount).kind, NodeKind::PortalMount) => mount,
+            _ => {
+                let mount = self.insert(NodeKind::PortalMount);
+                self.map_element(mount, id);
+                mount
+            }
+        };
+        let parent = self.portal_target(target);
+        if self.node(mount).parent != Some(parent) {
+            let index = self.node(parent).children.len();
+            self.attach(parent, index, &[mount]);
+        }
+        let index = self.node(mount).children.len();
+        self.attach(mount, index, &nodes);
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+}
+
+/// Attributes that are removed instead of set to a falsy value
+fn is_boolean_attribute(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+    )
+}
+
+fn is_void_element(tag: &str) -> bool {
+    matches!(
+        tag,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
@@ -0,0 +1,301 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+mod dom;
+mod query;
+
+pub use dom::NodeId;
+
+use dioxus_core::{ComponentFunction, Element, Event, VirtualDom};
+use dioxus_html::{
+    EventData, SerializedFormData, SerializedHtmlEventConverter, SerializedMouseData,
+};
+use dom::{NodeKind, Tree};
+use std::collections::HashMap;
+
+/// A VirtualDom rendered to an in-memory DOM.
+///
+/// Every change the VirtualDom makes is applied to the DOM like a renderer would apply it to the real DOM, so tests
+/// can find nodes, send events to them and check the html they render.
+pub struct TestDom {
+    vdom: VirtualDom,
+    tree: Tree,
+}
+
+impl TestDom {
+    /// Render the component to a new DOM
+    pub fn new(app: fn() -> Element) -> Self {
+        Self::from_virtual_dom(VirtualDom::new(app))
+    }
+
+    /// Render the component with the props to a new DOM
+    pub fn new_with_props<P: Clone + 'static, M: 'static>(
+        root: impl ComponentFunction<P, M>,
+        root_props: P,
+    ) -> Self {
+        Self::from_virtual_dom(VirtualDom::new_with_props(root, root_props))
+    }
+
+    /// Render a VirtualDom that hasn't been rebuilt yet to a new DOM. This lets you provide root contexts before the
+    /// first render.
+    pub fn from_virtual_dom(mut vdom: VirtualDom) -> Self {
+        // Events are sent to the VirtualDom as serialized event data
+        dioxus_html::set_event_converter(Box::new(SerializedHtmlEventConverter));
+
+        let mut tree = Tree::new();
+        vdom.rebuild(&mut tree);
+        Self { vdom, tree }
+    }
+
+    /// The VirtualDom that renders to this DOM
+    pub fn vdom(&self) -> &VirtualDom {
+        &self.vdom
+    }
+
+    /// The VirtualDom that renders to this DOM. Call [`TestDom::update`] after changing its state to apply the changes.
+    pub fn vdom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.vdom
+    }
+
+    /// Run the tasks that are ready and render every component that changed
+    pub fn update(&mut self) {
+        self.vdom.render_immediate(&mut self.tree);
+    }
+
+    /// Wait until a task or a signal updates a component, then render the changes.
+    ///
+    /// This waits forever if nothing changes, so tests should only call it if they expect an update.
+    pub async fn wait_for_update(&mut self) {
+        self.vdom.wait_for_work().await;
+        self.update();
+    }
+
+    /// Wait until every suspended component resolves, rendering the changes along the way
+    pub async fn wait_for_suspense(&mut self) {
+        self.update();
+        while self.vdom.suspended_tasks_remaining() {
+            self.wait_for_update().await;
+        }
+    }
+
+    /// Render the whole DOM to html
+    pub fn html(&self) -> String {
+        self.node(self.tree.root()).html()
+    }
+
+    /// Get a reference to a node in the DOM
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node was removed from the DOM
+    pub fn node(&self, id: NodeId) -> NodeRef<'_> {
+        assert!(self.tree.contains(id), "{id:?} was removed from the dom");
+        NodeRef {
+            tree: &self.tree,
+            id,
+        }
+    }
+
+    /// Find every element whose text is the text. Whitespace is collapsed before comparing, and only the innermost
+    /// element that contains the text matches.
+    pub fn find_by_text(&self, text: &str) -> Vec<NodeId> {
+        query::by_text(&self.tree, text)
+    }
+
+    /// Get the only element whose text is the text
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one matching element
+    pub fn get_by_text(&self, text: &str) -> NodeId {
+        single(
+            self.find_by_text(text),
+            || format!("with the text {text:?}"),
+            self,
+        )
+    }
+
+    /// Find every element with the ARIA role, like `button`, `link`, `textbox` or `heading`. Elements match either their
+    /// `role` attribute or the implicit role of their tag.
+    pub fn find_by_role(&self, role: &str) -> Vec<NodeId> {
+        query::by_role(&self.tree, role)
+    }
+
+    /// Get the only element with the ARIA role
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one matching element
+    pub fn get_by_role(&self, role: &str) -> NodeId {
+        single(
+            self.find_by_role(role),
+            || format!("with the role {role:?}"),
+            self,
+        )
+    }
+
+    /// Get the element with the id attribute
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one matching element
+    pub fn get_by_id(&self, id: &str) -> NodeId {
+        let elements = query::by_selector(&self.tree, &format!("[id=\"{id}\"]"));
+        single(elements, || format!("with the id {id:?}"), self)
+    }
+
+    /// Find the first element matching the CSS selector.
+    ///
+    /// Tag, `*`, `#id`, `.class`, `[attr]` and `[attr=value]` selectors are supported, along with descendant and child
+    /// (`>`) combinators and selector lists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector can't be parsed
+    pub fn query_selector(&self, selector: &str) -> Option<NodeId> {
+        query::by_selector(&self.tree, selector).into_iter().next()
+    }
+
+    /// Find every element matching the CSS selector in document order. See [`TestDom::query_selector`] for the
+    /// supported selectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector can't be parsed
+    pub fn query_selector_all(&self, selector: &str) -> Vec<NodeId> {
+        query::by_selector(&self.tree, selector)
+    }
+
+    /// Click the node and render the changes
+    pub fn click(&mut self, node: NodeId) {
+        self.dispatch(
+            node,
+            "click",
+            EventData::Mouse(SerializedMouseData::default()),
+        );
+    }
+
+    /// Send an input event with the value to the node and render the changes
+    pub fn input(&mut self, node: NodeId, value: impl ToString) {
+        self.dispatch(
+            node,
+            "input",
+            EventData::Form(SerializedFormData::new(value.to_string(), HashMap::new())),
+        );
+    }
+
+    /// Send an event to the node and render the changes. The name is the name of the event without the `on` prefix,
+    /// like `click` or `keydown`. Events bubble if they do in the browser.
+    ///
+    /// If the node isn't an element the VirtualDom knows about, the event is sent to its closest ancestor that is. If
+    /// no ancestor has a listener, nothing happens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node was removed from the DOM
+    pub fn dispatch(&mut self, node: NodeId, name: &str, data: EventData) {
+        assert!(
+            self.tree.contains(node),
+            "{node:?} was removed from the dom"
+        );
+        if let Some(element) = self.tree.nearest_element(node) {
+            let event = Event::new(data.into_any(), dioxus_core_types::event_bubbles(name));
+            self.vdom.runtime().handle_event(name, event, element);
+        }
+        self.update();
+    }
+}
+
+/// Get the only element in the list or panic with the html of the DOM
+fn single(elements: Vec<NodeId>, description: impl Fn() -> String, dom: &TestDom) -> NodeId {
+    match elements.as_slice() {
+        [element] => *element,
+        [] => panic!("no element {}\n{}", description(), dom.html()),
+        _ => panic!(
+            "{} elements {}\n{}",
+            elements.len(),
+            description(),
+            dom.html()
+        ),
+    }
+}
+
+/// A node in the DOM of a [`TestDom`]
+#[derive(Clone, Copy)]
+pub struct NodeRef<'a> {
+    tree: &'a Tree,
+    id: NodeId,
+}
+
+impl<'a> NodeRef<'a> {
+    /// The id of the node
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// The tag of the element, or `None` for other nodes
+    pub fn tag(&self) -> Option<&'a str> {
+        match self.tree.node(self.id).kind {
+            NodeKind::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// The text of the node and all of its descendants
+    pub fn text(&self) -> String {
+        self.tree.text_content(self.id)
+    }
+
+    /// The value of an attribute of the element
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.tree.attribute(self.id, name)
+    }
+
+    /// The value of a style property of the element, like `color`
+    pub fn style(&self, name: &str) -> Option<&'a str> {
+        match &self.tree.node(self.id).kind {
+            NodeKind::Element { styles, .. } => styles
+                .iter()
+                .find(|(style, _)| *style == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The explicit or implicit ARIA role of the element
+    pub fn role(&self) -> Option<&'a str> {
+        query::role(self.tree, self.id)
+    }
+
+    /// Check if the element has a listener for the event, like `click`
+    pub fn has_listener(&self, name: &str) -> bool {
+        match &self.tree.node(self.id).kind {
+            NodeKind::Element { listeners, .. } => listeners.contains(&name),
+            _ => false,
+        }
+    }
+
+    /// The parent of the node
+    pub fn parent(&self) -> Option<NodeId> {
+        self.tree.node(self.id).parent
+    }
+
+    /// The children of the node. Placeholders for nodes that aren't rendered are included.
+    pub fn children(&self) -> &'a [NodeId] {
+        &self.tree.node(self.id).children
+    }
+
+    /// Render the node and its descendants to html
+    pub fn html(&self) -> String {
+        let mut html = String::new();
+        self.tree.write_html(self.id, &mut html);
+        html
+    }
+}
+
+impl std::fmt::Debug for NodeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.html())
+    }
+}
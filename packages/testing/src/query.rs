@@ -0,0 +1,293 @@
+//! Find nodes in the DOM of a [`TestDom`](crate::TestDom) by their text, role or a CSS selector
+
+use crate::dom::{NodeId, NodeKind, Tree};
+
+/// Find the elements whose text content matches the text, ignoring extra whitespace. Only the deepest matching
+/// elements are returned, so a match doesn't include every ancestor of the element that contains the text.
+pub(crate) fn by_text(tree: &Tree, text: &str) -> Vec<NodeId> {
+    let text = collapse_whitespace(text);
+    let matches = |id: NodeId| collapse_whitespace(&tree.text_content(id)) == text;
+    elements(tree)
+        .filter(|id| matches(*id))
+        .filter(|id| {
+            !tree.descendants(*id).into_iter().any(|child| {
+                matches!(tree.node(child).kind, NodeKind::Element { .. }) && matches(child)
+            })
+        })
+        .collect()
+}
+
+/// Find the elements with the role. An explicit `role` attribute takes priority over the implicit ARIA role of the
+/// element.
+pub(crate) fn by_role(tree: &Tree, role: &str) -> Vec<NodeId> {
+    elements(tree)
+        .filter(|id| self::role(tree, *id) == Some(role))
+        .collect()
+}
+
+/// Find the elements matching the CSS selector
+///
+/// # Panics
+///
+/// Panics if the selector is invalid or uses a feature that isn't supported. Tag, `*`, `#id`, `.class`, `[attr]` and
+/// `[attr=value]` selectors can be combined with descendant and child (`>`) combinators and selector lists (`,`).
+pub(crate) fn by_selector(tree: &Tree, selector: &str) -> Vec<NodeId> {
+    let selectors = parse_selector_list(selector)
+        .unwrap_or_else(|err| panic!("invalid selector {selector:?}: {err}"));
+    elements(tree)
+        .filter(|id| selectors.iter().any(|selector| selector.matches(tree, *id)))
+        .collect()
+}
+
+/// Every element in document order
+fn elements(tree: &Tree) -> impl Iterator<Item = NodeId> + '_ {
+    tree.descendants(tree.root())
+        .into_iter()
+        .filter(|id| matches!(tree.node(*id).kind, NodeKind::Element { .. }))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The explicit role or the implicit ARIA role of the element
+pub(crate) fn role(tree: &Tree, id: NodeId) -> Option<&str> {
+    if let Some(role) = tree.attribute(id, "role") {
+        return Some(role);
+    }
+    let NodeKind::Element { tag, .. } = tree.node(id).kind else {
+        return None;
+    };
+    let role = match tag {
+        "a" | "area" if tree.attribute(id, "href").is_some() => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" if tree.attribute(id, "alt") == Some("") => "presentation",
+        "img" => "img",
+        "input" => match tree.attribute(id, "type").unwrap_or("text") {
+            "button" | "image" | "reset" | "submit" => "button",
+            "checkbox" => "checkbox",
+            "number" => "spinbutton",
+            "radio" => "radio",
+            "range" => "slider",
+            "search" => "searchbox",
+            "email" | "tel" | "text" | "url" => "textbox",
+            _ => return None,
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" | "menu" => "list",
+        "option" => "option",
+        "progress" => "progressbar",
+        "section" => "region",
+        "select" if tree.attribute(id, "multiple").is_some() => "listbox",
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "thead" | "tfoot" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role)
+}
+
+/// A selector list like `button.primary, a > img`
+type SelectorList = Vec<ComplexSelector>;
+
+/// Compound selectors joined by combinators. The last compound selector matches the element itself.
+#[derive(Debug)]
+struct ComplexSelector {
+    compounds: Vec<(Combinator, CompoundSelector)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    /// The first compound selector has nothing on its left
+    None,
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Default)]
+struct CompoundSelector {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl ComplexSelector {
+    fn matches(&self, tree: &Tree, id: NodeId) -> bool {
+        Self::matches_from(tree, id, &self.compounds)
+    }
+
+    fn matches_from(tree: &Tree, id: NodeId, compounds: &[(Combinator, CompoundSelector)]) -> bool {
+        let Some(((combinator, compound), rest)) = compounds.split_last() else {
+            return true;
+        };
+        if !compound.matches(tree, id) {
+            return false;
+        }
+        let mut parent = tree.node(id).parent;
+        match combinator {
+            Combinator::None => true,
+            Combinator::Child => {
+                parent.is_some_and(|parent| Self::matches_from(tree, parent, rest))
+            }
+            Combinator::Descendant => {
+                while let Some(ancestor) = parent {
+                    if Self::matches_from(tree, ancestor, rest) {
+                        return true;
+                    }
+                    parent = tree.node(ancestor).parent;
+                }
+                false
+            }
+        }
+    }
+}
+
+impl CompoundSelector {
+    fn matches(&self, tree: &Tree, id: NodeId) -> bool {
+        let NodeKind::Element { tag, .. } = tree.node(id).kind else {
+            return false;
+        };
+        if self.tag.as_ref().is_some_and(|expected| expected != tag) {
+            return false;
+        }
+        if self
+            .id
+            .as_ref()
+            .is_some_and(|expected| tree.attribute(id, "id") != Some(expected))
+        {
+            return false;
+        }
+        let classes = tree.attribute(id, "class").unwrap_or_default();
+        if !self
+            .classes
+            .iter()
+            .all(|class| classes.split_whitespace().any(|c| c == class))
+        {
+            return false;
+        }
+        self.attributes.iter().all(
+            |(name, expected)| match (tree.attribute(id, name), expected) {
+                (Some(value), Some(expected)) => value == expected,
+                (value, None) => value.is_some(),
+                (None, Some(_)) => false,
+            },
+        )
+    }
+}
+
+fn parse_selector_list(input: &str) -> Result<SelectorList, String> {
+    input.split(',').map(parse_complex_selector).collect()
+}
+
+fn parse_complex_selector(input: &str) -> Result<ComplexSelector, String> {
+    let mut compounds = Vec::new();
+    let mut combinator = Combinator::None;
+    let mut chars = input.trim().chars().peekable();
+
+    while chars.peek().is_some() {
+        let compound = parse_compound_selector(&mut chars)?;
+        compounds.push((combinator, compound));
+
+        // Whitespace is a descendant combinator unless it surrounds a `>`
+        combinator = Combinator::Descendant;
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.next_if_eq(&'>').is_some() {
+            combinator = Combinator::Child;
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_none() {
+                return Err("expected a selector after `>`".to_string());
+            }
+        }
+    }
+
+    if compounds.is_empty() {
+        return Err("expected a selector".to_string());
+    }
+    Ok(ComplexSelector { compounds })
+}
+
+fn parse_compound_selector(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+) -> Result<CompoundSelector, String> {
+    let mut compound = CompoundSelector::default();
+    let mut empty = true;
+
+    if chars.next_if_eq(&'*').is_some() {
+        empty = false;
+    } else if chars.peek().is_some_and(|c| is_ident_char(*c)) {
+        compound.tag = Some(parse_ident(chars));
+        empty = false;
+    }
+
+    loop {
+        match chars.peek() {
+            Some('#') => {
+                chars.next();
+                compound.id = Some(parse_ident(chars));
+            }
+            Some('.') => {
+                chars.next();
+                compound.classes.push(parse_ident(chars));
+            }
+            Some('[') => {
+                chars.next();
+                let name = parse_ident(chars);
+                let value = match chars.next() {
+                    Some(']') => None,
+                    Some('=') => {
+                        let value = match chars.next_if(|c| *c == '"' || *c == '\'') {
+                            Some(quote) => {
+                                let value: String =
+                                    chars.by_ref().take_while(|c| *c != quote).collect();
+                                value
+                            }
+                            None => parse_ident(chars),
+                        };
+                        if chars.next() != Some(']') {
+                            return Err(format!("expected `]` after the value of [{name}]"));
+                        }
+                        Some(value)
+                    }
+                    _ => return Err(format!("unsupported attribute selector [{name}")),
+                };
+                compound.attributes.push((name, value));
+            }
+            Some(c) if c.is_whitespace() || *c == '>' => break,
+            None => break,
+            Some(c) => return Err(format!("unsupported character {c:?}")),
+        }
+        empty = false;
+    }
+
+    if empty {
+        return Err("expected a selector".to_string());
+    }
+    Ok(compound)
+}
+
+fn parse_ident(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut ident = String::new();
+    while let Some(c) = chars.next_if(|c| is_ident_char(*c)) {
+        ident.push(c);
+    }
+    ident
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
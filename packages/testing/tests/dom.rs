@@ -0,0 +1,269 @@
+use dioxus::prelude::*;
+use dioxus_testing::TestDom;
+use std::time::Duration;
+
+#[test]
+fn click_updates_the_dom() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        rsx! {
+            button { onclick: move |_| count += 1, "increment" }
+            p { "count: {count}" }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(dom.html(), "<button>increment</button><p>count: 0</p>");
+
+    for _ in 0..3 {
+        dom.click(dom.get_by_role("button"));
+    }
+    assert_eq!(dom.node(dom.get_by_text("count: 3")).tag(), Some("p"));
+    assert_eq!(dom.html(), "<button>increment</button><p>count: 3</p>");
+}
+
+#[test]
+fn events_bubble_from_text_nodes() {
+    fn app() -> Element {
+        let mut clicked = use_signal(|| false);
+        rsx! {
+            div { onclick: move |_| clicked.set(true),
+                span { "inner" }
+            }
+            if clicked() {
+                "clicked"
+            }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert!(dom.find_by_text("clicked").is_empty());
+    let text = dom.node(dom.get_by_text("inner")).children()[0];
+    dom.click(text);
+    assert!(dom.html().ends_with("clicked"));
+}
+
+#[test]
+fn input_events_carry_the_value() {
+    fn app() -> Element {
+        let mut name = use_signal(String::new);
+        rsx! {
+            input { r#type: "text", value: "{name}", oninput: move |event| name.set(event.value()) }
+            h1 { "hello {name}" }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    dom.input(dom.get_by_role("textbox"), "world");
+    assert_eq!(dom.node(dom.get_by_role("heading")).text(), "hello world");
+    assert_eq!(
+        dom.node(dom.get_by_role("textbox")).attribute("value"),
+        Some("world")
+    );
+}
+
+#[test]
+fn lists_and_conditional_nodes() {
+    fn app() -> Element {
+        let mut items = use_signal(|| vec![1, 2, 3]);
+        rsx! {
+            button { id: "remove", onclick: move |_| { items.write().remove(0); }, "remove" }
+            button { id: "reverse", onclick: move |_| items.write().reverse(), "reverse" }
+            ul {
+                for item in items() {
+                    li { key: "{item}", "item {item}" }
+                }
+            }
+            if items.is_empty() {
+                p { "empty" }
+            }
+        }
+    }
+
+    let items = |dom: &TestDom| {
+        dom.query_selector_all("ul > li")
+            .into_iter()
+            .map(|li| dom.node(li).text())
+            .collect::<Vec<_>>()
+    };
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(items(&dom), ["item 1", "item 2", "item 3"]);
+
+    dom.click(dom.get_by_id("reverse"));
+    assert_eq!(items(&dom), ["item 3", "item 2", "item 1"]);
+
+    for _ in 0..3 {
+        dom.click(dom.get_by_id("remove"));
+    }
+    assert!(items(&dom).is_empty());
+    assert_eq!(dom.find_by_role("listitem").len(), 0);
+    assert!(dom.query_selector("p").is_some());
+}
+
+#[test]
+#[should_panic(expected = "was removed from the dom")]
+fn removed_nodes_stay_removed_when_their_slot_is_reused() {
+    fn app() -> Element {
+        let mut first = use_signal(|| true);
+        rsx! {
+            button { onclick: move |_| first.toggle(), "toggle" }
+            if first() {
+                p { "first" }
+            } else {
+                p { "second" }
+            }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    let first = dom.get_by_text("first");
+    // Toggling back creates a new paragraph in the slots the old one freed
+    dom.click(dom.get_by_role("button"));
+    dom.click(dom.get_by_role("button"));
+    assert_eq!(dom.html(), "<button>toggle</button><p>first</p>");
+    dom.node(first);
+}
+
+#[test]
+fn queries() {
+    fn app() -> Element {
+        rsx! {
+            nav {
+                a { href: "/", class: "link home", "Home" }
+                a { href: "/about", class: "link", "About" }
+            }
+            main {
+                div { role: "alert", "Saved" }
+                input { r#type: "checkbox", checked: true }
+                img { src: "/logo.png", alt: "" }
+                section { "data-active": "true",
+                    p { class: "note", "  some\n   text  " }
+                }
+            }
+        }
+    }
+
+    let dom = TestDom::new(app);
+    assert_eq!(dom.find_by_role("link").len(), 2);
+    assert_eq!(dom.find_by_role("navigation").len(), 1);
+    assert_eq!(dom.node(dom.get_by_role("alert")).text(), "Saved");
+    assert_eq!(dom.find_by_role("checkbox").len(), 1);
+    assert_eq!(dom.find_by_role("presentation").len(), 1);
+    assert_eq!(dom.node(dom.get_by_text("some text")).tag(), Some("p"));
+
+    let home = dom.query_selector("nav a.link.home").unwrap();
+    assert_eq!(dom.node(home).attribute("href"), Some("/"));
+    assert_eq!(dom.query_selector_all("a.link").len(), 2);
+    assert_eq!(dom.query_selector_all("main > p").len(), 0);
+    assert_eq!(dom.query_selector_all("main p").len(), 1);
+    assert_eq!(
+        dom.query_selector_all("[data-active=true] > .note, img")
+            .len(),
+        2
+    );
+    assert_eq!(dom.query_selector_all("[href='/about']").len(), 1);
+    assert_eq!(dom.query_selector_all("*").len(), 9);
+}
+
+#[test]
+#[should_panic(expected = "2 elements with the role \"link\"")]
+fn get_panics_for_multiple_matches() {
+    fn app() -> Element {
+        rsx! {
+            a { href: "/a", "a" }
+            a { href: "/b", "b" }
+        }
+    }
+
+    TestDom::new(app).get_by_role("link");
+}
+
+#[test]
+fn html_snapshot() {
+    fn app() -> Element {
+        let disabled = false;
+        rsx! {
+            div { class: "card", color: "red", "data-text": "a \"quote\"",
+                input { disabled, value: "x" }
+                br {}
+                "<escaped> & text"
+                div { dangerous_inner_html: "<b>raw</b>" }
+            }
+        }
+    }
+
+    let dom = TestDom::new(app);
+    assert_eq!(
+        dom.html(),
+        "<div class=\"card\" data-text=\"a &#34;quote&#34;\" style=\"color:red;\">\
+         <input value=\"x\"/><br/>&#60;escaped&#62; &#38; text<div><b>raw</b></div></div>"
+    );
+}
+
+#[tokio::test]
+async fn tasks_update_the_dom() {
+    fn app() -> Element {
+        let mut status = use_signal(|| "loading");
+        use_future(move || async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            status.set("done");
+        });
+        rsx! { "{status}" }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(dom.html(), "loading");
+    dom.wait_for_update().await;
+    assert_eq!(dom.html(), "done");
+}
+
+#[tokio::test]
+async fn suspense_resolves() {
+    fn app() -> Element {
+        rsx! {
+            SuspenseBoundary { fallback: |_| rsx! { "loading" },
+                Greeting {}
+            }
+        }
+    }
+
+    #[component]
+    fn Greeting() -> Element {
+        let name = use_resource(|| async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "world"
+        })
+        .suspend()?;
+        rsx! { h1 { "hello {name}" } }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(dom.html(), "loading");
+    dom.wait_for_suspense().await;
+    assert_eq!(dom.html(), "<h1>hello world</h1>");
+}
+
+#[test]
+fn portals_render_in_the_target_and_bubble_to_the_logical_parent() {
+    fn app() -> Element {
+        let mut clicks = use_signal(|| 0);
+        rsx! {
+            div { id: "sidebar" }
+            main { onclick: move |_| clicks += 1,
+                "clicks: {clicks}"
+                Portal { target: "modals", button { "modal" } }
+                Portal { target: PortalTarget::element("sidebar"), "sidebar" }
+            }
+        }
+    }
+
+    let mut dom = TestDom::new(app);
+    assert_eq!(
+        dom.html(),
+        "<div id=\"sidebar\">sidebar</div><main>clicks: 0</main><div data-dioxus-portal=\"modals\"><button>modal</button></div>"
+    );
+
+    dom.click(dom.get_by_role("button"));
+    assert_eq!(dom.node(dom.get_by_text("clicks: 1")).tag(), Some("main"));
+}
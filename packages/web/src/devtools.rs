@@ -3,10 +3,12 @@
 //! This sets up a websocket connection to the devserver and handles messages from it.
 //! We also set up a little recursive timer that will attempt to reconnect if the connection is lost.
 
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::time::Duration;
 
-use dioxus_devtools::{DevserverMsg, HotReloadMsg};
+use dioxus_core::VirtualDom;
+use dioxus_devtools::{ClientMsg, DevserverMsg, HotReloadMsg};
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use js_sys::JsString;
 use wasm_bindgen::JsCast;
@@ -21,19 +23,45 @@ const POLL_INTERVAL_SCALE_FACTOR: i32 = 2;
 const TOAST_TIMEOUT: Duration = Duration::from_secs(5);
 const TOAST_TIMEOUT_LONG: Duration = Duration::from_secs(3600); // Duration::MAX is too long for JS.
 
-pub(crate) fn init() -> UnboundedReceiver<HotReloadMsg> {
+thread_local! {
+    // The websocket that is currently connected to the devserver
+    static WEBSOCKET: RefCell<Option<WebSocket>> = const { RefCell::new(None) };
+}
+
+/// Connect to the devserver. Returns the hot reload messages and the requests for an inspector snapshot the main loop
+/// should handle.
+pub(crate) fn init() -> (UnboundedReceiver<HotReloadMsg>, UnboundedReceiver<()>) {
     // Create the tx/rx pair that we'll use for the top-level future in the dioxus loop
     let (tx, rx) = unbounded();
+    let (inspect_tx, inspect_rx) = unbounded();
 
     // Wire up the websocket to the devserver
-    make_ws(tx.clone(), POLL_INTERVAL_MIN, false);
+    make_ws(tx.clone(), inspect_tx, POLL_INTERVAL_MIN, false);
 
     playground(tx);
 
-    rx
+    (rx, inspect_rx)
 }
 
-fn make_ws(tx: UnboundedSender<HotReloadMsg>, poll_interval: i32, reload: bool) {
+/// Send a snapshot of the components and signals in the VirtualDom to the devserver
+pub(crate) fn send_inspector_snapshot(dom: &VirtualDom) {
+    let msg = ClientMsg::Inspector(dioxus_devtools::inspect(dom));
+    let Ok(text) = serde_json::to_string(&msg) else {
+        return;
+    };
+    WEBSOCKET.with_borrow(|ws| {
+        if let Some(ws) = ws {
+            _ = ws.send_with_str(&text);
+        }
+    });
+}
+
+fn make_ws(
+    tx: UnboundedSender<HotReloadMsg>,
+    inspect_tx: UnboundedSender<()>,
+    poll_interval: i32,
+    reload: bool,
+) {
     // Get the location of the devserver, using the current location plus the /_dioxus path
     // The idea here being that the devserver is always located on the /_dioxus behind a proxy
     let location = web_sys::window().unwrap().location();
@@ -48,9 +76,11 @@ fn make_ws(tx: UnboundedSender<HotReloadMsg>, poll_interval: i32, reload: bool)
     );
 
     let ws = WebSocket::new(&url).unwrap();
+    WEBSOCKET.with_borrow_mut(|socket| *socket = Some(ws.clone()));
 
     // Set the onmessage handler to bounce messages off to the main dioxus loop
     let tx_ = tx.clone();
+    let inspect_tx_ = inspect_tx.clone();
     ws.set_onmessage(Some(
         Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
             let Ok(text) = e.data().dyn_into::<JsString>() else {
@@ -64,6 +94,9 @@ fn make_ws(tx: UnboundedSender<HotReloadMsg>, poll_interval: i32, reload: bool)
             match serde_json::from_str::<DevserverMsg>(string) {
                 Ok(DevserverMsg::HotReload(hr)) => _ = tx_.unbounded_send(hr),
 
+                // The devserver wants a snapshot of the app for the inspector
+                Ok(DevserverMsg::Inspect) => _ = inspect_tx_.unbounded_send(()),
+
                 // todo: we want to throw a screen here that shows the user that the devserver has disconnected
                 // Would be nice to do that with dioxus itself or some html/css
                 // But if the dev server shutsdown we don't want to be super aggressive about it... let's
@@ -139,12 +172,14 @@ fn make_ws(tx: UnboundedSender<HotReloadMsg>, poll_interval: i32, reload: bool)
 
             // set timeout to reload the page in timeout_ms
             let tx = tx.clone();
+            let inspect_tx = inspect_tx.clone();
             web_sys::window()
                 .unwrap()
                 .set_timeout_with_callback_and_timeout_and_arguments_0(
                     Closure::<dyn FnMut()>::new(move || {
                         make_ws(
                             tx.clone(),
+                            inspect_tx.clone(),
                             POLL_INTERVAL_MAX.min(poll_interval * POLL_INTERVAL_SCALE_FACTOR),
                             true,
                         );
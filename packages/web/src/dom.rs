@@ -48,6 +48,11 @@ pub struct WebsysDom {
 
     #[cfg(feature = "hydrate")]
     pub(crate) suspense_hydration_ids: crate::hydration::SuspenseHydrationIds,
+
+    // Portals are not hydrated. The server renders their children outside of the app root, so we collect the portal
+    // scopes we find while hydrating and mount them on the client once the rest of the scope is hydrated.
+    #[cfg(feature = "hydrate")]
+    pub(crate) hydrated_portals: Vec<dioxus_core::ScopeId>,
 }
 
 impl WebsysDom {
@@ -135,6 +140,8 @@ impl WebsysDom {
             skip_mutations: false,
             #[cfg(feature = "hydrate")]
             suspense_hydration_ids: Default::default(),
+            #[cfg(feature = "hydrate")]
+            hydrated_portals: Default::default(),
         }
     }
 }
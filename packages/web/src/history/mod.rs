@@ -1,25 +1,20 @@
-use scroll::ScrollPosition;
+use dioxus_history::HistoryBlocker;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::{prelude::Closure, JsValue};
 use web_sys::{window, Window};
 use web_sys::{Event, History, ScrollRestoration};
 
-mod scroll;
-
 fn base_path() -> Option<String> {
     let base_path = dioxus_cli_config::web_base_path();
     tracing::trace!("Using base_path from the CLI: {:?}", base_path);
     base_path
 }
 
-#[allow(clippy::extra_unused_type_parameters)]
-fn update_scroll(window: &Window, history: &History) {
-    let scroll = ScrollPosition::of_window(window);
-    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y], None) {
-        web_sys::console::error_1(&err);
-    }
-}
-
 /// A [`dioxus_history::History`] provider that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
 ///
 /// # Prefix
@@ -33,10 +28,20 @@ fn update_scroll(window: &Window, history: &History) {
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
 pub struct WebHistory {
-    do_scroll_restoration: bool,
     history: History,
     prefix: Option<String>,
     window: Window,
+    /// If the router restores the scroll position instead of the browser
+    do_scroll_restoration: bool,
+    /// The position of the current entry in the history. This is stored in the state of each entry to find out how
+    /// far the user moved when the browser navigates through the history.
+    index: Rc<Cell<f64>>,
+    /// The routes of the entries that were visited since the page loaded by their index
+    routes: Rc<RefCell<BTreeMap<i64, String>>>,
+    /// Set when the next popstate event was started by the router, which already checked the navigation blockers
+    skip_blocker: Rc<Cell<bool>>,
+    blocker: Rc<RefCell<Option<HistoryBlocker>>>,
+    before_unload: RefCell<Option<js_sys::Function>>,
 }
 
 impl Default for WebHistory {
@@ -48,8 +53,9 @@ impl Default for WebHistory {
 impl WebHistory {
     /// Create a new [`WebHistory`].
     ///
-    /// If `do_scroll_restoration` is [`true`], [`WebHistory`] will take control of the history
-    /// state. It'll also set the browsers scroll restoration to `manual`.
+    /// If `do_scroll_restoration` is [`true`], the browsers scroll restoration is set to `manual`, so the router can
+    /// restore the scroll position when navigating through the history. Otherwise, the router doesn't scroll the
+    /// page and the browser restores the scroll position itself.
     pub fn new(prefix: Option<String>, do_scroll_restoration: bool) -> Self {
         let myself = Self::new_inner(prefix, do_scroll_restoration);
 
@@ -57,8 +63,18 @@ impl WebHistory {
         let current_route_str = current_route.to_string();
         let prefix_str = myself.prefix.as_deref().unwrap_or("");
         let current_url = format!("{prefix_str}{current_route_str}");
-        let state = myself.create_state();
-        let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        // Keep the index of the entry if the page was reloaded
+        if let Some(index) = get_current_index(&myself.history) {
+            myself.index.set(index);
+        }
+        let entry_state = get_current_state(&myself.history);
+        let _ = replace_state_with_url(
+            &myself.history,
+            myself.index.get(),
+            entry_state.as_deref(),
+            Some(&current_url),
+        );
+        myself.remember_route();
 
         myself
     }
@@ -85,40 +101,36 @@ impl WebHistory {
             .map(|prefix| format!("/{prefix}"));
 
         Self {
-            do_scroll_restoration,
             history,
             prefix,
             window,
+            do_scroll_restoration,
+            index: Rc::new(Cell::new(0.0)),
+            routes: Default::default(),
+            skip_blocker: Default::default(),
+            blocker: Default::default(),
+            before_unload: Default::default(),
         }
     }
+}
 
-    fn scroll_pos(&self) -> ScrollPosition {
-        self.do_scroll_restoration
-            .then(|| ScrollPosition::of_window(&self.window))
-            .unwrap_or_default()
+impl WebHistory {
+    fn route_from_location(&self) -> String {
+        route_from_location(&self.window, self.prefix.as_deref())
     }
 
-    fn create_state(&self) -> [f64; 2] {
-        let scroll = self.scroll_pos();
-        [scroll.x, scroll.y]
+    /// Store the route of the current entry so it can be looked up when navigating back or forward to it
+    fn remember_route(&self) {
+        let index = self.index.get() as i64;
+        self.routes
+            .borrow_mut()
+            .insert(index, self.route_from_location());
     }
-}
 
-impl WebHistory {
-    fn route_from_location(&self) -> String {
-        let location = self.window.location();
-        let path = location.pathname().unwrap_or_else(|_| "/".into())
-            + &location.search().unwrap_or("".into())
-            + &location.hash().unwrap_or("".into());
-        let mut path = match self.prefix {
-            None => &path,
-            Some(ref prefix) => path.strip_prefix(prefix).unwrap_or(prefix),
-        };
-        // If the path is empty, parse the root route instead
-        if path.is_empty() {
-            path = "/"
-        }
-        path.to_string()
+    /// The route of an entry relative to the current entry if it was visited since the page loaded
+    fn route_at(&self, delta: i64) -> Option<String> {
+        let index = self.index.get() as i64 + delta;
+        self.routes.borrow().get(&index).cloned()
     }
 
     fn full_path(&self, state: &String) -> String {
@@ -129,16 +141,50 @@ impl WebHistory {
     }
 
     fn handle_nav(&self, result: Result<(), JsValue>) {
-        match result {
-            Ok(_) => {
-                if self.do_scroll_restoration {
-                    self.window.scroll_to_with_x_and_y(0.0, 0.0)
-                }
-            }
-            Err(e) => {
-                web_sys::console::error_2(&JsValue::from_str("failed to change state: "), &e);
+        if let Err(e) = result {
+            web_sys::console::error_2(&JsValue::from_str("failed to change state: "), &e);
+        }
+    }
+
+    fn push_entry(&self, route: String, entry_state: Option<&str>) {
+        if route == self.route_from_location() {
+            // don't push the same state twice, but keep the new entry state
+            if entry_state.is_some() {
+                self.handle_nav(replace_state_with_url(
+                    &self.history,
+                    self.index.get(),
+                    entry_state,
+                    None,
+                ));
             }
+            return;
         }
+
+        let path = self.full_path(&route);
+
+        self.index.set(self.index.get() + 1.0);
+        self.handle_nav(push_state_and_url(
+            &self.history,
+            self.index.get(),
+            entry_state,
+            path,
+        ));
+        // Pushing removes every entry after the new one
+        let index = self.index.get() as i64;
+        self.routes.borrow_mut().split_off(&index);
+        self.remember_route();
+    }
+
+    fn replace_entry(&self, route: String, entry_state: Option<&str>) {
+        let path = self.full_path(&route);
+
+        self.handle_nav(replace_state_with_url(
+            &self.history,
+            self.index.get(),
+            entry_state,
+            Some(&path),
+        ));
+        self.remember_route();
     }
 
     fn navigate_external(&self, url: String) -> bool {
@@ -167,58 +213,114 @@ impl dioxus_history::History for WebHistory {
     }
 
     fn go_back(&self) {
+        // The router already checked the blockers before it went back
+        self.skip_blocker.set(true);
         if let Err(e) = self.history.back() {
+            self.skip_blocker.set(false);
             web_sys::console::error_2(&JsValue::from_str("failed to go back: "), &e);
         }
     }
 
     fn go_forward(&self) {
+        self.skip_blocker.set(true);
         if let Err(e) = self.history.forward() {
+            self.skip_blocker.set(false);
             web_sys::console::error_2(&JsValue::from_str("failed to go forward: "), &e);
         }
     }
 
-    fn push(&self, state: String) {
-        if state == self.current_route() {
-            // don't push the same state twice
-            return;
-        }
-
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
+    fn back_route(&self) -> Option<String> {
+        self.route_at(-1)
+    }
 
-        // update the scroll position before pushing the new state
-        update_scroll(&w, &h);
+    fn forward_route(&self) -> Option<String> {
+        self.route_at(1)
+    }
 
-        let path = self.full_path(&state);
+    fn push(&self, state: String) {
+        self.push_entry(state, None);
+    }
 
-        let state: [f64; 2] = self.create_state();
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+    fn push_with_state(&self, route: String, state: String) {
+        self.push_entry(route, Some(&state));
     }
 
     fn replace(&self, state: String) {
-        let path = self.full_path(&state);
+        self.replace_entry(state, None);
+    }
+
+    fn replace_with_state(&self, path: String, state: String) {
+        self.replace_entry(path, Some(&state));
+    }
 
-        let state = self.create_state();
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+    fn current_state(&self) -> Option<String> {
+        get_current_state(&self.history)
     }
 
     fn external(&self, url: String) -> bool {
         self.navigate_external(url)
     }
 
+    fn scroll_restoration(&self) -> bool {
+        self.do_scroll_restoration
+    }
+
     fn updater(&self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
-        let w = self.window.clone();
         let h = self.history.clone();
-        let d = self.do_scroll_restoration;
+        let index = self.index.clone();
+        let blocker = self.blocker.clone();
+        let routes = self.routes.clone();
+        let skip_blocker = self.skip_blocker.clone();
+        let window = self.window.clone();
+        let prefix = self.prefix.clone();
+        // Set when the next popstate event undoes a blocked navigation
+        let undoing = Rc::new(Cell::new(false));
+        // Set when the next popstate event retries a blocked navigation
+        let retrying = Rc::new(Cell::new(false));
 
         let function = Closure::wrap(Box::new(move |_| {
-            (*callback)();
-            if d {
-                if let Some([x, y]) = get_current(&h) {
-                    ScrollPosition { x, y }.scroll_to(w.clone())
+            let new_index = get_current_index(&h).unwrap_or_default();
+            routes.borrow_mut().insert(
+                new_index as i64,
+                route_from_location(&window, prefix.as_deref()),
+            );
+            let skip = skip_blocker.replace(false);
+            if undoing.replace(false) {
+                index.set(new_index);
+                return;
+            }
+
+            let delta = (new_index - index.get()) as i32;
+            let blocker = blocker.borrow().clone();
+            let retried = retrying.replace(false);
+            if let Some(blocker) = blocker.filter(|_| delta != 0 && !retried && !skip) {
+                let retry: Rc<dyn Fn()> = Rc::new({
+                    let h = h.clone();
+                    let retrying = retrying.clone();
+                    move || {
+                        retrying.set(true);
+                        if let Err(e) = h.go_with_delta(delta) {
+                            web_sys::console::error_2(
+                                &JsValue::from_str("failed to retry navigation: "),
+                                &e,
+                            );
+                        }
+                    }
+                });
+                if blocker(Some(retry)) {
+                    undoing.set(true);
+                    if let Err(e) = h.go_with_delta(-delta) {
+                        web_sys::console::error_2(
+                            &JsValue::from_str("failed to block navigation: "),
+                            &e,
+                        );
+                    }
+                    return;
                 }
             }
+
+            index.set(new_index);
+            (*callback)();
         }) as Box<dyn FnMut(Event)>);
         self.window
             .add_event_listener_with_callback(
@@ -227,43 +329,111 @@ impl dioxus_history::History for WebHistory {
             )
             .unwrap();
     }
+
+    fn set_navigation_blocker(&self, blocker: Option<HistoryBlocker>) {
+        // Only listen to beforeunload while navigation is blocked, so the page can still be cached by the browser
+        let mut before_unload = self.before_unload.borrow_mut();
+        match (&blocker, before_unload.as_ref()) {
+            (Some(_), None) => {
+                let blocker = self.blocker.clone();
+                let function = Closure::wrap(Box::new(move |event: Event| {
+                    let blocker = blocker.borrow().clone();
+                    if blocker.is_some_and(|blocker| blocker(None)) {
+                        event.prevent_default();
+                        // Older browsers only show the confirmation dialog if the return value is set
+                        let _ = js_sys::Reflect::set(
+                            &event,
+                            &JsValue::from_str("returnValue"),
+                            &JsValue::from_str(""),
+                        );
+                    }
+                }) as Box<dyn FnMut(Event)>)
+                .into_js_value()
+                .unchecked_into::<js_sys::Function>();
+                if let Err(e) = self
+                    .window
+                    .add_event_listener_with_callback("beforeunload", &function)
+                {
+                    web_sys::console::error_2(
+                        &JsValue::from_str("failed to block unloading: "),
+                        &e,
+                    );
+                }
+                *before_unload = Some(function);
+            }
+            (None, Some(function)) => {
+                let _ = self
+                    .window
+                    .remove_event_listener_with_callback("beforeunload", function);
+                *before_unload = None;
+            }
+            _ => {}
+        }
+        *self.blocker.borrow_mut() = blocker;
+    }
+}
+
+/// Get the route of the current location without the prefix
+fn route_from_location(window: &Window, prefix: Option<&str>) -> String {
+    let location = window.location();
+    let path = location.pathname().unwrap_or_else(|_| "/".into())
+        + &location.search().unwrap_or("".into())
+        + &location.hash().unwrap_or("".into());
+    let mut path = match prefix {
+        None => &path,
+        Some(prefix) => path.strip_prefix(prefix).unwrap_or(prefix),
+    };
+    // If the path is empty, parse the root route instead
+    if path.is_empty() {
+        path = "/"
+    }
+    path.to_string()
+}
+
+/// Create the state of a history entry. The state is an array of the index of the entry and the state that was
+/// attached to the entry with [`dioxus_history::History::push_with_state`] if there is one.
+fn create_entry_state(index: f64, entry_state: Option<&str>) -> js_sys::Array {
+    let state = js_sys::Array::new();
+    state.push(&JsValue::from(index));
+    if let Some(entry_state) = entry_state {
+        state.push(&JsValue::from_str(entry_state));
+    }
+    state
 }
 
 pub(crate) fn replace_state_with_url(
     history: &History,
-    value: &[f64; 2],
+    index: f64,
+    entry_state: Option<&str>,
     url: Option<&str>,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-
-    history.replace_state_with_url(&position, "", url)
+    let state = create_entry_state(index, entry_state);
+    history.replace_state_with_url(&state, "", url)
 }
 
 pub(crate) fn push_state_and_url(
     history: &History,
-    value: &[f64; 2],
+    index: f64,
+    entry_state: Option<&str>,
     url: String,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-
-    history.push_state_with_url(&position, "", Some(&url))
+    let state = create_entry_state(index, entry_state);
+    history.push_state_with_url(&state, "", Some(&url))
 }
 
-pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
-    use wasm_bindgen::JsCast;
-
+/// Get the index of the current entry in the history.
+pub(crate) fn get_current_index(history: &History) -> Option<f64> {
     let state = history.state();
     if let Err(err) = &state {
         web_sys::console::error_1(err);
     }
-    state.ok().and_then(|state| {
-        let state = state.dyn_into::<js_sys::Array>().ok()?;
-        let x = state.get(0).as_f64()?;
-        let y = state.get(1).as_f64()?;
-        Some([x, y])
-    })
+    let state = state.ok()?.dyn_into::<js_sys::Array>().ok()?;
+    state.get(0).as_f64()
+}
+
+/// Get the state that was attached to the current entry with [`dioxus_history::History::push_with_state`].
+pub(crate) fn get_current_state(history: &History) -> Option<String> {
+    let state = history.state().ok()?;
+    let state = state.dyn_into::<js_sys::Array>().ok()?;
+    state.get(1).as_string()
 }
@@ -10,6 +10,8 @@ use dioxus_core::{DynamicNode, ElementId};
 use dioxus_fullstack_protocol::HydrationContext;
 use futures_channel::mpsc::UnboundedReceiver;
 use std::fmt::Write;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
 use RehydrationError::*;
 
 use super::SuspenseMessage;
@@ -199,6 +201,15 @@ impl WebsysDom {
 
         self.interpreter.base().hydrate(ids, under);
 
+        // Mount the children of any portals we found now that the rest of the scope is hydrated
+        let portals = std::mem::take(&mut self.hydrated_portals);
+        if !portals.is_empty() {
+            for portal in portals {
+                PortalProps::mount_hydrated(portal, dom, self);
+            }
+            self.flush_edits();
+        }
+
         #[cfg(feature = "mounted")]
         for id in to_mount {
             self.send_mount_event(id);
@@ -233,6 +244,22 @@ impl WebsysDom {
         );
         closure.forget();
 
+        // The server renders the children of portals at the end of the document. They are mounted again on the client
+        // once the scope that contains them is hydrated
+        if let Ok(portals) = self
+            .document
+            .query_selector_all("[data-dioxus-ssr-portal],[data-dioxus-ssr-portal-element]")
+        {
+            for i in 0..portals.length() {
+                if let Some(portal) = portals
+                    .get(i)
+                    .and_then(|node| node.dyn_into::<Element>().ok())
+                {
+                    portal.remove();
+                }
+            }
+        }
+
         // Rehydrate the root scope that was rendered on the server. We will likely run into suspense boundaries.
         // Any suspense boundaries we run into are stored for hydration later.
         self.start_hydration_at_scope(vdom.base_scope(), vdom, vec![self.root.clone()])?;
@@ -257,6 +284,11 @@ impl WebsysDom {
             }
         }
 
+        // The root node of a portal is the placeholder in the logical parent. The children are mounted after hydration
+        if scope.portal().is_some() {
+            self.hydrated_portals.push(scope.id());
+        }
+
         self.rehydrate_vnode(dom, scope.root_node(), ids, to_mount)
     }
 
@@ -55,7 +55,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
     let runtime = virtual_dom.runtime();
 
     #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hotreload_rx = devtools::init();
+    let (mut hotreload_rx, mut inspect_rx) = devtools::init();
 
     let should_hydrate = web_config.hydrate;
 
@@ -155,6 +155,8 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         // if there is work then this future resolves immediately.
         #[cfg(all(feature = "devtools", debug_assertions))]
         let template;
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        let mut inspect = false;
         #[allow(unused)]
         let mut hydration_work: Option<SuspenseMessage> = None;
 
@@ -171,6 +173,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
             #[allow(unused)]
             {
                 let mut devtools_next = hotreload_rx.select_next_some();
+                let mut inspect_next = inspect_rx.select_next_some();
                 select! {
                     _ = work => {
                         template = None;
@@ -178,6 +181,10 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
                     new_template = devtools_next => {
                         template = Some(new_template);
                     },
+                    _ = inspect_next => {
+                        template = None;
+                        inspect = true;
+                    },
                     hydration_data = rx_hydration => {
                         template = None;
                         #[cfg(feature = "hydrate")]
@@ -223,6 +230,11 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
             }
         }
 
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        if inspect {
+            devtools::send_inspector_snapshot(&virtual_dom);
+        }
+
         #[cfg(feature = "hydrate")]
         if let Some(hydration_data) = hydration_work {
             websys_dom.rehydrate_streaming(hydration_data, &mut virtual_dom);
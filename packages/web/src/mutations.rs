@@ -1,7 +1,7 @@
 use crate::dom::WebsysDom;
 use dioxus_core::prelude::*;
 use dioxus_core::WriteMutations;
-use dioxus_core::{AttributeValue, ElementId};
+use dioxus_core::{AttributeValue, ElementId, PortalTarget};
 use dioxus_core_types::event_bubbles;
 use dioxus_interpreter_js::minimal_bindings;
 use wasm_bindgen::JsCast;
@@ -259,4 +259,16 @@ impl WriteMutations for WebsysDom {
         }
         self.interpreter.push_root(id.0 as u32)
     }
+
+    fn mount_portal(&mut self, target: &PortalTarget, id: ElementId, m: usize) {
+        if self.skip_mutations() {
+            return;
+        }
+        let (kind, target) = match target {
+            PortalTarget::Named(name) => (0, name),
+            PortalTarget::Element(element) => (1, element),
+        };
+        self.interpreter
+            .mount_portal(kind, target, id.0 as u32, m as u16)
+    }
 }